@@ -43,6 +43,9 @@ class ProposalType(Enum):
     RISK_LIMIT = 2
     STRATEGY = 3
     EMERGENCY = 4
+    TREASURY_TRANSFER = 5
+    TREASURY_TOKEN_TRANSFER = 6
+    AUTHORITY_HANDOVER = 7
 
 
 class VoteType(Enum):
@@ -85,7 +88,9 @@ class BaseAgent(ABC):
         name: str,
         keypair_path: str,
         rpc_url: str = "https://api.devnet.solana.com",
-        program_id: Optional[str] = None
+        program_id: Optional[str] = None,
+        manifest: bytes = b"",
+        swarm_id: Optional[int] = None
     ):
         """
         Initialize the agent.
@@ -96,9 +101,12 @@ class BaseAgent(ABC):
             keypair_path: Path to the agent's keypair file
             rpc_url: Solana RPC endpoint
             program_id: Agent swarm program ID
+            manifest: Encoded agent manifest checked by the swarm on registration
+            swarm_id: Swarm to join; defaults to the SWARM_ID environment variable, else 0
         """
         self.agent_type = agent_type
         self.name = name
+        self.manifest = manifest
         self.rpc_url = rpc_url
         
         # Load keypair
@@ -113,6 +121,7 @@ class BaseAgent(ABC):
         
         # Program configuration
         self.program_id = Pubkey.from_string(program_id) if program_id else None
+        self.swarm_id = swarm_id if swarm_id is not None else int(os.getenv("SWARM_ID", 0))
         self.program: Optional[Program] = None
         
         # Agent state
@@ -157,6 +166,7 @@ class BaseAgent(ABC):
             tx = await self.program.rpc["register_agent"](
                 self.agent_type.value,
                 self.name,
+                list(self.manifest),
                 ctx={
                     "accounts": {
                         "swarm_state": swarm_pda,
@@ -179,7 +189,8 @@ class BaseAgent(ABC):
         self,
         proposal_type: ProposalType,
         data: bytes,
-        description: str
+        description: str,
+        objective: Optional[bytes] = None
     ) -> Pubkey:
         """
         Create a new proposal for the swarm to vote on.
@@ -188,6 +199,7 @@ class BaseAgent(ABC):
             proposal_type: Type of proposal
             data: Serialized proposal data
             description: Human-readable description
+            objective: Optional 32-byte objective id shared by competing proposals
             
         Returns:
             Pubkey of the created proposal
@@ -211,6 +223,7 @@ class BaseAgent(ABC):
                 proposal_type.value,
                 list(data),
                 description,
+                list(objective) if objective else None,
                 ctx={
                     "accounts": {
                         "swarm_state": swarm_pda,
@@ -262,7 +275,9 @@ class BaseAgent(ABC):
                         "swarm_state": swarm_pda,
                         "agent": agent_pda,
                         "proposal": proposal_pda,
+                        "vote_record": self._get_vote_record_pda(proposal_pda),
                         "voter": self.pubkey,
+                        "system_program": Pubkey.default(),
                     },
                     "signers": [self.keypair],
                 }
@@ -329,20 +344,26 @@ class BaseAgent(ABC):
             return []
     
     def _get_swarm_pda(self) -> Pubkey:
-        """Get the swarm state PDA"""
-        seeds = [b"swarm"]
+        """Get the swarm state PDA, seeded by the swarm id"""
+        seeds = [b"swarm", self.swarm_id.to_bytes(8, 'little')]
         pda, _ = Pubkey.find_program_address(seeds, self.program_id)
         return pda
     
     def _get_agent_pda(self) -> Pubkey:
-        """Get this agent's PDA"""
-        seeds = [b"agent", bytes(self.pubkey)]
+        """Get this agent's PDA in the swarm"""
+        seeds = [b"agent", bytes(self._get_swarm_pda()), bytes(self.pubkey)]
         pda, _ = Pubkey.find_program_address(seeds, self.program_id)
         return pda
     
     def _get_proposal_pda(self, proposal_id: int) -> Pubkey:
-        """Get a proposal PDA by ID"""
-        seeds = [b"proposal", proposal_id.to_bytes(8, 'little')]
+        """Get a proposal PDA of the swarm by ID"""
+        seeds = [b"proposal", bytes(self._get_swarm_pda()), proposal_id.to_bytes(8, 'little')]
+        pda, _ = Pubkey.find_program_address(seeds, self.program_id)
+        return pda
+    
+    def _get_vote_record_pda(self, proposal_pda: Pubkey) -> Pubkey:
+        """Get this agent's vote record PDA for a proposal"""
+        seeds = [b"vote", bytes(proposal_pda), bytes(self.pubkey)]
         pda, _ = Pubkey.find_program_address(seeds, self.program_id)
         return pda
     
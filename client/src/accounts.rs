@@ -0,0 +1,33 @@
+//! Decoding of raw account data into the program's account types
+
+use agent_swarm::state::Proposal;
+use anchor_lang::{AccountDeserialize, ZeroCopy};
+use solana_sdk::pubkey::Pubkey;
+use std::mem::size_of;
+
+use crate::error::ClientError;
+
+/// Decode a Borsh account, checking its discriminator
+pub fn decode<T: AccountDeserialize>(address: &Pubkey, data: &[u8]) -> Result<T, ClientError> {
+    T::try_deserialize(&mut &data[..]).map_err(|_| ClientError::InvalidAccount(*address))
+}
+
+/// Decode a zero-copy account such as `Agent` or `Proposal`, checking its
+/// discriminator. Trailing bytes past the struct are ignored.
+pub fn decode_zero_copy<T: ZeroCopy>(address: &Pubkey, data: &[u8]) -> Result<T, ClientError> {
+    let body = data
+        .get(8..8 + size_of::<T>())
+        .filter(|_| data[..8] == T::discriminator())
+        .ok_or(ClientError::InvalidAccount(*address))?;
+    Ok(bytemuck::pod_read_unaligned(body))
+}
+
+/// The payload written to a proposal so far, stored after its fixed fields
+pub fn proposal_data<'a>(
+    address: &Pubkey,
+    proposal: &Proposal,
+    data: &'a [u8],
+) -> Result<&'a [u8], ClientError> {
+    data.get(Proposal::LEN..Proposal::LEN + proposal.data_written as usize)
+        .ok_or(ClientError::InvalidAccount(*address))
+}
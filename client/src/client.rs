@@ -0,0 +1,132 @@
+use agent_swarm::pda;
+use agent_swarm::state::{Agent, AgentRegistry, Portfolio, Proposal, SwarmState, VoteRecord};
+use agent_swarm::ProposalType;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::accounts::{decode, decode_zero_copy, proposal_data};
+use crate::error::ClientError;
+use crate::instructions::CreateProposal;
+
+/// One swarm seen through an RPC node
+pub struct SwarmClient {
+    rpc: RpcClient,
+    swarm: Pubkey,
+}
+
+impl SwarmClient {
+    pub fn new(rpc: RpcClient, swarm_id: u64) -> Self {
+        Self { rpc, swarm: pda::find_swarm_address(swarm_id).0 }
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    /// Address of the swarm's `SwarmState`
+    pub fn swarm(&self) -> Pubkey {
+        self.swarm
+    }
+
+    fn account_data(&self, address: &Pubkey) -> Result<Vec<u8>, ClientError> {
+        self.rpc
+            .get_account_with_commitment(address, self.rpc.commitment())?
+            .value
+            .map(|account| account.data)
+            .ok_or(ClientError::AccountNotFound(*address))
+    }
+
+    pub fn swarm_state(&self) -> Result<SwarmState, ClientError> {
+        decode(&self.swarm, &self.account_data(&self.swarm)?)
+    }
+
+    pub fn agent_registry(&self) -> Result<AgentRegistry, ClientError> {
+        let address = pda::find_agent_registry_address(&self.swarm).0;
+        decode(&address, &self.account_data(&address)?)
+    }
+
+    /// Treasury holdings and target weights as of the last execution
+    pub fn portfolio(&self) -> Result<Portfolio, ClientError> {
+        let address = pda::find_portfolio_address(&self.swarm).0;
+        decode(&address, &self.account_data(&address)?)
+    }
+
+    /// The agent registered by `owner`
+    pub fn agent(&self, owner: &Pubkey) -> Result<Agent, ClientError> {
+        let address = pda::find_agent_address(&self.swarm, owner).0;
+        decode_zero_copy(&address, &self.account_data(&address)?)
+    }
+
+    /// Proposal `proposal_id` and the payload written to it so far
+    pub fn proposal(&self, proposal_id: u64) -> Result<(Proposal, Vec<u8>), ClientError> {
+        let address = pda::find_proposal_address(&self.swarm, proposal_id).0;
+        let data = self.account_data(&address)?;
+        let proposal: Proposal = decode_zero_copy(&address, &data)?;
+        let payload = proposal_data(&address, &proposal, &data)?.to_vec();
+        Ok((proposal, payload))
+    }
+
+    /// `owner`'s vote on `proposal`, if it has voted
+    pub fn vote_record(
+        &self,
+        proposal: &Pubkey,
+        owner: &Pubkey,
+    ) -> Result<Option<VoteRecord>, ClientError> {
+        let address = pda::find_vote_record_address(proposal, owner).0;
+        match self.account_data(&address) {
+            Ok(data) => decode(&address, &data).map(Some),
+            Err(ClientError::AccountNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// A `create_proposal` builder for the swarm's next proposal id, passing
+    /// the treasury when the swarm charges a deposit. Another proposal
+    /// landing first takes the id, failing the transaction.
+    pub fn create_proposal(
+        &self,
+        owner: Pubkey,
+        proposal_type: ProposalType,
+        description: impl Into<String>,
+    ) -> Result<CreateProposal, ClientError> {
+        let swarm_state = self.swarm_state()?;
+        let builder = CreateProposal::new(
+            self.swarm,
+            swarm_state.total_proposals,
+            owner,
+            proposal_type,
+            description,
+        );
+        Ok(if swarm_state.congestion.base_deposit > 0 {
+            builder.pays_deposit()
+        } else {
+            builder
+        })
+    }
+
+    /// Sign `instructions` with a fresh blockhash, send and confirm them
+    pub fn send(
+        &self,
+        instructions: &[Instruction],
+        payer: &dyn Signer,
+        signers: &[&dyn Signer],
+    ) -> Result<Signature, ClientError> {
+        let mut all_signers = vec![payer];
+        all_signers.extend(
+            signers
+                .iter()
+                .copied()
+                .filter(|signer| signer.pubkey() != payer.pubkey()),
+        );
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            self.rpc.get_latest_blockhash()?,
+        );
+        Ok(self.rpc.send_and_confirm_transaction(&transaction)?)
+    }
+}
@@ -0,0 +1,30 @@
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ClientError {
+    Rpc(Box<solana_client::client_error::ClientError>),
+    AccountNotFound(Pubkey),
+    /// The account exists but is not the expected type or layout
+    InvalidAccount(Pubkey),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Rpc(err) => write!(f, "RPC error: {}", err),
+            ClientError::AccountNotFound(address) => write!(f, "Account {} not found", address),
+            ClientError::InvalidAccount(address) => {
+                write!(f, "Account {} could not be decoded", address)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(Box::new(err))
+    }
+}
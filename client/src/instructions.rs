@@ -0,0 +1,334 @@
+//! Typed instruction builders.
+//!
+//! Each builder takes the arguments that identify the call, fills in every
+//! PDA the program expects and leaves optional accounts unset unless asked
+//! for. Agents are named by their owner, which seeds the agent account;
+//! the signer defaults to the owner and is overridden with `signing_key`
+//! once the agent has rotated its key.
+
+use agent_swarm::oracle::PriceCondition;
+use agent_swarm::{accounts, instruction, pda, AgentType, ProposalType, VoteType, ID};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_program;
+
+/// Any program instruction from its Anchor account and argument structs,
+/// for calls without a builder here
+pub fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// `register_agent`: `owner` joins `swarm` as a new agent
+pub struct RegisterAgent {
+    swarm: Pubkey,
+    owner: Pubkey,
+    agent_type: AgentType,
+    name: String,
+    manifest: Vec<u8>,
+}
+
+impl RegisterAgent {
+    pub fn new(swarm: Pubkey, owner: Pubkey, agent_type: AgentType, name: impl Into<String>) -> Self {
+        Self { swarm, owner, agent_type, name: name.into(), manifest: Vec::new() }
+    }
+
+    /// Capability manifest, encoded as `manifest::AgentManifest`
+    pub fn manifest(mut self, manifest: Vec<u8>) -> Self {
+        self.manifest = manifest;
+        self
+    }
+
+    pub fn instruction(self) -> Instruction {
+        let custom_agent_type = self
+            .agent_type
+            .custom_id()
+            .map(|id| pda::find_custom_agent_type_address(&self.swarm, id).0);
+        build(
+            accounts::RegisterAgent {
+                swarm_state: self.swarm,
+                agent: pda::find_agent_address(&self.swarm, &self.owner).0,
+                agent_registry: pda::find_agent_registry_address(&self.swarm).0,
+                custom_agent_type,
+                owner: self.owner,
+                system_program: system_program::ID,
+            },
+            instruction::RegisterAgent {
+                agent_type: self.agent_type,
+                name: self.name,
+                manifest: self.manifest,
+            },
+        )
+    }
+}
+
+/// `deregister_agent`: retire `owner`'s agent from `swarm`
+pub struct DeregisterAgent {
+    swarm: Pubkey,
+    owner: Pubkey,
+    signing_key: Pubkey,
+}
+
+impl DeregisterAgent {
+    pub fn new(swarm: Pubkey, owner: Pubkey) -> Self {
+        Self { swarm, owner, signing_key: owner }
+    }
+
+    pub fn signing_key(mut self, signing_key: Pubkey) -> Self {
+        self.signing_key = signing_key;
+        self
+    }
+
+    pub fn instruction(self) -> Instruction {
+        build(
+            accounts::DeregisterAgent {
+                swarm_state: self.swarm,
+                agent: pda::find_agent_address(&self.swarm, &self.owner).0,
+                agent_registry: pda::find_agent_registry_address(&self.swarm).0,
+                owner: self.signing_key,
+            },
+            instruction::DeregisterAgent {},
+        )
+    }
+}
+
+/// `create_proposal`: proposal `proposal_id` of `swarm`, which must be the
+/// swarm's current `total_proposals`
+pub struct CreateProposal {
+    swarm: Pubkey,
+    proposal_id: u64,
+    owner: Pubkey,
+    signing_key: Pubkey,
+    proposal_type: ProposalType,
+    description: String,
+    data: Vec<u8>,
+    total_data_len: Option<u16>,
+    objective: Option<[u8; 32]>,
+    data_hash: Option<[u8; 32]>,
+    condition: Option<PriceCondition>,
+    tags: Vec<String>,
+    committee: Option<u8>,
+    pays_deposit: bool,
+}
+
+impl CreateProposal {
+    pub fn new(
+        swarm: Pubkey,
+        proposal_id: u64,
+        owner: Pubkey,
+        proposal_type: ProposalType,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            swarm,
+            proposal_id,
+            owner,
+            signing_key: owner,
+            proposal_type,
+            description: description.into(),
+            data: Vec::new(),
+            total_data_len: None,
+            objective: None,
+            data_hash: None,
+            condition: None,
+            tags: Vec::new(),
+            committee: None,
+            pays_deposit: false,
+        }
+    }
+
+    pub fn signing_key(mut self, signing_key: Pubkey) -> Self {
+        self.signing_key = signing_key;
+        self
+    }
+
+    /// The payload, or its first chunk when `total_data_len` is larger
+    pub fn data(mut self, data: Vec<u8>) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Full payload length when the rest follows in `ExtendProposalData`
+    pub fn total_data_len(mut self, total_data_len: u16) -> Self {
+        self.total_data_len = Some(total_data_len);
+        self
+    }
+
+    pub fn objective(mut self, objective: [u8; 32]) -> Self {
+        self.objective = Some(objective);
+        self
+    }
+
+    /// Commit to a payload by its hash instead of uploading it
+    pub fn data_hash(mut self, data_hash: [u8; 32]) -> Self {
+        self.data_hash = Some(data_hash);
+        self
+    }
+
+    pub fn condition(mut self, condition: PriceCondition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+
+    /// Topic tags agents can filter proposals by, at most `MAX_PROPOSAL_TAGS`
+    pub fn tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Committee handling this proposal type, when the swarm has one
+    pub fn committee(mut self, committee_id: u8) -> Self {
+        self.committee = Some(committee_id);
+        self
+    }
+
+    /// Pass the treasury so the proposal deposit can be paid; needed
+    /// whenever the swarm charges one
+    pub fn pays_deposit(mut self) -> Self {
+        self.pays_deposit = true;
+        self
+    }
+
+    pub fn address(&self) -> Pubkey {
+        pda::find_proposal_address(&self.swarm, self.proposal_id).0
+    }
+
+    pub fn instruction(self) -> Instruction {
+        let page = self.proposal_id / agent_swarm::constants::PROPOSAL_PAGE_SIZE;
+        build(
+            accounts::CreateProposal {
+                swarm_state: self.swarm,
+                agent: pda::find_agent_address(&self.swarm, &self.owner).0,
+                proposal: self.address(),
+                proposal_page: pda::find_proposal_page_address(&self.swarm, page).0,
+                proposer: self.signing_key,
+                system_program: system_program::ID,
+                committee: self
+                    .committee
+                    .map(|id| pda::find_committee_address(&self.swarm, id).0),
+                treasury: self
+                    .pays_deposit
+                    .then(|| pda::find_treasury_address(&self.swarm).0),
+            },
+            instruction::CreateProposal {
+                total_data_len: self.total_data_len.unwrap_or(self.data.len() as u16),
+                proposal_type: self.proposal_type,
+                data: self.data,
+                description: self.description,
+                objective: self.objective,
+                data_hash: self.data_hash,
+                condition: self.condition,
+                tags: self.tags,
+            },
+        )
+    }
+}
+
+/// `extend_proposal_data`: upload the next chunk of a proposal's payload
+pub struct ExtendProposalData {
+    swarm: Pubkey,
+    proposal: Pubkey,
+    owner: Pubkey,
+    signing_key: Pubkey,
+    chunk: Vec<u8>,
+}
+
+impl ExtendProposalData {
+    pub fn new(swarm: Pubkey, proposal: Pubkey, owner: Pubkey, chunk: Vec<u8>) -> Self {
+        Self { swarm, proposal, owner, signing_key: owner, chunk }
+    }
+
+    pub fn signing_key(mut self, signing_key: Pubkey) -> Self {
+        self.signing_key = signing_key;
+        self
+    }
+
+    pub fn instruction(self) -> Instruction {
+        build(
+            accounts::ExtendProposalData {
+                swarm_state: self.swarm,
+                proposal: self.proposal,
+                proposer_agent: pda::find_agent_address(&self.swarm, &self.owner).0,
+                proposer: self.signing_key,
+                system_program: system_program::ID,
+            },
+            instruction::ExtendProposalData { chunk: self.chunk },
+        )
+    }
+}
+
+/// `vote_proposal`: `owner`'s agent votes on `proposal`
+pub struct VoteProposal {
+    swarm: Pubkey,
+    proposal: Pubkey,
+    owner: Pubkey,
+    signing_key: Pubkey,
+    vote: VoteType,
+    reasoning: String,
+    vote_escrow: bool,
+    committee: Option<u8>,
+}
+
+impl VoteProposal {
+    pub fn new(swarm: Pubkey, proposal: Pubkey, owner: Pubkey, vote: VoteType) -> Self {
+        Self {
+            swarm,
+            proposal,
+            owner,
+            signing_key: owner,
+            vote,
+            reasoning: String::new(),
+            vote_escrow: false,
+            committee: None,
+        }
+    }
+
+    pub fn signing_key(mut self, signing_key: Pubkey) -> Self {
+        self.signing_key = signing_key;
+        self
+    }
+
+    pub fn reasoning(mut self, reasoning: impl Into<String>) -> Self {
+        self.reasoning = reasoning.into();
+        self
+    }
+
+    /// Boost the vote with the signer's vote escrow
+    pub fn vote_escrow(mut self) -> Self {
+        self.vote_escrow = true;
+        self
+    }
+
+    /// Committee the proposal is scoped to, when it is
+    pub fn committee(mut self, committee_id: u8) -> Self {
+        self.committee = Some(committee_id);
+        self
+    }
+
+    pub fn instruction(self) -> Instruction {
+        build(
+            accounts::VoteProposal {
+                swarm_state: self.swarm,
+                agent: pda::find_agent_address(&self.swarm, &self.owner).0,
+                proposal: self.proposal,
+                vote_record: pda::find_vote_record_address(&self.proposal, &self.owner).0,
+                voter: self.signing_key,
+                system_program: system_program::ID,
+                vote_escrow: self
+                    .vote_escrow
+                    .then(|| pda::find_vote_escrow_address(&self.swarm, &self.signing_key).0),
+                committee: self
+                    .committee
+                    .map(|id| pda::find_committee_address(&self.swarm, id).0),
+            },
+            instruction::VoteProposal {
+                vote: self.vote,
+                reasoning: self.reasoning,
+            },
+        )
+    }
+}
@@ -0,0 +1,16 @@
+//! Rust client for the agent_swarm program.
+//!
+//! Operators writing agent bots in Rust build instructions with the typed
+//! builders in [`instructions`], derive addresses with [`pda`] and read
+//! swarm accounts through [`SwarmClient`] or the decoders in [`accounts`].
+//! The program crate is linked with `no-entrypoint`, so seeds, account
+//! layouts and instruction encodings always match the deployed program.
+
+pub mod accounts;
+pub mod client;
+pub mod error;
+pub mod instructions;
+
+pub use agent_swarm::{pda, state, AgentType, ProposalType, VoteType, ID};
+pub use client::SwarmClient;
+pub use error::ClientError;
@@ -0,0 +1,148 @@
+//! Typed decoding of the program's Anchor events
+
+use agent_swarm::events;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+
+macro_rules! swarm_events {
+    ($($name:ident),* $(,)?) => {
+        /// Every event the program emits. Variants are boxed since the
+        /// events differ widely in size.
+        pub enum SwarmEvent {
+            $($name(Box<events::$name>),)*
+        }
+
+        impl SwarmEvent {
+            /// Decode an event from its discriminator and Borsh body.
+            /// Fields appended by a newer program are ignored.
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                let discriminator = data.get(..8)?;
+                let mut body = &data[8..];
+                $(
+                    if discriminator == <events::$name as Discriminator>::discriminator() {
+                        return events::$name::deserialize(&mut body)
+                            .ok()
+                            .map(|event| SwarmEvent::$name(Box::new(event)));
+                    }
+                )*
+                None
+            }
+
+            /// The event's struct name, e.g. `ProposalCreated`
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(SwarmEvent::$name(_) => stringify!($name),)*
+                }
+            }
+        }
+    };
+}
+
+swarm_events! {
+    TreasuryTransferIn,
+    TreasuryTransferOut,
+    AgentSlashed,
+    BadgeConfigured,
+    BadgeAwarded,
+    OutputAttested,
+    AttestationCited,
+    CrossChainMessagePosted,
+    RealizedPnl,
+    FeePaid,
+    ProposalFailed,
+    RebalanceApproved,
+    StrategyParametersSet,
+    RebalanceTriggered,
+    StandingOrderPlaced,
+    StandingOrderTriggered,
+    DcaScheduleCreated,
+    DcaLegExecuted,
+    ArbReportSubmitted,
+    ArbReportCredited,
+    InsuranceTermsSet,
+    InsurancePremiumSwept,
+    InsuranceClaimPaid,
+    CircuitBreakerTripped,
+    TradingResumed,
+    PnlAttributed,
+    HarvestConfigured,
+    HarvestExecuted,
+    StandingOrderExpired,
+    TaskCreated,
+    TaskClaimed,
+    TaskCompleted,
+    TaskClosed,
+    CoalitionCreated,
+    CoalitionMembershipChanged,
+    CoalitionRulesUpdated,
+    CoalitionFundsDeposited,
+    CoalitionFundsWithdrawn,
+    CoalitionBallotCast,
+    CoalitionVoteCast,
+    CommitteeConfigured,
+    OutcomeArchived,
+    SwarmInitialized,
+    SwarmConfigUpdated,
+    SwarmAccountCreated,
+    AgentRegistered,
+    CustomAgentTypeRegistered,
+    AgentDeregistered,
+    AgentKeyRotated,
+    AgentGuardianChanged,
+    ProposalCreated,
+    ProposalDataExtended,
+    PayloadBufferWritten,
+    PayloadBufferClosed,
+    VoteCast,
+    ExecutorAssigned,
+    ExecutionScheduled,
+    ProposalLinked,
+    RandomnessCommitted,
+    RandomnessRevealed,
+    ProposalExecuted,
+    OutcomeRecorded,
+    ProposalClosed,
+    ReputationChanged,
+    MembershipLinked,
+    ReputationSynced,
+    VoteSettled,
+    EloMatchSettled,
+    ProposalEndorsed,
+    EndorsementSettled,
+    SabbaticalSet,
+    VotingRewardClaimed,
+    SpendCapSet,
+    PriceFeedSet,
+    TwapObserved,
+    ExposureLimitSet,
+    TradeExposureReserved,
+    BountyAttached,
+    BountyReleased,
+    BondPosted,
+    UnbondRequested,
+    BondWithdrawn,
+    RewardEpochSettled,
+    EpochAdvanced,
+    RewardsClaimed,
+    TokensLocked,
+    LockExtended,
+    LockedTokensWithdrawn,
+    EmissionsMinted,
+    ListingCreated,
+    ListingDelisted,
+    ListingUpdated,
+    ListingTransferred,
+    VersionPublished,
+    AgentPurchased,
+    SaleProceedsClaimed,
+    PurchaseRefunded,
+    SubscriptionTermsSet,
+    Subscribed,
+    SubscriptionRenewed,
+    AgentRated,
+    AgentReviewed,
+    AuctionStarted,
+    BidPlaced,
+    BidCancelled,
+    AuctionSettled,
+    AccountMigrated,
+}
@@ -0,0 +1,16 @@
+//! Event indexing for the agent_swarm program.
+//!
+//! [`subscribe`] follows the program's transaction logs over a websocket
+//! and yields each event as a typed [`SwarmEvent`], ready for dashboards
+//! and learning pipelines. [`parse_logs`] decodes logs fetched any other
+//! way. With the `sqlite` or `postgres` feature, [`sink`] stores events in
+//! a `swarm_events` table keyed by transaction and position.
+
+pub mod events;
+pub mod logs;
+pub mod sink;
+pub mod stream;
+
+pub use events::SwarmEvent;
+pub use logs::parse_logs;
+pub use stream::{subscribe, IndexedEvent};
@@ -0,0 +1,41 @@
+//! Extraction of events from transaction logs
+
+use agent_swarm::ID;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::events::SwarmEvent;
+
+/// Events the program emitted in a transaction's logs, in order, each with
+/// its raw discriminator and Borsh bytes. The invocation stack is tracked
+/// so data logged by other programs, including ones the swarm calls, is
+/// skipped.
+pub fn parse_logs(logs: &[String]) -> Vec<(SwarmEvent, Vec<u8>)> {
+    let program = ID.to_string();
+    let mut invocations: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        let Some(rest) = log.strip_prefix("Program ") else {
+            continue;
+        };
+        if let Some(data) = rest.strip_prefix("data: ") {
+            if invocations.last() != Some(&program.as_str()) {
+                continue;
+            }
+            let Ok(bytes) = STANDARD.decode(data) else {
+                continue;
+            };
+            if let Some(event) = SwarmEvent::decode(&bytes) {
+                events.push((event, bytes));
+            }
+        } else if let Some((id, status)) = rest.split_once(' ') {
+            if status.starts_with("invoke [") {
+                invocations.push(id);
+            } else if status == "success" || status.starts_with("failed") {
+                invocations.pop();
+            }
+        }
+    }
+    events
+}
@@ -0,0 +1,105 @@
+//! Optional database sinks. Both write one `swarm_events` row per event,
+//! keyed by transaction signature and position, so replaying a range of
+//! transactions is harmless. The event body stays Borsh; readers decode it
+//! with [`SwarmEvent::decode`](crate::SwarmEvent::decode).
+
+#[cfg(feature = "sqlite")]
+pub use self::sqlite::SqliteSink;
+
+#[cfg(feature = "postgres")]
+pub use self::postgres::PostgresSink;
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use rusqlite::{params, Connection};
+
+    use crate::stream::IndexedEvent;
+
+    pub struct SqliteSink {
+        connection: Connection,
+    }
+
+    impl SqliteSink {
+        /// Open or create the database at `path` and its `swarm_events` table
+        pub fn open(path: &str) -> rusqlite::Result<Self> {
+            let connection = Connection::open(path)?;
+            connection.execute_batch(
+                "CREATE TABLE IF NOT EXISTS swarm_events (
+                    signature TEXT NOT NULL,
+                    event_index INTEGER NOT NULL,
+                    slot INTEGER NOT NULL,
+                    name TEXT NOT NULL,
+                    data BLOB NOT NULL,
+                    PRIMARY KEY (signature, event_index)
+                );
+                CREATE INDEX IF NOT EXISTS swarm_events_name ON swarm_events (name, slot);",
+            )?;
+            Ok(Self { connection })
+        }
+
+        pub fn insert(&self, event: &IndexedEvent) -> rusqlite::Result<()> {
+            self.connection.execute(
+                "INSERT OR IGNORE INTO swarm_events (signature, event_index, slot, name, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    event.signature,
+                    event.index,
+                    event.slot as i64,
+                    event.event.name(),
+                    event.data,
+                ],
+            )?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use tokio_postgres::Client;
+
+    use crate::stream::IndexedEvent;
+
+    pub struct PostgresSink {
+        client: Client,
+    }
+
+    impl PostgresSink {
+        /// Use a connected client, creating the `swarm_events` table if
+        /// needed. The caller drives the client's connection task.
+        pub async fn new(client: Client) -> Result<Self, tokio_postgres::Error> {
+            client
+                .batch_execute(
+                    "CREATE TABLE IF NOT EXISTS swarm_events (
+                        signature TEXT NOT NULL,
+                        event_index INTEGER NOT NULL,
+                        slot BIGINT NOT NULL,
+                        name TEXT NOT NULL,
+                        data BYTEA NOT NULL,
+                        PRIMARY KEY (signature, event_index)
+                    );
+                    CREATE INDEX IF NOT EXISTS swarm_events_name ON swarm_events (name, slot);",
+                )
+                .await?;
+            Ok(Self { client })
+        }
+
+        pub async fn insert(&self, event: &IndexedEvent) -> Result<(), tokio_postgres::Error> {
+            self.client
+                .execute(
+                    "INSERT INTO swarm_events (signature, event_index, slot, name, data)
+                     VALUES ($1, $2, $3, $4, $5)
+                     ON CONFLICT DO NOTHING",
+                    &[
+                        &event.signature,
+                        &(event.index as i32),
+                        &(event.slot as i64),
+                        &event.event.name(),
+                        &event.data,
+                    ],
+                )
+                .await?;
+            Ok(())
+        }
+    }
+}
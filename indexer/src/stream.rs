@@ -0,0 +1,64 @@
+//! Live event subscription
+
+use agent_swarm::ID;
+use async_stream::stream;
+use futures_util::{Stream, StreamExt};
+use solana_pubsub_client::nonblocking::pubsub_client::{PubsubClient, PubsubClientError};
+use solana_rpc_client_api::config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::commitment_config::CommitmentConfig;
+
+use crate::events::SwarmEvent;
+use crate::logs::parse_logs;
+
+/// An event and the transaction it came from
+pub struct IndexedEvent {
+    pub signature: String,
+    pub slot: u64,
+    /// Position among the program's events in the transaction
+    pub index: u32,
+    pub event: SwarmEvent,
+    /// Discriminator and Borsh body as logged
+    pub data: Vec<u8>,
+}
+
+/// Follow every successful transaction mentioning the program at
+/// `commitment`. The stream ends when the websocket closes; a failed
+/// subscription is its only error.
+pub async fn subscribe(
+    ws_url: &str,
+    commitment: CommitmentConfig,
+) -> Result<impl Stream<Item = Result<IndexedEvent, PubsubClientError>>, PubsubClientError> {
+    let client = PubsubClient::new(ws_url).await?;
+
+    Ok(stream! {
+        let subscription = client
+            .logs_subscribe(
+                RpcTransactionLogsFilter::Mentions(vec![ID.to_string()]),
+                RpcTransactionLogsConfig { commitment: Some(commitment) },
+            )
+            .await;
+        let (mut notifications, _unsubscribe) = match subscription {
+            Ok(subscription) => subscription,
+            Err(err) => {
+                yield Err(err);
+                return;
+            }
+        };
+
+        while let Some(notification) = notifications.next().await {
+            // Events of failed transactions never took effect
+            if notification.value.err.is_some() {
+                continue;
+            }
+            for (index, (event, data)) in parse_logs(&notification.value.logs).into_iter().enumerate() {
+                yield Ok(IndexedEvent {
+                    signature: notification.value.signature.clone(),
+                    slot: notification.context.slot,
+                    index: index as u32,
+                    event,
+                    data,
+                });
+            }
+        }
+    })
+}
@@ -0,0 +1,353 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::*;
+use crate::marketplace::AgentListing;
+use crate::state::{SwarmState, Treasury};
+
+pub const MIN_AUCTION_DURATION: i64 = 3600;       // 1 hour
+pub const MAX_AUCTION_DURATION: i64 = 7 * 86400;  // 7 days
+
+/// Exclusive sale of a listing's ownership. Bids are escrowed as lamports on
+/// their `Bid` accounts until the auction settles.
+#[account]
+pub struct Auction {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub reserve_price: u64,
+    pub ends_at: i64,
+    pub highest_bid: u64,
+    pub highest_bidder: Option<Pubkey>,
+    pub bump: u8,
+}
+
+impl Auction {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        32 + // listing
+        32 + // seller
+        8 +  // reserve_price
+        8 +  // ends_at
+        8 +  // highest_bid
+        (1 + 32) + // highest_bidder
+        1;   // bump
+}
+
+#[account]
+pub struct Bid {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub placed_at: i64,
+    pub bump: u8,
+}
+
+impl Bid {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        32 + // auction
+        32 + // bidder
+        8 +  // amount
+        8 +  // placed_at
+        1;   // bump
+}
+
+#[derive(Accounts)]
+pub struct StartAuction<'info> {
+    #[account(constraint = listing.owner == seller.key() @ SwarmError::Unauthorized)]
+    pub listing: Account<'info, AgentListing>,
+    
+    #[account(
+        init,
+        payer = seller,
+        space = Auction::LEN,
+        seeds = [AUCTION_SEED, listing.key().as_ref()],
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+    
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction.listing.as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, Auction>,
+    
+    #[account(
+        init,
+        payer = bidder,
+        space = Bid::LEN,
+        seeds = [BID_SEED, auction.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+    
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelBid<'info> {
+    /// CHECK: The bid's auction. Closed once settled, in which case every
+    /// remaining bid lost and is refundable.
+    #[account(address = bid.auction)]
+    pub auction: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [BID_SEED, bid.auction.as_ref(), bidder.key().as_ref()],
+        bump = bid.bump
+    )]
+    pub bid: Account<'info, Bid>,
+    
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+}
+
+/// Permissionless crank run after the auction ends
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(
+        mut,
+        close = seller,
+        seeds = [AUCTION_SEED, listing.key().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, Auction>,
+    
+    #[account(mut, address = auction.listing)]
+    pub listing: Account<'info, AgentListing>,
+    
+    /// Required when the auction received a bid
+    #[account(
+        mut,
+        close = winner,
+        seeds = [BID_SEED, auction.key().as_ref(), winner.key().as_ref()],
+        bump = winning_bid.bump
+    )]
+    pub winning_bid: Option<Account<'info, Bid>>,
+    
+    /// CHECK: The highest bidder, refunded the winning bid's rent
+    #[account(mut)]
+    pub winner: UncheckedAccount<'info>,
+    
+    /// CHECK: Receives the proceeds; checked against `auction.seller`
+    #[account(mut, address = auction.seller)]
+    pub seller: UncheckedAccount<'info>,
+    
+    /// CHECK: Receives the listing's royalty on resales; checked against `listing.creator`
+    #[account(mut, address = listing.creator)]
+    pub creator: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [SWARM_SEED, MARKETPLACE_SWARM_ID.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, swarm_state.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub cranker: Signer<'info>,
+}
+
+pub fn start_auction(ctx: Context<StartAuction>, reserve_price: u64, duration: i64) -> Result<()> {
+    require!(
+        (MIN_AUCTION_DURATION..=MAX_AUCTION_DURATION).contains(&duration),
+        SwarmError::InvalidParameter
+    );
+    
+    let now = Clock::get()?.unix_timestamp;
+    let auction = &mut ctx.accounts.auction;
+    auction.listing = ctx.accounts.listing.key();
+    auction.seller = ctx.accounts.seller.key();
+    auction.reserve_price = reserve_price;
+    auction.ends_at = now.checked_add(duration).ok_or(SwarmError::ArithmeticOverflow)?;
+    auction.highest_bid = 0;
+    auction.highest_bidder = None;
+    auction.version = Auction::VERSION;
+    auction.bump = ctx.bumps.auction;
+    
+    emit!(AuctionStarted {
+        auction: auction.key(),
+        listing: auction.listing,
+        seller: auction.seller,
+        reserve_price,
+        ends_at: auction.ends_at,
+        timestamp: now,
+    });
+    
+    msg!("Auction started for {} until {}", ctx.accounts.listing.name, auction.ends_at);
+    Ok(())
+}
+
+pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let clock = Clock::get()?;
+    
+    require!(clock.unix_timestamp < auction.ends_at, SwarmError::AuctionEnded);
+    require!(
+        amount > auction.highest_bid && amount >= auction.reserve_price,
+        SwarmError::BidTooLow
+    );
+    require!(ctx.accounts.bidder.key() != auction.seller, SwarmError::Unauthorized);
+    
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.bidder.to_account_info(),
+                to: ctx.accounts.bid.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    let bid = &mut ctx.accounts.bid;
+    bid.auction = auction.key();
+    bid.bidder = ctx.accounts.bidder.key();
+    bid.amount = amount;
+    bid.placed_at = clock.unix_timestamp;
+    bid.version = Bid::VERSION;
+    bid.bump = ctx.bumps.bid;
+    
+    auction.highest_bid = amount;
+    auction.highest_bidder = Some(bid.bidder);
+    
+    emit!(BidPlaced {
+        auction: bid.auction,
+        bidder: bid.bidder,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Bid of {} placed by {}", amount, bid.bidder);
+    Ok(())
+}
+
+pub fn cancel_bid(ctx: Context<CancelBid>) -> Result<()> {
+    let auction_info = &ctx.accounts.auction;
+    
+    if !auction_info.data_is_empty() {
+        let auction = Auction::try_deserialize(&mut &auction_info.try_borrow_data()?[..])?;
+        require!(
+            auction.highest_bidder != Some(ctx.accounts.bidder.key()),
+            SwarmError::BidLocked
+        );
+    }
+    
+    // `close = bidder` refunds the escrowed bid together with the rent
+    emit!(BidCancelled {
+        auction: auction_info.key(),
+        bidder: ctx.accounts.bidder.key(),
+        amount: ctx.accounts.bid.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Bid of {} withdrawn", ctx.accounts.bid.amount);
+    Ok(())
+}
+
+pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    let clock = Clock::get()?;
+    
+    require!(clock.unix_timestamp >= auction.ends_at, SwarmError::AuctionActive);
+    
+    let Some(highest_bidder) = auction.highest_bidder else {
+        emit!(AuctionSettled {
+            auction: auction.key(),
+            listing: auction.listing,
+            winner: None,
+            amount: 0,
+            royalty: 0,
+            fee: 0,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!("Auction for {} ended without bids", ctx.accounts.listing.name);
+        return Ok(());
+    };
+    
+    require_keys_eq!(ctx.accounts.winner.key(), highest_bidder, SwarmError::Unauthorized);
+    let winning_bid = ctx
+        .accounts
+        .winning_bid
+        .as_ref()
+        .ok_or(SwarmError::MissingExecutionAccount)?;
+    
+    let amount = winning_bid.amount;
+    let fee = (amount as u128 * ctx.accounts.swarm_state.marketplace_fee_bps as u128
+        / BPS_DENOMINATOR as u128) as u64;
+    
+    // The creator earns a royalty whenever someone else resells the listing
+    let royalty = if auction.seller == ctx.accounts.listing.creator {
+        0
+    } else {
+        (amount as u128 * ctx.accounts.listing.royalty_bps as u128
+            / BPS_DENOMINATOR as u128) as u64
+    };
+    let proceeds = amount
+        .checked_sub(fee)
+        .and_then(|rest| rest.checked_sub(royalty))
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    
+    winning_bid.sub_lamports(amount)?;
+    ctx.accounts.seller.add_lamports(proceeds)?;
+    
+    if royalty > 0 {
+        ctx.accounts.creator.add_lamports(royalty)?;
+        msg!("Royalty of {} paid to creator {}", royalty, ctx.accounts.listing.creator);
+    }
+    
+    if fee > 0 {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.add_lamports(fee)?;
+        treasury.record_fee(fee)?;
+        
+        emit!(FeePaid {
+            schema_version: ACCOUNTING_SCHEMA_VERSION,
+            asset: NATIVE_SOL_ASSET,
+            amount: fee,
+            payer: highest_bidder,
+            recipient: treasury.key(),
+            proposal: None,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
+    let listing = &mut ctx.accounts.listing;
+    listing.owner = highest_bidder;
+    listing.updated_at = clock.unix_timestamp;
+    
+    emit!(AuctionSettled {
+        auction: auction.key(),
+        listing: listing.key(),
+        winner: Some(highest_bidder),
+        amount,
+        royalty,
+        fee,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Auction for {} won by {} at {}", listing.name, highest_bidder, amount);
+    Ok(())
+}
@@ -5,11 +5,72 @@ pub const SWARM_SEED: &[u8] = b"swarm";
 pub const AGENT_SEED: &[u8] = b"agent";
 pub const PROPOSAL_SEED: &[u8] = b"proposal";
 pub const OUTCOME_SEED: &[u8] = b"outcome";
+pub const VOTE_RECORD_SEED: &[u8] = b"vote";
+pub const MARKETPLACE_SEED: &[u8] = b"marketplace";
+pub const LISTING_SEED: &[u8] = b"listing";
+pub const PURCHASE_SEED: &[u8] = b"purchase";
+pub const RATING_SEED: &[u8] = b"rating";
+pub const SUBSCRIPTION_SEED: &[u8] = b"subscription";
+pub const REVIEW_SEED: &[u8] = b"review";
+pub const LISTING_VERSION_SEED: &[u8] = b"listing_version";
+pub const LISTING_INDEX_SEED: &[u8] = b"listing_index";
+pub const AUCTION_SEED: &[u8] = b"auction";
+pub const BID_SEED: &[u8] = b"bid";
+pub const ELO_MATCH_SEED: &[u8] = b"elo_match";
+pub const ENDORSEMENT_SEED: &[u8] = b"endorsement";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const VAULT_AUTHORITY_SEED: &[u8] = b"vault_authority";
+pub const SPEND_TRACKER_SEED: &[u8] = b"spend_tracker";
+pub const BOUNTY_SEED: &[u8] = b"bounty";
+pub const PENDING_EXPOSURE_SEED: &[u8] = b"pending_exposure";
+pub const BOND_SEED: &[u8] = b"bond";
+pub const SLASH_SEED: &[u8] = b"slash";
+pub const EPOCH_REWARDS_SEED: &[u8] = b"epoch_rewards";
+pub const VOTE_ESCROW_SEED: &[u8] = b"vote_escrow";
+pub const GOVERNANCE_MINT_SEED: &[u8] = b"governance_mint";
+pub const STAKE_TICKET_SEED: &[u8] = b"stake_ticket";
+pub const PROGRAM_WHITELIST_SEED: &[u8] = b"program_whitelist";
+pub const RISK_CONFIG_SEED: &[u8] = b"risk_config";
+pub const OUTCOME_ARCHIVE_SEED: &[u8] = b"outcome_archive";
+pub const PROPOSAL_PAGE_SEED: &[u8] = b"proposal_page";
+pub const PAYLOAD_BUFFER_SEED: &[u8] = b"payload_buffer";
+pub const AGENT_REGISTRY_SEED: &[u8] = b"agent_registry";
+pub const CUSTOM_AGENT_TYPE_SEED: &[u8] = b"custom_agent_type";
+pub const TWAP_TRACKER_SEED: &[u8] = b"twap_tracker";
+pub const RANDOMNESS_SEED: &[u8] = b"randomness";
+pub const EPOCH_STATS_SEED: &[u8] = b"epoch_stats";
+pub const EXECUTION_QUEUE_SEED: &[u8] = b"execution_queue";
+pub const REBALANCE_SCHEDULE_SEED: &[u8] = b"rebalance_schedule";
+pub const STRATEGY_CONFIG_SEED: &[u8] = b"strategy_config";
+pub const PORTFOLIO_SEED: &[u8] = b"portfolio";
+pub const STANDING_ORDER_SEED: &[u8] = b"standing_order";
+pub const DCA_SCHEDULE_SEED: &[u8] = b"dca_schedule";
+pub const ARB_REPORT_SEED: &[u8] = b"arb_report";
+pub const HARVEST_CONFIG_SEED: &[u8] = b"harvest_config";
+pub const INSURANCE_FUND_SEED: &[u8] = b"insurance_fund";
+pub const TASK_SEED: &[u8] = b"task";
+pub const COALITION_SEED: &[u8] = b"coalition";
+pub const COALITION_BALLOT_SEED: &[u8] = b"coalition_ballot";
+pub const COMMITTEE_SEED: &[u8] = b"committee";
+pub const AGENT_IDENTITY_SEED: &[u8] = b"agent_identity";
+pub const MEMBERSHIP_SEED: &[u8] = b"membership";
+pub const CROSS_CHAIN_EMITTER_SEED: &[u8] = b"cross_chain_emitter";
+pub const CROSS_CHAIN_MESSAGE_SEED: &[u8] = b"cross_chain_message";
+pub const BADGE_CONFIG_SEED: &[u8] = b"badge_config";
+pub const BADGE_SEED: &[u8] = b"badge";
+pub const ATTESTATION_SEED: &[u8] = b"attestation";
+pub const AUDIT_LOG_SEED: &[u8] = b"audit_log";
 
 pub const MAX_AGENT_NAME_LENGTH: usize = 32;
+pub const MAX_AGENT_TYPE_NAME_LENGTH: usize = 32;
 pub const MAX_DESCRIPTION_LENGTH: usize = 256;
 pub const MAX_REASONING_LENGTH: usize = 512;
+pub const MAX_PROPOSAL_TAGS: usize = 4; // Topic tags a proposal may carry
+pub const MAX_PROPOSAL_TAG_LENGTH: usize = 16;
+pub const MAX_REASONING_EXCERPT_LENGTH: usize = 128; // Reasoning kept on a vote record beside its hash
 pub const MAX_PROPOSAL_DATA_LENGTH: usize = 1024;
+pub const MAX_COMMITTED_PAYLOAD_LENGTH: usize = 8192; // Hash-committed payloads are staged in a buffer, not the proposal
+pub const DEFAULT_MIN_DESCRIPTION_LENGTH: u16 = 16;
 pub const MAX_OUTCOME_METRICS_LENGTH: usize = 512;
 
 pub const MIN_AGENTS: u8 = 3;
@@ -25,3 +86,106 @@ pub const MAX_REPUTATION: u16 = 10000;
 
 pub const BYZANTINE_FAULT_TOLERANCE_NUMERATOR: u8 = 1;   // Can tolerate up to 1/3 malicious agents
 pub const BYZANTINE_FAULT_TOLERANCE_DENOMINATOR: u8 = 3;
+
+// Performance scores fed to `Agent::update_reputation` when a vote is settled
+pub const VOTE_SETTLEMENT_REWARD_SCORE: u16 = 600;  // +10 reputation
+pub const VOTE_SETTLEMENT_PENALTY_SCORE: u16 = 400; // -10 reputation
+
+pub const INITIAL_ELO_RATING: u16 = 1200;
+pub const MAX_ELO_RATING: u16 = 4000;
+pub const ELO_K_FACTOR: u16 = 32;
+
+pub const BPS_DENOMINATOR: u16 = 10000;
+
+pub const DEFAULT_EXECUTOR_WINDOW: i64 = 600;  // 10 minutes
+
+pub const MAX_SABBATICAL_DURATION: i64 = 30 * 86400; // 30 days
+
+pub const MAX_PROPOSAL_TYPES: usize = 16;        // Room for future ProposalType variants
+pub const DEFAULT_SPEND_EPOCH_LENGTH: i64 = 86400; // 1 day
+pub const NO_SPEND_CAP: u64 = u64::MAX;
+
+pub const MAX_EXPOSURE_LIMITS: usize = 8;
+pub const MAX_PERP_LIMITS: usize = 8;
+pub const MAX_PENDING_TRADES: usize = 16;
+
+pub const BOND_UNBONDING_PERIOD: i64 = 7 * 86400; // 7 days
+pub const DEFAULT_SLASH_TREASURY_BPS: u16 = 5000;  // Half to treasury, half burned
+
+pub const DEFAULT_AUTHORITY_INACTIVITY_PERIOD: i64 = 90 * 86400; // 90 days
+pub const SUPERMAJORITY_BPS: u16 = 6667; // Two thirds of weighted votes
+
+// Performance payouts: points earned per epoch, paid pro rata from the budget
+pub const DEFAULT_REWARD_EPOCH_LENGTH: i64 = 7 * 86400; // 1 week
+pub const REWARD_POINTS_CORRECT_VOTE: u32 = 1;
+pub const REWARD_POINTS_SUCCESSFUL_PROPOSAL: u32 = 3;
+pub const REWARD_HISTORY_EPOCHS: usize = 8; // Unclaimed points older than this are forfeited
+pub const REPUTATION_DECAY_PER_EPOCH: u16 = 10; // Lost for each busy epoch an agent sat out
+
+pub const GOVERNANCE_TOKEN_DECIMALS: u8 = 6;
+
+// Vote escrow: a full-length lock on `ve_max_boost_power` tokens doubles vote weight
+pub const MIN_VOTE_ESCROW_LOCK: i64 = 7 * 86400;    // 1 week
+pub const MAX_VOTE_ESCROW_LOCK: i64 = 365 * 86400;  // 1 year
+pub const VE_MAX_BOOST_BPS: u16 = 10000;
+
+pub const DEFAULT_SALE_DISPUTE_WINDOW: i64 = 3 * 86400; // 3 days
+
+/// Swarm whose fee rate, dispute window and treasury the shared marketplace uses
+pub const MARKETPLACE_SWARM_ID: u64 = 0;
+
+pub const MAX_ALLOWED_PROGRAMS: usize = 8; // Whitelisted targets for protocol calls
+pub const MAX_REBALANCE_ALLOCATIONS: usize = 8;
+pub const MAX_REBALANCE_VENUES: usize = 4; // Preferred swap programs a rebalance schedule lists
+pub const MAX_ORDER_ROUTE_DATA: usize = 512; // Largest Jupiter route a StandingOrder stores
+pub const MAX_REBALANCE_TRADES: usize = 8; // Swaps one Rebalance proposal may list
+pub const REBALANCE_TOLERANCE_BPS: u16 = 100; // Share of portfolio value a rebalance's trades may miss a target by
+pub const MIN_DCA_INTERVAL: i64 = 300; // DCA legs run at most every five minutes
+pub const MIN_REBALANCE_INTERVAL: i64 = 3600; // Scheduled rebalances run at most hourly
+pub const MAX_STRATEGY_CALLS: usize = 4; // CPIs one Strategy proposal may chain
+pub const MAX_BALANCE_CHECKS: usize = 4; // Vault balances one Strategy proposal may verify
+pub const MAX_STRATEGY_LOOKBACKS: usize = 8; // Lookback windows a StrategyConfig holds
+pub const MAX_STRATEGY_THRESHOLDS: usize = 16; // Signal thresholds a StrategyConfig holds
+pub const MAX_PORTFOLIO_HOLDINGS: usize = 16; // Mints a Portfolio tracks at once
+pub const MAX_LIQUIDITY_BINS: usize = 70; // Widest Meteora DLMM position, in bins
+pub const MAX_WHIRLPOOL_TICK_INDEX: i32 = 443_636; // Outermost tick an Orca Whirlpool position may reach
+pub const MAX_HARVEST_POSITIONS: usize = 8; // Whirlpool positions a HarvestConfig collects from
+pub const MAX_PRICE_FEEDS: usize = 8; // Mints with a registered oracle feed
+pub const MAX_PRICE_AGE: i64 = 60; // Default oldest oracle price a trade may be checked against (seconds)
+pub const MAX_FEED_PRICE_AGE: i64 = 3600; // Longest staleness a feed may be configured with (seconds)
+pub const MAX_TWAP_OBSERVATIONS: usize = 32; // Observations a TwapTracker keeps
+pub const MAX_AUDIT_ENTRIES: usize = 64; // Privileged actions an AuditLog keeps
+pub const MAX_TWAP_WINDOW: i64 = 86400; // Longest averaging window a check may ask for (1 day)
+pub const TWAP_PRICE_EXPONENT: i32 = -8; // TwapTracker prices are stored as price * 10^8
+pub const RANDOMNESS_DELAY_SLOTS: u64 = 2; // Slots between a randomness commit and the slot hash it uses
+pub const RANDOMNESS_REVEAL_SLOTS: u64 = 256; // Slots after the target slot a commit may be revealed in
+pub const DEFAULT_MIN_HEALTH_FACTOR_BPS: u16 = 12000; // Lending obligations must stay 1.2x collateralized
+pub const DEFAULT_MAX_EXECUTION_ATTEMPTS: u8 = 3; // Reported failures before a proposal is marked failed
+pub const DEFAULT_CONGESTION_THRESHOLD: u16 = 10; // Recent proposals per doubling of the proposal deposit
+pub const DEFAULT_CONGESTION_HALF_LIFE: i64 = 3600; // Recent proposal volume halves every hour
+pub const MAX_CONGESTION_DOUBLINGS: u32 = 20; // Proposal deposit tops out at 2^20 times the base
+pub const MAX_ALLOWED_MINTS: usize = 16; // Mints a RiskConfig may allow trading
+pub const MAX_VENUE_CAPS: usize = 4; // Swap programs a RiskConfig may cap the volume of
+pub const RISK_VOLUME_WINDOW: i64 = 86400; // Daily trade volume window (1 day)
+pub const MAX_VALUE_MARKS: usize = 24; // Portfolio value marks a circuit breaker window keeps
+pub const MIN_CIRCUIT_BREAKER_WINDOW: i64 = 3600; // Shortest circuit breaker window (1 hour)
+pub const MAX_CIRCUIT_BREAKER_WINDOW: i64 = 604800; // Longest circuit breaker window (1 week)
+pub const OUTCOME_RETENTION_PERIOD: i64 = 30 * 86400; // Outcomes stay full accounts for 30 days before archiving
+pub const OUTCOME_TREE_DEPTH: usize = 20; // Outcome archive holds up to 2^20 leaves
+pub const PROPOSAL_PAGE_SIZE: u64 = 64; // Proposals per ProposalPage, one bit each in its status masks
+pub const MAX_QUEUED_EXECUTIONS: usize = 32; // Scheduled proposals an ExecutionQueue holds
+pub const ARB_REPORT_WINDOW: i64 = 600; // Seconds a trade has to follow an ArbReport for it to pay out
+pub const ARB_REPORT_REPUTATION: u16 = 20; // Reputation an ArbReport earns when a trade follows it
+pub const INSURANCE_CLAIM_WINDOW: i64 = OUTCOME_RETENTION_PERIOD; // A loss is claimable while its Outcome is unarchived
+pub const MAX_RECENT_INSURANCE_CLAIMS: usize = 16; // Claims an InsuranceFund remembers inside the claim window
+pub const MAX_TASK_REPUTATION_REWARD: u16 = 50; // Reputation one accepted task may earn
+pub const MIN_TASK_CLAIM_TIMEOUT: i64 = 300; // 5 minutes
+pub const MAX_COALITION_MEMBERS: usize = 16;
+pub const MAX_COMMITTEE_AGENT_TYPES: usize = 4;
+pub const MAX_CROSS_CHAIN_PAYLOAD_LENGTH: usize = 512; // Call data a CrossChain proposal may carry
+pub const BADGE_SYMBOL: &str = "SWARM";
+pub const MAX_BADGE_URI_PREFIX_LENGTH: usize = 160; // Leaves room for the badge suffix in Bubblegum's 200-byte URI
+
+// Endorsement settlement: stake returned +20% on success, -50% on failure
+pub const ENDORSEMENT_REWARD_BPS: u16 = 2000;
+pub const ENDORSEMENT_SLASH_BPS: u16 = 5000;
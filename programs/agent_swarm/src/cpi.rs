@@ -1,9 +1,39 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{self, Mint, TokenInterface};
+use crate::errors::SwarmError;
+use crate::state::ProgramWhitelist;
+use crate::payloads::{
+    LendingAction, LiquidityPayload, PerpDirection, PerpOrderPayload, StakingPayload,
+    WhirlpoolPayload,
+};
 
 /// CPI Helper for cross-program invocations
 pub struct CPIHelper;
 
+/// Accounts of a `transfer_checked` of `mint` from `from` to `to`, approved
+/// by `authority`
+pub struct TokenTransferAccounts<'a, 'info> {
+    pub from: &'a InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub to: &'a InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub authority: &'a AccountInfo<'info>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    /// Extra accounts for the mint's Token-2022 transfer hook, if any
+    pub hook_accounts: &'a [AccountInfo<'info>],
+}
+
+/// Venue a swap routes through: its program and the swap instruction's
+/// accounts in the venue's order, with the PDA that signs the swap
+pub struct SwapAccounts<'a, 'info> {
+    pub authority: &'a AccountInfo<'info>,
+    pub swap_program: &'a AccountInfo<'info>,
+    pub route_accounts: &'a [AccountInfo<'info>],
+}
+
 impl CPIHelper {
     /// Execute SPL token transfer via CPI
     pub fn transfer_tokens<'info>(
@@ -27,23 +57,70 @@ impl CPIHelper {
         Ok(())
     }
     
-    /// Execute Jupiter swap via CPI
-    /// Note: Actual Jupiter CPI requires their program ID and instruction format
+    /// Execute a `transfer_checked` under either token program, signed by
+    /// `accounts.authority`. A mint's transfer hook finds its extra accounts
+    /// in `accounts.hook_accounts`; allowing the hook is the caller's job.
+    ///
+    /// Returns the amount `to` receives after the mint's transfer fee.
+    pub fn transfer_tokens_checked<'info>(
+        accounts: &TokenTransferAccounts<'_, 'info>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<u64> {
+        let TokenTransferAccounts { from, to, mint, authority, token_program, hook_accounts } =
+            *accounts;
+        require_keys_eq!(from.mint, mint.key(), SwarmError::InvalidParameter);
+        require_keys_eq!(to.mint, mint.key(), SwarmError::InvalidParameter);
+        let fee = token_extensions::transfer_fee(&mint.to_account_info(), amount)?;
+        
+        spl_token_2022::onchain::invoke_transfer_checked(
+            token_program.key,
+            from.to_account_info(),
+            mint.to_account_info(),
+            to.to_account_info(),
+            authority.clone(),
+            hook_accounts,
+            amount,
+            mint.decimals,
+            signer_seeds,
+        )?;
+        Ok(amount - fee)
+    }
+    
+    /// Execute a Jupiter v6 route via CPI, signed by `venue.authority`.
+    ///
+    /// `route_data` is the route (or shared-accounts route) instruction as
+    /// built by the Jupiter API and `venue.route_accounts` its accounts in
+    /// order. The route itself is opaque to us, so the swap is checked by
+    /// balances: returns `(spent, received)`.
     pub fn jupiter_swap<'info>(
-        user: &AccountInfo<'info>,
-        source_token: &Account<'info, TokenAccount>,
-        destination_token: &Account<'info, TokenAccount>,
-        jupiter_program: &AccountInfo<'info>,
+        source_token: &mut InterfaceAccount<'info, token_interface::TokenAccount>,
+        destination_token: &mut InterfaceAccount<'info, token_interface::TokenAccount>,
+        venue: &SwapAccounts<'_, 'info>,
+        route_data: &[u8],
         amount_in: u64,
         minimum_amount_out: u64,
-    ) -> Result<()> {
-        msg!("CPI: Initiating Jupiter swap");
-        msg!("  Amount in: {}", amount_in);
-        msg!("  Minimum out: {}", minimum_amount_out);
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<(u64, u64)> {
+        let SwapAccounts { authority, swap_program: jupiter_program, route_accounts } = *venue;
+        require_keys_eq!(
+            jupiter_program.key(),
+            jupiter::get_jupiter_v6_program_id(),
+            SwarmError::UntrustedProgram
+        );
         
-        // In production, would construct actual Jupiter CPI call
-        // For now, this is a framework placeholder
-        Ok(())
+        let instruction = Instruction {
+            program_id: jupiter_program.key(),
+            accounts: Self::signed_metas(route_accounts, authority.key),
+            data: route_data.to_vec(),
+        };
+        let mut account_infos = route_accounts.to_vec();
+        account_infos.push(jupiter_program.clone());
+        
+        msg!("CPI: Jupiter swap of {} (min out {})", amount_in, minimum_amount_out);
+        Self::checked_swap(source_token, destination_token, amount_in, minimum_amount_out, || {
+            invoke_signed(&instruction, &account_infos, signer_seeds)
+        })
     }
     
     /// Execute Orca swap via CPI
@@ -64,37 +141,377 @@ impl CPIHelper {
         Ok(())
     }
     
-    /// Execute Raydium swap via CPI
+    /// Execute a Raydium AMM V4 `swap_base_in` via CPI, signed by
+    /// `pool.authority`.
+    ///
+    /// `pool.route_accounts` are the swap's 18 accounts in Raydium's order;
+    /// see `raydium::validate_swap_accounts`. Returns `(spent, received)`.
     pub fn raydium_swap<'info>(
-        user: &AccountInfo<'info>,
-        source_token: &Account<'info, TokenAccount>,
-        destination_token: &Account<'info, TokenAccount>,
-        pool_account: &AccountInfo<'info>,
-        raydium_program: &AccountInfo<'info>,
+        source_token: &mut InterfaceAccount<'info, token_interface::TokenAccount>,
+        destination_token: &mut InterfaceAccount<'info, token_interface::TokenAccount>,
+        pool: &SwapAccounts<'_, 'info>,
         amount_in: u64,
         minimum_amount_out: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<(u64, u64)> {
+        let SwapAccounts {
+            authority,
+            swap_program: raydium_program,
+            route_accounts: pool_accounts,
+        } = *pool;
+        require_keys_eq!(
+            raydium_program.key(),
+            raydium::get_raydium_v4_program_id(),
+            SwarmError::UntrustedProgram
+        );
+        raydium::validate_swap_accounts(
+            pool_accounts,
+            &source_token.key(),
+            &destination_token.key(),
+            authority.key,
+        )?;
+        
+        let instruction = Instruction {
+            program_id: raydium_program.key(),
+            accounts: Self::signed_metas(pool_accounts, authority.key),
+            data: raydium::swap_base_in_data(amount_in, minimum_amount_out),
+        };
+        let mut account_infos = pool_accounts.to_vec();
+        account_infos.push(raydium_program.clone());
+        
+        msg!("CPI: Raydium swap of {} (min out {})", amount_in, minimum_amount_out);
+        Self::checked_swap(source_token, destination_token, amount_in, minimum_amount_out, || {
+            invoke_signed(&instruction, &account_infos, signer_seeds)
+        })
+    }
+    
+    /// Execute a Solend deposit, withdrawal or borrow via CPI, signed by
+    /// `authority` as both obligation owner and transfer authority.
+    ///
+    /// `accounts` are the instruction's accounts in Solend's order; see
+    /// `solend::validate_accounts`.
+    pub fn solend_call<'info>(
+        authority: &AccountInfo<'info>,
+        lending_program: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        action: LendingAction,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
     ) -> Result<()> {
-        msg!("CPI: Initiating Raydium swap");
-        msg!("  Amount in: {}", amount_in);
-        msg!("  Minimum out: {}", minimum_amount_out);
+        require_keys_eq!(lending_program.key(), solend::PROGRAM_ID, SwarmError::UntrustedProgram);
+        solend::validate_accounts(action, accounts, authority.key)?;
+        
+        let instruction = Instruction {
+            program_id: lending_program.key(),
+            accounts: Self::signed_metas(accounts, authority.key),
+            data: solend::instruction_data(action, amount),
+        };
+        let mut account_infos = accounts.to_vec();
+        account_infos.push(lending_program.clone());
+        
+        msg!("CPI: Solend {:?} of {}", action, amount);
+        invoke_signed(&instruction, &account_infos, signer_seeds)?;
+        Ok(())
+    }
+    
+    /// Execute a Marinade deposit, order-unstake or claim via CPI, signed by
+    /// `authority`. An order's ticket account must already exist.
+    ///
+    /// `accounts` are the instruction's accounts in Marinade's order; see
+    /// `marinade::validate_accounts`.
+    pub fn marinade_call<'info>(
+        authority: &AccountInfo<'info>,
+        marinade_program: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        payload: &StakingPayload,
+        ticket: &Pubkey,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require_keys_eq!(marinade_program.key(), marinade::PROGRAM_ID, SwarmError::UntrustedProgram);
+        marinade::validate_accounts(payload, accounts, authority.key, ticket)?;
+        
+        let instruction = Instruction {
+            program_id: marinade_program.key(),
+            accounts: Self::signed_metas(accounts, authority.key),
+            data: marinade::instruction_data(payload),
+        };
+        let mut account_infos = accounts.to_vec();
+        account_infos.push(marinade_program.clone());
+        
+        msg!("CPI: Marinade {:?}", payload);
+        invoke_signed(&instruction, &account_infos, signer_seeds)?;
+        Ok(())
+    }
+    
+    /// Place a Drift perp market order via CPI, signed by `authority` as the
+    /// user account's authority.
+    ///
+    /// `accounts` are Drift's state, the vault's user account and `authority`,
+    /// followed by the oracles and markets Drift reads for the order.
+    pub fn drift_place_perp_order<'info>(
+        authority: &AccountInfo<'info>,
+        drift_program: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        payload: &PerpOrderPayload,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require_keys_eq!(drift_program.key(), drift::PROGRAM_ID, SwarmError::UntrustedProgram);
+        require!(accounts.len() >= 3, SwarmError::MissingExecutionAccount);
+        require_keys_eq!(*accounts[2].key, *authority.key, SwarmError::InvalidPayload);
+        
+        let instruction = Instruction {
+            program_id: drift_program.key(),
+            accounts: Self::signed_metas(accounts, authority.key),
+            data: drift::place_perp_order_data(payload)?,
+        };
+        let mut account_infos = accounts.to_vec();
+        account_infos.push(drift_program.clone());
+        
+        msg!(
+            "CPI: Drift {:?} order of {} in market {}",
+            payload.direction,
+            payload.base_asset_amount,
+            payload.market_index
+        );
+        invoke_signed(&instruction, &account_infos, signer_seeds)?;
+        Ok(())
+    }
+    
+    /// Execute a Meteora DLMM position instruction via CPI, signed by
+    /// `authority` as the position's owner and, when opening, its payer
+    /// and base.
+    ///
+    /// `accounts` are the instruction's accounts in DLMM's order; see
+    /// `meteora::validate_accounts`.
+    pub fn meteora_dlmm_call<'info>(
+        authority: &AccountInfo<'info>,
+        dlmm_program: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        payload: &LiquidityPayload,
+        rent_receiver: &Pubkey,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require_keys_eq!(dlmm_program.key(), meteora::PROGRAM_ID, SwarmError::UntrustedProgram);
+        meteora::validate_accounts(payload, accounts, authority.key, rent_receiver)?;
+        
+        let instruction = Instruction {
+            program_id: dlmm_program.key(),
+            accounts: Self::signed_metas(accounts, authority.key),
+            data: meteora::instruction_data(payload)?,
+        };
+        let mut account_infos = accounts.to_vec();
+        account_infos.push(dlmm_program.clone());
+        
+        msg!("CPI: Meteora DLMM {:?}", payload);
+        invoke_signed(&instruction, &account_infos, signer_seeds)?;
+        Ok(())
+    }
+    
+    /// Execute an Orca Whirlpool position instruction via CPI, signed by
+    /// `authority` as the position's authority and, when opening, its
+    /// funder and owner. Collecting fees first updates them from the
+    /// position's tick arrays.
+    ///
+    /// `accounts` are the instruction's accounts in Whirlpool's order; see
+    /// `whirlpool::validate_accounts`.
+    pub fn whirlpool_call<'info>(
+        authority: &AccountInfo<'info>,
+        whirlpool_program: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        payload: &WhirlpoolPayload,
+        rent_receiver: &Pubkey,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require_keys_eq!(whirlpool_program.key(), whirlpool::PROGRAM_ID, SwarmError::UntrustedProgram);
+        whirlpool::validate_accounts(payload, accounts, authority.key, rent_receiver)?;
+        
+        let accounts = if let WhirlpoolPayload::CollectFees = payload {
+            // whirlpool, position, tick array lower, tick array upper
+            let update_accounts = [
+                accounts[0].clone(),
+                accounts[2].clone(),
+                accounts[9].clone(),
+                accounts[10].clone(),
+            ];
+            let instruction = Instruction {
+                program_id: whirlpool_program.key(),
+                accounts: Self::signed_metas(&update_accounts, authority.key),
+                data: whirlpool::UPDATE_FEES_AND_REWARDS_DISCRIMINATOR.to_vec(),
+            };
+            let mut account_infos = update_accounts.to_vec();
+            account_infos.push(whirlpool_program.clone());
+            invoke_signed(&instruction, &account_infos, signer_seeds)?;
+            &accounts[..whirlpool::COLLECT_FEES_ACCOUNT_COUNT]
+        } else {
+            accounts
+        };
         
-        // In production, would construct actual Raydium CPI call
+        let instruction = Instruction {
+            program_id: whirlpool_program.key(),
+            accounts: Self::signed_metas(accounts, authority.key),
+            data: whirlpool::instruction_data(payload, accounts)?,
+        };
+        let mut account_infos = accounts.to_vec();
+        account_infos.push(whirlpool_program.clone());
+        
+        msg!("CPI: Orca Whirlpool {:?}", payload);
+        invoke_signed(&instruction, &account_infos, signer_seeds)?;
+        Ok(())
+    }
+    
+    /// Post `payload` through the Wormhole core bridge, emitted by a PDA and
+    /// paying the bridge's message fee from the payer.
+    ///
+    /// `accounts` are `post_message`'s accounts in the bridge's order; see
+    /// `wormhole::validate_accounts`. `signer_seeds` must sign for both the
+    /// emitter and the message, which the bridge creates and so can only be
+    /// posted once.
+    pub fn wormhole_post_message<'info>(
+        wormhole_program: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        nonce: u32,
+        payload: &[u8],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require_keys_eq!(wormhole_program.key(), wormhole::PROGRAM_ID, SwarmError::UntrustedProgram);
+        wormhole::validate_accounts(accounts)?;
+        
+        let fee = wormhole::message_fee(&accounts[0])?;
+        if fee > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    accounts[8].clone(),
+                    anchor_lang::system_program::Transfer {
+                        from: accounts[4].clone(),
+                        to: accounts[5].clone(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+        
+        let instruction = Instruction {
+            program_id: wormhole_program.key(),
+            accounts: accounts
+                .iter()
+                .enumerate()
+                .map(|(slot, account)| AccountMeta {
+                    pubkey: *account.key,
+                    is_signer: account.is_signer || wormhole::PDA_SIGNER_SLOTS.contains(&slot),
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: wormhole::instruction_data(nonce, payload),
+        };
+        let mut account_infos = accounts.to_vec();
+        account_infos.push(wormhole_program.clone());
+        
+        msg!("CPI: Wormhole post_message ({} bytes)", payload.len());
+        invoke_signed(&instruction, &account_infos, signer_seeds)?;
+        Ok(())
+    }
+    
+    /// Mint a compressed NFT into a Bubblegum tree via `mint_v1`, signed by
+    /// the PDA tree delegate.
+    ///
+    /// `accounts` are `mint_v1`'s accounts in Bubblegum's order; see
+    /// `bubblegum::validate_accounts`.
+    pub fn bubblegum_mint<'info>(
+        bubblegum_program: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        tree_delegate: &Pubkey,
+        metadata: &bubblegum::MetadataArgs,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require_keys_eq!(bubblegum_program.key(), bubblegum::PROGRAM_ID, SwarmError::UntrustedProgram);
+        bubblegum::validate_accounts(accounts, tree_delegate)?;
+        
+        let instruction = Instruction {
+            program_id: bubblegum_program.key(),
+            accounts: Self::signed_metas(accounts, tree_delegate),
+            data: bubblegum::instruction_data(metadata)?,
+        };
+        let mut account_infos = accounts.to_vec();
+        account_infos.push(bubblegum_program.clone());
+        
+        msg!("CPI: Bubblegum mint_v1 {}", metadata.name);
+        invoke_signed(&instruction, &account_infos, signer_seeds)?;
         Ok(())
     }
     
-    /// Execute generic protocol interaction via CPI
+    /// Account metas for `accounts`, with the PDA `authority` marked as a
+    /// signer wherever the route expects it
+    fn signed_metas(accounts: &[AccountInfo], authority: &Pubkey) -> Vec<AccountMeta> {
+        accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer || account.key == authority,
+                is_writable: account.is_writable,
+            })
+            .collect()
+    }
+    
+    /// Run `swap` and enforce its limits on the actual balance changes
+    fn checked_swap<'info>(
+        source_token: &mut InterfaceAccount<'info, token_interface::TokenAccount>,
+        destination_token: &mut InterfaceAccount<'info, token_interface::TokenAccount>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        swap: impl FnOnce() -> std::result::Result<(), ProgramError>,
+    ) -> Result<(u64, u64)> {
+        let source_before = source_token.amount;
+        let destination_before = destination_token.amount;
+        
+        swap()?;
+        
+        source_token.reload()?;
+        destination_token.reload()?;
+        let spent = source_before.saturating_sub(source_token.amount);
+        let received = destination_token.amount.saturating_sub(destination_before);
+        require!(
+            spent <= amount_in && received >= minimum_amount_out,
+            SwarmError::SlippageExceeded
+        );
+        
+        msg!("CPI: Swapped {} for {}", spent, received);
+        Ok((spent, received))
+    }
+    
+    /// Execute a generic protocol call via CPI, signed by `authority`.
+    ///
+    /// `metas` describe the instruction's accounts; apart from `authority`,
+    /// every one of them must be among `accounts`. Whitelisting the target
+    /// is the caller's job.
     pub fn execute_protocol_call<'info>(
-        user: &AccountInfo<'info>,
+        authority: &AccountInfo<'info>,
         target_program: &AccountInfo<'info>,
         instruction_data: Vec<u8>,
-        accounts: Vec<AccountInfo<'info>>,
+        metas: Vec<AccountMeta>,
+        accounts: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
     ) -> Result<()> {
+        let mut account_infos = accounts.to_vec();
+        account_infos.push(authority.clone());
+        account_infos.push(target_program.clone());
+        require!(
+            metas
+                .iter()
+                .all(|meta| account_infos.iter().any(|account| *account.key == meta.pubkey)),
+            SwarmError::MissingExecutionAccount
+        );
+        
         msg!("CPI: Executing generic protocol call");
         msg!("  Target program: {}", target_program.key());
         msg!("  Instruction size: {} bytes", instruction_data.len());
         
-        // Generic CPI framework for any Solana program
-        // Agents can propose CPI calls to any protocol
+        let instruction = Instruction {
+            program_id: target_program.key(),
+            accounts: metas,
+            data: instruction_data,
+        };
+        
+        invoke_signed(&instruction, &account_infos, signer_seeds)?;
         Ok(())
     }
 }
@@ -106,9 +523,177 @@ pub mod whirlpool {
     /// Orca Whirlpool program ID (compile-time constant — no runtime unwrap)
     pub const PROGRAM_ID: Pubkey = solana_program::pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
 
+    pub const OPEN_POSITION_DISCRIMINATOR: [u8; 8] = [135, 128, 47, 77, 15, 152, 240, 49];
+    pub const INCREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
+    pub const DECREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [160, 38, 208, 111, 104, 91, 44, 1];
+    pub const UPDATE_FEES_AND_REWARDS_DISCRIMINATOR: [u8; 8] = [154, 230, 250, 13, 236, 209, 75, 223];
+    pub const COLLECT_FEES_DISCRIMINATOR: [u8; 8] = [164, 152, 207, 99, 30, 186, 19, 182];
+    pub const CLOSE_POSITION_DISCRIMINATOR: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+
+    const POSITION_SEED: &[u8] = b"position";
+
+    /// Size of a `Position` account, including its discriminator
+    pub const POSITION_ACCOUNT_LEN: usize = 216;
+
+    /// Open: funder, owner, position, position mint, position token account,
+    /// whirlpool, token program, system program, rent, associated token
+    /// program
+    pub const OPEN_ACCOUNT_COUNT: usize = 10;
+
+    /// Increase or decrease: whirlpool, token program, position authority,
+    /// position, position token account, token owner account A, token owner
+    /// account B, token vault A, token vault B, tick array lower, tick array
+    /// upper
+    pub const MODIFY_ACCOUNT_COUNT: usize = 11;
+
+    /// Collect fees: whirlpool, position authority, position, position token
+    /// account, token owner account A, token vault A, token owner account B,
+    /// token vault B, token program
+    pub const COLLECT_FEES_ACCOUNT_COUNT: usize = 9;
+
+    /// Close: position authority, receiver, position, position mint,
+    /// position token account, token program
+    pub const CLOSE_ACCOUNT_COUNT: usize = 6;
+
     pub fn get_whirlpool_program_id() -> Pubkey {
         PROGRAM_ID
     }
+
+    /// The position tracked by the NFT `position_mint`
+    pub fn find_position_address(position_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[POSITION_SEED, position_mint.as_ref()], &PROGRAM_ID)
+    }
+
+    /// Rent the funder pays to open a position: the position, its NFT mint
+    /// and the token account holding the NFT
+    pub fn open_position_rent(rent: &Rent) -> u64 {
+        rent.minimum_balance(POSITION_ACCOUNT_LEN)
+            + rent.minimum_balance(anchor_spl::token::Mint::LEN)
+            + rent.minimum_balance(TokenAccount::LEN)
+    }
+
+    /// Instruction data for `payload`; opening a position reads its mint
+    /// from `accounts` for the position's bump
+    pub fn instruction_data(payload: &WhirlpoolPayload, accounts: &[AccountInfo]) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        match payload {
+            WhirlpoolPayload::OpenPosition { tick_lower_index, tick_upper_index } => {
+                data.extend_from_slice(&OPEN_POSITION_DISCRIMINATOR);
+                data.push(find_position_address(accounts[3].key).1);
+                data.extend_from_slice(&tick_lower_index.to_le_bytes());
+                data.extend_from_slice(&tick_upper_index.to_le_bytes());
+            }
+            WhirlpoolPayload::IncreaseLiquidity { liquidity_amount, token_max_a, token_max_b } => {
+                data.extend_from_slice(&INCREASE_LIQUIDITY_DISCRIMINATOR);
+                data.extend_from_slice(&liquidity_amount.to_le_bytes());
+                data.extend_from_slice(&token_max_a.to_le_bytes());
+                data.extend_from_slice(&token_max_b.to_le_bytes());
+            }
+            WhirlpoolPayload::DecreaseLiquidity { liquidity_amount, token_min_a, token_min_b } => {
+                data.extend_from_slice(&DECREASE_LIQUIDITY_DISCRIMINATOR);
+                data.extend_from_slice(&liquidity_amount.to_le_bytes());
+                data.extend_from_slice(&token_min_a.to_le_bytes());
+                data.extend_from_slice(&token_min_b.to_le_bytes());
+            }
+            WhirlpoolPayload::CollectFees => {
+                data.extend_from_slice(&COLLECT_FEES_DISCRIMINATOR);
+            }
+            WhirlpoolPayload::ClosePosition => {
+                data.extend_from_slice(&CLOSE_POSITION_DISCRIMINATOR);
+            }
+        }
+        Ok(data)
+    }
+
+    /// The position a vault-held NFT token account tracks
+    fn position_held_by(token_account: &AccountInfo, owner: &Pubkey) -> Result<Pubkey> {
+        let token_account = TokenAccount::try_deserialize(&mut &token_account.try_borrow_data()?[..])?;
+        require_keys_eq!(token_account.owner, *owner, SwarmError::Unauthorized);
+        require!(token_account.amount == 1, SwarmError::InvalidPayload);
+        Ok(find_position_address(&token_account.mint).0)
+    }
+
+    /// Check an instruction's accounts: the vault authority as funder, owner
+    /// or position authority, the position of an NFT the vault holds, the
+    /// vault's token accounts and the treasury receiving closed positions'
+    /// rent. Collecting fees also takes the position's lower and upper tick
+    /// arrays after the instruction's accounts.
+    pub fn validate_accounts(
+        payload: &WhirlpoolPayload,
+        accounts: &[AccountInfo],
+        owner: &Pubkey,
+        rent_receiver: &Pubkey,
+    ) -> Result<()> {
+        match payload {
+            WhirlpoolPayload::OpenPosition { .. } => {
+                require!(accounts.len() == OPEN_ACCOUNT_COUNT, SwarmError::MissingExecutionAccount);
+                require_keys_eq!(*accounts[0].key, *owner, SwarmError::InvalidPayload);
+                require_keys_eq!(*accounts[1].key, *owner, SwarmError::InvalidPayload);
+                require_keys_eq!(
+                    *accounts[2].key,
+                    find_position_address(accounts[3].key).0,
+                    SwarmError::InvalidPayload
+                );
+                require_keys_eq!(
+                    *accounts[4].key,
+                    anchor_spl::associated_token::get_associated_token_address(owner, accounts[3].key),
+                    SwarmError::InvalidPayload
+                );
+                require_keys_eq!(*accounts[6].key, anchor_spl::token::ID, SwarmError::UntrustedProgram);
+                require_keys_eq!(*accounts[7].key, System::id(), SwarmError::UntrustedProgram);
+            }
+            WhirlpoolPayload::IncreaseLiquidity { .. } | WhirlpoolPayload::DecreaseLiquidity { .. } => {
+                require!(accounts.len() == MODIFY_ACCOUNT_COUNT, SwarmError::MissingExecutionAccount);
+                require_keys_eq!(*accounts[1].key, anchor_spl::token::ID, SwarmError::UntrustedProgram);
+                require_keys_eq!(*accounts[2].key, *owner, SwarmError::InvalidPayload);
+                require_keys_eq!(
+                    *accounts[3].key,
+                    position_held_by(&accounts[4], owner)?,
+                    SwarmError::InvalidPayload
+                );
+                for slot in [5, 6] {
+                    let token_account =
+                        TokenAccount::try_deserialize(&mut &accounts[slot].try_borrow_data()?[..])?;
+                    require_keys_eq!(token_account.owner, *owner, SwarmError::Unauthorized);
+                }
+            }
+            WhirlpoolPayload::CollectFees => {
+                require!(
+                    accounts.len() == COLLECT_FEES_ACCOUNT_COUNT + 2,
+                    SwarmError::MissingExecutionAccount
+                );
+                require_keys_eq!(*accounts[1].key, *owner, SwarmError::InvalidPayload);
+                require_keys_eq!(
+                    *accounts[2].key,
+                    position_held_by(&accounts[3], owner)?,
+                    SwarmError::InvalidPayload
+                );
+                for slot in [4, 6] {
+                    let token_account =
+                        TokenAccount::try_deserialize(&mut &accounts[slot].try_borrow_data()?[..])?;
+                    require_keys_eq!(token_account.owner, *owner, SwarmError::Unauthorized);
+                }
+                require_keys_eq!(*accounts[8].key, anchor_spl::token::ID, SwarmError::UntrustedProgram);
+            }
+            WhirlpoolPayload::ClosePosition => {
+                require!(accounts.len() == CLOSE_ACCOUNT_COUNT, SwarmError::MissingExecutionAccount);
+                require_keys_eq!(*accounts[0].key, *owner, SwarmError::InvalidPayload);
+                require_keys_eq!(*accounts[1].key, *rent_receiver, SwarmError::InvalidPayload);
+                require_keys_eq!(
+                    *accounts[2].key,
+                    find_position_address(accounts[3].key).0,
+                    SwarmError::InvalidPayload
+                );
+                require_keys_eq!(
+                    *accounts[4].key,
+                    anchor_spl::associated_token::get_associated_token_address(owner, accounts[3].key),
+                    SwarmError::InvalidPayload
+                );
+                require_keys_eq!(*accounts[5].key, anchor_spl::token::ID, SwarmError::UntrustedProgram);
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Raydium integration helpers
@@ -118,9 +703,49 @@ pub mod raydium {
     /// Raydium AMM V4 program ID (compile-time constant — no runtime unwrap)
     pub const PROGRAM_ID: Pubkey = solana_program::pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
 
+    /// AMM V4 pool authority, the PDA of `[b"amm authority"]` under `PROGRAM_ID`
+    pub const AMM_AUTHORITY: Pubkey = solana_program::pubkey!("5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1");
+
+    /// Instruction tag of `swap_base_in`
+    pub const SWAP_BASE_IN_TAG: u8 = 9;
+
+    /// Accounts taken by `swap_base_in`: token program, amm, amm authority,
+    /// open orders, target orders, coin vault, pc vault, market program,
+    /// market, bids, asks, event queue, market coin vault, market pc vault,
+    /// market vault signer, user source, user destination, user owner
+    pub const SWAP_ACCOUNT_COUNT: usize = 18;
+
     pub fn get_raydium_v4_program_id() -> Pubkey {
         PROGRAM_ID
     }
+
+    /// `swap_base_in` instruction data
+    pub fn swap_base_in_data(amount_in: u64, minimum_amount_out: u64) -> Vec<u8> {
+        let mut data = Vec::with_capacity(17);
+        data.push(SWAP_BASE_IN_TAG);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+        data
+    }
+
+    /// Check the swap's account layout: a pool owned by the AMM program,
+    /// the AMM authority, and the swarm's own token accounts and signer in
+    /// the user slots
+    pub fn validate_swap_accounts(
+        accounts: &[AccountInfo],
+        source: &Pubkey,
+        destination: &Pubkey,
+        owner: &Pubkey,
+    ) -> Result<()> {
+        require!(accounts.len() == SWAP_ACCOUNT_COUNT, SwarmError::MissingExecutionAccount);
+        require_keys_eq!(*accounts[0].key, anchor_spl::token::ID, SwarmError::UntrustedProgram);
+        require_keys_eq!(*accounts[1].owner, PROGRAM_ID, SwarmError::UntrustedProgram);
+        require_keys_eq!(*accounts[2].key, AMM_AUTHORITY, SwarmError::UntrustedProgram);
+        require_keys_eq!(*accounts[15].key, *source, SwarmError::InvalidPayload);
+        require_keys_eq!(*accounts[16].key, *destination, SwarmError::InvalidPayload);
+        require_keys_eq!(*accounts[17].key, *owner, SwarmError::InvalidPayload);
+        Ok(())
+    }
 }
 
 /// Jupiter integration helpers
@@ -134,3 +759,778 @@ pub mod jupiter {
         PROGRAM_ID
     }
 }
+
+/// Solend (SPL token-lending) integration helpers
+pub mod solend {
+    use super::*;
+
+    /// Solend main pool program ID (compile-time constant — no runtime unwrap)
+    pub const PROGRAM_ID: Pubkey = solana_program::pubkey!("So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo");
+
+    pub const BORROW_OBLIGATION_LIQUIDITY_TAG: u8 = 10;
+    pub const DEPOSIT_RESERVE_LIQUIDITY_AND_OBLIGATION_COLLATERAL_TAG: u8 = 14;
+    pub const WITHDRAW_OBLIGATION_COLLATERAL_AND_REDEEM_RESERVE_COLLATERAL_TAG: u8 = 15;
+    pub const FLASH_BORROW_RESERVE_LIQUIDITY_TAG: u8 = 19;
+
+    /// Where the swarm's own accounts sit in one instruction's account list
+    pub struct AccountLayout {
+        pub account_count: usize,
+        /// Token accounts the vault sends from or receives into
+        pub vault_token_slots: &'static [usize],
+        pub obligation_slot: usize,
+        /// Obligation owner and, where taken, user transfer authority
+        pub authority_slots: &'static [usize],
+        pub token_program_slot: usize,
+    }
+
+    /// Deposit: source liquidity, destination collateral, reserve, reserve
+    /// liquidity supply, reserve collateral mint, lending market, market
+    /// authority, collateral supply, obligation, obligation owner, pyth
+    /// price, switchboard price, transfer authority, token program
+    pub const DEPOSIT_LAYOUT: AccountLayout = AccountLayout {
+        account_count: 14,
+        vault_token_slots: &[0, 1],
+        obligation_slot: 8,
+        authority_slots: &[9, 12],
+        token_program_slot: 13,
+    };
+
+    /// Withdraw: collateral supply, destination collateral, reserve,
+    /// obligation, lending market, market authority, destination liquidity,
+    /// reserve collateral mint, reserve liquidity supply, obligation owner,
+    /// transfer authority, token program
+    pub const WITHDRAW_LAYOUT: AccountLayout = AccountLayout {
+        account_count: 12,
+        vault_token_slots: &[1, 6],
+        obligation_slot: 3,
+        authority_slots: &[9, 10],
+        token_program_slot: 11,
+    };
+
+    /// Borrow: reserve liquidity supply, destination liquidity, reserve,
+    /// fee receiver, obligation, lending market, market authority,
+    /// obligation owner, token program
+    pub const BORROW_LAYOUT: AccountLayout = AccountLayout {
+        account_count: 9,
+        vault_token_slots: &[1],
+        obligation_slot: 4,
+        authority_slots: &[7],
+        token_program_slot: 8,
+    };
+
+    pub fn layout(action: LendingAction) -> &'static AccountLayout {
+        match action {
+            LendingAction::Deposit => &DEPOSIT_LAYOUT,
+            LendingAction::Withdraw => &WITHDRAW_LAYOUT,
+            LendingAction::Borrow => &BORROW_LAYOUT,
+        }
+    }
+
+    pub fn instruction_data(action: LendingAction, amount: u64) -> Vec<u8> {
+        let tag = match action {
+            LendingAction::Deposit => DEPOSIT_RESERVE_LIQUIDITY_AND_OBLIGATION_COLLATERAL_TAG,
+            LendingAction::Withdraw => WITHDRAW_OBLIGATION_COLLATERAL_AND_REDEEM_RESERVE_COLLATERAL_TAG,
+            LendingAction::Borrow => BORROW_OBLIGATION_LIQUIDITY_TAG,
+        };
+        let mut data = Vec::with_capacity(9);
+        data.push(tag);
+        data.extend_from_slice(&amount.to_le_bytes());
+        data
+    }
+
+    /// Check an instruction's accounts: the vault's token accounts, the
+    /// vault's own obligation and the vault authority in the signer slots
+    pub fn validate_accounts(
+        action: LendingAction,
+        accounts: &[AccountInfo],
+        owner: &Pubkey,
+    ) -> Result<()> {
+        let layout = layout(action);
+        require!(accounts.len() == layout.account_count, SwarmError::MissingExecutionAccount);
+        require_keys_eq!(
+            *accounts[layout.token_program_slot].key,
+            anchor_spl::token::ID,
+            SwarmError::UntrustedProgram
+        );
+        for &slot in layout.authority_slots {
+            require_keys_eq!(*accounts[slot].key, *owner, SwarmError::InvalidPayload);
+        }
+        for &slot in layout.vault_token_slots {
+            let token_account = TokenAccount::try_deserialize(&mut &accounts[slot].try_borrow_data()?[..])?;
+            require_keys_eq!(token_account.owner, *owner, SwarmError::Unauthorized);
+        }
+        let obligation = Obligation::load(&accounts[layout.obligation_slot])?;
+        require_keys_eq!(obligation.owner, *owner, SwarmError::Unauthorized);
+        Ok(())
+    }
+
+    /// The leading fields of a Solend `Obligation` account. Values are USD
+    /// amounts scaled by 10^18.
+    pub struct Obligation {
+        pub stale: bool,
+        pub owner: Pubkey,
+        pub deposited_value: u128,
+        pub borrowed_value: u128,
+        pub allowed_borrow_value: u128,
+        pub unhealthy_borrow_value: u128,
+    }
+
+    /// Bytes read from the front of an obligation: version, last update
+    /// slot and stale flag, lending market, owner and four values
+    const OBLIGATION_PREFIX_LEN: usize = 1 + 8 + 1 + 32 + 32 + 4 * 16;
+
+    impl Obligation {
+        pub fn load(account: &AccountInfo) -> Result<Self> {
+            require_keys_eq!(*account.owner, PROGRAM_ID, SwarmError::UntrustedProgram);
+            let data = account.try_borrow_data()?;
+            require!(data.len() >= OBLIGATION_PREFIX_LEN, SwarmError::InvalidPayload);
+
+            let value = |offset: usize| {
+                u128::from_le_bytes(data[offset..offset + 16].try_into().unwrap())
+            };
+            Ok(Self {
+                stale: data[9] != 0,
+                owner: Pubkey::new_from_array(data[42..74].try_into().unwrap()),
+                deposited_value: value(74),
+                borrowed_value: value(90),
+                allowed_borrow_value: value(106),
+                unhealthy_borrow_value: value(122),
+            })
+        }
+
+        /// Liquidation threshold over debt in bps; `None` without debt
+        pub fn health_factor_bps(&self) -> Option<u128> {
+            if self.borrowed_value == 0 {
+                return None;
+            }
+            Some(self.unhealthy_borrow_value.saturating_mul(10_000) / self.borrowed_value)
+        }
+    }
+}
+
+/// Marinade liquid staking integration helpers
+pub mod marinade {
+    use super::*;
+
+    /// Marinade Finance program ID (compile-time constant — no runtime unwrap)
+    pub const PROGRAM_ID: Pubkey = solana_program::pubkey!("MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD");
+
+    pub const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+    pub const ORDER_UNSTAKE_DISCRIMINATOR: [u8; 8] = [97, 167, 144, 107, 117, 190, 128, 36];
+    pub const CLAIM_DISCRIMINATOR: [u8; 8] = [62, 198, 214, 193, 213, 159, 108, 210];
+
+    /// Size of a delayed-unstake ticket: discriminator, state, beneficiary,
+    /// lamports and created epoch
+    pub const TICKET_ACCOUNT_LEN: usize = 8 + 32 + 32 + 8 + 8;
+
+    /// Deposit: state, mSOL mint, liquidity pool SOL leg, liquidity pool
+    /// mSOL leg, mSOL leg authority, reserve, transfer from, mint to, mSOL
+    /// mint authority, system program, token program
+    pub const DEPOSIT_ACCOUNT_COUNT: usize = 11;
+
+    /// Order unstake: state, mSOL mint, burn from, burn authority, new
+    /// ticket, clock, rent, token program
+    pub const ORDER_UNSTAKE_ACCOUNT_COUNT: usize = 8;
+
+    /// Claim: state, reserve, ticket, transfer SOL to, clock, system program
+    pub const CLAIM_ACCOUNT_COUNT: usize = 6;
+
+    pub fn instruction_data(payload: &StakingPayload) -> Vec<u8> {
+        match payload {
+            StakingPayload::Deposit { lamports } => {
+                [&DEPOSIT_DISCRIMINATOR[..], &lamports.to_le_bytes()].concat()
+            }
+            StakingPayload::OrderUnstake { msol_amount } => {
+                [&ORDER_UNSTAKE_DISCRIMINATOR[..], &msol_amount.to_le_bytes()].concat()
+            }
+            StakingPayload::Claim { .. } => CLAIM_DISCRIMINATOR.to_vec(),
+        }
+    }
+
+    /// Check an instruction's accounts: the vault authority paying, burning
+    /// or receiving, the vault's mSOL account, and the swarm's own ticket
+    pub fn validate_accounts(
+        payload: &StakingPayload,
+        accounts: &[AccountInfo],
+        owner: &Pubkey,
+        ticket: &Pubkey,
+    ) -> Result<()> {
+        let vault_token_slot = match payload {
+            StakingPayload::Deposit { .. } => {
+                require!(accounts.len() == DEPOSIT_ACCOUNT_COUNT, SwarmError::MissingExecutionAccount);
+                require_keys_eq!(*accounts[6].key, *owner, SwarmError::InvalidPayload);
+                require_keys_eq!(*accounts[9].key, System::id(), SwarmError::UntrustedProgram);
+                require_keys_eq!(*accounts[10].key, anchor_spl::token::ID, SwarmError::UntrustedProgram);
+                Some(7)
+            }
+            StakingPayload::OrderUnstake { .. } => {
+                require!(
+                    accounts.len() == ORDER_UNSTAKE_ACCOUNT_COUNT,
+                    SwarmError::MissingExecutionAccount
+                );
+                require_keys_eq!(*accounts[3].key, *owner, SwarmError::InvalidPayload);
+                require_keys_eq!(*accounts[4].key, *ticket, SwarmError::InvalidPayload);
+                require_keys_eq!(*accounts[7].key, anchor_spl::token::ID, SwarmError::UntrustedProgram);
+                Some(2)
+            }
+            StakingPayload::Claim { .. } => {
+                require!(accounts.len() == CLAIM_ACCOUNT_COUNT, SwarmError::MissingExecutionAccount);
+                require_keys_eq!(*accounts[2].key, *ticket, SwarmError::InvalidPayload);
+                require_keys_eq!(*accounts[3].key, *owner, SwarmError::InvalidPayload);
+                require_keys_eq!(*accounts[5].key, System::id(), SwarmError::UntrustedProgram);
+                None
+            }
+        };
+        if let Some(slot) = vault_token_slot {
+            let token_account = TokenAccount::try_deserialize(&mut &accounts[slot].try_borrow_data()?[..])?;
+            require_keys_eq!(token_account.owner, *owner, SwarmError::Unauthorized);
+        }
+        Ok(())
+    }
+}
+
+/// Drift v2 perpetuals integration helpers
+pub mod drift {
+    use super::*;
+
+    /// Drift v2 program ID (compile-time constant — no runtime unwrap)
+    pub const PROGRAM_ID: Pubkey = solana_program::pubkey!("dRiftyHA39MWEi3m9aunc5MzRF1JYuBsbn6VPcn33UH");
+
+    pub const PLACE_PERP_ORDER_DISCRIMINATOR: [u8; 8] = [69, 161, 93, 202, 120, 126, 76, 185];
+
+    const ORDER_TYPE_MARKET: u8 = 0;
+    const MARKET_TYPE_PERP: u8 = 1;
+    const POST_ONLY_NONE: u8 = 0;
+    const TRIGGER_CONDITION_ABOVE: u8 = 0;
+
+    /// Drift's `OrderParams`, with its enums as their Borsh tags
+    #[derive(AnchorSerialize)]
+    struct OrderParams {
+        order_type: u8,
+        market_type: u8,
+        direction: u8,
+        user_order_id: u8,
+        base_asset_amount: u64,
+        price: u64,
+        market_index: u16,
+        reduce_only: bool,
+        post_only: u8,
+        immediate_or_cancel: bool,
+        max_ts: Option<i64>,
+        trigger_price: Option<u64>,
+        trigger_condition: u8,
+        oracle_price_offset: Option<i32>,
+        auction_duration: Option<u8>,
+        auction_start_price: Option<i64>,
+        auction_end_price: Option<i64>,
+    }
+
+    /// `place_perp_order` data for a market order bounded by `limit_price`
+    pub fn place_perp_order_data(payload: &PerpOrderPayload) -> Result<Vec<u8>> {
+        let params = OrderParams {
+            order_type: ORDER_TYPE_MARKET,
+            market_type: MARKET_TYPE_PERP,
+            direction: match payload.direction {
+                PerpDirection::Long => 0,
+                PerpDirection::Short => 1,
+            },
+            user_order_id: 0,
+            base_asset_amount: payload.base_asset_amount,
+            price: payload.limit_price,
+            market_index: payload.market_index,
+            reduce_only: payload.reduce_only,
+            post_only: POST_ONLY_NONE,
+            immediate_or_cancel: false,
+            max_ts: None,
+            trigger_price: None,
+            trigger_condition: TRIGGER_CONDITION_ABOVE,
+            oracle_price_offset: None,
+            auction_duration: None,
+            auction_start_price: None,
+            auction_end_price: None,
+        };
+        let mut data = PLACE_PERP_ORDER_DISCRIMINATOR.to_vec();
+        params.serialize(&mut data)?;
+        Ok(data)
+    }
+
+    /// Offset of `perp_positions` in a `User` account: discriminator,
+    /// authority, delegate, name and eight 40-byte spot positions
+    const PERP_POSITIONS_OFFSET: usize = 8 + 32 + 32 + 32 + 8 * 40;
+    const PERP_POSITION_LEN: usize = 96;
+    const PERP_POSITION_COUNT: usize = 8;
+
+    /// A user's exposure in one perp market, in base units
+    #[derive(Default)]
+    pub struct PerpPosition {
+        pub base_asset_amount: i64,
+        pub open_bids: i64,
+        pub open_asks: i64,
+    }
+
+    impl PerpPosition {
+        /// Largest absolute position the user could reach if every open
+        /// order filled
+        pub fn worst_case_size(&self) -> u64 {
+            self.base_asset_amount
+                .unsigned_abs()
+                .saturating_add(self.open_bids.unsigned_abs())
+                .saturating_add(self.open_asks.unsigned_abs())
+        }
+    }
+
+    /// Read `authority`'s position in `market_index` from a Drift `User`
+    pub fn load_perp_position(
+        user: &AccountInfo,
+        authority: &Pubkey,
+        market_index: u16,
+    ) -> Result<PerpPosition> {
+        require_keys_eq!(*user.owner, PROGRAM_ID, SwarmError::UntrustedProgram);
+        let data = user.try_borrow_data()?;
+        require!(
+            data.len() >= PERP_POSITIONS_OFFSET + PERP_POSITION_COUNT * PERP_POSITION_LEN,
+            SwarmError::InvalidPayload
+        );
+        require_keys_eq!(
+            Pubkey::new_from_array(data[8..40].try_into().unwrap()),
+            *authority,
+            SwarmError::Unauthorized
+        );
+
+        let i64_at = |offset: usize| i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+        for slot in 0..PERP_POSITION_COUNT {
+            let start = PERP_POSITIONS_OFFSET + slot * PERP_POSITION_LEN;
+            let slot_market = u16::from_le_bytes(data[start + 92..start + 94].try_into().unwrap());
+            let position = PerpPosition {
+                base_asset_amount: i64_at(start + 8),
+                open_bids: i64_at(start + 40),
+                open_asks: i64_at(start + 48),
+            };
+            let open_orders = data[start + 94];
+            if slot_market == market_index && (position.worst_case_size() > 0 || open_orders > 0) {
+                return Ok(position);
+            }
+        }
+        Ok(PerpPosition::default())
+    }
+}
+
+/// Meteora DLMM (liquidity book) integration helpers
+pub mod meteora {
+    use super::*;
+    use crate::payloads::BinLiquidity;
+
+    /// Meteora DLMM program ID (compile-time constant — no runtime unwrap)
+    pub const PROGRAM_ID: Pubkey = solana_program::pubkey!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
+
+    pub const INITIALIZE_POSITION_PDA_DISCRIMINATOR: [u8; 8] = [46, 82, 125, 146, 85, 141, 228, 153];
+    pub const ADD_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [181, 157, 89, 67, 143, 182, 52, 72];
+    pub const REMOVE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [80, 85, 209, 72, 24, 206, 177, 108];
+    pub const CLOSE_POSITION_DISCRIMINATOR: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+
+    const POSITION_SEED: &[u8] = b"position";
+
+    /// Size of a `PositionV2` account, including its discriminator
+    pub const POSITION_ACCOUNT_LEN: usize = 8120;
+
+    /// Open: payer, base, position, pair, owner, system program, rent,
+    /// event authority, program
+    pub const OPEN_ACCOUNT_COUNT: usize = 9;
+
+    /// Add or remove: position, pair, bitmap extension, user token X, user
+    /// token Y, reserve X, reserve Y, mint X, mint Y, lower bin array, upper
+    /// bin array, sender, token X program, token Y program, event authority,
+    /// program
+    pub const MODIFY_ACCOUNT_COUNT: usize = 16;
+
+    /// Close: position, pair, lower bin array, upper bin array, sender, rent
+    /// receiver, event authority, program
+    pub const CLOSE_ACCOUNT_COUNT: usize = 8;
+
+    #[derive(AnchorSerialize)]
+    struct LiquidityParameter {
+        amount_x: u64,
+        amount_y: u64,
+        bin_liquidity_dist: Vec<BinLiquidity>,
+    }
+
+    /// The position `base` opens on `lb_pair` over `width` bins from
+    /// `lower_bin_id`
+    pub fn find_position_address(
+        lb_pair: &Pubkey,
+        base: &Pubkey,
+        lower_bin_id: i32,
+        width: i32,
+    ) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                POSITION_SEED,
+                lb_pair.as_ref(),
+                base.as_ref(),
+                &lower_bin_id.to_le_bytes(),
+                &width.to_le_bytes(),
+            ],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
+    pub fn instruction_data(payload: &LiquidityPayload) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        match payload {
+            LiquidityPayload::OpenPosition { lower_bin_id, width } => {
+                data.extend_from_slice(&INITIALIZE_POSITION_PDA_DISCRIMINATOR);
+                data.extend_from_slice(&lower_bin_id.to_le_bytes());
+                data.extend_from_slice(&width.to_le_bytes());
+            }
+            LiquidityPayload::AddLiquidity { amount_x, amount_y, bins } => {
+                data.extend_from_slice(&ADD_LIQUIDITY_DISCRIMINATOR);
+                LiquidityParameter {
+                    amount_x: *amount_x,
+                    amount_y: *amount_y,
+                    bin_liquidity_dist: bins.clone(),
+                }
+                .serialize(&mut data)?;
+            }
+            LiquidityPayload::RemoveLiquidity { bins } => {
+                data.extend_from_slice(&REMOVE_LIQUIDITY_DISCRIMINATOR);
+                bins.serialize(&mut data)?;
+            }
+            LiquidityPayload::ClosePosition => {
+                data.extend_from_slice(&CLOSE_POSITION_DISCRIMINATOR);
+            }
+        }
+        Ok(data)
+    }
+
+    /// Check an instruction's accounts: the vault authority as payer, base,
+    /// owner or sender, the vault's token accounts, the position derived from
+    /// the vault authority and the treasury receiving closed positions' rent
+    pub fn validate_accounts(
+        payload: &LiquidityPayload,
+        accounts: &[AccountInfo],
+        owner: &Pubkey,
+        rent_receiver: &Pubkey,
+    ) -> Result<()> {
+        match payload {
+            LiquidityPayload::OpenPosition { lower_bin_id, width } => {
+                require!(accounts.len() == OPEN_ACCOUNT_COUNT, SwarmError::MissingExecutionAccount);
+                require_keys_eq!(*accounts[0].key, *owner, SwarmError::InvalidPayload);
+                require_keys_eq!(*accounts[1].key, *owner, SwarmError::InvalidPayload);
+                require_keys_eq!(
+                    *accounts[2].key,
+                    find_position_address(accounts[3].key, owner, *lower_bin_id, *width),
+                    SwarmError::InvalidPayload
+                );
+                require_keys_eq!(*accounts[4].key, *owner, SwarmError::InvalidPayload);
+                require_keys_eq!(*accounts[5].key, System::id(), SwarmError::UntrustedProgram);
+            }
+            LiquidityPayload::AddLiquidity { .. } | LiquidityPayload::RemoveLiquidity { .. } => {
+                require!(accounts.len() == MODIFY_ACCOUNT_COUNT, SwarmError::MissingExecutionAccount);
+                require_keys_eq!(*accounts[11].key, *owner, SwarmError::InvalidPayload);
+                for slot in [3, 4] {
+                    let token_account =
+                        TokenAccount::try_deserialize(&mut &accounts[slot].try_borrow_data()?[..])?;
+                    require_keys_eq!(token_account.owner, *owner, SwarmError::Unauthorized);
+                }
+            }
+            LiquidityPayload::ClosePosition => {
+                require!(accounts.len() == CLOSE_ACCOUNT_COUNT, SwarmError::MissingExecutionAccount);
+                require_keys_eq!(*accounts[4].key, *owner, SwarmError::InvalidPayload);
+                require_keys_eq!(*accounts[5].key, *rent_receiver, SwarmError::InvalidPayload);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Jito block engine tips
+pub mod jito {
+    use super::*;
+
+    /// Accounts the block engine accepts tips on
+    pub const TIP_ACCOUNTS: [Pubkey; 8] = [
+        solana_program::pubkey!("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"),
+        solana_program::pubkey!("HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe"),
+        solana_program::pubkey!("Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY"),
+        solana_program::pubkey!("ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49"),
+        solana_program::pubkey!("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh"),
+        solana_program::pubkey!("ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt"),
+        solana_program::pubkey!("DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL"),
+        solana_program::pubkey!("3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT"),
+    ];
+
+    pub fn is_tip_account(account: &Pubkey) -> bool {
+        TIP_ACCOUNTS.contains(account)
+    }
+}
+
+/// Wormhole core bridge integration helpers
+pub mod wormhole {
+    use super::*;
+
+    /// Wormhole core bridge program ID
+    pub const PROGRAM_ID: Pubkey = solana_program::pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+
+    /// Core bridge instruction tag of `post_message`
+    pub const POST_MESSAGE_TAG: u8 = 1;
+    /// Guardians attest once the posting slot is finalized
+    pub const CONSISTENCY_FINALIZED: u8 = 32;
+
+    pub const CONFIG_SEED: &[u8] = b"Bridge";
+    pub const FEE_COLLECTOR_SEED: &[u8] = b"fee_collector";
+    pub const SEQUENCE_SEED: &[u8] = b"Sequence";
+
+    /// Offset of the message fee in the bridge config: guardian set index,
+    /// last lamports and guardian set expiration time come first
+    pub const CONFIG_FEE_OFFSET: usize = 4 + 8 + 4;
+
+    pub fn config_address() -> Pubkey {
+        Pubkey::find_program_address(&[CONFIG_SEED], &PROGRAM_ID).0
+    }
+
+    pub fn fee_collector_address() -> Pubkey {
+        Pubkey::find_program_address(&[FEE_COLLECTOR_SEED], &PROGRAM_ID).0
+    }
+
+    /// The bridge's sequence tracker for `emitter`
+    pub fn sequence_address(emitter: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[SEQUENCE_SEED, emitter.as_ref()], &PROGRAM_ID).0
+    }
+
+    /// Post message: config, message, emitter, sequence, payer, fee
+    /// collector, clock, rent, system program
+    pub const POST_MESSAGE_ACCOUNT_COUNT: usize = 9;
+    /// Message and emitter, signed for by the swarm's PDAs
+    pub const PDA_SIGNER_SLOTS: [usize; 2] = [1, 2];
+
+    /// Check the bridge accounts of a `post_message`: its config, the
+    /// emitter's sequence tracker, the fee collector and the sysvars
+    pub fn validate_accounts(accounts: &[AccountInfo]) -> Result<()> {
+        require!(
+            accounts.len() == POST_MESSAGE_ACCOUNT_COUNT,
+            SwarmError::MissingExecutionAccount
+        );
+        require_keys_eq!(*accounts[0].key, config_address(), SwarmError::InvalidPayload);
+        require_keys_eq!(*accounts[3].key, sequence_address(accounts[2].key), SwarmError::InvalidPayload);
+        require_keys_eq!(*accounts[5].key, fee_collector_address(), SwarmError::InvalidPayload);
+        require_keys_eq!(*accounts[6].key, solana_program::sysvar::clock::ID, SwarmError::InvalidPayload);
+        require_keys_eq!(*accounts[7].key, solana_program::sysvar::rent::ID, SwarmError::InvalidPayload);
+        require_keys_eq!(*accounts[8].key, System::id(), SwarmError::UntrustedProgram);
+        Ok(())
+    }
+
+    pub fn instruction_data(nonce: u32, payload: &[u8]) -> Vec<u8> {
+        let mut data = vec![POST_MESSAGE_TAG];
+        data.extend_from_slice(&nonce.to_le_bytes());
+        data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        data.extend_from_slice(payload);
+        data.push(CONSISTENCY_FINALIZED);
+        data
+    }
+
+    /// Lamports the bridge charges per message
+    pub fn message_fee(config: &AccountInfo) -> Result<u64> {
+        require_keys_eq!(*config.owner, PROGRAM_ID, SwarmError::UntrustedProgram);
+        let data = config.try_borrow_data()?;
+        require!(data.len() >= CONFIG_FEE_OFFSET + 8, SwarmError::InvalidPayload);
+        Ok(u64::from_le_bytes(data[CONFIG_FEE_OFFSET..CONFIG_FEE_OFFSET + 8].try_into().unwrap()))
+    }
+
+    /// Sequence number the bridge will assign to the emitter's next message.
+    /// The tracker is only created by the emitter's first message.
+    pub fn next_sequence(sequence: &AccountInfo) -> Result<u64> {
+        let data = sequence.try_borrow_data()?;
+        if data.len() < 8 {
+            return Ok(0);
+        }
+        require_keys_eq!(*sequence.owner, PROGRAM_ID, SwarmError::UntrustedProgram);
+        Ok(u64::from_le_bytes(data[..8].try_into().unwrap()))
+    }
+}
+
+/// Metaplex Bubblegum (compressed NFT) integration helpers
+pub mod bubblegum {
+    use super::*;
+
+    pub const PROGRAM_ID: Pubkey = solana_program::pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+    pub const NOOP_PROGRAM_ID: Pubkey = solana_program::pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+    pub const COMPRESSION_PROGRAM_ID: Pubkey = solana_program::pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+    pub const MINT_V1_DISCRIMINATOR: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+
+    /// Mint: tree config, leaf owner, leaf delegate, merkle tree, payer,
+    /// tree creator or delegate, log wrapper, compression program, system
+    /// program
+    pub const MINT_V1_ACCOUNT_COUNT: usize = 9;
+
+    pub const MAX_NAME_LENGTH: usize = 32;
+    pub const MAX_SYMBOL_LENGTH: usize = 10;
+    pub const MAX_URI_LENGTH: usize = 200;
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum TokenStandard {
+        NonFungible,
+        FungibleAsset,
+        Fungible,
+        NonFungibleEdition,
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum TokenProgramVersion {
+        Original,
+        Token2022,
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+    pub struct Collection {
+        pub verified: bool,
+        pub key: Pubkey,
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum UseMethod {
+        Burn,
+        Multiple,
+        Single,
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+    pub struct Uses {
+        pub use_method: UseMethod,
+        pub remaining: u64,
+        pub total: u64,
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+    pub struct Creator {
+        pub address: Pubkey,
+        pub verified: bool,
+        pub share: u8,
+    }
+
+    /// Bubblegum's `MetadataArgs`
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+    pub struct MetadataArgs {
+        pub name: String,
+        pub symbol: String,
+        pub uri: String,
+        pub seller_fee_basis_points: u16,
+        pub primary_sale_happened: bool,
+        pub is_mutable: bool,
+        pub edition_nonce: Option<u8>,
+        pub token_standard: Option<TokenStandard>,
+        pub collection: Option<Collection>,
+        pub uses: Option<Uses>,
+        pub token_program_version: TokenProgramVersion,
+        pub creators: Vec<Creator>,
+    }
+
+    /// Tree config Bubblegum keeps for `merkle_tree`
+    pub fn tree_config_address(merkle_tree: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[merkle_tree.as_ref()], &PROGRAM_ID).0
+    }
+
+    pub fn instruction_data(metadata: &MetadataArgs) -> Result<Vec<u8>> {
+        require!(
+            metadata.name.len() <= MAX_NAME_LENGTH
+                && metadata.symbol.len() <= MAX_SYMBOL_LENGTH
+                && metadata.uri.len() <= MAX_URI_LENGTH,
+            SwarmError::InvalidParameter
+        );
+        let mut data = MINT_V1_DISCRIMINATOR.to_vec();
+        metadata.serialize(&mut data)?;
+        Ok(data)
+    }
+
+    /// Check a `mint_v1`'s accounts: the tree's config, `tree_delegate`
+    /// signing for the tree, and the programs Bubblegum calls
+    pub fn validate_accounts(accounts: &[AccountInfo], tree_delegate: &Pubkey) -> Result<()> {
+        require!(accounts.len() == MINT_V1_ACCOUNT_COUNT, SwarmError::MissingExecutionAccount);
+        require_keys_eq!(
+            *accounts[0].key,
+            tree_config_address(accounts[3].key),
+            SwarmError::InvalidParameter
+        );
+        require_keys_eq!(*accounts[5].key, *tree_delegate, SwarmError::Unauthorized);
+        require_keys_eq!(*accounts[6].key, NOOP_PROGRAM_ID, SwarmError::UntrustedProgram);
+        require_keys_eq!(*accounts[7].key, COMPRESSION_PROGRAM_ID, SwarmError::UntrustedProgram);
+        require_keys_eq!(*accounts[8].key, System::id(), SwarmError::UntrustedProgram);
+        Ok(())
+    }
+}
+
+/// Token-2022 mint extension helpers. Classic SPL mints have none of them.
+pub mod token_extensions {
+    use super::*;
+    use spl_token_2022::extension::transfer_fee::{self, TransferFeeConfig};
+    use spl_token_2022::extension::{transfer_hook, BaseStateWithExtensions, StateWithExtensions};
+    use spl_token_2022::state::Mint as MintState;
+
+    /// Fee `mint` withholds from a transfer of `amount` in the current epoch
+    pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+        if *mint.owner != spl_token_2022::ID {
+            return Ok(0);
+        }
+        let data = mint.try_borrow_data()?;
+        let state = StateWithExtensions::<MintState>::unpack(&data)?;
+        match state.get_extension::<TransferFeeConfig>() {
+            Ok(config) => config
+                .calculate_epoch_fee(Clock::get()?.epoch, amount)
+                .ok_or(error!(SwarmError::ArithmeticOverflow)),
+            Err(_) => Ok(0),
+        }
+    }
+
+    /// Program `mint` invokes on every transfer, if any
+    pub fn transfer_hook_program(mint: &AccountInfo) -> Result<Option<Pubkey>> {
+        if *mint.owner != spl_token_2022::ID {
+            return Ok(None);
+        }
+        let data = mint.try_borrow_data()?;
+        let state = StateWithExtensions::<MintState>::unpack(&data)?;
+        Ok(transfer_hook::get_program_id(&state))
+    }
+
+    /// Require `mint`'s transfer hook, if it has one, to be on `whitelist`
+    pub fn require_allowed_hook(
+        mint: &AccountInfo,
+        whitelist: Option<&ProgramWhitelist>,
+    ) -> Result<()> {
+        if let Some(program) = transfer_hook_program(mint)? {
+            require!(
+                whitelist.is_some_and(|whitelist| whitelist.contains(&program)),
+                SwarmError::TransferHookNotAllowed
+            );
+        }
+        Ok(())
+    }
+
+    /// Move the transfer fees withheld in `token_account` to its mint, which
+    /// anyone may do. An account still holding withheld fees cannot close.
+    pub fn harvest_withheld_fees<'info>(
+        token_program: &AccountInfo<'info>,
+        mint: &AccountInfo<'info>,
+        token_account: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let has_fee_config = *mint.owner == spl_token_2022::ID && {
+            let data = mint.try_borrow_data()?;
+            StateWithExtensions::<MintState>::unpack(&data)?
+                .get_extension::<TransferFeeConfig>()
+                .is_ok()
+        };
+        if !has_fee_config {
+            return Ok(());
+        }
+        let instruction = transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+            token_program.key,
+            mint.key,
+            &[token_account.key],
+        )?;
+        anchor_lang::solana_program::program::invoke(
+            &instruction,
+            &[mint.clone(), token_account.clone(), token_program.clone()],
+        )?;
+        Ok(())
+    }
+}
@@ -61,4 +61,472 @@ pub enum SwarmError {
     
     #[msg("Swarm not initialized")]
     SwarmNotInitialized,
+    
+    #[msg("Vote already settled")]
+    VoteAlreadySettled,
+    
+    #[msg("Proposals do not compete for the same objective")]
+    NotCompetingProposals,
+    
+    #[msg("Match result cannot be determined yet")]
+    MatchUndecided,
+    
+    #[msg("Invalid configuration parameter")]
+    InvalidConfig,
+    
+    #[msg("Transaction contains an instruction from an untrusted program")]
+    UntrustedInstruction,
+    
+    #[msg("Instructions sysvar account required")]
+    MissingInstructionsSysvar,
+    
+    #[msg("Proposal is reserved for its assigned executor")]
+    NotAssignedExecutor,
+    
+    #[msg("Agent type cannot perform this action")]
+    InvalidAgentType,
+    
+    #[msg("Invalid endorsement stake")]
+    InvalidStake,
+    
+    #[msg("Agents cannot endorse their own proposals")]
+    SelfEndorsement,
+    
+    #[msg("Endorsement already settled")]
+    EndorsementAlreadySettled,
+    
+    #[msg("Proposal outcome not yet known")]
+    OutcomePending,
+    
+    #[msg("Invalid parameter")]
+    InvalidParameter,
+    
+    #[msg("Malformed agent manifest")]
+    InvalidManifest,
+    
+    #[msg("Agent manifest does not meet swarm requirements")]
+    IncompatibleManifest,
+    
+    #[msg("Proposal payload is malformed for its type")]
+    InvalidPayload,
+    
+    #[msg("Treasury balance too low")]
+    InsufficientTreasuryFunds,
+    
+    #[msg("Account required for this proposal type is missing")]
+    MissingExecutionAccount,
+    
+    #[msg("Agent is on sabbatical")]
+    OnSabbatical,
+    
+    #[msg("Sabbatical duration out of range")]
+    InvalidSabbatical,
+    
+    #[msg("Treasury spend cap for this proposal type exceeded this epoch")]
+    SpendCapExceeded,
+    
+    #[msg("Description is empty or below the swarm's minimum length")]
+    DescriptionTooShort,
+    
+    #[msg("Vote is not eligible for a reward")]
+    NotRewardEligible,
+    
+    #[msg("Reward already claimed")]
+    RewardAlreadyClaimed,
+    
+    #[msg("Bounty requires a successful recorded outcome")]
+    BountyNotClaimable,
+    
+    #[msg("Bounty can only be refunded after expiry or a failed outcome")]
+    BountyNotRefundable,
+    
+    #[msg("Pending trade exposure would exceed the swarm's limit")]
+    ExposureLimitExceeded,
+    
+    #[msg("Trade exposure already reserved")]
+    ExposureAlreadyReserved,
+    
+    #[msg("Trade exposure must be reserved before execution")]
+    ExposureNotReserved,
+    
+    #[msg("Bond is unbonding or still in its unbonding period")]
+    BondUnbonding,
+    
+    #[msg("Swarm authority has not been inactive long enough")]
+    AuthorityStillActive,
+    
+    #[msg("Reward epoch has not ended yet")]
+    EpochNotOver,
+    
+    #[msg("Lock duration out of range")]
+    InvalidLockDuration,
+    
+    #[msg("Tokens are still locked")]
+    TokensStillLocked,
+    
+    #[msg("Listing does not offer subscriptions")]
+    SubscriptionsDisabled,
+    
+    #[msg("Auction has ended")]
+    AuctionEnded,
+    
+    #[msg("Auction is still running")]
+    AuctionActive,
+    
+    #[msg("Bid must exceed the current highest bid and the reserve price")]
+    BidTooLow,
+    
+    #[msg("The highest bid cannot be withdrawn before settlement")]
+    BidLocked,
+    
+    #[msg("Sale proceeds are held until the refund window closes")]
+    RefundWindowOpen,
+    
+    #[msg("Refund window has closed")]
+    RefundWindowClosed,
+    
+    #[msg("Nothing is held in escrow for this sale")]
+    SaleAlreadySettled,
+    
+    #[msg("Review text is empty or exceeds maximum length")]
+    ReviewTooLong,
+    
+    #[msg("Program is not an allowed CPI target")]
+    UntrustedProgram,
+    
+    #[msg("Swap spent more than its amount or returned less than its minimum")]
+    SlippageExceeded,
+    
+    #[msg("Price account is not a verified update for the registered feed")]
+    InvalidPriceFeed,
+    
+    #[msg("Oracle price is too old")]
+    StalePrice,
+    
+    #[msg("No oracle feed is registered for this mint")]
+    MissingPriceFeed,
+    
+    #[msg("Trade minimum implies more slippage than the swarm allows")]
+    OracleSlippageExceeded,
+    
+    #[msg("Lending obligation is stale or below the minimum health factor")]
+    ObligationUnhealthy,
+    
+    #[msg("Vault balance change deviates from the Strategy's expected delta")]
+    BalanceDeviation,
+    
+    #[msg("Proposal ran out of execution attempts and can only be closed")]
+    ProposalFailed,
+    
+    #[msg("Proposal has not failed")]
+    ProposalNotFailed,
+    
+    #[msg("Mint is not allowed by the swarm's risk config")]
+    MintNotAllowed,
+    
+    #[msg("Trade exceeds the maximum position size")]
+    PositionLimitExceeded,
+    
+    #[msg("Trade exceeds the maximum daily volume")]
+    DailyVolumeExceeded,
+    
+    #[msg("Proposals must be executed by a top-level instruction")]
+    ExecutionViaCpi,
+    
+    #[msg("Transaction executes more than one proposal")]
+    DuplicateExecution,
+    
+    #[msg("Transaction takes a flash loan before executing")]
+    FlashLoanInTransaction,
+    
+    #[msg("Execution tip exceeds the swarm's maximum")]
+    TipExceedsCap,
+    
+    #[msg("Account is not a Jito tip account")]
+    InvalidTipAccount,
+    
+    #[msg("Outcome is still within its retention period")]
+    OutcomeRetained,
+    
+    #[msg("Outcome has been archived")]
+    OutcomeArchived,
+    
+    #[msg("Outcome archive is full")]
+    OutcomeArchiveFull,
+    
+    #[msg("Proposal data has not been fully uploaded")]
+    ProposalDataIncomplete,
+    
+    #[msg("Account is not a migratable account of this program")]
+    UnknownAccountType,
+    
+    #[msg("Account is already at the current layout version")]
+    AccountUpToDate,
+    
+    #[msg("No upgrade exists from this account layout version")]
+    UnsupportedAccountVersion,
+    
+    #[msg("Account belongs to a different swarm")]
+    SwarmMismatch,
+    
+    #[msg("Proposal does not commit to its payload by hash")]
+    PayloadNotCommitted,
+    
+    #[msg("Staged payload does not match the proposal's committed hash")]
+    PayloadHashMismatch,
+    
+    #[msg("Oracle price is less certain than the feed allows")]
+    OracleDeviationExceeded,
+    
+    #[msg("Proposal's price condition is not met")]
+    PriceConditionNotMet,
+    
+    #[msg("TWAP tracker has no observations spanning the window")]
+    InsufficientTwapHistory,
+    
+    #[msg("Oracle price strays too far from its TWAP")]
+    TwapDeviationExceeded,
+    
+    #[msg("Randomness cannot be revealed before its target slot has passed")]
+    RandomnessNotReady,
+    
+    #[msg("Randomness reveal window has passed")]
+    RandomnessExpired,
+    
+    #[msg("Revealed secret does not match the commitment")]
+    RandomnessMismatch,
+    
+    #[msg("Proposal has no revealed randomness")]
+    RandomnessNotRevealed,
+    
+    #[msg("Proposal already has revealed randomness")]
+    RandomnessAlreadyRevealed,
+    
+    #[msg("Executor was drawn at random and cannot be changed")]
+    ExecutorAlreadyDrawn,
+    
+    #[msg("Agent is not the executor drawn for this proposal")]
+    NotDrawnExecutor,
+    
+    #[msg("Randomness commit can still be revealed")]
+    RandomnessRevealOpen,
+    
+    #[msg("Agent's reputation does not decay for this epoch")]
+    NotDecayEligible,
+    
+    #[msg("Scheduled proposal cannot execute yet")]
+    ExecutionTooEarly,
+    
+    #[msg("Execution queue is full")]
+    ExecutionQueueFull,
+    
+    #[msg("Voting on the proposal has already started")]
+    VotingAlreadyStarted,
+    
+    #[msg("Rebalance schedule is stopped or not yet due")]
+    RebalanceNotDue,
+    
+    #[msg("Task is not open for claiming")]
+    TaskNotClaimable,
+    
+    #[msg("Task has not been completed")]
+    TaskNotCompleted,
+    
+    #[msg("Task is claimed and the claim has not lapsed")]
+    TaskClaimActive,
+    
+    #[msg("Agent is not a member of the coalition")]
+    NotCoalitionMember,
+    
+    #[msg("Agent is already a member of the coalition")]
+    AlreadyCoalitionMember,
+    
+    #[msg("Coalition has reached its member limit")]
+    CoalitionFull,
+    
+    #[msg("No position has reached the coalition's bloc threshold")]
+    CoalitionPositionUndecided,
+    
+    #[msg("Coalition vote has already been cast")]
+    CoalitionVoteAlreadyCast,
+    
+    #[msg("Not enough coalition members approved")]
+    InsufficientCoalitionApprovals,
+    
+    #[msg("Proposal type is handled by a committee that was not provided")]
+    MissingCommittee,
+    
+    #[msg("Proposal type is already handled by another committee")]
+    CommitteeScopeOverlap,
+    
+    #[msg("Linked proposal must belong to another swarm")]
+    InvalidLinkedProposal,
+    
+    #[msg("Linked proposal is not approved in its swarm")]
+    LinkedProposalNotApproved,
+    
+    #[msg("Mint's transfer hook program is not whitelisted")]
+    TransferHookNotAllowed,
+    
+    #[msg("Agent has not earned this badge")]
+    BadgeNotEarned,
+    
+    #[msg("Missing or mismatched ed25519 signature instruction")]
+    InvalidSignature,
+    
+    #[msg("Attestation belongs to another swarm")]
+    InvalidAttestation,
+    
+    #[msg("Agent's guardian must co-sign")]
+    GuardianSignatureRequired,
+    
+    #[msg("Trade exceeds its venue's volume cap")]
+    VenueCapExceeded,
+    
+    #[msg("Rebalance weight exceeds the concentration limit")]
+    ConcentrationLimitExceeded,
+    
+    #[msg("Trade would exceed the drawdown limit")]
+    DrawdownLimitExceeded,
+    
+    #[msg("Portfolio holds too many mints")]
+    TooManyHoldings,
+    
+    #[msg("Rebalance trades do not match the portfolio's required deltas")]
+    RebalanceMismatch,
+    
+    #[msg("Standing order has expired")]
+    StandingOrderExpired,
+    
+    #[msg("Standing order has not expired yet")]
+    StandingOrderActive,
+    
+    #[msg("DCA leg is not due yet")]
+    DcaLegNotDue,
+    
+    #[msg("Arbitrage report does not match the trade, has expired or already paid out")]
+    ArbReportNotClaimable,
+    
+    #[msg("Arbitrage report is still open")]
+    ArbReportOpen,
+    
+    #[msg("No Harvest proposal has configured harvesting yet")]
+    HarvestNotConfigured,
+    
+    #[msg("Harvest is worth more than the crank threshold and needs a Harvest proposal")]
+    HarvestAboveCrankThreshold,
+    
+    #[msg("Harvest crank is not due yet")]
+    HarvestNotDue,
+    
+    #[msg("Loss is outside the claim window or was already reimbursed")]
+    InsuranceClaimNotEligible,
+    
+    #[msg("Insurance fund is tracking too many recent claims")]
+    TooManyInsuranceClaims,
+    
+    #[msg("Strategy outcome's shortfall is below the insurance loss threshold")]
+    InsuranceLossBelowThreshold,
+    
+    #[msg("Insurance fund cannot cover the claim")]
+    InsufficientInsuranceFunds,
+    
+    #[msg("Trading is paused by the circuit breaker")]
+    TradingPaused,
+    
+    #[msg("No circuit breaker is configured")]
+    CircuitBreakerNotConfigured,
+    
+    #[msg("Portfolio value was marked too recently")]
+    ValueMarkTooSoon,
+    
+    #[msg("Proposal carries too many tags")]
+    TooManyProposalTags,
+    
+    #[msg("Proposal tag is empty, too long, repeated or has invalid characters")]
+    InvalidProposalTag,
+    
+    #[msg("Proposal still has open bounties or unsettled endorsements")]
+    ProposalHasOpenClaims,
+    
+    #[msg("Proposal has too many open bounties or endorsements")]
+    TooManyOpenClaims,
+    
+    #[msg("Outcome still has unsettled votes, bounties or endorsements")]
+    OutcomeUnsettled,
+    
+    #[msg("Proposal already has a nominated executor")]
+    ExecutorAlreadyAssigned,
+}
+
+/// Identifies which on-chain check rejected an instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FailedCheck {
+    AgentNameLength,
+    DescriptionLength,
+    DescriptionQuality,
+    ReasoningLength,
+    ProposalDataLength,
+    MetricsLength,
+    AgentCapacity,
+    VoterCapacity,
+    AgentCount,
+    MinVotes,
+    ProposalTimeout,
+    ProposalExpiry,
+    ExecutorWindow,
+    Quorum,
+    Approval,
+    PerformanceScore,
+    Supermajority,
+    AuthorityInactivity,
+    PriceCondition,
+    ExecutionSchedule,
+    RebalanceDelta,
+    HarvestValue,
+    InsuranceLoss,
+}
+
+/// Structured failure details written to return data.
+///
+/// Automated agents can read this from the transaction metadata (or a
+/// simulation result) and branch on `check` instead of parsing log strings.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ErrorContext {
+    pub error_code: u32,
+    pub check: FailedCheck,
+    pub expected: i64,
+    pub actual: i64,
+}
+
+/// Publish an `ErrorContext` as return data and hand back the Anchor error
+pub fn with_context(error: SwarmError, check: FailedCheck, expected: i64, actual: i64) -> Error {
+    let context = ErrorContext {
+        error_code: error as u32 + anchor_lang::error::ERROR_CODE_OFFSET,
+        check,
+        expected,
+        actual,
+    };
+
+    if let Ok(bytes) = context.try_to_vec() {
+        anchor_lang::solana_program::program::set_return_data(&bytes);
+    }
+
+    msg!("Check {:?} failed: expected {}, actual {}", check, expected, actual);
+    error.into()
+}
+
+/// Like `require!`, but also records which check failed and the values involved
+#[macro_export]
+macro_rules! require_ctx {
+    ($cond:expr, $error:expr, $check:expr, $expected:expr, $actual:expr $(,)?) => {
+        if !($cond) {
+            return Err($crate::errors::with_context(
+                $error,
+                $check,
+                $expected as i64,
+                $actual as i64,
+            ));
+        }
+    };
 }
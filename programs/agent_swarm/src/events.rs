@@ -0,0 +1,1042 @@
+use anchor_lang::prelude::*;
+use crate::marketplace::ListingCategory;
+use crate::payloads::{RebalanceTrade, TargetAllocation};
+use crate::instructions::SwarmConfigParams;
+use crate::oracle::{OracleSource, PriceCondition};
+use crate::state::{BadgeKind, BalanceDelta, CoalitionRules, TaskKind};
+use crate::{AgentType, ProposalType, VoteType};
+
+/// Version of the accounting event schemas below. Fields are only ever
+/// appended; any other change bumps this number.
+pub const ACCOUNTING_SCHEMA_VERSION: u8 = 1;
+
+/// Asset id used in accounting events for native SOL (SPL mints use the mint)
+pub const NATIVE_SOL_ASSET: Pubkey = Pubkey::new_from_array([0; 32]);
+
+/// Value entering the treasury
+#[event]
+pub struct TreasuryTransferIn {
+    pub schema_version: u8,
+    pub asset: Pubkey,
+    pub amount: u64,
+    pub from: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Value leaving the treasury through an executed proposal
+#[event]
+pub struct TreasuryTransferOut {
+    pub schema_version: u8,
+    pub asset: Pubkey,
+    pub amount: u64,
+    pub to: Pubkey,
+    pub proposal: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Bond slashed by an executed `Emergency` proposal
+#[event]
+pub struct AgentSlashed {
+    pub agent: Pubkey,
+    pub proposal: Pubkey,
+    pub to_treasury: u64,
+    pub burned: u64,
+    pub remaining_bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeConfigured {
+    pub swarm: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub votes_milestone: u64,
+    pub proposals_milestone: u64,
+    pub top_reputation_enabled: bool,
+    pub timestamp: i64,
+}
+
+/// Compressed NFT badge minted to an agent's owner
+#[event]
+pub struct BadgeAwarded {
+    pub agent: Pubkey,
+    pub owner: Pubkey,
+    pub kind: BadgeKind,
+    pub epoch: u64,
+    pub merkle_tree: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Agent's signature over an off-chain output verified and recorded
+#[event]
+pub struct OutputAttested {
+    pub attestation: Pubkey,
+    pub swarm: Pubkey,
+    pub agent: Pubkey,
+    pub signer: Pubkey,
+    pub payload_hash: [u8; 32],
+    pub model_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// A proposal now cites an attested off-chain output
+#[event]
+pub struct AttestationCited {
+    pub proposal: Pubkey,
+    pub attestation: Pubkey,
+    pub payload_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Wormhole message posted for an executed `CrossChain` proposal
+#[event]
+pub struct CrossChainMessagePosted {
+    pub swarm: Pubkey,
+    pub proposal: Pubkey,
+    pub message: Pubkey,
+    pub sequence: u64,
+    pub target_chain: u16,
+    pub target_address: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Gain or loss realized when a swarm position is closed
+#[event]
+pub struct RealizedPnl {
+    pub schema_version: u8,
+    pub proposal: Pubkey,
+    pub asset: Pubkey,
+    pub cost_basis: u64,
+    pub proceeds: u64,
+    pub pnl: i64,
+    pub timestamp: i64,
+}
+
+/// Fee paid by or to the swarm (protocol fees, tips, venue fees)
+#[event]
+pub struct FeePaid {
+    pub schema_version: u8,
+    pub asset: Pubkey,
+    pub amount: u64,
+    pub payer: Pubkey,
+    pub recipient: Pubkey,
+    pub proposal: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+/// Proposal that reached the swarm's maximum execution attempts
+#[event]
+pub struct ProposalFailed {
+    pub proposal: Pubkey,
+    pub attempts: u8,
+    pub last_failure_code: u32,
+    pub timestamp: i64,
+}
+
+/// Target weights approved by an executed `Rebalance` proposal, for
+/// executors to trade towards
+#[event]
+pub struct RebalanceApproved {
+    pub proposal: Pubkey,
+    pub allocations: Vec<TargetAllocation>,
+    pub trades: Vec<RebalanceTrade>,
+    pub timestamp: i64,
+}
+
+/// An executed `Strategy` proposal replaced the swarm's strategy parameters
+#[event]
+pub struct StrategyParametersSet {
+    pub config: Pubkey,
+    pub proposal: Pubkey,
+    pub parameter_version: u64,
+    pub allocations: Vec<TargetAllocation>,
+    pub lookback_windows: Vec<i64>,
+    pub thresholds: Vec<i64>,
+    pub timestamp: i64,
+}
+
+/// A rebalance schedule came due and created its next proposal
+#[event]
+pub struct RebalanceTriggered {
+    pub schedule: Pubkey,
+    pub proposal: Pubkey,
+    pub next_run_at: i64,
+    pub timestamp: i64,
+}
+
+/// An executed `Strategy` proposal placed a stop-loss or take-profit
+#[event]
+pub struct StandingOrderPlaced {
+    pub order: Pubkey,
+    pub proposal: Pubkey,
+    pub trigger: PriceCondition,
+    pub mint_in: Pubkey,
+    pub mint_out: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// A keeper fired a standing order whose trigger was met
+#[event]
+pub struct StandingOrderTriggered {
+    pub order: Pubkey,
+    pub keeper: Pubkey,
+    pub price: u64,
+    pub spent: u64,
+    pub received: u64,
+    pub timestamp: i64,
+}
+
+/// An executed `DcaSchedule` proposal started buying into a position
+#[event]
+pub struct DcaScheduleCreated {
+    pub schedule: Pubkey,
+    pub proposal: Pubkey,
+    pub mint_in: Pubkey,
+    pub mint_out: Pubkey,
+    pub amount_per_leg: u64,
+    pub interval: i64,
+    pub total_budget: u64,
+    pub timestamp: i64,
+}
+
+/// A keeper executed one leg of a DCA schedule
+#[event]
+pub struct DcaLegExecuted {
+    pub schedule: Pubkey,
+    pub keeper: Pubkey,
+    pub leg: u32,
+    pub spent: u64,
+    pub received: u64,
+    /// Whether this leg spent the rest of the budget, closing the schedule
+    pub completed: bool,
+    pub timestamp: i64,
+}
+
+/// An Arbitrage agent reported an opportunity
+#[event]
+pub struct ArbReportSubmitted {
+    pub report: Pubkey,
+    pub reporter: Pubkey,
+    pub buy_venue: Pubkey,
+    pub sell_venue: Pubkey,
+    pub expected_edge_bps: u16,
+    pub route_hash: [u8; 32],
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// An executed trade followed an arbitrage report, paying its reporter
+#[event]
+pub struct ArbReportCredited {
+    pub report: Pubkey,
+    pub reporter: Pubkey,
+    pub proposal: Pubkey,
+    pub reputation: u16,
+    pub bounty: u64,
+    pub timestamp: i64,
+}
+
+/// A `Harvest` proposal set the base asset, positions and crank limits
+#[event]
+pub struct HarvestConfigured {
+    pub config: Pubkey,
+    pub proposal: Pubkey,
+    pub base_mint: Pubkey,
+    pub positions: Vec<Pubkey>,
+    pub crank_threshold: u64,
+    pub crank_interval: i64,
+    pub timestamp: i64,
+}
+
+/// An `Insurance` proposal set the fund's terms
+#[event]
+pub struct InsuranceTermsSet {
+    pub fund: Pubkey,
+    pub proposal: Pubkey,
+    pub fee_share_bps: u16,
+    pub loss_threshold_bps: u16,
+    pub timestamp: i64,
+}
+
+/// The insurance fund's share of new protocol fees left the treasury
+#[event]
+pub struct InsurancePremiumSwept {
+    pub fund: Pubkey,
+    pub premium: u64,
+    /// `Treasury::fees_collected` swept through
+    pub fees_swept: u64,
+    pub timestamp: i64,
+}
+
+/// An `Insurance` proposal reimbursed the treasury for a strategy's loss
+#[event]
+pub struct InsuranceClaimPaid {
+    pub fund: Pubkey,
+    pub proposal: Pubkey,
+    pub loss_proposal: Pubkey,
+    pub attestation: Pubkey,
+    pub shortfall_bps: u64,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// The marked portfolio value fell far enough below the window's peak to
+/// pause trading and rebalancing
+#[event]
+pub struct CircuitBreakerTripped {
+    pub risk_config: Pubkey,
+    pub value: u64,
+    pub peak: u64,
+    pub drawdown_bps: u64,
+    pub timestamp: i64,
+}
+
+/// A supermajority `Emergency` proposal lifted a circuit breaker pause
+#[event]
+pub struct TradingResumed {
+    pub risk_config: Pubkey,
+    pub proposal: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Realized PnL of an executed proposal was attributed to its proposer, or
+/// a share of it to a voter who approved it
+#[event]
+pub struct PnlAttributed {
+    pub agent: Pubkey,
+    pub proposal: Pubkey,
+    pub pnl: i64,
+    pub as_proposer: bool,
+    pub timestamp: i64,
+}
+
+/// Fees were collected from the registered positions and, when `spent` is
+/// nonzero, rewards swapped into the base asset
+#[event]
+pub struct HarvestExecuted {
+    pub config: Pubkey,
+    /// `None` for a permissionless crank
+    pub proposal: Option<Pubkey>,
+    pub harvester: Pubkey,
+    pub positions: u8,
+    pub mint_in: Pubkey,
+    pub spent: u64,
+    pub received: u64,
+    pub timestamp: i64,
+}
+
+/// A standing order lapsed unfired and was closed
+#[event]
+pub struct StandingOrderExpired {
+    pub order: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaskCreated {
+    pub task: Pubkey,
+    pub swarm: Pubkey,
+    pub creator: Pubkey,
+    pub kind: TaskKind,
+    pub target: Pubkey,
+    pub reputation_reward: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaskClaimed {
+    pub task: Pubkey,
+    pub claimant: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaskCompleted {
+    pub task: Pubkey,
+    pub claimant: Pubkey,
+    pub result_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// A task was closed, crediting its claimant when `accepted`
+#[event]
+pub struct TaskClosed {
+    pub task: Pubkey,
+    pub claimant: Option<Pubkey>,
+    pub accepted: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CoalitionCreated {
+    pub coalition: Pubkey,
+    pub swarm: Pubkey,
+    pub founder: Pubkey,
+    pub name: String,
+    pub rules: CoalitionRules,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CoalitionMembershipChanged {
+    pub coalition: Pubkey,
+    pub member: Pubkey,
+    pub joined: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CoalitionRulesUpdated {
+    pub coalition: Pubkey,
+    pub rules: CoalitionRules,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CoalitionFundsDeposited {
+    pub coalition: Pubkey,
+    pub from: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CoalitionFundsWithdrawn {
+    pub coalition: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub approvals: u8,
+    pub timestamp: i64,
+}
+
+/// A member's internal vote on how the coalition should vote
+#[event]
+pub struct CoalitionBallotCast {
+    pub coalition: Pubkey,
+    pub proposal: Pubkey,
+    pub member: Pubkey,
+    pub vote: VoteType,
+    pub timestamp: i64,
+}
+
+/// The coalition's position was cast into a proposal for its members
+#[event]
+pub struct CoalitionVoteCast {
+    pub coalition: Pubkey,
+    pub proposal: Pubkey,
+    pub vote: VoteType,
+    pub voters: u8,
+    pub weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitteeConfigured {
+    pub committee: Pubkey,
+    pub swarm: Pubkey,
+    pub committee_id: u8,
+    pub agent_types: Vec<AgentType>,
+    pub proposal_types: u16,
+    pub min_votes: u8,
+    pub timestamp: i64,
+}
+
+/// Outcome folded into the `OutcomeArchive` merkle tree; carries every
+/// field of the closed account so indexers can rebuild the leaf
+#[event]
+pub struct OutcomeArchived {
+    pub proposal: Pubkey,
+    pub executed_by: Pubkey,
+    pub success: bool,
+    pub metrics: Vec<u8>,
+    pub executed_at: i64,
+    pub balance_deltas: Vec<BalanceDelta>,
+    pub realized_pnl: Option<i64>,
+    pub bump: u8,
+    pub leaf_index: u64,
+    pub root: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Swarm created by `initialize`
+#[event]
+pub struct SwarmInitialized {
+    pub swarm: Pubkey,
+    pub swarm_id: u64,
+    pub authority: Pubkey,
+    pub max_agents: u8,
+    pub min_votes_required: u8,
+    pub proposal_timeout: i64,
+    pub timestamp: i64,
+}
+
+/// Parameters changed by `update_swarm_config`; `None` fields were left as is
+#[event]
+pub struct SwarmConfigUpdated {
+    pub params: SwarmConfigParams,
+    pub timestamp: i64,
+}
+
+/// Swarm-owned account (treasury, risk config, whitelist, vault token
+/// account, ...) created outside of a proposal
+#[event]
+pub struct SwarmAccountCreated {
+    pub account: Pubkey,
+    pub created_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentRegistered {
+    pub agent: Pubkey,
+    pub owner: Pubkey,
+    pub agent_type: AgentType,
+    pub name: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CustomAgentTypeRegistered {
+    pub swarm: Pubkey,
+    pub id: u8,
+    pub name: String,
+    pub schema_hash: [u8; 32],
+    pub creator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentDeregistered {
+    pub agent: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Agent's signing key replaced; the agent account and its history stay
+#[event]
+pub struct AgentKeyRotated {
+    pub agent: Pubkey,
+    pub owner: Pubkey,
+    pub old_signing_key: Pubkey,
+    pub new_signing_key: Pubkey,
+    pub guardian_cosigned: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentGuardianChanged {
+    pub agent: Pubkey,
+    pub guardian: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub proposal_type: ProposalType,
+    pub data_len: u16,
+    pub data_hash: Option<[u8; 32]>,
+    pub objective: Option<[u8; 32]>,
+    pub condition: Option<PriceCondition>,
+    pub expires_at: i64,
+    /// Lamports paid into the treasury, escalated by congestion
+    pub deposit: u64,
+    pub tags: Vec<String>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalDataExtended {
+    pub proposal: Pubkey,
+    pub data_written: u16,
+    pub data_len: u16,
+    pub timestamp: i64,
+}
+
+/// A hash-committed payload was staged or grew
+#[event]
+pub struct PayloadBufferWritten {
+    pub buffer: Pubkey,
+    pub proposal: Pubkey,
+    pub owner: Pubkey,
+    pub data_len: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayloadBufferClosed {
+    pub buffer: Pubkey,
+    pub proposal: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub vote: VoteType,
+    pub weight: u32,
+    /// Matches the vote record's `reasoning_hash`
+    pub reasoning_hash: [u8; 32],
+    pub reasoning: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExecutorAssigned {
+    pub proposal: Pubkey,
+    pub executor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExecutionScheduled {
+    pub proposal: Pubkey,
+    pub execute_not_before: i64,
+    pub timestamp: i64,
+}
+
+/// A proposal now waits on `linked_proposal`'s approval in another swarm
+#[event]
+pub struct ProposalLinked {
+    pub proposal: Pubkey,
+    pub linked_proposal: Pubkey,
+    pub linked_swarm: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RandomnessCommitted {
+    pub proposal: Pubkey,
+    pub committer: Pubkey,
+    pub target_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RandomnessRevealed {
+    pub proposal: Pubkey,
+    pub randomness: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub executor: Pubkey,
+    pub proposal_type: ProposalType,
+    pub weighted_votes_for: u64,
+    pub weighted_votes_against: u64,
+    pub tags: Vec<String>,
+    pub timestamp: i64,
+}
+
+/// Outcome written by `record_outcome`, or by `execute_proposal` for a
+/// `Strategy` with balance checks
+#[event]
+pub struct OutcomeRecorded {
+    pub proposal: Pubkey,
+    pub executed_by: Pubkey,
+    pub success: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalClosed {
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Agent reputation moved by the authority, a vote settlement or an
+/// endorsement
+#[event]
+pub struct ReputationChanged {
+    pub agent: Pubkey,
+    pub old_reputation: u16,
+    pub new_reputation: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MembershipLinked {
+    pub identity: Pubkey,
+    pub swarm: Pubkey,
+    pub agent: Pubkey,
+    pub linked: bool,
+    pub timestamp: i64,
+}
+
+/// A swarm's reputation changes were folded into the agent's identity
+#[event]
+pub struct ReputationSynced {
+    pub identity: Pubkey,
+    pub agent: Pubkey,
+    pub drift: i32,
+    pub reputation: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteSettled {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub correct: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EloMatchSettled {
+    pub winner_proposal: Pubkey,
+    pub loser_proposal: Pubkey,
+    pub rating_change: u16,
+    pub winner_rating: u16,
+    pub loser_rating: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalEndorsed {
+    pub proposal: Pubkey,
+    pub endorser: Pubkey,
+    pub stake: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EndorsementSettled {
+    pub proposal: Pubkey,
+    pub endorser: Pubkey,
+    pub stake: u16,
+    pub payout: u16,
+    pub timestamp: i64,
+}
+
+/// `until` is the current time when a sabbatical is ended early
+#[event]
+pub struct SabbaticalSet {
+    pub agent: Pubkey,
+    pub until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VotingRewardClaimed {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SpendCapSet {
+    pub proposal_type: ProposalType,
+    pub cap: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceFeedSet {
+    pub mint: Pubkey,
+    /// `None` when the feed was removed
+    pub source: Option<OracleSource>,
+    pub max_age: i64,
+    pub max_deviation_bps: u16,
+    pub timestamp: i64,
+}
+
+/// An oracle price was folded into a mint's TWAP
+#[event]
+pub struct TwapObserved {
+    pub swarm: Pubkey,
+    pub mint: Pubkey,
+    /// Scaled by `TWAP_PRICE_EXPONENT`
+    pub price: i64,
+    pub published_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExposureLimitSet {
+    pub mint: Pubkey,
+    /// `None` when the limit was removed
+    pub max_amount: Option<u64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradeExposureReserved {
+    pub proposal: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyAttached {
+    pub proposal: Pubkey,
+    pub funder: Pubkey,
+    /// `None` for SOL bounties
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Bounty paid to the executor, or refunded to the funder
+#[event]
+pub struct BountyReleased {
+    pub proposal: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub refunded: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BondPosted {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnbondRequested {
+    pub owner: Pubkey,
+    pub withdrawable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BondWithdrawn {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardEpochSettled {
+    pub epoch: u64,
+    pub budget: u64,
+    pub points: u64,
+    pub timestamp: i64,
+}
+
+/// An epoch ended and its activity was recorded in `EpochStats`
+#[event]
+pub struct EpochAdvanced {
+    pub swarm: Pubkey,
+    pub epoch: u64,
+    pub proposals: u64,
+    pub executions: u64,
+    pub failures: u64,
+    pub votes: u64,
+    pub success_rate_bps: u16,
+    pub participation_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub agent: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensLocked {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub lock_end: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LockExtended {
+    pub owner: Pubkey,
+    pub lock_end: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LockedTokensWithdrawn {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmissionsMinted {
+    pub mint: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingCreated {
+    pub listing: Pubkey,
+    pub agent: Pubkey,
+    pub owner: Pubkey,
+    pub agent_type: AgentType,
+    pub category: ListingCategory,
+    pub price: u64,
+    pub price_mint: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingDelisted {
+    pub listing: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingUpdated {
+    pub listing: Pubkey,
+    pub price: u64,
+    pub price_mint: Option<Pubkey>,
+    pub category: ListingCategory,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingTransferred {
+    pub listing: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VersionPublished {
+    pub listing: Pubkey,
+    pub version: String,
+    pub changelog_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentPurchased {
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub price_paid: u64,
+    pub refund_deadline: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SaleProceedsClaimed {
+    pub purchase: Pubkey,
+    pub seller: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PurchaseRefunded {
+    pub purchase: Pubkey,
+    pub buyer: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionTermsSet {
+    pub listing: Pubkey,
+    pub period_price: u64,
+    pub period_length: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Subscribed {
+    pub listing: Pubkey,
+    pub subscriber: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionRenewed {
+    pub listing: Pubkey,
+    pub subscriber: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentRated {
+    pub listing: Pubkey,
+    pub rater: Pubkey,
+    pub stars: u8,
+    pub average: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentReviewed {
+    pub listing: Pubkey,
+    pub reviewer: Pubkey,
+    pub stars: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionStarted {
+    pub auction: Pubkey,
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub reserve_price: u64,
+    pub ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidPlaced {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidCancelled {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionSettled {
+    pub auction: Pubkey,
+    pub listing: Pubkey,
+    pub winner: Option<Pubkey>,
+    pub amount: u64,
+    pub royalty: u64,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
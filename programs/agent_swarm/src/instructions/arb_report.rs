@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::ArbReportSubmitted;
+use crate::AgentType;
+
+/// Arbitrage agent reports an opportunity along the route hashing to
+/// `route_hash`. The first report of a route holds it until it expires.
+#[derive(Accounts)]
+#[instruction(route_hash: [u8; 32])]
+pub struct SubmitArbReport<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == reporter.key() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.is_active() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.agent_type() == AgentType::Arbitrage @ SwarmError::InvalidAgentType
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+
+    #[account(
+        init,
+        payer = reporter,
+        space = ArbReport::LEN,
+        seeds = [ARB_REPORT_SEED, swarm_state.key().as_ref(), route_hash.as_ref()],
+        bump
+    )]
+    pub arb_report: Account<'info, ArbReport>,
+
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Close an arbitrage report that paid out or expired (permissionless),
+/// returning its rent to whoever submitted it
+#[derive(Accounts)]
+pub struct CloseArbReport<'info> {
+    #[account(
+        mut,
+        close = payer,
+        seeds = [ARB_REPORT_SEED, arb_report.swarm.as_ref(), arb_report.route_hash.as_ref()],
+        bump = arb_report.bump
+    )]
+    pub arb_report: Account<'info, ArbReport>,
+
+    /// CHECK: Receives the report's rent; must be the account that paid it
+    #[account(mut, address = arb_report.payer @ SwarmError::Unauthorized)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+pub fn submit_arb_report(
+    ctx: Context<SubmitArbReport>,
+    route_hash: [u8; 32],
+    buy_venue: Pubkey,
+    sell_venue: Pubkey,
+    expected_edge_bps: u16,
+) -> Result<()> {
+    require!(
+        buy_venue != Pubkey::default()
+            && sell_venue != Pubkey::default()
+            && expected_edge_bps > 0
+            && route_hash != [0; 32],
+        SwarmError::InvalidParameter
+    );
+
+    let clock = Clock::get()?;
+    let mut agent = ctx.accounts.agent.load_mut()?;
+    agent.last_active = clock.unix_timestamp;
+
+    let arb_report = &mut ctx.accounts.arb_report;
+    arb_report.swarm = ctx.accounts.swarm_state.key();
+    arb_report.reporter = agent.owner;
+    arb_report.buy_venue = buy_venue;
+    arb_report.sell_venue = sell_venue;
+    arb_report.expected_edge_bps = expected_edge_bps;
+    arb_report.route_hash = route_hash;
+    arb_report.reported_at = clock.unix_timestamp;
+    arb_report.expires_at = clock.unix_timestamp
+        .checked_add(ARB_REPORT_WINDOW)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    arb_report.credited_proposal = None;
+    arb_report.payer = ctx.accounts.reporter.key();
+    arb_report.version = ArbReport::VERSION;
+    arb_report.bump = ctx.bumps.arb_report;
+
+    emit!(ArbReportSubmitted {
+        report: arb_report.key(),
+        reporter: arb_report.reporter,
+        buy_venue,
+        sell_venue,
+        expected_edge_bps,
+        route_hash,
+        expires_at: arb_report.expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Arbitrage report: {} bps expected, open until {}", expected_edge_bps, arb_report.expires_at);
+
+    Ok(())
+}
+
+pub fn close_arb_report(ctx: Context<CloseArbReport>) -> Result<()> {
+    let arb_report = &ctx.accounts.arb_report;
+    require!(
+        arb_report.credited_proposal.is_some()
+            || Clock::get()?.unix_timestamp > arb_report.expires_at,
+        SwarmError::ArbReportOpen
+    );
+    msg!("Arbitrage report closed");
+    Ok(())
+}
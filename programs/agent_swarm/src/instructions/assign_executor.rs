@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::AgentType;
+use crate::events::ExecutorAssigned;
+
+/// Proposer nominates an Execution agent for its proposal, once. Only
+/// enforced when the swarm runs in `ExecutionMode::Permissioned`.
+#[derive(Accounts)]
+pub struct AssignExecutor<'info> {
+    #[account(mut)]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    /// The proposer's agent, whose signing key must sign
+    #[account(
+        seeds = [AGENT_SEED, proposal.load()?.swarm.as_ref(), proposal.load()?.proposer.as_ref()],
+        bump = proposer_agent.load()?.bump,
+        constraint = proposer_agent.load()?.signing_key == proposer.key() @ SwarmError::Unauthorized
+    )]
+    pub proposer_agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            proposal.load()?.swarm.as_ref(),
+            executor_agent.load()?.owner.as_ref()
+        ],
+        bump = executor_agent.load()?.bump,
+        constraint = executor_agent.load()?.is_active() @ SwarmError::Unauthorized,
+        constraint = executor_agent.load()?.agent_type() == AgentType::Execution @ SwarmError::InvalidAgentType
+    )]
+    pub executor_agent: AccountLoader<'info, Agent>,
+    
+    pub proposer: Signer<'info>,
+}
+
+pub fn assign_executor(ctx: Context<AssignExecutor>) -> Result<()> {
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    let executor_agent = ctx.accounts.executor_agent.load()?;
+    let clock = Clock::get()?;
+
+    require!(!proposal.is_executed(), SwarmError::ProposalAlreadyExecuted);
+    require!(!proposal.is_failed(), SwarmError::ProposalFailed);
+    require!(
+        !proposal.is_expired(clock.unix_timestamp),
+        SwarmError::ProposalExpired
+    );
+    proposal.nominate_executor(executor_agent.owner, clock.unix_timestamp)?;
+
+    emit!(ExecutorAssigned {
+        proposal: ctx.accounts.proposal.key(),
+        executor: executor_agent.owner,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Executor assigned: {}", executor_agent.owner);
+
+    Ok(())
+}
+
+/// Assign the Execution agent drawn by the proposal's revealed randomness
+/// (permissionless). Replaces any nomination and cannot itself be replaced,
+/// so neither proposer nor executors can steer who executes.
+#[derive(Accounts)]
+pub struct AssignRandomExecutor<'info> {
+    #[account(
+        mut,
+        constraint = proposal.load()?.randomness().is_some() @ SwarmError::RandomnessNotRevealed,
+        constraint = !proposal.load()?.is_executor_drawn() @ SwarmError::ExecutorAlreadyDrawn
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        seeds = [AGENT_REGISTRY_SEED, proposal.load()?.swarm.as_ref()],
+        bump = agent_registry.bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
+    #[account(
+        seeds = [
+            AGENT_SEED,
+            proposal.load()?.swarm.as_ref(),
+            executor_agent.load()?.owner.as_ref()
+        ],
+        bump = executor_agent.load()?.bump
+    )]
+    pub executor_agent: AccountLoader<'info, Agent>,
+}
+
+pub fn assign_random_executor(ctx: Context<AssignRandomExecutor>) -> Result<()> {
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    let executor_agent = ctx.accounts.executor_agent.load()?;
+    let clock = Clock::get()?;
+
+    require!(!proposal.is_executed(), SwarmError::ProposalAlreadyExecuted);
+    require!(!proposal.is_failed(), SwarmError::ProposalFailed);
+    require!(
+        !proposal.is_expired(clock.unix_timestamp),
+        SwarmError::ProposalExpired
+    );
+
+    let drawn = ctx.accounts
+        .agent_registry
+        .draw(AgentType::Execution, &proposal.randomness)
+        .ok_or(SwarmError::AgentNotFound)?;
+    require_keys_eq!(ctx.accounts.executor_agent.key(), drawn, SwarmError::NotDrawnExecutor);
+
+    proposal.assigned_executor = executor_agent.owner;
+    proposal.assigned_at = clock.unix_timestamp;
+    proposal.executor_drawn = 1;
+
+    emit!(ExecutorAssigned {
+        proposal: ctx.accounts.proposal.key(),
+        executor: executor_agent.owner,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Executor drawn: {}", executor_agent.owner);
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::{AttestationCited, OutputAttested};
+use crate::introspection::assert_ed25519_signature;
+
+/// Record an agent's signature over an off-chain output. Its signing key
+/// signs `Attestation::message` off-chain and anyone can relay it, placing
+/// the ed25519 program instruction directly before this one.
+#[derive(Accounts)]
+#[instruction(payload_hash: [u8; 32])]
+pub struct AttestOutput<'info> {
+    #[account(
+        seeds = [AGENT_SEED, agent.load()?.swarm.as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.is_active() @ SwarmError::AgentNotFound
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = Attestation::LEN,
+        seeds = [ATTESTATION_SEED, agent.key().as_ref(), payload_hash.as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+    
+    /// CHECK: Address-constrained to the instructions sysvar
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Proposer cites an attested output as the proposal's basis. Only allowed
+/// before voting starts, so voters see the citation they approve.
+#[derive(Accounts)]
+pub struct CiteAttestation<'info> {
+    #[account(mut)]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    /// The proposer's agent, whose signing key must sign
+    #[account(
+        seeds = [AGENT_SEED, proposal.load()?.swarm.as_ref(), proposal.load()?.proposer.as_ref()],
+        bump = proposer_agent.load()?.bump,
+        constraint = proposer_agent.load()?.signing_key == proposer.key() @ SwarmError::Unauthorized
+    )]
+    pub proposer_agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        constraint = attestation.swarm == proposal.load()?.swarm @ SwarmError::InvalidAttestation
+    )]
+    pub attestation: Account<'info, Attestation>,
+    
+    pub proposer: Signer<'info>,
+}
+
+pub fn attest_output(
+    ctx: Context<AttestOutput>,
+    payload_hash: [u8; 32],
+    model_hash: [u8; 32],
+) -> Result<()> {
+    let agent_key = ctx.accounts.agent.key();
+    let (swarm, signing_key) = {
+        let agent = ctx.accounts.agent.load()?;
+        (agent.swarm, agent.signing_key)
+    };
+    assert_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        &signing_key,
+        &Attestation::message(&agent_key, &payload_hash, &model_hash),
+    )?;
+
+    let clock = Clock::get()?;
+    let attestation = &mut ctx.accounts.attestation;
+    attestation.swarm = swarm;
+    attestation.agent = agent_key;
+    attestation.signer = signing_key;
+    attestation.payload_hash = payload_hash;
+    attestation.model_hash = model_hash;
+    attestation.slot = clock.slot;
+    attestation.attested_at = clock.unix_timestamp;
+    attestation.version = Attestation::VERSION;
+    attestation.bump = ctx.bumps.attestation;
+
+    emit!(OutputAttested {
+        attestation: attestation.key(),
+        swarm,
+        agent: agent_key,
+        signer: signing_key,
+        payload_hash,
+        model_hash,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Output attested by {} for agent {}", signing_key, agent_key);
+
+    Ok(())
+}
+
+pub fn cite_attestation(ctx: Context<CiteAttestation>) -> Result<()> {
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+
+    require!(proposal.total_voters == 0, SwarmError::VotingAlreadyStarted);
+    require!(!proposal.is_executed(), SwarmError::ProposalAlreadyExecuted);
+
+    proposal.attestation = ctx.accounts.attestation.key();
+
+    emit!(AttestationCited {
+        proposal: ctx.accounts.proposal.key(),
+        attestation: proposal.attestation,
+        payload_hash: ctx.accounts.attestation.payload_hash,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Proposal cites attestation {}", proposal.attestation);
+
+    Ok(())
+}
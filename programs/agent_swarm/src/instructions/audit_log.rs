@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::SwarmAccountCreated;
+
+/// Create the swarm's empty audit log. Config changes, committee setup and
+/// slashes require it, as do `RiskLimit` and `AuthorityHandover` executions.
+#[derive(Accounts)]
+pub struct InitializeAuditLog<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == swarm_state.authority @ SwarmError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_audit_log(ctx: Context<InitializeAuditLog>) -> Result<()> {
+    let audit_log = &mut ctx.accounts.audit_log;
+    audit_log.swarm = ctx.accounts.swarm_state.key();
+    audit_log.entries = Vec::new();
+    audit_log.next_slot = 0;
+    audit_log.total_entries = 0;
+    audit_log.version = AuditLog::VERSION;
+    audit_log.bump = ctx.bumps.audit_log;
+
+    emit!(SwarmAccountCreated {
+        account: audit_log.key(),
+        created_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Audit log initialized");
+    Ok(())
+}
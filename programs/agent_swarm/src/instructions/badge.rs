@@ -0,0 +1,252 @@
+use anchor_lang::prelude::*;
+use crate::cpi::{bubblegum, CPIHelper};
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::{BadgeAwarded, BadgeConfigured};
+
+/// Set the swarm's badge milestones and tree (authority only). The tree's
+/// owner makes the `BadgeConfig` PDA its delegate so awards can mint.
+#[derive(Accounts)]
+pub struct ConfigureBadges<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump,
+        constraint = swarm_state.authority == authority.key() @ SwarmError::Unauthorized
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = BadgeConfig::LEN,
+        seeds = [BADGE_CONFIG_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub badge_config: Account<'info, BadgeConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless crank minting a badge to an agent's owner once it is
+/// earned. For `TopReputation` every other active agent of the swarm
+/// follows as remaining accounts.
+#[derive(Accounts)]
+#[instruction(kind: BadgeKind, epoch: u64)]
+pub struct AwardBadge<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        seeds = [BADGE_CONFIG_SEED, swarm_state.key().as_ref()],
+        bump = badge_config.bump
+    )]
+    pub badge_config: Account<'info, BadgeConfig>,
+    
+    #[account(
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.is_active() @ SwarmError::AgentNotFound
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        init,
+        payer = cranker,
+        space = AgentBadge::LEN,
+        seeds = [BADGE_SEED, agent.key().as_ref(), &[kind.index()], &epoch.to_le_bytes()],
+        bump
+    )]
+    pub badge: Account<'info, AgentBadge>,
+    
+    /// Required for `TopReputation` badges
+    #[account(
+        seeds = [AGENT_REGISTRY_SEED, swarm_state.key().as_ref()],
+        bump = agent_registry.bump
+    )]
+    pub agent_registry: Option<Account<'info, AgentRegistry>>,
+    
+    /// CHECK: Bubblegum's config of `merkle_tree`, checked in `bubblegum::validate_accounts`
+    #[account(mut)]
+    pub tree_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Agent owner receiving the badge
+    #[account(address = agent.load()?.owner @ SwarmError::Unauthorized)]
+    pub leaf_owner: UncheckedAccount<'info>,
+    
+    /// CHECK: The swarm's badge tree
+    #[account(mut, address = badge_config.merkle_tree @ SwarmError::InvalidParameter)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL Noop, checked in `bubblegum::validate_accounts`
+    pub log_wrapper: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL Account Compression, checked in `bubblegum::validate_accounts`
+    pub compression_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Metaplex Bubblegum
+    #[account(address = bubblegum::PROGRAM_ID @ SwarmError::UntrustedProgram)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_badges(
+    ctx: Context<ConfigureBadges>,
+    merkle_tree: Pubkey,
+    votes_milestone: u64,
+    proposals_milestone: u64,
+    top_reputation_enabled: bool,
+    uri_prefix: String,
+) -> Result<()> {
+    require!(
+        merkle_tree != Pubkey::default() && uri_prefix.len() <= MAX_BADGE_URI_PREFIX_LENGTH,
+        SwarmError::InvalidConfig
+    );
+
+    let swarm_state = &ctx.accounts.swarm_state;
+    let badge_config = &mut ctx.accounts.badge_config;
+    if badge_config.swarm == Pubkey::default() {
+        badge_config.swarm = swarm_state.key();
+        badge_config.next_top_reputation_epoch = swarm_state.rewards.epoch;
+        badge_config.version = BadgeConfig::VERSION;
+        badge_config.bump = ctx.bumps.badge_config;
+    }
+    badge_config.merkle_tree = merkle_tree;
+    badge_config.votes_milestone = votes_milestone;
+    badge_config.proposals_milestone = proposals_milestone;
+    badge_config.top_reputation_enabled = top_reputation_enabled;
+    badge_config.uri_prefix = uri_prefix;
+
+    emit!(BadgeConfigured {
+        swarm: badge_config.swarm,
+        merkle_tree,
+        votes_milestone,
+        proposals_milestone,
+        top_reputation_enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!(
+        "Badges: {} votes, {} successful proposals, top reputation {}",
+        votes_milestone,
+        proposals_milestone,
+        top_reputation_enabled
+    );
+
+    Ok(())
+}
+
+pub fn award_badge<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AwardBadge<'info>>,
+    kind: BadgeKind,
+    epoch: u64,
+) -> Result<()> {
+    let agent_key = ctx.accounts.agent.key();
+    let (owner, reputation) = {
+        let agent = ctx.accounts.agent.load()?;
+        require!(ctx.accounts.badge_config.is_earned(kind, &agent), SwarmError::BadgeNotEarned);
+        (agent.owner, agent.reputation)
+    };
+
+    let badge_config = &mut ctx.accounts.badge_config;
+    if kind == BadgeKind::TopReputation {
+        // One holder per epoch, proven against every other active agent
+        require!(
+            epoch == ctx.accounts.swarm_state.rewards.epoch
+                && epoch >= badge_config.next_top_reputation_epoch,
+            SwarmError::BadgeNotEarned
+        );
+        let registry = ctx
+            .accounts
+            .agent_registry
+            .as_ref()
+            .ok_or(SwarmError::MissingExecutionAccount)?;
+        let others: Vec<&RegistryEntry> =
+            registry.active().filter(|entry| entry.agent != agent_key).collect();
+        require!(
+            ctx.remaining_accounts.len() == others.len(),
+            SwarmError::MissingExecutionAccount
+        );
+        for (entry, account) in others.iter().zip(ctx.remaining_accounts) {
+            require_keys_eq!(account.key(), entry.agent, SwarmError::AgentNotFound);
+            let other = AccountLoader::<Agent>::try_from(account)?;
+            require!(
+                other.load()?.reputation <= reputation,
+                SwarmError::BadgeNotEarned
+            );
+        }
+        badge_config.next_top_reputation_epoch =
+            epoch.checked_add(1).ok_or(SwarmError::ArithmeticOverflow)?;
+    } else {
+        require!(epoch == 0, SwarmError::InvalidParameter);
+    }
+
+    let metadata = bubblegum::MetadataArgs {
+        name: kind.name().to_string(),
+        symbol: BADGE_SYMBOL.to_string(),
+        uri: format!("{}{}", badge_config.uri_prefix, kind.uri_suffix()),
+        seller_fee_basis_points: 0,
+        primary_sale_happened: false,
+        is_mutable: false,
+        edition_nonce: None,
+        token_standard: Some(bubblegum::TokenStandard::NonFungible),
+        collection: None,
+        uses: None,
+        token_program_version: bubblegum::TokenProgramVersion::Original,
+        creators: Vec::new(),
+    };
+    let swarm_key = ctx.accounts.swarm_state.key();
+    let bump = [badge_config.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[BADGE_CONFIG_SEED, swarm_key.as_ref(), &bump]];
+    CPIHelper::bubblegum_mint(
+        &ctx.accounts.bubblegum_program,
+        &[
+            ctx.accounts.tree_config.to_account_info(),
+            ctx.accounts.leaf_owner.to_account_info(),
+            ctx.accounts.leaf_owner.to_account_info(),
+            ctx.accounts.merkle_tree.to_account_info(),
+            ctx.accounts.cranker.to_account_info(),
+            badge_config.to_account_info(),
+            ctx.accounts.log_wrapper.to_account_info(),
+            ctx.accounts.compression_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &badge_config.key(),
+        &metadata,
+        signer_seeds,
+    )?;
+    badge_config.badges_minted = badge_config.badges_minted.saturating_add(1);
+
+    let clock = Clock::get()?;
+    let badge = &mut ctx.accounts.badge;
+    badge.agent = agent_key;
+    badge.owner = owner;
+    badge.kind = kind;
+    badge.epoch = epoch;
+    badge.merkle_tree = badge_config.merkle_tree;
+    badge.awarded_at = clock.unix_timestamp;
+    badge.version = AgentBadge::VERSION;
+    badge.bump = ctx.bumps.badge;
+
+    emit!(BadgeAwarded {
+        agent: agent_key,
+        owner,
+        kind,
+        epoch,
+        merkle_tree: badge.merkle_tree,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("{} badge awarded to {}", kind.name(), owner);
+
+    Ok(())
+}
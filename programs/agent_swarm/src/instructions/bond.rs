@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::{BondPosted, BondWithdrawn, UnbondRequested};
+
+/// Lock SOL behind an agent. Bonds remain slashable until withdrawn, and can
+/// only be withdrawn `BOND_UNBONDING_PERIOD` after unbonding starts.
+#[derive(Accounts)]
+pub struct PostBond<'info> {
+    #[account(
+        seeds = [AGENT_SEED, agent.load()?.swarm.as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == owner.key() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.is_active() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = AgentBond::LEN,
+        seeds = [BOND_SEED, agent.load()?.swarm.as_ref(), agent.load()?.owner.as_ref()],
+        bump
+    )]
+    pub bond: Account<'info, AgentBond>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnbond<'info> {
+    #[account(
+        mut,
+        seeds = [BOND_SEED, bond.swarm.as_ref(), bond.owner.as_ref()],
+        bump = bond.bump,
+        constraint = bond.unbonding_at == 0 @ SwarmError::BondUnbonding
+    )]
+    pub bond: Account<'info, AgentBond>,
+    
+    /// The bonded agent, whose signing key must sign
+    #[account(
+        seeds = [AGENT_SEED, bond.swarm.as_ref(), bond.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == owner.key() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawBond<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [BOND_SEED, bond.swarm.as_ref(), bond.owner.as_ref()],
+        bump = bond.bump
+    )]
+    pub bond: Account<'info, AgentBond>,
+    
+    /// The bonded agent, whose signing key must sign
+    #[account(
+        seeds = [AGENT_SEED, bond.swarm.as_ref(), bond.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == owner.key() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn post_bond(ctx: Context<PostBond>, lamports: u64) -> Result<()> {
+    require!(lamports > 0, SwarmError::InvalidParameter);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.bond.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+
+    let bond = &mut ctx.accounts.bond;
+    let agent = ctx.accounts.agent.load()?;
+    bond.swarm = agent.swarm;
+    bond.owner = agent.owner;
+    bond.amount = lamports;
+    bond.total_slashed = 0;
+    bond.unbonding_at = 0;
+    bond.version = AgentBond::VERSION;
+    bond.bump = ctx.bumps.bond;
+
+    emit!(BondPosted {
+        owner: bond.owner,
+        amount: lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Bond posted: {} lamports for {}", lamports, agent.name());
+    Ok(())
+}
+
+pub fn request_unbond(ctx: Context<RequestUnbond>) -> Result<()> {
+    let bond = &mut ctx.accounts.bond;
+    bond.unbonding_at = Clock::get()?.unix_timestamp;
+    let withdrawable_at = bond.unbonding_at.saturating_add(BOND_UNBONDING_PERIOD);
+
+    emit!(UnbondRequested {
+        owner: bond.owner,
+        withdrawable_at,
+        timestamp: bond.unbonding_at,
+    });
+    msg!("Unbonding started; withdrawable after {}", withdrawable_at);
+    Ok(())
+}
+
+pub fn withdraw_bond(ctx: Context<WithdrawBond>) -> Result<()> {
+    let bond = &ctx.accounts.bond;
+    let clock = Clock::get()?;
+
+    require!(
+        bond.is_withdrawable(clock.unix_timestamp),
+        SwarmError::BondUnbonding
+    );
+
+    // `close = owner` returns the remaining bond together with the rent
+    emit!(BondWithdrawn {
+        owner: bond.owner,
+        amount: bond.amount,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Bond withdrawn: {} lamports", bond.amount);
+    Ok(())
+}
@@ -0,0 +1,314 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
+use crate::state::*;
+use crate::constants::*;
+use crate::cpi::CPIHelper;
+use crate::errors::SwarmError;
+use crate::events::{BountyAttached, BountyReleased};
+
+/// Escrow a bounty for whoever executes `proposal` successfully.
+///
+/// SOL bounties are held as lamports on the bounty account itself. SPL
+/// bounties are moved into `escrow_token_account`, a token account owned by
+/// the bounty PDA that the funder creates beforehand.
+#[derive(Accounts)]
+pub struct AttachBounty<'info> {
+    #[account(mut)]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        init,
+        payer = funder,
+        space = Bounty::LEN,
+        seeds = [BOUNTY_SEED, proposal.key().as_ref(), funder.key().as_ref()],
+        bump
+    )]
+    pub bounty: Account<'info, Bounty>,
+    
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = funder_token_account.owner == funder.key() @ SwarmError::Unauthorized
+    )]
+    pub funder_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = escrow_token_account.owner == bounty.key() @ SwarmError::Unauthorized
+    )]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Pay a bounty to the agent that recorded a successful outcome
+#[derive(Accounts)]
+pub struct ClaimBounty<'info> {
+    #[account(mut)]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        seeds = [OUTCOME_SEED, proposal.key().as_ref()],
+        bump = outcome.bump,
+        constraint = outcome.success @ SwarmError::BountyNotClaimable,
+        constraint = outcome.executed_by == executor.key() @ SwarmError::Unauthorized
+    )]
+    pub outcome: Account<'info, Outcome>,
+    
+    #[account(
+        mut,
+        close = funder,
+        seeds = [BOUNTY_SEED, proposal.key().as_ref(), bounty.funder.as_ref()],
+        bump = bounty.bump
+    )]
+    pub bounty: Account<'info, Bounty>,
+    
+    /// CHECK: Receives the bounty account's rent; checked against `bounty.funder`
+    #[account(mut, address = bounty.funder)]
+    pub funder: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = executor_token_account.owner == executor.key() @ SwarmError::Unauthorized
+    )]
+    pub executor_token_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+/// Permissionless crank returning a bounty to its funder once the proposal
+/// expired unexecuted, ran out of execution attempts or its recorded outcome
+/// failed
+#[derive(Accounts)]
+pub struct RefundBounty<'info> {
+    #[account(mut)]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        seeds = [OUTCOME_SEED, proposal.key().as_ref()],
+        bump = outcome.bump
+    )]
+    pub outcome: Option<Account<'info, Outcome>>,
+    
+    #[account(
+        mut,
+        close = funder,
+        seeds = [BOUNTY_SEED, proposal.key().as_ref(), bounty.funder.as_ref()],
+        bump = bounty.bump
+    )]
+    pub bounty: Account<'info, Bounty>,
+    
+    /// CHECK: Receives the refund; checked against `bounty.funder`
+    #[account(mut, address = bounty.funder)]
+    pub funder: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = funder_token_account.owner == bounty.funder @ SwarmError::Unauthorized
+    )]
+    pub funder_token_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    
+    pub cranker: Signer<'info>,
+}
+
+pub fn attach_bounty(ctx: Context<AttachBounty>, amount: u64) -> Result<()> {
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, SwarmError::InvalidParameter);
+    require!(!proposal.is_executed(), SwarmError::ProposalAlreadyExecuted);
+    require!(!proposal.is_failed(), SwarmError::ProposalFailed);
+    require!(
+        !proposal.is_expired(clock.unix_timestamp),
+        SwarmError::ProposalExpired
+    );
+    proposal.open_bounties = proposal
+        .open_bounties
+        .checked_add(1)
+        .ok_or(SwarmError::TooManyOpenClaims)?;
+
+    let mint = match (
+        ctx.accounts.funder_token_account.as_ref(),
+        ctx.accounts.escrow_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+    ) {
+        (Some(funder_token_account), Some(escrow_token_account), Some(token_program)) => {
+            require_keys_eq!(
+                funder_token_account.mint,
+                escrow_token_account.mint,
+                SwarmError::InvalidParameter
+            );
+            CPIHelper::transfer_tokens(
+                funder_token_account,
+                escrow_token_account,
+                &ctx.accounts.funder.to_account_info(),
+                token_program,
+                amount,
+                &[],
+            )?;
+            Some(escrow_token_account.mint)
+        }
+        (None, None, _) => {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.funder.to_account_info(),
+                        to: ctx.accounts.bounty.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+            None
+        }
+        _ => return err!(SwarmError::MissingExecutionAccount),
+    };
+
+    let bounty = &mut ctx.accounts.bounty;
+    bounty.proposal = ctx.accounts.proposal.key();
+    bounty.funder = ctx.accounts.funder.key();
+    bounty.mint = mint;
+    bounty.amount = amount;
+    bounty.created_at = clock.unix_timestamp;
+    bounty.version = Bounty::VERSION;
+    bounty.bump = ctx.bumps.bounty;
+
+    emit!(BountyAttached {
+        proposal: bounty.proposal,
+        funder: bounty.funder,
+        mint,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Bounty of {} attached to proposal {}", amount, ctx.accounts.proposal.key());
+    Ok(())
+}
+
+pub fn claim_bounty(ctx: Context<ClaimBounty>) -> Result<()> {
+    let amount = ctx.accounts.bounty.amount;
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    proposal.open_bounties = proposal.open_bounties.saturating_sub(1);
+
+    release_bounty(
+        &ctx.accounts.bounty,
+        &ctx.accounts.funder.to_account_info(),
+        &ctx.accounts.executor.to_account_info(),
+        ctx.accounts.escrow_token_account.as_ref(),
+        ctx.accounts.executor_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    emit!(BountyReleased {
+        proposal: ctx.accounts.proposal.key(),
+        recipient: ctx.accounts.executor.key(),
+        amount,
+        refunded: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Bounty of {} paid to {}", amount, ctx.accounts.executor.key());
+    Ok(())
+}
+
+pub fn refund_bounty(ctx: Context<RefundBounty>) -> Result<()> {
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    let clock = Clock::get()?;
+
+    require!(
+        proposal.is_bounty_refundable(
+            ctx.accounts.outcome.as_ref().map(|outcome| outcome.success),
+            clock.unix_timestamp,
+        ),
+        SwarmError::BountyNotRefundable
+    );
+    proposal.open_bounties = proposal.open_bounties.saturating_sub(1);
+
+    let amount = ctx.accounts.bounty.amount;
+    let funder = ctx.accounts.funder.to_account_info();
+
+    release_bounty(
+        &ctx.accounts.bounty,
+        &funder,
+        &funder,
+        ctx.accounts.escrow_token_account.as_ref(),
+        ctx.accounts.funder_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    emit!(BountyReleased {
+        proposal: ctx.accounts.proposal.key(),
+        recipient: funder.key(),
+        amount,
+        refunded: true,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Bounty of {} refunded to {}", amount, funder.key());
+    Ok(())
+}
+
+/// Move the escrowed bounty to `recipient`. SPL escrows are emptied and
+/// closed back to the funder; the bounty account itself is closed by the
+/// caller's `close` constraint.
+fn release_bounty<'info>(
+    bounty: &Account<'info, Bounty>,
+    funder: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    escrow_token_account: Option<&Account<'info, TokenAccount>>,
+    recipient_token_account: Option<&Account<'info, TokenAccount>>,
+    token_program: Option<&Program<'info, Token>>,
+) -> Result<()> {
+    let Some(mint) = bounty.mint else {
+        bounty.sub_lamports(bounty.amount)?;
+        recipient.add_lamports(bounty.amount)?;
+        return Ok(());
+    };
+
+    let (Some(escrow_token_account), Some(recipient_token_account), Some(token_program)) =
+        (escrow_token_account, recipient_token_account, token_program)
+    else {
+        return err!(SwarmError::MissingExecutionAccount);
+    };
+    require_keys_eq!(escrow_token_account.owner, bounty.key(), SwarmError::Unauthorized);
+    require_keys_eq!(escrow_token_account.mint, mint, SwarmError::InvalidParameter);
+
+    let bump = [bounty.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        BOUNTY_SEED,
+        bounty.proposal.as_ref(),
+        bounty.funder.as_ref(),
+        &bump,
+    ]];
+
+    CPIHelper::transfer_tokens(
+        escrow_token_account,
+        recipient_token_account,
+        &bounty.to_account_info(),
+        token_program,
+        bounty.amount,
+        signer_seeds,
+    )?;
+
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow_token_account.to_account_info(),
+            destination: funder.clone(),
+            authority: bounty.to_account_info(),
+        },
+        signer_seeds,
+    ))
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::CircuitBreakerTripped;
+use crate::instructions::treasury::vault_token_account;
+use crate::risk::UnitPrice;
+
+/// Mark the value of the vault's portfolio at oracle prices for the circuit
+/// breaker (permissionless). Trading and rebalancing pause when the mark
+/// falls further below the window's peak than the breaker allows.
+///
+/// The remaining accounts hold, for each portfolio holding in `holdings`
+/// order, the vault's associated token account of its mint then its price
+/// update. Other vault-owned token accounts are rejected so the mark cannot
+/// be driven down with empty ones.
+#[derive(Accounts)]
+pub struct MarkPortfolioValue<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+
+    #[account(
+        mut,
+        seeds = [RISK_CONFIG_SEED, swarm_state.key().as_ref()],
+        bump = risk_config.bump
+    )]
+    pub risk_config: Account<'info, RiskConfig>,
+
+    #[account(
+        seeds = [PORTFOLIO_SEED, swarm_state.key().as_ref()],
+        bump = portfolio.bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+}
+
+pub fn mark_portfolio_value<'info>(
+    ctx: Context<'_, '_, '_, 'info, MarkPortfolioValue<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let swarm_state = &ctx.accounts.swarm_state;
+    let holdings = &ctx.accounts.portfolio.holdings;
+    require!(
+        ctx.remaining_accounts.len() == 2 * holdings.len(),
+        SwarmError::MissingExecutionAccount
+    );
+
+    let mut value: u64 = 0;
+    for (holding, accounts) in holdings.iter().zip(ctx.remaining_accounts.chunks(2)) {
+        let token_account = vault_token_account(swarm_state, &accounts[0])?;
+        require_keys_eq!(
+            accounts[0].key(),
+            get_associated_token_address_with_program_id(
+                &swarm_state.vault_authority,
+                &holding.mint,
+                accounts[0].owner,
+            ),
+            SwarmError::InvalidParameter
+        );
+        let holding_value = UnitPrice::load(swarm_state, &accounts[1], &holding.mint, clock.unix_timestamp)?
+            .value(token_account.amount)?;
+        value = value
+            .checked_add(holding_value as u64)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+    }
+
+    let risk_config = &mut ctx.accounts.risk_config;
+    let was_paused = risk_config.trading_paused;
+    let (peak, drawdown_bps) = risk_config.mark_value(value, clock.unix_timestamp)?;
+    msg!("Portfolio marked at {}, {} bps below the window's peak", value, drawdown_bps);
+    if risk_config.trading_paused && !was_paused {
+        emit!(CircuitBreakerTripped {
+            risk_config: risk_config.key(),
+            value,
+            peak,
+            drawdown_bps,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!("Circuit breaker tripped: trading paused");
+    }
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::VotingRewardClaimed;
+use crate::instructions::treasury::{
+    pay_lamports_from_treasury, pay_tokens_from_treasury, TreasuryTokenSource,
+};
+use crate::VoteType;
+
+#[derive(Accounts)]
+pub struct ClaimVotingRewards<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(constraint = proposal.load()?.swarm == swarm_state.key() @ SwarmError::SwarmMismatch)]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        seeds = [OUTCOME_SEED, proposal.key().as_ref()],
+        bump = outcome.bump,
+        constraint = outcome.success @ SwarmError::NotRewardEligible
+    )]
+    pub outcome: Account<'info, Outcome>,
+    
+    #[account(
+        mut,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), vote_record.voter.as_ref()],
+        bump = vote_record.bump,
+        constraint = vote_record.vote == VoteType::Approve @ SwarmError::NotRewardEligible,
+        constraint = !vote_record.reward_claimed @ SwarmError::RewardAlreadyClaimed
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    /// The voting agent, whose signing key must sign
+    #[account(
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), vote_record.voter.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == voter.key() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, swarm_state.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    /// CHECK: Data-less PDA owning the vault token accounts; required when
+    /// rewards are paid in an SPL mint
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, swarm_state.key().as_ref()],
+        bump = swarm_state.vault_authority_bump
+    )]
+    pub vault_authority: Option<UncheckedAccount<'info>>,
+    
+    /// Required when rewards are paid in an SPL mint; a transfer hook's
+    /// accounts follow as remaining accounts
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = voter_token_account.owner == voter.key() @ SwarmError::Unauthorized
+    )]
+    pub voter_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn claim_voting_rewards<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimVotingRewards<'info>>,
+) -> Result<()> {
+    let swarm_state = &ctx.accounts.swarm_state;
+    let proposal_key = ctx.accounts.proposal.key();
+    let reward = swarm_state.voter_reward;
+
+    require!(reward > 0, SwarmError::NotRewardEligible);
+
+    match swarm_state.voter_reward_mint {
+        None => {
+            pay_lamports_from_treasury(
+                &mut ctx.accounts.treasury,
+                &ctx.accounts.voter.to_account_info(),
+                reward,
+                proposal_key,
+            )?;
+        }
+        Some(mint) => {
+            let (
+                Some(vault_authority),
+                Some(treasury_token_account),
+                Some(voter_token_account),
+                Some(reward_mint),
+                Some(token_program),
+            ) = (
+                ctx.accounts.vault_authority.as_ref(),
+                ctx.accounts.treasury_token_account.as_ref(),
+                ctx.accounts.voter_token_account.as_ref(),
+                ctx.accounts.mint.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ) else {
+                return err!(SwarmError::MissingExecutionAccount);
+            };
+            require_keys_eq!(treasury_token_account.mint, mint, SwarmError::InvalidParameter);
+            pay_tokens_from_treasury(
+                &TreasuryTokenSource {
+                    swarm_state,
+                    vault_authority,
+                    treasury_token_account,
+                    mint: reward_mint,
+                    token_program,
+                    hook_accounts: ctx.remaining_accounts,
+                },
+                voter_token_account,
+                reward,
+                proposal_key,
+            )?;
+        }
+    }
+
+    ctx.accounts.vote_record.reward_claimed = true;
+
+    emit!(VotingRewardClaimed {
+        proposal: proposal_key,
+        voter: ctx.accounts.voter.key(),
+        mint: swarm_state.voter_reward_mint,
+        amount: reward,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Voting reward claimed: {}", reward);
+
+    Ok(())
+}
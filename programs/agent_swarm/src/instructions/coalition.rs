@@ -0,0 +1,462 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::{with_context, FailedCheck, SwarmError};
+use crate::events::{
+    CoalitionBallotCast, CoalitionCreated, CoalitionFundsDeposited, CoalitionFundsWithdrawn,
+    CoalitionMembershipChanged, CoalitionRulesUpdated, CoalitionVoteCast,
+};
+use crate::{require_ctx, AgentType, VoteType};
+
+/// Governance agent forms a coalition, becoming its first member
+#[derive(Accounts)]
+#[instruction(coalition_id: u64)]
+pub struct CreateCoalition<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), founder_agent.load()?.owner.as_ref()],
+        bump = founder_agent.load()?.bump,
+        constraint = founder_agent.load()?.signing_key == founder.key() @ SwarmError::Unauthorized,
+        constraint = founder_agent.load()?.is_active() @ SwarmError::Unauthorized,
+        constraint = founder_agent.load()?.agent_type() == AgentType::Governance @ SwarmError::InvalidAgentType
+    )]
+    pub founder_agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        init,
+        payer = founder,
+        space = Coalition::LEN,
+        seeds = [
+            COALITION_SEED,
+            swarm_state.key().as_ref(),
+            founder.key().as_ref(),
+            &coalition_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub coalition: Account<'info, Coalition>,
+    
+    #[account(mut)]
+    pub founder: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Active agent joins a coalition; the founder co-signs unless membership
+/// is open
+#[derive(Accounts)]
+pub struct JoinCoalition<'info> {
+    #[account(mut)]
+    pub coalition: Account<'info, Coalition>,
+    
+    #[account(
+        seeds = [AGENT_SEED, coalition.swarm.as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == member.key() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.is_active() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    pub member: Signer<'info>,
+    
+    #[account(address = coalition.founder @ SwarmError::Unauthorized)]
+    pub founder: Option<Signer<'info>>,
+}
+
+/// Member leaves a coalition
+#[derive(Accounts)]
+pub struct LeaveCoalition<'info> {
+    #[account(
+        mut,
+        constraint = coalition.is_member(&member.key()) @ SwarmError::NotCoalitionMember
+    )]
+    pub coalition: Account<'info, Coalition>,
+    
+    pub member: Signer<'info>,
+}
+
+/// Founder changes the coalition's internal rules
+#[derive(Accounts)]
+pub struct UpdateCoalitionRules<'info> {
+    #[account(mut, has_one = founder @ SwarmError::Unauthorized)]
+    pub coalition: Account<'info, Coalition>,
+    
+    pub founder: Signer<'info>,
+}
+
+/// Add SOL to a coalition's shared treasury (anyone)
+#[derive(Accounts)]
+pub struct DepositCoalitionFunds<'info> {
+    #[account(mut)]
+    pub coalition: Account<'info, Coalition>,
+    
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Pay SOL out of a coalition's treasury. The other approving members sign
+/// the transaction and are passed as `remaining_accounts`.
+#[derive(Accounts)]
+pub struct WithdrawCoalitionFunds<'info> {
+    #[account(
+        mut,
+        constraint = coalition.is_member(&member.key()) @ SwarmError::NotCoalitionMember
+    )]
+    pub coalition: Account<'info, Coalition>,
+    
+    /// CHECK: Receives lamports only
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    
+    pub member: Signer<'info>,
+}
+
+/// Member records its internal vote on how the coalition should vote
+#[derive(Accounts)]
+pub struct CoalitionVote<'info> {
+    #[account(
+        constraint = coalition.is_member(&member.key()) @ SwarmError::NotCoalitionMember
+    )]
+    pub coalition: Account<'info, Coalition>,
+    
+    #[account(
+        constraint = proposal.load()?.swarm == coalition.swarm @ SwarmError::SwarmMismatch
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = CoalitionBallot::LEN,
+        seeds = [COALITION_BALLOT_SEED, coalition.key().as_ref(), proposal.key().as_ref()],
+        bump
+    )]
+    pub ballot: Account<'info, CoalitionBallot>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Cast a decided coalition position into the proposal on behalf of every
+/// member that voted internally (permissionless). The members' `Agent`
+/// accounts are passed, writable, as `remaining_accounts`.
+#[derive(Accounts)]
+pub struct CastCoalitionVote<'info> {
+    #[account(
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        constraint = coalition.swarm == swarm_state.key() @ SwarmError::SwarmMismatch
+    )]
+    pub coalition: Account<'info, Coalition>,
+    
+    #[account(
+        mut,
+        seeds = [COALITION_BALLOT_SEED, coalition.key().as_ref(), proposal.key().as_ref()],
+        bump = ballot.bump,
+        constraint = !ballot.cast @ SwarmError::CoalitionVoteAlreadyCast
+    )]
+    pub ballot: Account<'info, CoalitionBallot>,
+    
+    #[account(mut)]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    /// Required for proposals scoped to a committee; members outside it
+    /// are skipped
+    #[account(
+        seeds = [COMMITTEE_SEED, swarm_state.key().as_ref(), &[committee.committee_id]],
+        bump = committee.bump
+    )]
+    pub committee: Option<Account<'info, Committee>>,
+}
+
+pub fn create_coalition(
+    ctx: Context<CreateCoalition>,
+    coalition_id: u64,
+    name: String,
+    rules: CoalitionRules,
+) -> Result<()> {
+    require_ctx!(
+        name.len() <= MAX_AGENT_NAME_LENGTH,
+        SwarmError::AgentNameTooLong,
+        FailedCheck::AgentNameLength,
+        MAX_AGENT_NAME_LENGTH,
+        name.len()
+    );
+    rules.validate()?;
+
+    let clock = Clock::get()?;
+    let coalition = &mut ctx.accounts.coalition;
+    coalition.swarm = ctx.accounts.swarm_state.key();
+    coalition.founder = ctx.accounts.founder.key();
+    coalition.coalition_id = coalition_id;
+    coalition.name = name;
+    coalition.members = vec![ctx.accounts.founder.key()];
+    coalition.rules = rules;
+    coalition.created_at = clock.unix_timestamp;
+    coalition.version = Coalition::VERSION;
+    coalition.bump = ctx.bumps.coalition;
+
+    emit!(CoalitionCreated {
+        coalition: coalition.key(),
+        swarm: coalition.swarm,
+        founder: coalition.founder,
+        name: coalition.name.clone(),
+        rules,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Coalition formed: {}", coalition.name);
+
+    Ok(())
+}
+
+pub fn join_coalition(ctx: Context<JoinCoalition>) -> Result<()> {
+    let coalition = &mut ctx.accounts.coalition;
+    let member = ctx.accounts.member.key();
+
+    require!(
+        coalition.rules.open_membership || ctx.accounts.founder.is_some(),
+        SwarmError::Unauthorized
+    );
+    require!(!coalition.is_member(&member), SwarmError::AlreadyCoalitionMember);
+    require!(
+        coalition.members.len() < MAX_COALITION_MEMBERS,
+        SwarmError::CoalitionFull
+    );
+
+    coalition.members.push(member);
+
+    emit!(CoalitionMembershipChanged {
+        coalition: coalition.key(),
+        member,
+        joined: true,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("{} joined coalition {}", member, coalition.name);
+
+    Ok(())
+}
+
+pub fn leave_coalition(ctx: Context<LeaveCoalition>) -> Result<()> {
+    let coalition = &mut ctx.accounts.coalition;
+    let member = ctx.accounts.member.key();
+    coalition.members.retain(|m| *m != member);
+
+    emit!(CoalitionMembershipChanged {
+        coalition: coalition.key(),
+        member,
+        joined: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("{} left coalition {}", member, coalition.name);
+
+    Ok(())
+}
+
+pub fn update_coalition_rules(ctx: Context<UpdateCoalitionRules>, rules: CoalitionRules) -> Result<()> {
+    rules.validate()?;
+    let coalition = &mut ctx.accounts.coalition;
+    coalition.rules = rules;
+
+    emit!(CoalitionRulesUpdated {
+        coalition: coalition.key(),
+        rules,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Coalition rules updated: {:?}", rules);
+
+    Ok(())
+}
+
+pub fn deposit_coalition_funds(ctx: Context<DepositCoalitionFunds>, lamports: u64) -> Result<()> {
+    require!(lamports > 0, SwarmError::InvalidParameter);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.coalition.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+
+    emit!(CoalitionFundsDeposited {
+        coalition: ctx.accounts.coalition.key(),
+        from: ctx.accounts.depositor.key(),
+        amount: lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Deposited {} lamports into coalition {}", lamports, ctx.accounts.coalition.name);
+
+    Ok(())
+}
+
+pub fn withdraw_coalition_funds(ctx: Context<WithdrawCoalitionFunds>, lamports: u64) -> Result<()> {
+    let coalition = &mut ctx.accounts.coalition;
+
+    let mut approvers = vec![ctx.accounts.member.key()];
+    for account in ctx.remaining_accounts {
+        if account.is_signer && coalition.is_member(account.key) && !approvers.contains(account.key) {
+            approvers.push(*account.key);
+        }
+    }
+    require!(
+        coalition.meets_threshold(approvers.len(), coalition.rules.treasury_threshold_bps),
+        SwarmError::InsufficientCoalitionApprovals
+    );
+
+    // The coalition account is owned by this program, so it is debited
+    // directly; the rent-exempt minimum stays behind
+    let rent_floor = Rent::get()?.minimum_balance(Coalition::LEN);
+    let available = coalition.get_lamports().saturating_sub(rent_floor);
+    require!(lamports <= available, SwarmError::InsufficientTreasuryFunds);
+
+    coalition.sub_lamports(lamports)?;
+    ctx.accounts.recipient.add_lamports(lamports)?;
+
+    emit!(CoalitionFundsWithdrawn {
+        coalition: coalition.key(),
+        to: ctx.accounts.recipient.key(),
+        amount: lamports,
+        approvals: approvers.len() as u8,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!(
+        "Withdrew {} lamports from coalition {} with {} approvals",
+        lamports,
+        coalition.name,
+        approvers.len()
+    );
+
+    Ok(())
+}
+
+pub fn coalition_vote(ctx: Context<CoalitionVote>, vote: VoteType) -> Result<()> {
+    let proposal = ctx.accounts.proposal.load()?;
+    let member = ctx.accounts.member.key();
+    let clock = Clock::get()?;
+
+    require!(!proposal.is_executed(), SwarmError::ProposalAlreadyExecuted);
+    require!(!proposal.is_expired(clock.unix_timestamp), SwarmError::ProposalExpired);
+    require!(!proposal.has_voted(&member), SwarmError::AlreadyVoted);
+
+    let ballot = &mut ctx.accounts.ballot;
+    if ballot.coalition == Pubkey::default() {
+        ballot.coalition = ctx.accounts.coalition.key();
+        ballot.proposal = ctx.accounts.proposal.key();
+        ballot.version = CoalitionBallot::VERSION;
+        ballot.bump = ctx.bumps.ballot;
+    }
+    require!(!ballot.cast, SwarmError::CoalitionVoteAlreadyCast);
+    require!(!ballot.voters.contains(&member), SwarmError::AlreadyVoted);
+    ballot.record(member, &vote);
+
+    emit!(CoalitionBallotCast {
+        coalition: ballot.coalition,
+        proposal: ballot.proposal,
+        member,
+        vote: vote.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Coalition ballot: {} voted {:?}", member, vote);
+
+    Ok(())
+}
+
+pub fn cast_coalition_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CastCoalitionVote<'info>>,
+) -> Result<()> {
+    let coalition = &ctx.accounts.coalition;
+    let ballot = &mut ctx.accounts.ballot;
+    let swarm_state = &mut ctx.accounts.swarm_state;
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    let clock = Clock::get()?;
+
+    require!(!proposal.is_executed(), SwarmError::ProposalAlreadyExecuted);
+    require_ctx!(
+        !proposal.is_expired(clock.unix_timestamp),
+        SwarmError::ProposalExpired,
+        FailedCheck::ProposalExpiry,
+        proposal.expires_at,
+        clock.unix_timestamp
+    );
+    require!(proposal.is_data_complete(), SwarmError::ProposalDataIncomplete);
+
+    let vote = ballot
+        .position(coalition)
+        .ok_or(SwarmError::CoalitionPositionUndecided)?;
+    let committee = Committee::for_proposal(&proposal, ctx.accounts.committee.as_deref())?;
+
+    // Every current member that voted internally votes with the bloc, unless
+    // it already voted on the proposal directly, is on sabbatical or sits
+    // outside the proposal's committee
+    let mut voters = 0u8;
+    let mut total_weight = 0u64;
+    for account in ctx.remaining_accounts {
+        let agent_loader = AccountLoader::<Agent>::try_from(account)?;
+        let mut agent = agent_loader.load_mut()?;
+        require_keys_eq!(agent.swarm, coalition.swarm, SwarmError::SwarmMismatch);
+        require!(
+            ballot.voters.contains(&agent.signing_key) && coalition.is_member(&agent.signing_key),
+            SwarmError::NotCoalitionMember
+        );
+        if !agent.is_active()
+            || agent.is_on_sabbatical(clock.unix_timestamp)
+            || proposal.has_voted(&agent.owner)
+            || committee.is_some_and(|committee| !committee.admits(agent.agent_type()))
+        {
+            continue;
+        }
+
+        let weight = agent.blended_vote_weight(swarm_state.elo_blend_bps);
+        proposal
+            .record_vote(&agent.owner, vote.clone(), weight)
+            .map_err(|_| with_context(
+                SwarmError::MaxAgentsReached,
+                FailedCheck::VoterCapacity,
+                Proposal::MAX_VOTERS as i64,
+                proposal.voters().len() as i64,
+            ))?;
+
+        agent.votes_cast = agent.votes_cast.saturating_add(1);
+        agent.last_active = clock.unix_timestamp;
+        swarm_state.rewards.votes = swarm_state.rewards.votes.saturating_add(1);
+        voters += 1;
+        total_weight = total_weight.saturating_add(weight as u64);
+    }
+    ballot.cast = true;
+
+    emit!(CoalitionVoteCast {
+        coalition: coalition.key(),
+        proposal: ctx.accounts.proposal.key(),
+        vote: vote.clone(),
+        voters,
+        weight: total_weight,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!(
+        "Coalition {} voted {:?} for {} members with weight {}",
+        coalition.name,
+        vote,
+        voters,
+        total_weight
+    );
+
+    Ok(())
+}
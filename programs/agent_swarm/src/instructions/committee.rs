@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::{FailedCheck, SwarmError};
+use crate::events::CommitteeConfigured;
+use crate::{require_ctx, AgentType, ProposalType};
+
+/// Create or reconfigure a committee (authority only)
+#[derive(Accounts)]
+#[instruction(committee_id: u8)]
+pub struct ConfigureCommittee<'info> {
+    #[account(
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump,
+        constraint = swarm_state.authority == authority.key() @ SwarmError::Unauthorized
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Committee::LEN,
+        seeds = [COMMITTEE_SEED, swarm_state.key().as_ref(), &[committee_id]],
+        bump
+    )]
+    pub committee: Account<'info, Committee>,
+    
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, swarm_state.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_committee(
+    ctx: Context<ConfigureCommittee>,
+    committee_id: u8,
+    name: String,
+    agent_types: Vec<AgentType>,
+    proposal_types: Vec<ProposalType>,
+    min_votes: u8,
+) -> Result<()> {
+    require!(name.len() <= MAX_AGENT_TYPE_NAME_LENGTH, SwarmError::AgentNameTooLong);
+    require!(
+        !agent_types.is_empty() && agent_types.len() <= MAX_COMMITTEE_AGENT_TYPES && min_votes > 0,
+        SwarmError::InvalidConfig
+    );
+    // `Proposal::committee` stores the id + 1
+    require!(committee_id < u8::MAX, SwarmError::InvalidParameter);
+    // Replacing the authority always takes the whole swarm
+    require!(
+        !proposal_types.contains(&ProposalType::AuthorityHandover),
+        SwarmError::InvalidConfig
+    );
+
+    let swarm_state = &mut ctx.accounts.swarm_state;
+    let committee = &mut ctx.accounts.committee;
+    if committee.swarm == Pubkey::default() {
+        committee.swarm = swarm_state.key();
+        committee.committee_id = committee_id;
+        committee.version = Committee::VERSION;
+        committee.bump = ctx.bumps.committee;
+    }
+
+    let scope = proposal_types
+        .iter()
+        .fold(0u16, |mask, proposal_type| mask | 1 << proposal_type.index());
+    let other_committees = swarm_state.committee_proposal_types & !committee.proposal_types;
+    require!(other_committees & scope == 0, SwarmError::CommitteeScopeOverlap);
+    swarm_state.committee_proposal_types = other_committees | scope;
+
+    committee.name = name;
+    committee.agent_types = agent_types;
+    committee.proposal_types = scope;
+    committee.min_votes = min_votes;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    ctx.accounts.audit_log.record(
+        ctx.accounts.authority.key(),
+        AuditAction::CommitteeConfigured,
+        committee.key(),
+        timestamp,
+    );
+
+    emit!(CommitteeConfigured {
+        committee: committee.key(),
+        swarm: committee.swarm,
+        committee_id,
+        agent_types: committee.agent_types.clone(),
+        proposal_types: scope,
+        min_votes,
+        timestamp,
+    });
+    msg!(
+        "Committee {} now handles proposal types {:#06b} with {} votes",
+        committee.name,
+        scope,
+        min_votes
+    );
+
+    Ok(())
+}
+
+/// Require `proposal`'s quorum: its committee's `min_votes` when it was
+/// scoped to one, otherwise the swarm-wide quorum
+pub fn require_quorum(
+    proposal: &Proposal,
+    swarm_state: &SwarmState,
+    committee: Option<&Committee>,
+) -> Result<()> {
+    match Committee::for_proposal(proposal, committee)? {
+        Some(committee) => {
+            require_ctx!(
+                proposal.total_voters >= committee.min_votes,
+                SwarmError::InsufficientVotes,
+                FailedCheck::Quorum,
+                committee.min_votes,
+                proposal.total_voters
+            );
+        }
+        None => {
+            require_ctx!(
+                proposal.has_quorum(swarm_state.min_votes_required, swarm_state.active_agents),
+                SwarmError::InsufficientVotes,
+                FailedCheck::Quorum,
+                (swarm_state.min_votes_required as u16).max((swarm_state.active_agents as u16).div_ceil(2)),
+                proposal.total_voters
+            );
+        }
+    }
+    Ok(())
+}
@@ -1,35 +1,78 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use crate::state::*;
 use crate::constants::*;
-use crate::errors::SwarmError;
+use crate::errors::{FailedCheck, SwarmError};
+use crate::require_ctx;
+use crate::payloads;
 use crate::ProposalType;
+use crate::oracle::PriceCondition;
+use crate::events::{
+    ProposalCreated, ProposalDataExtended, TreasuryTransferIn, ACCOUNTING_SCHEMA_VERSION,
+    NATIVE_SOL_ASSET,
+};
 
 #[derive(Accounts)]
+#[instruction(proposal_type: ProposalType, data: Vec<u8>)]
 pub struct CreateProposal<'info> {
     #[account(mut)]
     pub swarm_state: Account<'info, SwarmState>,
     
     #[account(
         mut,
-        seeds = [AGENT_SEED, proposer.key().as_ref()],
-        bump = agent.bump,
-        constraint = agent.is_active @ SwarmError::Unauthorized
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == proposer.key() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.is_active() @ SwarmError::Unauthorized
     )]
-    pub agent: Account<'info, Agent>,
+    pub agent: AccountLoader<'info, Agent>,
     
     #[account(
         init,
         payer = proposer,
-        space = Proposal::LEN,
-        seeds = [PROPOSAL_SEED, swarm_state.total_proposals.to_le_bytes().as_ref()],
+        space = Proposal::space(data.len()),
+        seeds = [
+            PROPOSAL_SEED,
+            swarm_state.key().as_ref(),
+            swarm_state.total_proposals.to_le_bytes().as_ref()
+        ],
         bump
     )]
-    pub proposal: Account<'info, Proposal>,
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = ProposalPage::LEN,
+        seeds = [
+            PROPOSAL_PAGE_SEED,
+            swarm_state.key().as_ref(),
+            &(swarm_state.total_proposals / PROPOSAL_PAGE_SIZE).to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal_page: Account<'info, ProposalPage>,
     
     #[account(mut)]
     pub proposer: Signer<'info>,
     
     pub system_program: Program<'info, System>,
+    
+    /// Required for proposal types a committee handles; only its members
+    /// may propose them
+    #[account(
+        seeds = [COMMITTEE_SEED, swarm_state.key().as_ref(), &[committee.committee_id]],
+        bump = committee.bump
+    )]
+    pub committee: Option<Account<'info, Committee>>,
+    
+    /// Required while the swarm charges a proposal deposit
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, swarm_state.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Option<Account<'info, Treasury>>,
 }
 
 pub fn create_proposal(
@@ -37,31 +80,124 @@ pub fn create_proposal(
     proposal_type: ProposalType,
     data: Vec<u8>,
     description: String,
+    objective: Option<[u8; 32]>,
+    total_data_len: u16,
+    data_hash: Option<[u8; 32]>,
+    condition: Option<PriceCondition>,
+    tags: Vec<String>,
 ) -> Result<()> {
-    require!(
-        data.len() <= MAX_PROPOSAL_DATA_LENGTH,
-        SwarmError::ProposalDataTooLong
+    require_ctx!(
+        total_data_len as usize <= MAX_PROPOSAL_DATA_LENGTH,
+        SwarmError::ProposalDataTooLong,
+        FailedCheck::ProposalDataLength,
+        MAX_PROPOSAL_DATA_LENGTH,
+        total_data_len
     );
+    require!(data.len() <= total_data_len as usize, SwarmError::ProposalDataTooLong);
+
+    // A committed payload is checked against its hash when it executes
+    if data_hash.is_some() {
+        require!(payloads::allows_commitment(&proposal_type), SwarmError::InvalidPayload);
+        require!(total_data_len == 0, SwarmError::InvalidPayload);
+    }
 
-    require!(
+    require_ctx!(
         description.len() <= MAX_DESCRIPTION_LENGTH,
-        SwarmError::DescriptionTooLong
+        SwarmError::DescriptionTooLong,
+        FailedCheck::DescriptionLength,
+        MAX_DESCRIPTION_LENGTH,
+        description.len()
     );
 
+    Proposal::validate_tags(&tags)?;
+
+    let swarm_key = ctx.accounts.swarm_state.key();
     let swarm_state = &mut ctx.accounts.swarm_state;
-    let agent = &mut ctx.accounts.agent;
-    let proposal = &mut ctx.accounts.proposal;
+
+    // Voters should never face empty or low-effort proposals
+    let min_description_length = (swarm_state.min_description_length as usize).max(1);
+    require_ctx!(
+        description.trim().len() >= min_description_length,
+        SwarmError::DescriptionTooShort,
+        FailedCheck::DescriptionQuality,
+        min_description_length,
+        description.trim().len()
+    );
+
+    if let Some(condition) = &condition {
+        condition.validate(swarm_state)?;
+    }
+
+    // A partial payload is validated once `extend_proposal_data` completes it
+    if data.len() == total_data_len as usize && data_hash.is_none() {
+        payloads::validate(&proposal_type, &data, swarm_state.require_typed_payloads)?;
+    }
+    Proposal::write_data(&ctx.accounts.proposal.to_account_info(), 0, &data)?;
+
     let clock = Clock::get()?;
 
-    proposal.proposer = ctx.accounts.proposer.key();
-    proposal.proposal_type = proposal_type;
-    proposal.data = data;
-    proposal.description = description.clone();
+    // Flooding the swarm compounds the cost of every further proposal
+    let deposit = swarm_state.congestion.record(clock.unix_timestamp);
+    if deposit > 0 {
+        let treasury = ctx.accounts
+            .treasury
+            .as_mut()
+            .ok_or(SwarmError::MissingExecutionAccount)?;
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.proposer.to_account_info(),
+                    to: treasury.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+        treasury.total_deposited = treasury
+            .total_deposited
+            .checked_add(deposit)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+
+        emit!(TreasuryTransferIn {
+            schema_version: ACCOUNTING_SCHEMA_VERSION,
+            asset: NATIVE_SOL_ASSET,
+            amount: deposit,
+            from: ctx.accounts.proposer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    let mut agent = ctx.accounts.agent.load_mut()?;
+    let mut proposal = ctx.accounts.proposal.load_init()?;
+
+    if swarm_state.committee_proposal_types & (1 << proposal_type.index()) != 0 {
+        let committee = ctx.accounts
+            .committee
+            .as_ref()
+            .filter(|committee| committee.covers(&proposal_type))
+            .ok_or(SwarmError::MissingCommittee)?;
+        require!(committee.admits(agent.agent_type()), SwarmError::InvalidAgentType);
+        proposal.committee = committee.committee_id + 1;
+    }
+
+    proposal.swarm = swarm_key;
+    proposal.id = swarm_state.total_proposals;
+    proposal.proposer = agent.owner;
+    proposal.proposal_type_tag = proposal_type.index() as u8;
+    proposal.data_len = total_data_len;
+    proposal.data_written = data.len() as u16;
+    proposal.set_description(&description);
+    proposal.set_tags(&tags);
+    proposal.objective = objective.unwrap_or_default();
+    proposal.has_objective = objective.is_some() as u8;
+    proposal.data_hash = data_hash.unwrap_or_default();
+    proposal.data_committed = data_hash.is_some() as u8;
+    proposal.set_condition(condition);
     proposal.created_at = clock.unix_timestamp;
     proposal.expires_at = clock.unix_timestamp
         .checked_add(swarm_state.proposal_timeout)
         .ok_or(SwarmError::ArithmeticOverflow)?;
-    proposal.executed = false;
+    proposal.executed = 0;
     proposal.executed_at = 0;
     proposal.votes_for = 0;
     proposal.votes_against = 0;
@@ -69,14 +205,107 @@ pub fn create_proposal(
     proposal.weighted_votes_for = 0;
     proposal.weighted_votes_against = 0;
     proposal.total_voters = 0;
-    proposal.voters = Vec::new();
+    proposal.assigned_executor = Pubkey::default();
+    proposal.assigned_at = 0;
+    proposal.execution_attempts = 0;
+    proposal.last_failure_code = 0;
+    proposal.failed = 0;
+    proposal.outcome_archived = 0;
+    proposal.version = Proposal::VERSION;
     proposal.bump = ctx.bumps.proposal;
 
-    swarm_state.total_proposals += 1;
-    agent.proposals_created += 1;
+    let proposal_page = &mut ctx.accounts.proposal_page;
+    if proposal_page.proposals.is_empty() {
+        proposal_page.swarm = swarm_key;
+        proposal_page.page = proposal.id / PROPOSAL_PAGE_SIZE;
+        proposal_page.version = ProposalPage::VERSION;
+        proposal_page.bump = ctx.bumps.proposal_page;
+    }
+    proposal_page.push(ctx.accounts.proposal.key(), proposal.id);
+
+    swarm_state.total_proposals = swarm_state
+        .total_proposals
+        .checked_add(1)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    swarm_state.rewards.proposals = swarm_state.rewards.proposals.saturating_add(1);
+    agent.proposals_created = agent.proposals_created.saturating_add(1);
     agent.last_active = clock.unix_timestamp;
 
+    emit!(ProposalCreated {
+        proposal: ctx.accounts.proposal.key(),
+        proposer: proposal.proposer,
+        proposal_type,
+        data_len: total_data_len,
+        data_hash,
+        objective,
+        condition,
+        expires_at: proposal.expires_at,
+        deposit,
+        tags,
+        timestamp: clock.unix_timestamp,
+    });
     msg!("Proposal created: {}", description);
 
     Ok(())
 }
+
+/// Payloads too large for one transaction are uploaded in chunks after
+/// `create_proposal`, each growing the account by the chunk's size
+#[derive(Accounts)]
+#[instruction(chunk: Vec<u8>)]
+pub struct ExtendProposalData<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        constraint = proposal.load()?.swarm == swarm_state.key() @ SwarmError::SwarmMismatch,
+        constraint = proposal.load()?.data_written as usize + chunk.len()
+            <= proposal.load()?.data_len as usize @ SwarmError::ProposalDataTooLong,
+        realloc = Proposal::space(proposal.load()?.data_written as usize + chunk.len()),
+        realloc::payer = proposer,
+        realloc::zero = false
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    /// The proposer's agent, whose signing key must sign
+    #[account(
+        seeds = [AGENT_SEED, proposal.load()?.swarm.as_ref(), proposal.load()?.proposer.as_ref()],
+        bump = proposer_agent.load()?.bump,
+        constraint = proposer_agent.load()?.signing_key == proposer.key() @ SwarmError::Unauthorized
+    )]
+    pub proposer_agent: AccountLoader<'info, Agent>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn extend_proposal_data(ctx: Context<ExtendProposalData>, chunk: Vec<u8>) -> Result<()> {
+    let data_written = ctx.accounts.proposal.load()?.data_written;
+    Proposal::write_data(&ctx.accounts.proposal.to_account_info(), data_written as usize, &chunk)?;
+
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    // Bounded by `data_len` in the account constraints
+    proposal.data_written += chunk.len() as u16;
+    let (complete, proposal_type) = (proposal.is_data_complete(), proposal.proposal_type());
+    emit!(ProposalDataExtended {
+        proposal: ctx.accounts.proposal.key(),
+        data_written: proposal.data_written,
+        data_len: proposal.data_len,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    drop(proposal);
+
+    if complete {
+        let data = Proposal::load_data(&ctx.accounts.proposal)?;
+        payloads::validate(&proposal_type, &data, ctx.accounts.swarm_state.require_typed_payloads)?;
+    }
+
+    msg!("Proposal data extended to {} bytes", data_written as usize + chunk.len());
+    Ok(())
+}
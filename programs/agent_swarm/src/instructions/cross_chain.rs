@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use crate::cpi::{wormhole, CPIHelper};
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::CrossChainMessagePosted;
+use crate::payloads::{self, CrossChainPayload};
+use crate::ProposalType;
+
+/// Permissionless crank posting the Wormhole message carried by an executed
+/// `CrossChain` proposal, emitted by the swarm PDA. The message account is
+/// derived from the proposal, so each proposal posts once.
+#[derive(Accounts)]
+pub struct PostCrossChainMessage<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        constraint = proposal.load()?.swarm == swarm_state.key() @ SwarmError::SwarmMismatch,
+        constraint = proposal.load()?.is_executed() @ SwarmError::VotingInProgress,
+        constraint = proposal.load()?.proposal_type() == ProposalType::CrossChain @ SwarmError::InvalidPayload
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = CrossChainEmitter::LEN,
+        seeds = [CROSS_CHAIN_EMITTER_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub emitter: Account<'info, CrossChainEmitter>,
+    
+    /// CHECK: Created by the core bridge; our PDA so it can sign as the message
+    #[account(
+        mut,
+        seeds = [CROSS_CHAIN_MESSAGE_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub wormhole_message: UncheckedAccount<'info>,
+    
+    /// CHECK: Bridge config, checked in `wormhole::validate_accounts`
+    #[account(mut)]
+    pub wormhole_config: UncheckedAccount<'info>,
+    
+    /// CHECK: The bridge's sequence tracker for the swarm PDA, checked in `wormhole::validate_accounts`
+    #[account(mut)]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+    
+    /// CHECK: Bridge fee collector, checked in `wormhole::validate_accounts`
+    #[account(mut)]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+    
+    /// CHECK: The Wormhole core bridge
+    #[account(address = wormhole::PROGRAM_ID @ SwarmError::UntrustedProgram)]
+    pub wormhole_program: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn post_cross_chain_message(ctx: Context<PostCrossChainMessage>) -> Result<()> {
+    let payload: CrossChainPayload = payloads::decode(&Proposal::load_data(&ctx.accounts.proposal)?)?;
+    let proposal_key = ctx.accounts.proposal.key();
+    let swarm_key = ctx.accounts.swarm_state.key();
+    let sequence = wormhole::next_sequence(&ctx.accounts.wormhole_sequence)?;
+
+    let swarm_id = ctx.accounts.swarm_state.swarm_id.to_le_bytes();
+    let swarm_bump = [ctx.accounts.swarm_state.bump];
+    let message_bump = [ctx.bumps.wormhole_message];
+    let signer_seeds: &[&[&[u8]]] = &[
+        &[SWARM_SEED, swarm_id.as_ref(), &swarm_bump],
+        &[CROSS_CHAIN_MESSAGE_SEED, proposal_key.as_ref(), &message_bump],
+    ];
+    CPIHelper::wormhole_post_message(
+        &ctx.accounts.wormhole_program,
+        &[
+            ctx.accounts.wormhole_config.to_account_info(),
+            ctx.accounts.wormhole_message.to_account_info(),
+            ctx.accounts.swarm_state.to_account_info(),
+            ctx.accounts.wormhole_sequence.to_account_info(),
+            ctx.accounts.cranker.to_account_info(),
+            ctx.accounts.wormhole_fee_collector.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        payload.nonce,
+        &payload.message(&proposal_key),
+        signer_seeds,
+    )?;
+
+    let clock = Clock::get()?;
+    let emitter = &mut ctx.accounts.emitter;
+    if emitter.swarm == Pubkey::default() {
+        emitter.swarm = swarm_key;
+        emitter.version = CrossChainEmitter::VERSION;
+        emitter.bump = ctx.bumps.emitter;
+    }
+    emitter.messages_posted = emitter.messages_posted.saturating_add(1);
+    emitter.last_sequence = sequence;
+    emitter.last_proposal = proposal_key;
+    emitter.last_posted_at = clock.unix_timestamp;
+
+    emit!(CrossChainMessagePosted {
+        swarm: swarm_key,
+        proposal: proposal_key,
+        message: ctx.accounts.wormhole_message.key(),
+        sequence,
+        target_chain: payload.target_chain,
+        target_address: payload.target_address,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!(
+        "Cross-chain message {} posted to chain {} for proposal {}",
+        sequence,
+        payload.target_chain,
+        proposal_key
+    );
+
+    Ok(())
+}
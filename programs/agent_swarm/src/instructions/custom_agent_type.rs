@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::CustomAgentTypeRegistered;
+
+/// Give an `AgentType::Custom` id its meaning in this swarm. The creator
+/// proposes and pays for it; the authority (usually a governance program or
+/// multisig) approves by co-signing. Ids cannot be redefined.
+#[derive(Accounts)]
+#[instruction(id: u8)]
+pub struct RegisterCustomAgentType<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = CustomAgentType::LEN,
+        seeds = [CUSTOM_AGENT_TYPE_SEED, swarm_state.key().as_ref(), &[id]],
+        bump
+    )]
+    pub custom_agent_type: Account<'info, CustomAgentType>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(constraint = authority.key() == swarm_state.authority @ SwarmError::Unauthorized)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_custom_agent_type(
+    ctx: Context<RegisterCustomAgentType>,
+    id: u8,
+    name: String,
+    schema_hash: [u8; 32],
+) -> Result<()> {
+    require!(
+        !name.is_empty() && name.len() <= MAX_AGENT_TYPE_NAME_LENGTH,
+        SwarmError::InvalidParameter
+    );
+
+    let clock = Clock::get()?;
+    let custom_agent_type = &mut ctx.accounts.custom_agent_type;
+    custom_agent_type.swarm = ctx.accounts.swarm_state.key();
+    custom_agent_type.id = id;
+    custom_agent_type.name = name.clone();
+    custom_agent_type.schema_hash = schema_hash;
+    custom_agent_type.creator = ctx.accounts.creator.key();
+    custom_agent_type.created_at = clock.unix_timestamp;
+    custom_agent_type.version = CustomAgentType::VERSION;
+    custom_agent_type.bump = ctx.bumps.custom_agent_type;
+
+    emit!(CustomAgentTypeRegistered {
+        swarm: custom_agent_type.swarm,
+        id,
+        name: name.clone(),
+        schema_hash,
+        creator: custom_agent_type.creator,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Custom agent type {} registered: {}", id, name);
+    Ok(())
+}
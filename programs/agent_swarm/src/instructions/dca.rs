@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::*;
+use crate::constants::*;
+use crate::cpi::SwapAccounts;
+use crate::errors::SwarmError;
+use crate::events::DcaLegExecuted;
+use crate::instructions::treasury::execute_trade;
+use crate::oracle::{fair_amount_out, OraclePrice};
+use crate::payloads::{SwapRoute, TradePayload};
+use crate::ProposalType;
+
+/// Execute the next due leg of a DCA schedule (permissionless). The leg
+/// swaps through Raydium with its minimum output set from the oracle
+/// prices, runs the risk checks of a structured `Trade` and counts against
+/// the `DcaSchedule` spend cap. The leg that spends the rest of the budget
+/// closes the schedule, returning its rent to whoever created it.
+#[derive(Accounts)]
+pub struct ExecuteDcaLeg<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+
+    #[account(
+        mut,
+        seeds = [DCA_SCHEDULE_SEED, dca_schedule.proposal.as_ref()],
+        bump = dca_schedule.bump,
+        constraint = dca_schedule.swarm == swarm_state.key() @ SwarmError::SwarmMismatch
+    )]
+    pub dca_schedule: Account<'info, DcaSchedule>,
+
+    /// CHECK: Receives the schedule's rent once it completes; must be the
+    /// account that paid it
+    #[account(mut, address = dca_schedule.payer @ SwarmError::Unauthorized)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// CHECK: Data-less PDA owning the vault token accounts; signs the swap
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, swarm_state.key().as_ref()],
+        bump = swarm_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Vault token account of the schedule's input mint
+    #[account(mut)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault token account receiving the schedule's output mint
+    #[account(mut)]
+    pub output_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Raydium AMM V4, checked against its program id. The pool's
+    /// accounts follow as remaining accounts.
+    pub swap_program: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [RISK_CONFIG_SEED, swarm_state.key().as_ref()],
+        bump = risk_config.bump
+    )]
+    pub risk_config: Account<'info, RiskConfig>,
+
+    #[account(
+        mut,
+        seeds = [SPEND_TRACKER_SEED, swarm_state.key().as_ref()],
+        bump = spend_tracker.bump
+    )]
+    pub spend_tracker: Account<'info, SpendTracker>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = Portfolio::LEN,
+        seeds = [PORTFOLIO_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// CHECK: Pyth or Switchboard price account for the input mint; parsed
+    /// in `OraclePrice::load`
+    pub price_update_in: UncheckedAccount<'info>,
+
+    /// CHECK: Price account for the output mint
+    pub price_update_out: UncheckedAccount<'info>,
+
+    /// CHECK: `TwapTracker` of the input mint, required when the risk
+    /// config sets a `twap_guard`
+    pub twap_tracker_in: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: `TwapTracker` of the output mint
+    pub twap_tracker_out: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn execute_dca_leg<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteDcaLeg<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let swarm_state = &ctx.accounts.swarm_state;
+    let dca_schedule = &ctx.accounts.dca_schedule;
+    require!(clock.unix_timestamp >= dca_schedule.next_leg_at, SwarmError::DcaLegNotDue);
+
+    let amount = dca_schedule.next_leg_amount();
+    let feed_in = swarm_state
+        .price_feed(&dca_schedule.mint_in)
+        .ok_or(SwarmError::MissingPriceFeed)?;
+    let feed_out = swarm_state
+        .price_feed(&dca_schedule.mint_out)
+        .ok_or(SwarmError::MissingPriceFeed)?;
+    let fair_out = fair_amount_out(
+        amount,
+        &OraclePrice::load(&ctx.accounts.price_update_in, feed_in, clock.unix_timestamp)?,
+        feed_in.decimals,
+        &OraclePrice::load(&ctx.accounts.price_update_out, feed_out, clock.unix_timestamp)?,
+        feed_out.decimals,
+    )?;
+    let min_out = fair_out as u128
+        * (BPS_DENOMINATOR - dca_schedule.max_slippage_bps) as u128
+        / BPS_DENOMINATOR as u128;
+    let trade = TradePayload {
+        mint_in: dca_schedule.mint_in,
+        mint_out: dca_schedule.mint_out,
+        amount,
+        min_out: (min_out as u64).max(1),
+        route: SwapRoute::Raydium,
+        tip_lamports: 0,
+    };
+
+    let prices = (
+        Some(&*ctx.accounts.price_update_in),
+        Some(&*ctx.accounts.price_update_out),
+    );
+    let risk_config = &mut ctx.accounts.risk_config;
+    risk_config.check_trade(
+        swarm_state,
+        &trade,
+        ctx.accounts.swap_program.key,
+        prices,
+        (
+            ctx.accounts.twap_tracker_in.as_deref(),
+            ctx.accounts.twap_tracker_out.as_deref(),
+        ),
+        clock.unix_timestamp,
+    )?;
+    ctx.accounts
+        .spend_tracker
+        .record_spend(&ProposalType::DcaSchedule, amount, clock.unix_timestamp)?;
+
+    let (spent, received) = execute_trade(
+        swarm_state,
+        &mut ctx.accounts.treasury_token_account,
+        &mut ctx.accounts.output_token_account,
+        &SwapAccounts {
+            authority: &ctx.accounts.vault_authority,
+            swap_program: &ctx.accounts.swap_program,
+            route_accounts: ctx.remaining_accounts,
+        },
+        dca_schedule.proposal,
+        &trade,
+    )?;
+    risk_config.record_trade_result(
+        swarm_state,
+        &ctx.accounts.price_update_in,
+        &ctx.accounts.price_update_out,
+        &trade,
+        (spent, received),
+        clock.unix_timestamp,
+    )?;
+
+    let portfolio = &mut ctx.accounts.portfolio;
+    if portfolio.swarm == Pubkey::default() {
+        portfolio.swarm = swarm_state.key();
+        portfolio.version = Portfolio::VERSION;
+        portfolio.bump = ctx.bumps.portfolio;
+    }
+    for (mint, token_account) in [
+        (trade.mint_in, &ctx.accounts.treasury_token_account),
+        (trade.mint_out, &ctx.accounts.output_token_account),
+    ] {
+        portfolio.record_balance(mint, token_account.amount, dca_schedule.proposal, clock.unix_timestamp)?;
+    }
+
+    let dca_schedule = &mut ctx.accounts.dca_schedule;
+    let completed = dca_schedule.record_leg(spent, received, clock.unix_timestamp)?;
+    emit!(DcaLegExecuted {
+        schedule: dca_schedule.key(),
+        keeper: ctx.accounts.keeper.key(),
+        leg: dca_schedule.legs_executed,
+        spent,
+        received,
+        completed,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!(
+        "DCA leg {} executed: {} in, {} out",
+        dca_schedule.legs_executed,
+        spent,
+        received
+    );
+
+    if completed {
+        dca_schedule.close(ctx.accounts.payer.to_account_info())?;
+        msg!("DCA schedule completed after spending {}", dca_schedule.spent);
+    }
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::{EndorsementSettled, ProposalEndorsed};
+
+#[derive(Accounts)]
+pub struct EndorseProposal<'info> {
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, proposal.load()?.swarm.as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == endorser.key() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.is_active() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        mut,
+        constraint = proposal.load()?.proposer != agent.load()?.owner @ SwarmError::SelfEndorsement
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        init,
+        payer = endorser,
+        space = Endorsement::LEN,
+        seeds = [ENDORSEMENT_SEED, proposal.key().as_ref(), agent.load()?.owner.as_ref()],
+        bump
+    )]
+    pub endorsement: Account<'info, Endorsement>,
+    
+    #[account(mut)]
+    pub endorser: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless crank returning an endorsement stake once the outcome is
+/// known, or in full once the proposal can no longer execute
+#[derive(Accounts)]
+pub struct SettleEndorsement<'info> {
+    #[account(mut)]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        seeds = [OUTCOME_SEED, proposal.key().as_ref()],
+        bump = outcome.bump
+    )]
+    pub outcome: Option<Account<'info, Outcome>>,
+    
+    #[account(
+        mut,
+        seeds = [ENDORSEMENT_SEED, proposal.key().as_ref(), endorsement.endorser.as_ref()],
+        bump = endorsement.bump,
+        constraint = !endorsement.settled @ SwarmError::EndorsementAlreadySettled
+    )]
+    pub endorsement: Account<'info, Endorsement>,
+    
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, proposal.load()?.swarm.as_ref(), endorsement.endorser.as_ref()],
+        bump = agent.load()?.bump
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    pub cranker: Signer<'info>,
+}
+
+pub fn endorse_proposal(ctx: Context<EndorseProposal>, stake: u16) -> Result<()> {
+    let mut agent = ctx.accounts.agent.load_mut()?;
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    let clock = Clock::get()?;
+
+    require!(
+        stake > 0 && stake <= agent.reputation,
+        SwarmError::InvalidStake
+    );
+    require!(!proposal.is_executed(), SwarmError::ProposalAlreadyExecuted);
+    require!(!proposal.is_failed(), SwarmError::ProposalFailed);
+    require!(
+        !proposal.is_expired(clock.unix_timestamp),
+        SwarmError::ProposalExpired
+    );
+    proposal.open_endorsements = proposal
+        .open_endorsements
+        .checked_add(1)
+        .ok_or(SwarmError::TooManyOpenClaims)?;
+
+    // Stake is locked by removing it from reputation until settlement
+    agent.reputation = agent.reputation.saturating_sub(stake);
+    agent.last_active = clock.unix_timestamp;
+
+    let endorsement = &mut ctx.accounts.endorsement;
+    endorsement.proposal = ctx.accounts.proposal.key();
+    endorsement.endorser = agent.owner;
+    endorsement.stake = stake;
+    endorsement.endorsed_at = clock.unix_timestamp;
+    endorsement.settled = false;
+    endorsement.version = Endorsement::VERSION;
+    endorsement.bump = ctx.bumps.endorsement;
+
+    emit!(ProposalEndorsed {
+        proposal: endorsement.proposal,
+        endorser: endorsement.endorser,
+        stake,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Proposal endorsed with {} reputation", stake);
+
+    Ok(())
+}
+
+pub fn settle_endorsement(ctx: Context<SettleEndorsement>) -> Result<()> {
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    let clock = Clock::get()?;
+
+    let success = match &ctx.accounts.outcome {
+        Some(outcome) => Some(outcome.success),
+        None => {
+            require!(
+                proposal.is_failed()
+                    || (!proposal.is_executed() && proposal.is_expired(clock.unix_timestamp)),
+                SwarmError::OutcomePending
+            );
+            None
+        }
+    };
+    proposal.open_endorsements = proposal.open_endorsements.saturating_sub(1);
+
+    let endorsement = &mut ctx.accounts.endorsement;
+    let mut agent = ctx.accounts.agent.load_mut()?;
+    let payout = endorsement.payout(success);
+
+    agent.reputation = agent.reputation.saturating_add(payout).min(MAX_REPUTATION);
+    endorsement.settled = true;
+
+    emit!(EndorsementSettled {
+        proposal: endorsement.proposal,
+        endorser: endorsement.endorser,
+        stake: endorsement.stake,
+        payout,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!(
+        "Endorsement settled: staked {}, returned {} (reputation now {})",
+        endorsement.stake,
+        payout,
+        agent.reputation
+    );
+
+    Ok(())
+}
@@ -1,59 +1,1289 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::constants::*;
-use crate::errors::SwarmError;
+use crate::cpi::SwapAccounts;
+use crate::errors::{FailedCheck, SwarmError};
+use crate::require_ctx;
+use crate::introspection::{assert_no_reentry, assert_no_untrusted_instructions};
+use crate::events::{
+    ArbReportCredited, DcaScheduleCreated, HarvestConfigured, HarvestExecuted, InsuranceTermsSet,
+    OutcomeRecorded, PnlAttributed, ProposalExecuted, RebalanceApproved, ReputationChanged,
+    StandingOrderPlaced, StrategyParametersSet, TradingResumed,
+};
+use crate::payloads::{
+    self, EmergencyPayload, InsurancePayload, ProposalPayload, RiskLimitPayload, StrategyAction,
+    StrategyPayload,
+};
+use crate::risk::trade_pnl;
+use crate::instructions::committee::require_quorum;
+use crate::instructions::insurance::pay_insurance_claim;
+use crate::instructions::link_proposal::require_linked_approval;
+use crate::instructions::treasury::{
+    collect_harvest, execute_lending, execute_liquidity, execute_perp_order, pay_execution_tip, execute_staking, execute_strategy, execute_trade,
+    execute_whirlpool, measure_balance_deltas, pay_lamports_from_treasury, snapshot_balances, transfer_from_treasury,
+    transfer_tokens_from_treasury, TreasuryTokenSource,
+};
+use crate::ProposalType;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
 
 #[derive(Accounts)]
 pub struct ExecuteProposal<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
     pub swarm_state: Account<'info, SwarmState>,
     
     #[account(
         mut,
-        seeds = [AGENT_SEED, executor.key().as_ref()],
-        bump = agent.bump,
-        constraint = agent.is_active @ SwarmError::Unauthorized
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == executor.key() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.is_active() @ SwarmError::Unauthorized
     )]
-    pub agent: Account<'info, Agent>,
+    pub agent: AccountLoader<'info, Agent>,
     
-    #[account(mut)]
-    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        constraint = proposal.load()?.swarm == swarm_state.key() @ SwarmError::SwarmMismatch
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
     
+    #[account(
+        mut,
+        seeds = [
+            PROPOSAL_PAGE_SEED,
+            swarm_state.key().as_ref(),
+            &(proposal.load()?.id / PROPOSAL_PAGE_SIZE).to_le_bytes()
+        ],
+        bump = proposal_page.bump
+    )]
+    pub proposal_page: Account<'info, ProposalPage>,
+    
+    #[account(mut)]
     pub executor: Signer<'info>,
+    
+    /// CHECK: Address-constrained to the instructions sysvar
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    /// Required for any proposal that spends from the treasury
+    #[account(
+        mut,
+        seeds = [SPEND_TRACKER_SEED, swarm_state.key().as_ref()],
+        bump = spend_tracker.bump
+    )]
+    pub spend_tracker: Option<Account<'info, SpendTracker>>,
+    
+    /// Required for `TreasuryTransfer` proposals, staking, liquidity and
+    /// Whirlpool `Strategy` proposals, and execution tips
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, swarm_state.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Option<Account<'info, Treasury>>,
+    
+    /// CHECK: Must match the recipient in the `TreasuryTransfer` payload, or
+    /// the signing key of the agent whose `arb_report` a `Trade` pays out
+    #[account(mut)]
+    pub recipient: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Data-less PDA owning the vault token accounts; signs token
+    /// transfers, swaps and `Strategy` calls, and relays staked SOL and
+    /// position rent
+    #[account(
+        mut,
+        seeds = [VAULT_AUTHORITY_SEED, swarm_state.key().as_ref()],
+        bump = swarm_state.vault_authority_bump
+    )]
+    pub vault_authority: Option<UncheckedAccount<'info>>,
+    
+    /// Required for `TreasuryTokenTransfer` proposals and, as the input
+    /// side, for structured `Trade` proposals
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub recipient_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Required for `TreasuryTokenTransfer` proposals; a transfer hook's
+    /// accounts follow as remaining accounts
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// Required for staking `Strategy` proposals
+    pub system_program: Option<Program<'info, System>>,
+    
+    /// Required for `Trade` proposals when the swarm enforces exposure
+    /// limits, for structured `RiskLimit` proposals and for perp orders
+    #[account(
+        mut,
+        seeds = [PENDING_EXPOSURE_SEED, swarm_state.key().as_ref()],
+        bump = pending_exposure.bump
+    )]
+    pub pending_exposure: Option<Account<'info, PendingExposure>>,
+    
+    /// Required for call-list `Strategy` proposals and for `RiskLimit`
+    /// proposals changing the whitelist
+    #[account(
+        mut,
+        seeds = [PROGRAM_WHITELIST_SEED, swarm_state.key().as_ref()],
+        bump = program_whitelist.bump
+    )]
+    pub program_whitelist: Option<Account<'info, ProgramWhitelist>>,
+    
+    /// Required for `RiskLimit` and `AuthorityHandover` proposals
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, swarm_state.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+    
+    /// Treasury token account receiving a structured `Trade`'s output
+    #[account(mut)]
+    pub output_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Venue program for a structured `Trade`, checked against the
+    /// route's expected program id. The route's accounts follow as
+    /// remaining accounts.
+    pub swap_program: Option<UncheckedAccount<'info>>,
+    
+    /// Required for structured `Trade` and `Rebalance` proposals and for
+    /// `RiskLimit` proposals changing it
+    #[account(
+        mut,
+        seeds = [RISK_CONFIG_SEED, swarm_state.key().as_ref()],
+        bump = risk_config.bump
+    )]
+    pub risk_config: Option<Account<'info, RiskConfig>>,
+    
+    /// CHECK: Pyth price update for a structured `Trade`'s input mint,
+    /// required when the risk config sets `max_slippage_bps`; parsed and
+    /// checked in `oracle::check_trade_slippage`
+    pub price_update_in: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pyth price update for the trade's output mint
+    pub price_update_out: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: `TwapTracker` of a structured `Trade`'s input mint, required
+    /// when the risk config sets a `twap_guard`; parsed and checked in
+    /// `oracle::check_twap_deviation`
+    pub twap_tracker_in: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: `TwapTracker` of the trade's output mint
+    pub twap_tracker_out: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pyth or Switchboard price account for a conditional
+    /// proposal's base mint, or its `TwapTracker` when the condition has a
+    /// TWAP window; parsed in `PriceCondition::price`
+    pub condition_price_base: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Price account for the condition's quote mint
+    pub condition_price_quote: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Jito tip account paid a `Trade` or `Emergency` proposal's tip;
+    /// checked against the known tip accounts
+    #[account(mut)]
+    pub tip_account: Option<UncheckedAccount<'info>>,
+    
+    /// Required for `Strategy` proposals with balance checks; written with
+    /// the measured deltas in place of a later `record_outcome`, and for a
+    /// `Trade` when the risk config requires oracle prices; it records and
+    /// attributes the trade's oracle-valued PnL. Other `Trade`s may pass it.
+    #[account(
+        init,
+        payer = executor,
+        space = Outcome::LEN,
+        seeds = [OUTCOME_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub outcome: Option<Account<'info, Outcome>>,
+    
+    /// Credited when a verified `Strategy` executes as expected, or with a
+    /// `Trade`'s realized PnL; omitted when the proposer executes it
+    /// themselves
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), proposal.load()?.proposer.as_ref()],
+        bump = proposer_agent.load()?.bump
+    )]
+    pub proposer_agent: Option<AccountLoader<'info, Agent>>,
+    
+    /// Required for hash-committed proposals: the executor's staged
+    /// payload, closed back to them once executed
+    #[account(
+        mut,
+        close = executor,
+        seeds = [PAYLOAD_BUFFER_SEED, proposal.key().as_ref(), executor.key().as_ref()],
+        bump = payload_buffer.bump
+    )]
+    pub payload_buffer: Option<Account<'info, PayloadBuffer>>,
+    
+    /// Required for scheduled proposals, which leave the queue as they execute
+    #[account(
+        mut,
+        seeds = [EXECUTION_QUEUE_SEED, swarm_state.key().as_ref()],
+        bump = execution_queue.bump
+    )]
+    pub execution_queue: Option<Account<'info, ExecutionQueue>>,
+    
+    /// Required for `Rebalance` proposals that set a schedule
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = RebalanceSchedule::LEN,
+        seeds = [REBALANCE_SCHEDULE_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub rebalance_schedule: Option<Account<'info, RebalanceSchedule>>,
+    
+    /// Required for `Strategy` proposals setting parameters
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = StrategyConfig::LEN,
+        seeds = [STRATEGY_CONFIG_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub strategy_config: Option<Account<'info, StrategyConfig>>,
+    
+    /// Required for `Strategy` proposals placing a standing order
+    #[account(
+        init,
+        payer = executor,
+        space = StandingOrder::LEN,
+        seeds = [STANDING_ORDER_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub standing_order: Option<Account<'info, StandingOrder>>,
+    
+    /// Required for `DcaSchedule` proposals
+    #[account(
+        init,
+        payer = executor,
+        space = DcaSchedule::LEN,
+        seeds = [DCA_SCHEDULE_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub dca_schedule: Option<Account<'info, DcaSchedule>>,
+    
+    /// Required for `Harvest` proposals. The Whirlpool program and each
+    /// registered position's fee accounts come first in the remaining
+    /// accounts, followed by the swap's route accounts.
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = HarvestConfig::LEN,
+        seeds = [HARVEST_CONFIG_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub harvest_config: Option<Account<'info, HarvestConfig>>,
+    
+    /// Required for `Insurance` proposals
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = InsuranceFund::LEN,
+        seeds = [INSURANCE_FUND_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+    
+    /// Required for `Insurance` claims: the executed `Strategy` whose loss
+    /// is claimed
+    pub claimed_proposal: Option<AccountLoader<'info, Proposal>>,
+    
+    /// Required for `Insurance` claims: the claimed proposal's outcome
+    #[account(
+        seeds = [OUTCOME_SEED, claimed_outcome.proposal.as_ref()],
+        bump = claimed_outcome.bump
+    )]
+    pub claimed_outcome: Option<Account<'info, Outcome>>,
+    
+    /// Required for `Insurance` claims: the attestation the claim cites
+    pub cited_attestation: Option<Account<'info, Attestation>>,
+    
+    /// Required for structured `Trade`, `Rebalance` and
+    /// `TreasuryTokenTransfer` proposals, which update the holdings or
+    /// target weights it tracks. `Rebalance` proposals listing trades pass
+    /// a price update per holding, in `holdings` order, as remaining accounts.
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = Portfolio::LEN,
+        seeds = [PORTFOLIO_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub portfolio: Option<Account<'info, Portfolio>>,
+    
+    /// Arbitrage report credited by a structured `Trade` along its route
+    #[account(
+        mut,
+        seeds = [ARB_REPORT_SEED, swarm_state.key().as_ref(), arb_report.route_hash.as_ref()],
+        bump = arb_report.bump
+    )]
+    pub arb_report: Option<Account<'info, ArbReport>>,
+    
+    /// Required with `arb_report`: the agent that filed it, optional when it
+    /// executes the trade itself. Its bounty, if the swarm pays one, goes to
+    /// `recipient`, which must be its signing key.
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), arb_reporter.load()?.owner.as_ref()],
+        bump = arb_reporter.load()?.bump
+    )]
+    pub arb_reporter: Option<AccountLoader<'info, Agent>>,
+    
+    /// Required for proposals scoped to a committee
+    #[account(
+        seeds = [COMMITTEE_SEED, swarm_state.key().as_ref(), &[committee.committee_id]],
+        bump = committee.bump
+    )]
+    pub committee: Option<Account<'info, Committee>>,
+    
+    /// Required for proposals linked to a proposal in another swarm
+    #[account(
+        constraint = proposal.load()?.linked_proposal() == Some(linked_proposal.key())
+            @ SwarmError::InvalidLinkedProposal
+    )]
+    pub linked_proposal: Option<AccountLoader<'info, Proposal>>,
+    
+    /// The linked proposal's swarm, whose quorum it must meet
+    #[account(
+        seeds = [SWARM_SEED, linked_swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = linked_swarm_state.bump
+    )]
+    pub linked_swarm_state: Option<Account<'info, SwarmState>>,
 }
 
-pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
-    let proposal = &mut ctx.accounts.proposal;
+pub fn execute_proposal<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+) -> Result<()> {
+    let data = Proposal::load_data(&ctx.accounts.proposal)?;
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+
+    // Committed payloads were never validated, so check them like a fresh one
+    let data = if proposal.is_data_committed() {
+        let payload_buffer = ctx.accounts
+            .payload_buffer
+            .as_ref()
+            .ok_or(SwarmError::MissingExecutionAccount)?;
+        require!(payload_buffer.matches(&proposal.data_hash), SwarmError::PayloadHashMismatch);
+        payloads::validate(
+            &proposal.proposal_type(),
+            &payload_buffer.data,
+            ctx.accounts.swarm_state.require_typed_payloads,
+        )?;
+        payload_buffer.data.clone()
+    } else {
+        data
+    };
     let swarm_state = &mut ctx.accounts.swarm_state;
-    let agent = &mut ctx.accounts.agent;
+    let mut agent = ctx.accounts.agent.load_mut()?;
     let clock = Clock::get()?;
 
     require!(
-        !proposal.executed,
+        !proposal.is_executed(),
         SwarmError::ProposalAlreadyExecuted
     );
+    require!(!proposal.is_failed(), SwarmError::ProposalFailed);
 
-    require!(
-        !proposal.is_expired(clock.unix_timestamp),
-        SwarmError::ProposalExpired
+    require_ctx!(
+        proposal.can_execute(
+            &agent.owner,
+            swarm_state.execution_mode,
+            swarm_state.executor_window,
+            clock.unix_timestamp,
+        ),
+        SwarmError::NotAssignedExecutor,
+        FailedCheck::ExecutorWindow,
+        proposal.assigned_at.saturating_add(swarm_state.executor_window),
+        clock.unix_timestamp
     );
 
-    require!(
-        proposal.has_quorum(swarm_state.min_votes_required, swarm_state.active_agents),
-        SwarmError::InsufficientVotes
+    assert_no_reentry(&ctx.accounts.instructions_sysvar)?;
+    if swarm_state.execution_guard {
+        assert_no_untrusted_instructions(&ctx.accounts.instructions_sysvar)?;
+    }
+
+    require_ctx!(
+        !proposal.is_expired(clock.unix_timestamp),
+        SwarmError::ProposalExpired,
+        FailedCheck::ProposalExpiry,
+        proposal.expires_at,
+        clock.unix_timestamp
     );
 
-    require!(
+    require_quorum(&proposal, swarm_state, ctx.accounts.committee.as_deref())?;
+
+    require_ctx!(
         proposal.is_approved(),
-        SwarmError::InsufficientVotes
+        SwarmError::InsufficientVotes,
+        FailedCheck::Approval,
+        proposal.weighted_votes_against.saturating_add(1),
+        proposal.weighted_votes_for
     );
 
-    proposal.executed = true;
+    if proposal.proposal_type() == ProposalType::AuthorityHandover {
+        require_ctx!(
+            swarm_state.is_authority_inactive(clock.unix_timestamp),
+            SwarmError::AuthorityStillActive,
+            FailedCheck::AuthorityInactivity,
+            swarm_state.authority_last_active.saturating_add(swarm_state.authority_inactivity_period),
+            clock.unix_timestamp
+        );
+        require_supermajority(&proposal)?;
+    }
+
+    if proposal.linked_proposal().is_some() {
+        let (Some(linked_proposal), Some(linked_swarm_state)) =
+            (&ctx.accounts.linked_proposal, &ctx.accounts.linked_swarm_state)
+        else {
+            return err!(SwarmError::MissingExecutionAccount);
+        };
+        require_linked_approval(&*linked_proposal.load()?, linked_swarm_state, clock.unix_timestamp)?;
+    }
+
+    if let Some(execute_not_before) = proposal.execute_not_before() {
+        require_ctx!(
+            clock.unix_timestamp >= execute_not_before,
+            SwarmError::ExecutionTooEarly,
+            FailedCheck::ExecutionSchedule,
+            execute_not_before,
+            clock.unix_timestamp
+        );
+        ctx.accounts
+            .execution_queue
+            .as_mut()
+            .ok_or(SwarmError::MissingExecutionAccount)?
+            .remove(&ctx.accounts.proposal.key());
+    }
+
+    // A conditional proposal waits, still executable, until the price is right
+    if let Some(condition) = proposal.condition() {
+        let price = condition.price(
+            &swarm_state.key(),
+            swarm_state,
+            ctx.accounts.condition_price_base.as_ref().ok_or(SwarmError::MissingExecutionAccount)?,
+            ctx.accounts.condition_price_quote.as_ref().ok_or(SwarmError::MissingExecutionAccount)?,
+            clock.unix_timestamp,
+        )?;
+        require_ctx!(
+            condition.is_met(price),
+            SwarmError::PriceConditionNotMet,
+            FailedCheck::PriceCondition,
+            condition.threshold,
+            price
+        );
+    }
+
+    proposal.executed = 1;
     proposal.executed_at = clock.unix_timestamp;
+    ctx.accounts.proposal_page.mark_executed(proposal.id);
+
+    // Types that allow it may carry free-form data, which only signals
+    let payload = payloads::decode_proposal(&proposal.proposal_type(), &data)?;
+
+    if proposal.proposal_type() == ProposalType::Trade && swarm_state.enforce_exposure_limits {
+        let trade = match &payload {
+            Some(ProposalPayload::Trade(trade)) => trade,
+            _ => return err!(SwarmError::InvalidPayload),
+        };
+        ctx.accounts
+            .pending_exposure
+            .as_mut()
+            .ok_or(SwarmError::MissingExecutionAccount)?
+            .release(&ctx.accounts.proposal.key(), trade)?;
+    }
+
+    let tip_lamports = match &payload {
+        Some(ProposalPayload::Trade(trade)) => trade.tip_lamports,
+        Some(ProposalPayload::Emergency(EmergencyPayload::Slash(slash))) => slash.tip_lamports,
+        _ => 0,
+    };
+
+    match payload {
+        Some(ProposalPayload::Trade(payload)) => {
+            let (
+                Some(vault_authority),
+                Some(treasury_token_account),
+                Some(output_token_account),
+                Some(swap_program),
+            ) = (
+                ctx.accounts.vault_authority.as_ref(),
+                ctx.accounts.treasury_token_account.as_mut(),
+                ctx.accounts.output_token_account.as_mut(),
+                ctx.accounts.swap_program.as_ref(),
+            ) else {
+                return err!(SwarmError::MissingExecutionAccount);
+            };
+            let risk_config = risk_config(&mut ctx.accounts.risk_config)?;
+            let prices = (
+                ctx.accounts.price_update_in.as_deref(),
+                ctx.accounts.price_update_out.as_deref(),
+            );
+            risk_config.check_trade(
+                swarm_state,
+                &payload,
+                swap_program.key,
+                prices,
+                (
+                    ctx.accounts.twap_tracker_in.as_deref(),
+                    ctx.accounts.twap_tracker_out.as_deref(),
+                ),
+                clock.unix_timestamp,
+            )?;
+            // Priced trades always record their PnL, so losses cannot skip
+            // attribution
+            require!(
+                ctx.accounts.outcome.is_some() || !risk_config.requires_trade_prices(),
+                SwarmError::MissingExecutionAccount
+            );
+            spend_tracker(&mut ctx.accounts.spend_tracker)?
+                .record_spend(&proposal.proposal_type(), payload.amount, clock.unix_timestamp)?;
+            let executed = execute_trade(
+                swarm_state,
+                treasury_token_account,
+                output_token_account,
+                &SwapAccounts {
+                    authority: vault_authority,
+                    swap_program,
+                    route_accounts: ctx.remaining_accounts,
+                },
+                ctx.accounts.proposal.key(),
+                &payload,
+            )?;
+            let portfolio =
+                portfolio(&mut ctx.accounts.portfolio, swarm_state.key(), ctx.bumps.portfolio)?;
+            for (mint, token_account) in [
+                (payload.mint_in, &*treasury_token_account),
+                (payload.mint_out, &*output_token_account),
+            ] {
+                portfolio.record_balance(
+                    mint,
+                    token_account.amount,
+                    ctx.accounts.proposal.key(),
+                    clock.unix_timestamp,
+                )?;
+            }
+            if let (Some(price_update_in), Some(price_update_out)) = prices {
+                risk_config.record_trade_result(
+                    swarm_state,
+                    price_update_in,
+                    price_update_out,
+                    &payload,
+                    executed,
+                    clock.unix_timestamp,
+                )?;
+            }
+            if let Some(outcome) = ctx.accounts.outcome.as_mut() {
+                let (Some(price_update_in), Some(price_update_out)) = prices else {
+                    return err!(SwarmError::MissingExecutionAccount);
+                };
+                let pnl = trade_pnl(
+                    swarm_state,
+                    price_update_in,
+                    price_update_out,
+                    &payload,
+                    executed,
+                    clock.unix_timestamp,
+                )?;
+                outcome.proposal = ctx.accounts.proposal.key();
+                outcome.executed_by = ctx.accounts.executor.key();
+                outcome.success = pnl >= 0;
+                outcome.metrics = Vec::new();
+                outcome.executed_at = clock.unix_timestamp;
+                outcome.balance_deltas = Vec::new();
+                outcome.realized_pnl = Some(pnl);
+                outcome.version = Outcome::VERSION;
+                outcome.bump = ctx.bumps.outcome;
+                emit!(OutcomeRecorded {
+                    proposal: outcome.proposal,
+                    executed_by: outcome.executed_by,
+                    success: outcome.success,
+                    timestamp: clock.unix_timestamp,
+                });
+
+                let proposer_key = if proposal.proposer == agent.owner {
+                    agent.attribute_proposal_pnl(pnl);
+                    if pnl >= 0 {
+                        agent.credit_successful_proposal(&mut swarm_state.rewards);
+                    }
+                    ctx.accounts.agent.key()
+                } else {
+                    let proposer_agent = ctx
+                        .accounts
+                        .proposer_agent
+                        .as_ref()
+                        .ok_or(SwarmError::MissingExecutionAccount)?;
+                    let mut proposer = proposer_agent.load_mut()?;
+                    proposer.attribute_proposal_pnl(pnl);
+                    if pnl >= 0 {
+                        proposer.credit_successful_proposal(&mut swarm_state.rewards);
+                    }
+                    proposer_agent.key()
+                };
+                emit!(PnlAttributed {
+                    agent: proposer_key,
+                    proposal: ctx.accounts.proposal.key(),
+                    pnl,
+                    as_proposer: true,
+                    timestamp: clock.unix_timestamp,
+                });
+                msg!("Trade realized {} at oracle prices", pnl);
+            }
+            if let Some(arb_report) = ctx.accounts.arb_report.as_mut() {
+                require!(
+                    arb_report.is_claimable(&payload.route_hash()?, clock.unix_timestamp),
+                    SwarmError::ArbReportNotClaimable
+                );
+                // The executing agent is already loaded when it filed the report
+                let mut reporter_agent;
+                let (reporter, reporter_key) = if arb_report.reporter == agent.owner {
+                    (&mut *agent, ctx.accounts.agent.key())
+                } else {
+                    let arb_reporter = ctx
+                        .accounts
+                        .arb_reporter
+                        .as_ref()
+                        .ok_or(SwarmError::MissingExecutionAccount)?;
+                    reporter_agent = arb_reporter.load_mut()?;
+                    (&mut *reporter_agent, arb_reporter.key())
+                };
+                credit_arb_report(
+                    arb_report,
+                    reporter,
+                    reporter_key,
+                    ctx.accounts.treasury.as_mut(),
+                    ctx.accounts.recipient.as_deref(),
+                    swarm_state.arb_report_bounty,
+                    ctx.accounts.proposal.key(),
+                )?;
+            }
+        }
+        Some(ProposalPayload::Strategy(StrategyPayload {
+            action,
+            balance_checks,
+            fail_on_deviation,
+        })) => {
+            // Every action but setting parameters is signed by the vault
+            let vault_authority = ctx
+                .accounts
+                .vault_authority
+                .as_ref()
+                .ok_or(SwarmError::MissingExecutionAccount);
+            let balances_before =
+                snapshot_balances(swarm_state, ctx.remaining_accounts, &balance_checks)?;
+            match action {
+                StrategyAction::Calls { calls } => {
+                    let program_whitelist = ctx
+                        .accounts
+                        .program_whitelist
+                        .as_ref()
+                        .ok_or(SwarmError::MissingExecutionAccount)?;
+                    execute_strategy(
+                        swarm_state,
+                        program_whitelist,
+                        vault_authority?,
+                        ctx.remaining_accounts,
+                        calls,
+                    )?
+                }
+                StrategyAction::Lending(payload) => {
+                    execute_lending(swarm_state, vault_authority?, ctx.remaining_accounts, payload)?
+                }
+                StrategyAction::Staking(payload) => {
+                    let (Some(treasury), Some(system_program)) = (
+                        ctx.accounts.treasury.as_mut(),
+                        ctx.accounts.system_program.as_ref(),
+                    ) else {
+                        return err!(SwarmError::MissingExecutionAccount);
+                    };
+                    execute_staking(
+                        swarm_state,
+                        treasury,
+                        vault_authority?,
+                        system_program,
+                        ctx.remaining_accounts,
+                        ctx.accounts.proposal.key(),
+                        payload,
+                    )?
+                }
+                StrategyAction::Perp(payload) => {
+                    let pending_exposure = ctx
+                        .accounts
+                        .pending_exposure
+                        .as_ref()
+                        .ok_or(SwarmError::MissingExecutionAccount)?;
+                    execute_perp_order(
+                        swarm_state,
+                        pending_exposure,
+                        vault_authority?,
+                        ctx.remaining_accounts,
+                        payload,
+                    )?
+                }
+                StrategyAction::Liquidity(payload) => {
+                    let treasury = ctx
+                        .accounts
+                        .treasury
+                        .as_mut()
+                        .ok_or(SwarmError::MissingExecutionAccount)?;
+                    execute_liquidity(
+                        swarm_state,
+                        treasury,
+                        vault_authority?,
+                        ctx.remaining_accounts,
+                        ctx.accounts.proposal.key(),
+                        payload,
+                    )?
+                }
+                StrategyAction::Whirlpool(payload) => {
+                    let treasury = ctx
+                        .accounts
+                        .treasury
+                        .as_mut()
+                        .ok_or(SwarmError::MissingExecutionAccount)?;
+                    execute_whirlpool(
+                        swarm_state,
+                        treasury,
+                        vault_authority?,
+                        ctx.remaining_accounts,
+                        ctx.accounts.proposal.key(),
+                        payload,
+                    )?
+                }
+                StrategyAction::Parameters(parameters) => {
+                    let strategy_config = ctx
+                        .accounts
+                        .strategy_config
+                        .as_mut()
+                        .ok_or(SwarmError::MissingExecutionAccount)?;
+                    if strategy_config.swarm == Pubkey::default() {
+                        strategy_config.swarm = swarm_state.key();
+                        strategy_config.version = StrategyConfig::VERSION;
+                        strategy_config.bump = ctx.bumps.strategy_config;
+                    }
+                    strategy_config.set(parameters, ctx.accounts.proposal.key(), clock.unix_timestamp)?;
+                    emit!(StrategyParametersSet {
+                        config: strategy_config.key(),
+                        proposal: strategy_config.source_proposal,
+                        parameter_version: strategy_config.parameter_version,
+                        allocations: strategy_config.allocations.clone(),
+                        lookback_windows: strategy_config.lookback_windows.clone(),
+                        thresholds: strategy_config.thresholds.clone(),
+                        timestamp: clock.unix_timestamp,
+                    });
+                    msg!("Strategy parameters v{} adopted", strategy_config.parameter_version);
+                }
+                StrategyAction::StandingOrder(terms) => {
+                    terms.trigger.validate(swarm_state)?;
+                    require!(terms.expires_at > clock.unix_timestamp, SwarmError::InvalidPayload);
+                    let standing_order = ctx
+                        .accounts
+                        .standing_order
+                        .as_mut()
+                        .ok_or(SwarmError::MissingExecutionAccount)?;
+                    standing_order.swarm = swarm_state.key();
+                    standing_order.proposal = ctx.accounts.proposal.key();
+                    standing_order.trigger = terms.trigger;
+                    standing_order.trade = terms.trade;
+                    standing_order.expires_at = terms.expires_at;
+                    standing_order.payer = ctx.accounts.executor.key();
+                    standing_order.version = StandingOrder::VERSION;
+                    standing_order.bump = ctx.bumps.standing_order;
+                    emit!(StandingOrderPlaced {
+                        order: standing_order.key(),
+                        proposal: standing_order.proposal,
+                        trigger: standing_order.trigger,
+                        mint_in: standing_order.trade.mint_in,
+                        mint_out: standing_order.trade.mint_out,
+                        amount: standing_order.trade.amount,
+                        expires_at: standing_order.expires_at,
+                        timestamp: clock.unix_timestamp,
+                    });
+                    msg!("Standing order placed, expiring at {}", standing_order.expires_at);
+                }
+            }
+
+            if !balance_checks.is_empty() {
+                let (balance_deltas, deviated) = measure_balance_deltas(
+                    swarm_state,
+                    ctx.remaining_accounts,
+                    &balance_checks,
+                    &balances_before,
+                )?;
+                require!(!(deviated && fail_on_deviation), SwarmError::BalanceDeviation);
+
+                let outcome = ctx
+                    .accounts
+                    .outcome
+                    .as_mut()
+                    .ok_or(SwarmError::MissingExecutionAccount)?;
+                outcome.proposal = ctx.accounts.proposal.key();
+                outcome.executed_by = ctx.accounts.executor.key();
+                outcome.success = !deviated;
+                outcome.metrics = Vec::new();
+                outcome.executed_at = clock.unix_timestamp;
+                outcome.balance_deltas = balance_deltas;
+                outcome.realized_pnl = None;
+                outcome.version = Outcome::VERSION;
+                outcome.bump = ctx.bumps.outcome;
+                emit!(OutcomeRecorded {
+                    proposal: outcome.proposal,
+                    executed_by: outcome.executed_by,
+                    success: outcome.success,
+                    timestamp: clock.unix_timestamp,
+                });
+
+                if !deviated {
+                    if proposal.proposer == agent.owner {
+                        agent.credit_successful_proposal(&mut swarm_state.rewards);
+                    } else {
+                        ctx.accounts
+                            .proposer_agent
+                            .as_ref()
+                            .ok_or(SwarmError::MissingExecutionAccount)?
+                            .load_mut()?
+                            .credit_successful_proposal(&mut swarm_state.rewards);
+                    }
+                }
+                msg!("Balance checks recorded: success={}", !deviated);
+            }
+        }
+        Some(ProposalPayload::RiskLimit(payload)) => {
+            audit_log(&mut ctx.accounts.audit_log)?.record(
+                ctx.accounts.proposal.key(),
+                AuditAction::RiskLimitChanged,
+                swarm_state.key(),
+                clock.unix_timestamp,
+            );
+            match payload {
+                RiskLimitPayload::Exposure { mint, max_amount } => {
+                    pending_exposure(&mut ctx.accounts.pending_exposure)?
+                        .set_limit(mint, max_amount)?;
+                    msg!("Exposure limit for {} set to {:?}", mint, max_amount);
+                }
+                RiskLimitPayload::PerpPosition { market_index, max_base_amount } => {
+                    pending_exposure(&mut ctx.accounts.pending_exposure)?
+                        .set_perp_limit(market_index, max_base_amount)?;
+                    msg!("Perp limit for market {} set to {:?}", market_index, max_base_amount);
+                }
+                RiskLimitPayload::AllowProgram { program, allowed } => {
+                    ctx.accounts
+                        .program_whitelist
+                        .as_mut()
+                        .ok_or(SwarmError::MissingExecutionAccount)?
+                        .set(program, allowed)?;
+                    msg!("Program {} whitelisted: {}", program, allowed);
+                }
+                RiskLimitPayload::MaxPositionSize { max_amount } => {
+                    risk_config(&mut ctx.accounts.risk_config)?.max_position_size = max_amount;
+                    msg!("Max position size set to {:?}", max_amount);
+                }
+                RiskLimitPayload::MaxDailyVolume { max_amount } => {
+                    risk_config(&mut ctx.accounts.risk_config)?.max_daily_volume = max_amount;
+                    msg!("Max daily volume set to {:?}", max_amount);
+                }
+                RiskLimitPayload::MaxSlippage { max_slippage_bps } => {
+                    risk_config(&mut ctx.accounts.risk_config)?.max_slippage_bps = max_slippage_bps;
+                    msg!("Max slippage set to {:?} bps", max_slippage_bps);
+                }
+                RiskLimitPayload::AllowMint { mint, allowed } => {
+                    risk_config(&mut ctx.accounts.risk_config)?.set_mint(mint, allowed)?;
+                    msg!("Mint {} allowed: {}", mint, allowed);
+                }
+                RiskLimitPayload::MaxExecutionTip { max_lamports } => {
+                    risk_config(&mut ctx.accounts.risk_config)?.max_tip_lamports = max_lamports;
+                    msg!("Max execution tip set to {} lamports", max_lamports);
+                }
+                RiskLimitPayload::TwapGuard { guard } => {
+                    risk_config(&mut ctx.accounts.risk_config)?.twap_guard = guard;
+                    msg!("TWAP guard set to {:?}", guard);
+                }
+                RiskLimitPayload::MaxConcentration { max_weight_bps } => {
+                    risk_config(&mut ctx.accounts.risk_config)?.max_concentration_bps = max_weight_bps;
+                    msg!("Max concentration set to {:?} bps", max_weight_bps);
+                }
+                RiskLimitPayload::VenueCap { program, max_volume } => {
+                    risk_config(&mut ctx.accounts.risk_config)?.set_venue_cap(program, max_volume)?;
+                    msg!("Volume cap for venue {} set to {:?}", program, max_volume);
+                }
+                RiskLimitPayload::MaxDrawdown { max_drawdown } => {
+                    risk_config(&mut ctx.accounts.risk_config)?.set_max_drawdown(max_drawdown);
+                    msg!("Max drawdown set to {:?}", max_drawdown);
+                }
+                RiskLimitPayload::CircuitBreaker { breaker } => {
+                    risk_config(&mut ctx.accounts.risk_config)?.set_circuit_breaker(breaker);
+                    msg!("Circuit breaker set to {:?}", breaker);
+                }
+            }
+        }
+        Some(ProposalPayload::Rebalance(payload)) => {
+            risk_config(&mut ctx.accounts.risk_config)?.check_rebalance(&payload)?;
+            let portfolio =
+                portfolio(&mut ctx.accounts.portfolio, swarm_state.key(), ctx.bumps.portfolio)?;
+            portfolio.set_targets(&payload.allocations, ctx.accounts.proposal.key(), clock.unix_timestamp)?;
+            if !payload.trades.is_empty() {
+                let prices =
+                    portfolio.unit_prices(swarm_state, ctx.remaining_accounts, clock.unix_timestamp)?;
+                portfolio.check_rebalance_trades(&prices, &payload.trades)?;
+            }
+            if let Some(terms) = &payload.schedule {
+                let rebalance_schedule = ctx
+                    .accounts
+                    .rebalance_schedule
+                    .as_mut()
+                    .ok_or(SwarmError::MissingExecutionAccount)?;
+                if rebalance_schedule.swarm == Pubkey::default() {
+                    rebalance_schedule.swarm = swarm_state.key();
+                    rebalance_schedule.version = RebalanceSchedule::VERSION;
+                    rebalance_schedule.bump = ctx.bumps.rebalance_schedule;
+                }
+                rebalance_schedule.set(
+                    &payload.allocations,
+                    terms,
+                    ctx.accounts.proposal.key(),
+                    clock.unix_timestamp,
+                )?;
+                msg!("Rebalance scheduled every {} seconds", terms.interval);
+            }
+            emit!(RebalanceApproved {
+                proposal: ctx.accounts.proposal.key(),
+                allocations: payload.allocations,
+                trades: payload.trades,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        Some(ProposalPayload::TreasuryTransfer(payload)) => {
+            let treasury = ctx
+                .accounts
+                .treasury
+                .as_mut()
+                .ok_or(SwarmError::MissingExecutionAccount)?;
+            let recipient = ctx
+                .accounts
+                .recipient
+                .as_ref()
+                .ok_or(SwarmError::MissingExecutionAccount)?;
+            spend_tracker(&mut ctx.accounts.spend_tracker)?
+                .record_spend(&proposal.proposal_type(), payload.lamports, clock.unix_timestamp)?;
+            transfer_from_treasury(treasury, recipient, ctx.accounts.proposal.key(), &payload)?;
+        }
+        Some(ProposalPayload::TreasuryTokenTransfer(payload)) => {
+            let (
+                Some(vault_authority),
+                Some(treasury_token_account),
+                Some(recipient_token_account),
+                Some(mint),
+                Some(token_program),
+            ) = (
+                ctx.accounts.vault_authority.as_ref(),
+                ctx.accounts.treasury_token_account.as_mut(),
+                ctx.accounts.recipient_token_account.as_ref(),
+                ctx.accounts.mint.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ) else {
+                return err!(SwarmError::MissingExecutionAccount);
+            };
+            spend_tracker(&mut ctx.accounts.spend_tracker)?
+                .record_spend(&proposal.proposal_type(), payload.amount, clock.unix_timestamp)?;
+            transfer_tokens_from_treasury(
+                &TreasuryTokenSource {
+                    swarm_state,
+                    vault_authority,
+                    treasury_token_account,
+                    mint,
+                    token_program,
+                    hook_accounts: ctx.remaining_accounts,
+                },
+                recipient_token_account,
+                ctx.accounts.proposal.key(),
+                &payload,
+            )?;
+            treasury_token_account.reload()?;
+            portfolio(&mut ctx.accounts.portfolio, swarm_state.key(), ctx.bumps.portfolio)?
+                .record_balance(
+                    payload.mint,
+                    treasury_token_account.amount,
+                    ctx.accounts.proposal.key(),
+                    clock.unix_timestamp,
+                )?;
+        }
+        Some(ProposalPayload::AuthorityHandover(payload)) => {
+            msg!("Swarm authority handed over: {} -> {}", swarm_state.authority, payload.new_authority);
+            swarm_state.authority = payload.new_authority;
+            swarm_state.authority_last_active = clock.unix_timestamp;
+            audit_log(&mut ctx.accounts.audit_log)?.record(
+                ctx.accounts.proposal.key(),
+                AuditAction::AuthorityTransferred,
+                payload.new_authority,
+                clock.unix_timestamp,
+            );
+        }
+        Some(ProposalPayload::DcaSchedule(payload)) => {
+            // Every leg is priced against both mints' oracles
+            require!(
+                swarm_state.price_feed(&payload.mint_in).is_some()
+                    && swarm_state.price_feed(&payload.mint_out).is_some(),
+                SwarmError::MissingPriceFeed
+            );
+            let dca_schedule = ctx
+                .accounts
+                .dca_schedule
+                .as_mut()
+                .ok_or(SwarmError::MissingExecutionAccount)?;
+            dca_schedule.swarm = swarm_state.key();
+            dca_schedule.proposal = ctx.accounts.proposal.key();
+            dca_schedule.mint_in = payload.mint_in;
+            dca_schedule.mint_out = payload.mint_out;
+            dca_schedule.amount_per_leg = payload.amount_per_leg;
+            dca_schedule.interval = payload.interval;
+            dca_schedule.total_budget = payload.total_budget;
+            dca_schedule.max_slippage_bps = payload.max_slippage_bps;
+            dca_schedule.next_leg_at = clock.unix_timestamp;
+            dca_schedule.payer = ctx.accounts.executor.key();
+            dca_schedule.version = DcaSchedule::VERSION;
+            dca_schedule.bump = ctx.bumps.dca_schedule;
+            emit!(DcaScheduleCreated {
+                schedule: dca_schedule.key(),
+                proposal: dca_schedule.proposal,
+                mint_in: payload.mint_in,
+                mint_out: payload.mint_out,
+                amount_per_leg: payload.amount_per_leg,
+                interval: payload.interval,
+                total_budget: payload.total_budget,
+                timestamp: clock.unix_timestamp,
+            });
+            msg!("DCA schedule created: {} per leg every {} seconds", payload.amount_per_leg, payload.interval);
+        }
+        Some(ProposalPayload::Harvest(payload)) => {
+            let harvest_config = ctx
+                .accounts
+                .harvest_config
+                .as_mut()
+                .ok_or(SwarmError::MissingExecutionAccount)?;
+            if harvest_config.swarm == Pubkey::default() {
+                harvest_config.swarm = swarm_state.key();
+                harvest_config.version = HarvestConfig::VERSION;
+                harvest_config.bump = ctx.bumps.harvest_config;
+            }
+            if let Some(terms) = payload.terms {
+                harvest_config.set(terms, ctx.accounts.proposal.key());
+                emit!(HarvestConfigured {
+                    config: harvest_config.key(),
+                    proposal: harvest_config.source_proposal,
+                    base_mint: harvest_config.base_mint,
+                    positions: harvest_config.positions.clone(),
+                    crank_threshold: harvest_config.crank_threshold,
+                    crank_interval: harvest_config.crank_interval,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+            require!(harvest_config.is_configured(), SwarmError::HarvestNotConfigured);
+
+            let vault_authority = ctx
+                .accounts
+                .vault_authority
+                .as_ref()
+                .ok_or(SwarmError::MissingExecutionAccount)?;
+            let route_accounts = collect_harvest(
+                swarm_state,
+                vault_authority,
+                &harvest_config.positions,
+                ctx.remaining_accounts,
+            )?;
+
+            let mut trade = None;
+            let (mut spent, mut received) = (0, 0);
+            if let Some(swap) = payload.swap {
+                let payload = harvest_config.trade(swap)?;
+                let (Some(treasury_token_account), Some(output_token_account), Some(swap_program)) = (
+                    ctx.accounts.treasury_token_account.as_mut(),
+                    ctx.accounts.output_token_account.as_mut(),
+                    ctx.accounts.swap_program.as_ref(),
+                ) else {
+                    return err!(SwarmError::MissingExecutionAccount);
+                };
+                let risk_config = risk_config(&mut ctx.accounts.risk_config)?;
+                let prices = (
+                    ctx.accounts.price_update_in.as_deref(),
+                    ctx.accounts.price_update_out.as_deref(),
+                );
+                risk_config.check_trade(
+                    swarm_state,
+                    &payload,
+                    swap_program.key,
+                    prices,
+                    (
+                        ctx.accounts.twap_tracker_in.as_deref(),
+                        ctx.accounts.twap_tracker_out.as_deref(),
+                    ),
+                    clock.unix_timestamp,
+                )?;
+                spend_tracker(&mut ctx.accounts.spend_tracker)?
+                    .record_spend(&proposal.proposal_type(), payload.amount, clock.unix_timestamp)?;
+                (spent, received) = execute_trade(
+                    swarm_state,
+                    treasury_token_account,
+                    output_token_account,
+                    &SwapAccounts {
+                        authority: vault_authority,
+                        swap_program,
+                        route_accounts,
+                    },
+                    ctx.accounts.proposal.key(),
+                    &payload,
+                )?;
+                let portfolio =
+                    portfolio(&mut ctx.accounts.portfolio, swarm_state.key(), ctx.bumps.portfolio)?;
+                for (mint, token_account) in [
+                    (payload.mint_in, &*treasury_token_account),
+                    (payload.mint_out, &*output_token_account),
+                ] {
+                    portfolio.record_balance(
+                        mint,
+                        token_account.amount,
+                        ctx.accounts.proposal.key(),
+                        clock.unix_timestamp,
+                    )?;
+                }
+                if let (Some(price_update_in), Some(price_update_out)) = prices {
+                    risk_config.record_trade_result(
+                        swarm_state,
+                        price_update_in,
+                        price_update_out,
+                        &payload,
+                        (spent, received),
+                        clock.unix_timestamp,
+                    )?;
+                }
+                trade = Some(payload);
+            }
+
+            harvest_config.record_harvest(clock.unix_timestamp);
+            emit!(HarvestExecuted {
+                config: harvest_config.key(),
+                proposal: Some(ctx.accounts.proposal.key()),
+                harvester: ctx.accounts.executor.key(),
+                positions: harvest_config.positions.len() as u8,
+                mint_in: trade.map_or(Pubkey::default(), |trade| trade.mint_in),
+                spent,
+                received,
+                timestamp: clock.unix_timestamp,
+            });
+            msg!("Harvested {} positions, swapping {} for {}", harvest_config.positions.len(), spent, received);
+        }
+        Some(ProposalPayload::Insurance(payload)) => {
+            let insurance_fund = ctx
+                .accounts
+                .insurance_fund
+                .as_mut()
+                .ok_or(SwarmError::MissingExecutionAccount)?;
+            if insurance_fund.swarm == Pubkey::default() {
+                insurance_fund.swarm = swarm_state.key();
+                insurance_fund.version = InsuranceFund::VERSION;
+                insurance_fund.bump = ctx.bumps.insurance_fund;
+            }
+            match payload {
+                InsurancePayload::Terms { fee_share_bps, loss_threshold_bps } => {
+                    insurance_fund.fee_share_bps = fee_share_bps;
+                    insurance_fund.loss_threshold_bps = loss_threshold_bps;
+                    emit!(InsuranceTermsSet {
+                        fund: insurance_fund.key(),
+                        proposal: ctx.accounts.proposal.key(),
+                        fee_share_bps,
+                        loss_threshold_bps,
+                        timestamp: clock.unix_timestamp,
+                    });
+                    msg!("Insurance terms: {} bps of fees, {} bps loss threshold", fee_share_bps, loss_threshold_bps);
+                }
+                InsurancePayload::Claim { .. } => {
+                    let (Some(treasury), Some(claimed_proposal), Some(claimed_outcome), Some(cited_attestation)) = (
+                        ctx.accounts.treasury.as_mut(),
+                        ctx.accounts.claimed_proposal.as_ref(),
+                        ctx.accounts.claimed_outcome.as_ref(),
+                        ctx.accounts.cited_attestation.as_ref(),
+                    ) else {
+                        return err!(SwarmError::MissingExecutionAccount);
+                    };
+                    require!(
+                        proposal.attestation() == Some(cited_attestation.key()),
+                        SwarmError::InvalidAttestation
+                    );
+                    pay_insurance_claim(
+                        insurance_fund,
+                        treasury,
+                        claimed_proposal,
+                        claimed_outcome,
+                        cited_attestation,
+                        ctx.accounts.proposal.key(),
+                        payload,
+                    )?;
+                }
+            }
+        }
+        Some(ProposalPayload::Emergency(EmergencyPayload::ResumeTrading)) => {
+            require_supermajority(&proposal)?;
+            let risk_config = risk_config(&mut ctx.accounts.risk_config)?;
+            require!(risk_config.trading_paused, SwarmError::InvalidPayload);
+            risk_config.resume_trading();
+            audit_log(&mut ctx.accounts.audit_log)?.record(
+                ctx.accounts.proposal.key(),
+                AuditAction::TradingResumed,
+                risk_config.key(),
+                clock.unix_timestamp,
+            );
+            emit!(TradingResumed {
+                risk_config: risk_config.key(),
+                proposal: ctx.accounts.proposal.key(),
+                timestamp: clock.unix_timestamp,
+            });
+            msg!("Trading resumed");
+        }
+        // Bond slashes carried by Emergency proposals are applied by
+        // `slash_agent`, cross-chain messages are posted by `post_cross_chain_message`
+        Some(ProposalPayload::Emergency(EmergencyPayload::Slash(_)) | ProposalPayload::CrossChain(_))
+        | None => {}
+    }
+
+    if tip_lamports > 0 {
+        let (Some(treasury), Some(tip_account)) = (
+            ctx.accounts.treasury.as_mut(),
+            ctx.accounts.tip_account.as_ref(),
+        ) else {
+            return err!(SwarmError::MissingExecutionAccount);
+        };
+        pay_execution_tip(
+            risk_config(&mut ctx.accounts.risk_config)?,
+            treasury,
+            tip_account,
+            tip_lamports,
+            ctx.accounts.proposal.key(),
+        )?;
+    }
 
-    swarm_state.executed_proposals += 1;
+    swarm_state.executed_proposals = swarm_state
+        .executed_proposals
+        .checked_add(1)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    swarm_state.rewards.executions = swarm_state.rewards.executions.saturating_add(1);
     agent.last_active = clock.unix_timestamp;
 
+    emit!(ProposalExecuted {
+        proposal: ctx.accounts.proposal.key(),
+        executor: agent.owner,
+        proposal_type: proposal.proposal_type(),
+        weighted_votes_for: proposal.weighted_votes_for,
+        weighted_votes_against: proposal.weighted_votes_against,
+        tags: proposal.tags(),
+        timestamp: clock.unix_timestamp,
+    });
     msg!("Proposal executed successfully");
     msg!("Votes: {} for, {} against (weighted: {} vs {})", 
         proposal.votes_for, 
@@ -64,3 +1294,111 @@ pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
 
     Ok(())
 }
+
+fn require_supermajority(proposal: &Proposal) -> Result<()> {
+    require_ctx!(
+        proposal.has_supermajority(),
+        SwarmError::InsufficientVotes,
+        FailedCheck::Supermajority,
+        SUPERMAJORITY_BPS,
+        proposal.weighted_votes_for * BPS_DENOMINATOR as u64
+            / (proposal.weighted_votes_for + proposal.weighted_votes_against)
+    );
+    Ok(())
+}
+
+fn spend_tracker<'a, 'info>(
+    spend_tracker: &'a mut Option<Account<'info, SpendTracker>>,
+) -> Result<&'a mut Account<'info, SpendTracker>> {
+    spend_tracker
+        .as_mut()
+        .ok_or_else(|| error!(SwarmError::MissingExecutionAccount))
+}
+
+fn pending_exposure<'a, 'info>(
+    pending_exposure: &'a mut Option<Account<'info, PendingExposure>>,
+) -> Result<&'a mut Account<'info, PendingExposure>> {
+    pending_exposure
+        .as_mut()
+        .ok_or_else(|| error!(SwarmError::MissingExecutionAccount))
+}
+
+fn audit_log<'a, 'info>(
+    audit_log: &'a mut Option<Account<'info, AuditLog>>,
+) -> Result<&'a mut Account<'info, AuditLog>> {
+    audit_log
+        .as_mut()
+        .ok_or_else(|| error!(SwarmError::MissingExecutionAccount))
+}
+
+fn risk_config<'a, 'info>(
+    risk_config: &'a mut Option<Account<'info, RiskConfig>>,
+) -> Result<&'a mut Account<'info, RiskConfig>> {
+    risk_config
+        .as_mut()
+        .ok_or_else(|| error!(SwarmError::MissingExecutionAccount))
+}
+
+/// The swarm's portfolio, initializing it on first use
+fn portfolio<'a, 'info>(
+    portfolio: &'a mut Option<Account<'info, Portfolio>>,
+    swarm: Pubkey,
+    bump: u8,
+) -> Result<&'a mut Account<'info, Portfolio>> {
+    let portfolio = portfolio
+        .as_mut()
+        .ok_or_else(|| error!(SwarmError::MissingExecutionAccount))?;
+    if portfolio.swarm == Pubkey::default() {
+        portfolio.swarm = swarm;
+        portfolio.version = Portfolio::VERSION;
+        portfolio.bump = bump;
+    }
+    Ok(portfolio)
+}
+
+/// Credit the agent behind an arbitrage report the executed trade followed:
+/// reputation, plus the swarm's bounty from the treasury when it pays one
+fn credit_arb_report<'info>(
+    arb_report: &mut Account<'info, ArbReport>,
+    agent: &mut Agent,
+    agent_key: Pubkey,
+    treasury: Option<&mut Account<'info, Treasury>>,
+    recipient: Option<&AccountInfo<'info>>,
+    bounty: u64,
+    proposal: Pubkey,
+) -> Result<()> {
+    require!(agent.owner == arb_report.reporter, SwarmError::Unauthorized);
+    let now = Clock::get()?.unix_timestamp;
+
+    let old_reputation = agent.reputation;
+    agent.reputation = agent
+        .reputation
+        .saturating_add(ARB_REPORT_REPUTATION)
+        .min(MAX_REPUTATION);
+    emit!(ReputationChanged {
+        agent: agent_key,
+        old_reputation,
+        new_reputation: agent.reputation,
+        timestamp: now,
+    });
+
+    if bounty > 0 {
+        let (Some(treasury), Some(recipient)) = (treasury, recipient) else {
+            return err!(SwarmError::MissingExecutionAccount);
+        };
+        require_keys_eq!(recipient.key(), agent.signing_key, SwarmError::Unauthorized);
+        pay_lamports_from_treasury(treasury, recipient, bounty, proposal)?;
+    }
+
+    arb_report.credited_proposal = Some(proposal);
+    emit!(ArbReportCredited {
+        report: arb_report.key(),
+        reporter: arb_report.reporter,
+        proposal,
+        reputation: agent.reputation,
+        bounty,
+        timestamp: now,
+    });
+    msg!("Arbitrage report credited: reputation {}, bounty {}", agent.reputation, bounty);
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::{ProposalClosed, ProposalFailed};
+use crate::instructions::committee::require_quorum;
+
+/// The assigned executor or the proposer records an execution attempt that
+/// failed. A failing CPI reverts `execute_proposal` with it, so failures are
+/// reported separately.
+#[derive(Accounts)]
+pub struct ReportExecutionFailure<'info> {
+    #[account(
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == executor.key() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.is_active() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        mut,
+        constraint = proposal.load()?.swarm == swarm_state.key() @ SwarmError::SwarmMismatch,
+        constraint = !proposal.load()?.is_executed() @ SwarmError::ProposalAlreadyExecuted,
+        constraint = !proposal.load()?.is_failed() @ SwarmError::ProposalFailed
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        mut,
+        seeds = [
+            PROPOSAL_PAGE_SEED,
+            swarm_state.key().as_ref(),
+            &(proposal.load()?.id / PROPOSAL_PAGE_SIZE).to_le_bytes()
+        ],
+        bump = proposal_page.bump
+    )]
+    pub proposal_page: Account<'info, ProposalPage>,
+    
+    pub executor: Signer<'info>,
+    
+    /// Required for proposals scoped to a committee
+    #[account(
+        seeds = [COMMITTEE_SEED, swarm_state.key().as_ref(), &[committee.committee_id]],
+        bump = committee.bump
+    )]
+    pub committee: Option<Account<'info, Committee>>,
+}
+
+/// Proposer reclaims the rent of a proposal that ran out of execution
+/// attempts, after its bounties are refunded and endorsements settled
+#[derive(Accounts)]
+pub struct CloseFailedProposal<'info> {
+    #[account(
+        mut,
+        close = proposer,
+        constraint = proposal.load()?.is_failed() @ SwarmError::ProposalNotFailed,
+        constraint = proposal.load()?.is_closable() @ SwarmError::ProposalHasOpenClaims
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    /// The proposer's agent, whose signing key must sign
+    #[account(
+        seeds = [AGENT_SEED, proposal.load()?.swarm.as_ref(), proposal.load()?.proposer.as_ref()],
+        bump = proposer_agent.load()?.bump,
+        constraint = proposer_agent.load()?.signing_key == proposer.key() @ SwarmError::Unauthorized
+    )]
+    pub proposer_agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        mut,
+        seeds = [
+            PROPOSAL_PAGE_SEED,
+            proposal.load()?.swarm.as_ref(),
+            &(proposal.load()?.id / PROPOSAL_PAGE_SIZE).to_le_bytes()
+        ],
+        bump = proposal_page.bump
+    )]
+    pub proposal_page: Account<'info, ProposalPage>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+}
+
+pub fn report_execution_failure(
+    ctx: Context<ReportExecutionFailure>,
+    error_code: u32,
+) -> Result<()> {
+    let executor = ctx.accounts.agent.load()?.owner;
+    let swarm_state = &mut ctx.accounts.swarm_state;
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    let clock = Clock::get()?;
+
+    require!(
+        !proposal.is_expired(clock.unix_timestamp),
+        SwarmError::ProposalExpired
+    );
+    require_quorum(&proposal, swarm_state, ctx.accounts.committee.as_deref())?;
+    require!(proposal.is_approved(), SwarmError::InsufficientVotes);
+
+    let failed = proposal.record_failure(&executor, error_code, swarm_state.max_execution_attempts)?;
+
+    msg!(
+        "Execution attempt {} of {} failed with code {}",
+        proposal.execution_attempts,
+        swarm_state.max_execution_attempts,
+        error_code
+    );
+
+    if failed {
+        ctx.accounts.proposal_page.mark_failed(proposal.id);
+        swarm_state.rewards.failures = swarm_state.rewards.failures.saturating_add(1);
+        emit!(ProposalFailed {
+            proposal: ctx.accounts.proposal.key(),
+            attempts: proposal.execution_attempts,
+            last_failure_code: error_code,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn close_failed_proposal(ctx: Context<CloseFailedProposal>) -> Result<()> {
+    let proposal_id = ctx.accounts.proposal.load()?.id;
+    ctx.accounts.proposal_page.remove(proposal_id);
+    
+    emit!(ProposalClosed {
+        proposal: ctx.accounts.proposal.key(),
+        proposer: ctx.accounts.proposer_agent.load()?.owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Failed proposal {} closed", ctx.accounts.proposal.key());
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::{ExposureLimitSet, SwarmAccountCreated, TradeExposureReserved};
+use crate::payloads::{self, TradePayload};
+use crate::ProposalType;
+
+#[derive(Accounts)]
+pub struct InitializePendingExposure<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = PendingExposure::LEN,
+        seeds = [PENDING_EXPOSURE_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub pending_exposure: Account<'info, PendingExposure>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == swarm_state.authority @ SwarmError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Set the aggregate pending-trade limit for one input mint (authority only)
+#[derive(Accounts)]
+pub struct SetExposureLimit<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        seeds = [PENDING_EXPOSURE_SEED, swarm_state.key().as_ref()],
+        bump = pending_exposure.bump
+    )]
+    pub pending_exposure: Account<'info, PendingExposure>,
+    
+    #[account(constraint = authority.key() == swarm_state.authority @ SwarmError::Unauthorized)]
+    pub authority: Signer<'info>,
+}
+
+/// Permissionless crank reserving an approved Trade's size against the
+/// swarm's pending exposure before it can be executed
+#[derive(Accounts)]
+pub struct ReserveTradeExposure<'info> {
+    #[account(
+        constraint = proposal.load()?.proposal_type() == ProposalType::Trade @ SwarmError::InvalidPayload,
+        constraint = !proposal.load()?.is_executed() @ SwarmError::ProposalAlreadyExecuted,
+        constraint = proposal.load()?.is_approved() @ SwarmError::InsufficientVotes
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        mut,
+        seeds = [PENDING_EXPOSURE_SEED, proposal.load()?.swarm.as_ref()],
+        bump = pending_exposure.bump
+    )]
+    pub pending_exposure: Account<'info, PendingExposure>,
+    
+    pub cranker: Signer<'info>,
+}
+
+pub fn initialize_pending_exposure(ctx: Context<InitializePendingExposure>) -> Result<()> {
+    let pending_exposure = &mut ctx.accounts.pending_exposure;
+    pending_exposure.limits = Vec::new();
+    pending_exposure.pending = Vec::new();
+    pending_exposure.perp_limits = Vec::new();
+    pending_exposure.version = PendingExposure::VERSION;
+    pending_exposure.bump = ctx.bumps.pending_exposure;
+
+    emit!(SwarmAccountCreated {
+        account: pending_exposure.key(),
+        created_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Pending exposure tracking initialized");
+    Ok(())
+}
+
+pub fn set_exposure_limit(
+    ctx: Context<SetExposureLimit>,
+    mint: Pubkey,
+    max_amount: Option<u64>,
+) -> Result<()> {
+    ctx.accounts.pending_exposure.set_limit(mint, max_amount)?;
+
+    emit!(ExposureLimitSet {
+        mint,
+        max_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    if let Some(max_amount) = max_amount {
+        msg!("Exposure limit for {} set to {}", mint, max_amount);
+    } else {
+        msg!("Exposure limit for {} removed", mint);
+    }
+
+    Ok(())
+}
+
+pub fn reserve_trade_exposure(ctx: Context<ReserveTradeExposure>) -> Result<()> {
+    let data = Proposal::load_data(&ctx.accounts.proposal)?;
+    let proposal = ctx.accounts.proposal.load()?;
+    let clock = Clock::get()?;
+
+    require!(
+        !proposal.is_expired(clock.unix_timestamp),
+        SwarmError::ProposalExpired
+    );
+
+    let payload: TradePayload = payloads::decode(&data)?;
+    payload.validate()?;
+
+    let pending_exposure = &mut ctx.accounts.pending_exposure;
+    pending_exposure.reserve(ctx.accounts.proposal.key(), &payload, proposal.expires_at, clock.unix_timestamp)?;
+
+    emit!(TradeExposureReserved {
+        proposal: ctx.accounts.proposal.key(),
+        mint: payload.mint_in,
+        amount: payload.amount,
+        expires_at: proposal.expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!(
+        "Reserved {} of {} for proposal {} ({} of {} pending)",
+        payload.amount,
+        payload.mint_in,
+        ctx.accounts.proposal.key(),
+        pending_exposure.exposure(&payload.mint_in),
+        pending_exposure.limit(&payload.mint_in).unwrap_or(u64::MAX)
+    );
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::*;
+
+/// Create the swarm's governance token. The swarm PDA is the mint authority,
+/// so new supply only appears through `mint_emissions`.
+#[derive(Accounts)]
+pub struct CreateGovernanceMint<'info> {
+    #[account(
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump,
+        constraint = swarm_state.governance_mint.is_none() @ SwarmError::InvalidConfig
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [GOVERNANCE_MINT_SEED, swarm_state.key().as_ref()],
+        bump,
+        mint::decimals = GOVERNANCE_TOKEN_DECIMALS,
+        mint::authority = swarm_state
+    )]
+    pub governance_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == swarm_state.authority @ SwarmError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless crank minting one reward epoch's emission into the
+/// treasury's governance token account
+#[derive(Accounts)]
+pub struct MintEmissions<'info> {
+    #[account(
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        seeds = [GOVERNANCE_MINT_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub governance_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = governance_mint,
+        associated_token::authority = swarm_state.vault_authority
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    
+    pub cranker: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn create_governance_mint(ctx: Context<CreateGovernanceMint>) -> Result<()> {
+    let swarm_state = &mut ctx.accounts.swarm_state;
+    swarm_state.governance_mint = Some(ctx.accounts.governance_mint.key());
+    swarm_state.emission.last_epoch = swarm_state.rewards.epoch;
+
+    emit!(SwarmAccountCreated {
+        account: ctx.accounts.governance_mint.key(),
+        created_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Governance mint created: {}", ctx.accounts.governance_mint.key());
+    Ok(())
+}
+
+pub fn mint_emissions(ctx: Context<MintEmissions>) -> Result<()> {
+    let swarm_state = &ctx.accounts.swarm_state;
+    let emission = &swarm_state.emission;
+
+    require!(
+        swarm_state.rewards.epoch > emission.last_epoch,
+        SwarmError::EpochNotOver
+    );
+
+    let remaining_supply = emission
+        .max_supply
+        .saturating_sub(ctx.accounts.governance_mint.supply);
+    let amount = emission.per_epoch.min(remaining_supply);
+
+    if amount > 0 {
+        let swarm_id = swarm_state.swarm_id.to_le_bytes();
+        let bump = [swarm_state.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[SWARM_SEED, &swarm_id, &bump]];
+
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.governance_mint.to_account_info(),
+                    to: ctx.accounts.treasury_token_account.to_account_info(),
+                    authority: swarm_state.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit!(TreasuryTransferIn {
+            schema_version: ACCOUNTING_SCHEMA_VERSION,
+            asset: ctx.accounts.governance_mint.key(),
+            amount,
+            from: ctx.accounts.governance_mint.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    let swarm_state = &mut ctx.accounts.swarm_state;
+    swarm_state.emission.last_epoch = swarm_state.rewards.epoch;
+
+    emit!(EmissionsMinted {
+        mint: ctx.accounts.governance_mint.key(),
+        epoch: swarm_state.rewards.epoch,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Emitted {} governance tokens to the treasury", amount);
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::*;
+use crate::constants::*;
+use crate::cpi::SwapAccounts;
+use crate::errors::{FailedCheck, SwarmError};
+use crate::events::HarvestExecuted;
+use crate::instructions::treasury::{collect_harvest, execute_trade};
+use crate::oracle::{fair_amount_out, OraclePrice};
+use crate::payloads::{HarvestSwap, SwapRoute};
+use crate::risk::UnitPrice;
+use crate::{require_ctx, ProposalType};
+
+/// Harvest without a proposal (permissionless): collect the fees of the
+/// registered positions and swap `amount` of a reward mint into the base
+/// asset through Raydium, once `crank_interval` has passed since the last
+/// harvest. The swap's oracle value must not exceed the `crank_threshold` a
+/// `Harvest` proposal set; its minimum output is set from the oracle prices
+/// and it counts against the `Harvest` spend cap.
+///
+/// The Whirlpool program and each position's fee accounts come first in the
+/// remaining accounts, followed by the Raydium pool's accounts.
+#[derive(Accounts)]
+pub struct CrankHarvest<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+
+    #[account(
+        mut,
+        seeds = [HARVEST_CONFIG_SEED, swarm_state.key().as_ref()],
+        bump = harvest_config.bump
+    )]
+    pub harvest_config: Account<'info, HarvestConfig>,
+
+    /// CHECK: Data-less PDA owning the vault token accounts and positions;
+    /// signs the fee collection and the swap
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, swarm_state.key().as_ref()],
+        bump = swarm_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Vault token account of the reward mint being swapped
+    #[account(mut)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault token account of the base asset
+    #[account(mut)]
+    pub output_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Raydium AMM V4, checked against its program id
+    pub swap_program: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [RISK_CONFIG_SEED, swarm_state.key().as_ref()],
+        bump = risk_config.bump
+    )]
+    pub risk_config: Account<'info, RiskConfig>,
+
+    #[account(
+        mut,
+        seeds = [SPEND_TRACKER_SEED, swarm_state.key().as_ref()],
+        bump = spend_tracker.bump
+    )]
+    pub spend_tracker: Account<'info, SpendTracker>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = Portfolio::LEN,
+        seeds = [PORTFOLIO_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// CHECK: Pyth or Switchboard price account for the reward mint; parsed
+    /// in `OraclePrice::load`
+    pub price_update_in: UncheckedAccount<'info>,
+
+    /// CHECK: Price account for the base asset
+    pub price_update_out: UncheckedAccount<'info>,
+
+    /// CHECK: `TwapTracker` of the reward mint, required when the risk
+    /// config sets a `twap_guard`
+    pub twap_tracker_in: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: `TwapTracker` of the base asset
+    pub twap_tracker_out: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn crank_harvest<'info>(
+    ctx: Context<'_, '_, '_, 'info, CrankHarvest<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let swarm_state = &ctx.accounts.swarm_state;
+    let harvest_config = &ctx.accounts.harvest_config;
+    require!(amount > 0, SwarmError::InvalidParameter);
+    require!(harvest_config.is_configured(), SwarmError::HarvestNotConfigured);
+    require!(harvest_config.is_crank_due(clock.unix_timestamp), SwarmError::HarvestNotDue);
+
+    let mint_in = ctx.accounts.treasury_token_account.mint;
+    let value = UnitPrice::load(swarm_state, &ctx.accounts.price_update_in, &mint_in, clock.unix_timestamp)?
+        .value(amount)?;
+    require_ctx!(
+        value as u64 <= harvest_config.crank_threshold,
+        SwarmError::HarvestAboveCrankThreshold,
+        FailedCheck::HarvestValue,
+        harvest_config.crank_threshold,
+        value
+    );
+
+    let feed_in = swarm_state.price_feed(&mint_in).ok_or(SwarmError::MissingPriceFeed)?;
+    let feed_out = swarm_state
+        .price_feed(&harvest_config.base_mint)
+        .ok_or(SwarmError::MissingPriceFeed)?;
+    let fair_out = fair_amount_out(
+        amount,
+        &OraclePrice::load(&ctx.accounts.price_update_in, feed_in, clock.unix_timestamp)?,
+        feed_in.decimals,
+        &OraclePrice::load(&ctx.accounts.price_update_out, feed_out, clock.unix_timestamp)?,
+        feed_out.decimals,
+    )?;
+    let min_out = fair_out as u128
+        * (BPS_DENOMINATOR - harvest_config.max_slippage_bps) as u128
+        / BPS_DENOMINATOR as u128;
+    let trade = harvest_config.trade(HarvestSwap {
+        mint_in,
+        amount,
+        min_out: (min_out as u64).max(1),
+        route: SwapRoute::Raydium,
+    })?;
+
+    let route_accounts = collect_harvest(
+        swarm_state,
+        &ctx.accounts.vault_authority,
+        &harvest_config.positions,
+        ctx.remaining_accounts,
+    )?;
+
+    let prices = (
+        Some(&*ctx.accounts.price_update_in),
+        Some(&*ctx.accounts.price_update_out),
+    );
+    let risk_config = &mut ctx.accounts.risk_config;
+    risk_config.check_trade(
+        swarm_state,
+        &trade,
+        ctx.accounts.swap_program.key,
+        prices,
+        (
+            ctx.accounts.twap_tracker_in.as_deref(),
+            ctx.accounts.twap_tracker_out.as_deref(),
+        ),
+        clock.unix_timestamp,
+    )?;
+    ctx.accounts
+        .spend_tracker
+        .record_spend(&ProposalType::Harvest, amount, clock.unix_timestamp)?;
+
+    let (spent, received) = execute_trade(
+        swarm_state,
+        &mut ctx.accounts.treasury_token_account,
+        &mut ctx.accounts.output_token_account,
+        &SwapAccounts {
+            authority: &ctx.accounts.vault_authority,
+            swap_program: &ctx.accounts.swap_program,
+            route_accounts,
+        },
+        harvest_config.source_proposal,
+        &trade,
+    )?;
+    risk_config.record_trade_result(
+        swarm_state,
+        &ctx.accounts.price_update_in,
+        &ctx.accounts.price_update_out,
+        &trade,
+        (spent, received),
+        clock.unix_timestamp,
+    )?;
+
+    let portfolio = &mut ctx.accounts.portfolio;
+    if portfolio.swarm == Pubkey::default() {
+        portfolio.swarm = swarm_state.key();
+        portfolio.version = Portfolio::VERSION;
+        portfolio.bump = ctx.bumps.portfolio;
+    }
+    for (mint, token_account) in [
+        (trade.mint_in, &ctx.accounts.treasury_token_account),
+        (trade.mint_out, &ctx.accounts.output_token_account),
+    ] {
+        portfolio.record_balance(mint, token_account.amount, harvest_config.source_proposal, clock.unix_timestamp)?;
+    }
+
+    let harvest_config = &mut ctx.accounts.harvest_config;
+    harvest_config.record_harvest(clock.unix_timestamp);
+    emit!(HarvestExecuted {
+        config: harvest_config.key(),
+        proposal: None,
+        harvester: ctx.accounts.keeper.key(),
+        positions: harvest_config.positions.len() as u8,
+        mint_in,
+        spent,
+        received,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Harvest cranked: {} in, {} out", spent, received);
+    Ok(())
+}
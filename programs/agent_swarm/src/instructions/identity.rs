@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::{MembershipLinked, ReputationSynced};
+
+/// Create the owner's cross-swarm identity
+#[derive(Accounts)]
+pub struct CreateAgentIdentity<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = AgentIdentity::LEN,
+        seeds = [AGENT_IDENTITY_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub identity: Account<'info, AgentIdentity>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Owner links its agent in one swarm to its identity. The agent takes on
+/// the identity's reputation, replacing the score it had in that swarm.
+#[derive(Accounts)]
+pub struct LinkMembership<'info> {
+    #[account(
+        mut,
+        seeds = [AGENT_IDENTITY_SEED, owner.key().as_ref()],
+        bump = identity.bump
+    )]
+    pub identity: Account<'info, AgentIdentity>,
+    
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, agent.load()?.swarm.as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == owner.key() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = Membership::LEN,
+        seeds = [MEMBERSHIP_SEED, identity.key().as_ref(), agent.load()?.swarm.as_ref()],
+        bump
+    )]
+    pub membership: Account<'info, Membership>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Fold one swarm's reputation changes into the identity and refresh that
+/// swarm's copy (permissionless)
+#[derive(Accounts)]
+pub struct SyncReputation<'info> {
+    #[account(
+        mut,
+        seeds = [AGENT_IDENTITY_SEED, identity.owner.as_ref()],
+        bump = identity.bump
+    )]
+    pub identity: Account<'info, AgentIdentity>,
+    
+    #[account(
+        mut,
+        seeds = [MEMBERSHIP_SEED, identity.key().as_ref(), membership.swarm.as_ref()],
+        bump = membership.bump
+    )]
+    pub membership: Account<'info, Membership>,
+    
+    #[account(mut, address = membership.agent @ SwarmError::AgentNotFound)]
+    pub agent: AccountLoader<'info, Agent>,
+}
+
+/// Owner detaches a swarm's agent from its identity after a final sync
+#[derive(Accounts)]
+pub struct UnlinkMembership<'info> {
+    #[account(
+        mut,
+        seeds = [AGENT_IDENTITY_SEED, owner.key().as_ref()],
+        bump = identity.bump
+    )]
+    pub identity: Account<'info, AgentIdentity>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [MEMBERSHIP_SEED, identity.key().as_ref(), membership.swarm.as_ref()],
+        bump = membership.bump
+    )]
+    pub membership: Account<'info, Membership>,
+    
+    #[account(mut, address = membership.agent @ SwarmError::AgentNotFound)]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn create_agent_identity(ctx: Context<CreateAgentIdentity>) -> Result<()> {
+    let identity = &mut ctx.accounts.identity;
+    identity.owner = ctx.accounts.owner.key();
+    identity.reputation = INITIAL_REPUTATION;
+    identity.memberships = 0;
+    identity.created_at = Clock::get()?.unix_timestamp;
+    identity.version = AgentIdentity::VERSION;
+    identity.bump = ctx.bumps.identity;
+
+    msg!("Agent identity created for {}", identity.owner);
+    Ok(())
+}
+
+pub fn link_membership(ctx: Context<LinkMembership>) -> Result<()> {
+    let identity = &mut ctx.accounts.identity;
+    let mut agent = ctx.accounts.agent.load_mut()?;
+    let clock = Clock::get()?;
+
+    agent.reputation = identity.reputation;
+    identity.memberships = identity.memberships.saturating_add(1);
+
+    let membership = &mut ctx.accounts.membership;
+    membership.identity = identity.key();
+    membership.swarm = agent.swarm;
+    membership.agent = ctx.accounts.agent.key();
+    membership.synced_reputation = identity.reputation;
+    membership.synced_at = clock.unix_timestamp;
+    membership.version = Membership::VERSION;
+    membership.bump = ctx.bumps.membership;
+
+    emit!(MembershipLinked {
+        identity: membership.identity,
+        swarm: membership.swarm,
+        agent: membership.agent,
+        linked: true,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("{} now carries reputation {} into swarm {}", agent.name(), agent.reputation, agent.swarm);
+
+    Ok(())
+}
+
+pub fn sync_reputation(ctx: Context<SyncReputation>) -> Result<()> {
+    sync(
+        &mut ctx.accounts.identity,
+        &mut ctx.accounts.membership,
+        &ctx.accounts.agent,
+    )
+}
+
+pub fn unlink_membership(ctx: Context<UnlinkMembership>) -> Result<()> {
+    sync(
+        &mut ctx.accounts.identity,
+        &mut ctx.accounts.membership,
+        &ctx.accounts.agent,
+    )?;
+
+    let identity = &mut ctx.accounts.identity;
+    identity.memberships = identity.memberships.saturating_sub(1);
+
+    emit!(MembershipLinked {
+        identity: identity.key(),
+        swarm: ctx.accounts.membership.swarm,
+        agent: ctx.accounts.membership.agent,
+        linked: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Membership in swarm {} unlinked", ctx.accounts.membership.swarm);
+
+    Ok(())
+}
+
+fn sync(
+    identity: &mut Account<AgentIdentity>,
+    membership: &mut Account<Membership>,
+    agent: &AccountLoader<Agent>,
+) -> Result<()> {
+    let mut agent_data = agent.load_mut()?;
+    let clock = Clock::get()?;
+    let drift = agent_data.reputation as i32 - membership.synced_reputation as i32;
+
+    membership.sync(identity, &mut agent_data, clock.unix_timestamp);
+
+    emit!(ReputationSynced {
+        identity: identity.key(),
+        agent: agent.key(),
+        drift,
+        reputation: identity.reputation,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Reputation synced: drift {}, now {}", drift, identity.reputation);
+
+    Ok(())
+}
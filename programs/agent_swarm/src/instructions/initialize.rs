@@ -1,15 +1,20 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::constants::*;
-use crate::errors::SwarmError;
+use crate::errors::{FailedCheck, SwarmError};
+use crate::pda::find_vault_authority_address;
+use crate::require_ctx;
+use crate::ExecutionMode;
+use crate::events::SwarmInitialized;
 
 #[derive(Accounts)]
+#[instruction(swarm_id: u64)]
 pub struct Initialize<'info> {
     #[account(
         init,
         payer = authority,
         space = SwarmState::LEN,
-        seeds = [SWARM_SEED],
+        seeds = [SWARM_SEED, swarm_id.to_le_bytes().as_ref()],
         bump
     )]
     pub swarm_state: Account<'info, SwarmState>,
@@ -22,28 +27,41 @@ pub struct Initialize<'info> {
 
 pub fn initialize(
     ctx: Context<Initialize>,
+    swarm_id: u64,
     max_agents: u8,
     min_votes_required: u8,
     proposal_timeout: i64,
 ) -> Result<()> {
-    require!(
+    require_ctx!(
         max_agents >= MIN_AGENTS && max_agents <= MAX_AGENTS,
-        SwarmError::InvalidVoteCount
+        SwarmError::InvalidVoteCount,
+        FailedCheck::AgentCount,
+        if max_agents < MIN_AGENTS { MIN_AGENTS } else { MAX_AGENTS },
+        max_agents
     );
 
     // Integer majority: at least ceil(max_agents / 2)
-    require!(
-        min_votes_required >= ((max_agents as u16 + 1) / 2) as u8,
-        SwarmError::MinAgentsNotMet
+    let majority = (max_agents as u16).div_ceil(2);
+    require_ctx!(
+        min_votes_required as u16 >= majority,
+        SwarmError::MinAgentsNotMet,
+        FailedCheck::MinVotes,
+        majority,
+        min_votes_required
     );
 
-    require!(
+    require_ctx!(
         proposal_timeout >= MIN_PROPOSAL_TIMEOUT && proposal_timeout <= MAX_PROPOSAL_TIMEOUT,
-        SwarmError::InvalidProposalTimeout
+        SwarmError::InvalidProposalTimeout,
+        FailedCheck::ProposalTimeout,
+        proposal_timeout.clamp(MIN_PROPOSAL_TIMEOUT, MAX_PROPOSAL_TIMEOUT),
+        proposal_timeout
     );
 
+    let swarm_key = ctx.accounts.swarm_state.key();
     let swarm_state = &mut ctx.accounts.swarm_state;
     
+    swarm_state.swarm_id = swarm_id;
     swarm_state.authority = ctx.accounts.authority.key();
     swarm_state.max_agents = max_agents;
     swarm_state.active_agents = 0;
@@ -51,9 +69,60 @@ pub fn initialize(
     swarm_state.proposal_timeout = proposal_timeout;
     swarm_state.total_proposals = 0;
     swarm_state.executed_proposals = 0;
+    swarm_state.elo_blend_bps = 0;
+    swarm_state.execution_guard = false;
+    swarm_state.execution_mode = ExecutionMode::Open;
+    swarm_state.executor_window = DEFAULT_EXECUTOR_WINDOW;
+    swarm_state.required_capabilities = 0;
+    swarm_state.min_description_length = DEFAULT_MIN_DESCRIPTION_LENGTH;
+    swarm_state.require_typed_payloads = false;
+    swarm_state.voter_reward = 0;
+    swarm_state.voter_reward_mint = None;
+    swarm_state.enforce_exposure_limits = false;
+    swarm_state.slash_treasury_bps = DEFAULT_SLASH_TREASURY_BPS;
+    swarm_state.authority_last_active = Clock::get()?.unix_timestamp;
+    swarm_state.authority_inactivity_period = DEFAULT_AUTHORITY_INACTIVITY_PERIOD;
+    swarm_state.marketplace_fee_bps = 0;
+    swarm_state.rewards = RewardEpoch {
+        epoch_start: swarm_state.authority_last_active,
+        epoch_length: DEFAULT_REWARD_EPOCH_LENGTH,
+        ..RewardEpoch::default()
+    };
+    swarm_state.governance_mint = None;
+    swarm_state.ve_max_boost_power = 0;
+    swarm_state.emission = Emission::default();
+    swarm_state.sale_dispute_window = DEFAULT_SALE_DISPUTE_WINDOW;
+    (swarm_state.vault_authority, swarm_state.vault_authority_bump) = find_vault_authority_address(&swarm_key);
+    swarm_state.price_feeds = Vec::new();
+    swarm_state.min_health_factor_bps = DEFAULT_MIN_HEALTH_FACTOR_BPS;
+    swarm_state.max_execution_attempts = DEFAULT_MAX_EXECUTION_ATTEMPTS;
+    swarm_state.committee_proposal_types = 0;
+    swarm_state.congestion = ProposalCongestion {
+        threshold: DEFAULT_CONGESTION_THRESHOLD,
+        half_life: DEFAULT_CONGESTION_HALF_LIFE,
+        decayed_at: swarm_state.authority_last_active,
+        ..ProposalCongestion::default()
+    };
+    swarm_state.arb_report_bounty = 0;
+    swarm_state.version = SwarmState::VERSION;
     swarm_state.bump = ctx.bumps.swarm_state;
 
-    msg!("Agent Swarm initialized with max_agents: {}, min_votes: {}", max_agents, min_votes_required);
+    emit!(SwarmInitialized {
+        swarm: swarm_key,
+        swarm_id,
+        authority: swarm_state.authority,
+        max_agents,
+        min_votes_required,
+        proposal_timeout,
+        timestamp: swarm_state.authority_last_active,
+    });
+
+    msg!(
+        "Agent Swarm {} initialized with max_agents: {}, min_votes: {}",
+        swarm_id,
+        max_agents,
+        min_votes_required
+    );
 
     Ok(())
 }
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::{FailedCheck, SwarmError};
+use crate::events::{
+    InsuranceClaimPaid, InsurancePremiumSwept, TreasuryTransferIn, ACCOUNTING_SCHEMA_VERSION,
+    NATIVE_SOL_ASSET,
+};
+use crate::instructions::treasury::pay_lamports_from_treasury;
+use crate::payloads::InsurancePayload;
+use crate::{require_ctx, ProposalType};
+
+/// Sweep the insurance fund's share of the protocol fees the treasury has
+/// collected since the last sweep (permissionless)
+#[derive(Accounts)]
+pub struct FundInsurance<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, swarm_state.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED, swarm_state.key().as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+}
+
+pub fn fund_insurance(ctx: Context<FundInsurance>) -> Result<()> {
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    let premium = insurance_fund.sweep_premium(ctx.accounts.treasury.fees_collected)?;
+    if premium > 0 {
+        pay_lamports_from_treasury(
+            &mut ctx.accounts.treasury,
+            &insurance_fund.to_account_info(),
+            premium,
+            Pubkey::default(), // Not tied to a single proposal
+        )?;
+    }
+
+    emit!(InsurancePremiumSwept {
+        fund: insurance_fund.key(),
+        premium,
+        fees_swept: insurance_fund.fees_swept,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Insurance premium swept: {} lamports", premium);
+    Ok(())
+}
+
+/// Pay an executed `Insurance` claim from the fund to the treasury. The
+/// claimed proposal must be an executed `Strategy` of the same swarm whose
+/// outcome falls short of its balance checks by at least the fund's loss
+/// threshold, and the claim must cite an attestation made after that loss.
+pub fn pay_insurance_claim<'info>(
+    insurance_fund: &mut Account<'info, InsuranceFund>,
+    treasury: &mut Account<'info, Treasury>,
+    claimed_proposal: &AccountLoader<'info, Proposal>,
+    claimed_outcome: &Account<'info, Outcome>,
+    attestation: &Account<'info, Attestation>,
+    claim_proposal: Pubkey,
+    payload: InsurancePayload,
+) -> Result<()> {
+    let InsurancePayload::Claim { loss_proposal, lamports } = payload else {
+        return err!(SwarmError::InvalidPayload);
+    };
+    // A fund no `Terms` proposal has set up has no threshold to claim against
+    require!(insurance_fund.loss_threshold_bps > 0, SwarmError::InsuranceClaimNotEligible);
+    require_keys_eq!(claimed_proposal.key(), loss_proposal, SwarmError::InvalidPayload);
+    require_keys_eq!(claimed_outcome.proposal, loss_proposal, SwarmError::InvalidPayload);
+    {
+        let claimed = claimed_proposal.load()?;
+        require_keys_eq!(claimed.swarm, insurance_fund.swarm, SwarmError::SwarmMismatch);
+        require!(
+            claimed.proposal_type() == ProposalType::Strategy && claimed.is_executed(),
+            SwarmError::InsuranceClaimNotEligible
+        );
+    }
+    require!(
+        attestation.swarm == insurance_fund.swarm
+            && attestation.attested_at >= claimed_outcome.executed_at,
+        SwarmError::InvalidAttestation
+    );
+
+    let shortfall_bps = claimed_outcome.worst_shortfall_bps();
+    require_ctx!(
+        shortfall_bps >= insurance_fund.loss_threshold_bps as u64,
+        SwarmError::InsuranceLossBelowThreshold,
+        FailedCheck::InsuranceLoss,
+        insurance_fund.loss_threshold_bps,
+        shortfall_bps
+    );
+
+    let clock = Clock::get()?;
+    insurance_fund.record_claim(
+        InsuranceClaimRecord {
+            loss_proposal,
+            loss_executed_at: claimed_outcome.executed_at,
+            lamports,
+        },
+        clock.unix_timestamp,
+    )?;
+
+    let rent_floor = Rent::get()?.minimum_balance(InsuranceFund::LEN);
+    let available = insurance_fund.get_lamports().saturating_sub(rent_floor);
+    require!(lamports <= available, SwarmError::InsufficientInsuranceFunds);
+    insurance_fund.sub_lamports(lamports)?;
+    treasury.add_lamports(lamports)?;
+    treasury.total_deposited = treasury
+        .total_deposited
+        .checked_add(lamports)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+
+    emit!(TreasuryTransferIn {
+        schema_version: ACCOUNTING_SCHEMA_VERSION,
+        asset: NATIVE_SOL_ASSET,
+        amount: lamports,
+        from: insurance_fund.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(InsuranceClaimPaid {
+        fund: insurance_fund.key(),
+        proposal: claim_proposal,
+        loss_proposal,
+        attestation: attestation.key(),
+        shortfall_bps,
+        lamports,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Insurance claim paid: {} lamports for a {} bps shortfall", lamports, shortfall_bps);
+    Ok(())
+}
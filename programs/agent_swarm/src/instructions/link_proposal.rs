@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::ProposalLinked;
+use crate::instructions::committee::require_quorum;
+
+/// Proposer makes a proposal wait on a proposal in another swarm, so the
+/// two swarms only act together. Linking both ways makes execution joint:
+/// the first executes once the other is approved, the second once the
+/// first has executed. Only allowed before voting starts, so voters approve
+/// the link along with the proposal.
+#[derive(Accounts)]
+pub struct LinkProposal<'info> {
+    #[account(mut)]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    /// The proposer's agent, whose signing key must sign
+    #[account(
+        seeds = [AGENT_SEED, proposal.load()?.swarm.as_ref(), proposal.load()?.proposer.as_ref()],
+        bump = proposer_agent.load()?.bump,
+        constraint = proposer_agent.load()?.signing_key == proposer.key() @ SwarmError::Unauthorized
+    )]
+    pub proposer_agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        constraint = linked_proposal.load()?.swarm != proposal.load()?.swarm @ SwarmError::InvalidLinkedProposal
+    )]
+    pub linked_proposal: AccountLoader<'info, Proposal>,
+    
+    pub proposer: Signer<'info>,
+}
+
+pub fn link_proposal(ctx: Context<LinkProposal>) -> Result<()> {
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    let linked_proposal = ctx.accounts.linked_proposal.load()?;
+
+    require!(proposal.total_voters == 0, SwarmError::VotingAlreadyStarted);
+    require!(!proposal.is_executed(), SwarmError::ProposalAlreadyExecuted);
+
+    proposal.linked_proposal = ctx.accounts.linked_proposal.key();
+
+    emit!(ProposalLinked {
+        proposal: ctx.accounts.proposal.key(),
+        linked_proposal: proposal.linked_proposal,
+        linked_swarm: linked_proposal.swarm,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Proposal linked to {} in swarm {}", proposal.linked_proposal, linked_proposal.swarm);
+
+    Ok(())
+}
+
+/// Require `linked` to be executed, or approved and still executable under
+/// its own swarm's quorum. A linked proposal scoped to a committee only
+/// counts once it has executed.
+pub fn require_linked_approval(
+    linked: &Proposal,
+    linked_swarm_state: &Account<SwarmState>,
+    current_time: i64,
+) -> Result<()> {
+    require_keys_eq!(linked.swarm, linked_swarm_state.key(), SwarmError::SwarmMismatch);
+    if linked.is_executed() {
+        return Ok(());
+    }
+
+    require!(
+        !linked.is_failed()
+            && !linked.is_expired(current_time)
+            && linked.committee_id().is_none()
+            && linked.is_approved(),
+        SwarmError::LinkedProposalNotApproved
+    );
+    require_quorum(linked, linked_swarm_state, None)
+}
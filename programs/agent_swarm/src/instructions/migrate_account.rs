@@ -0,0 +1,492 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::SwarmError;
+use crate::events::AccountMigrated;
+use crate::marketplace::*;
+use crate::auction::{Auction, Bid};
+use crate::constants::{
+    DEFAULT_CONGESTION_HALF_LIFE, DEFAULT_CONGESTION_THRESHOLD, MAX_DESCRIPTION_LENGTH, MAX_PRICE_AGE,
+    MAX_PROPOSAL_TAGS, MAX_PROPOSAL_TAG_LENGTH,
+};
+
+/// Every account leads with its layout version, right after the
+/// discriminator, so it can be read without knowing the layout
+pub const ACCOUNT_VERSION_OFFSET: usize = 8;
+
+/// Rewrites an account body (the data after the discriminator) from layout
+/// version `from` into `from + 1`, growing it if the new layout is larger
+type Upgrade = fn(from: u8, body: &mut Vec<u8>) -> Result<()>;
+
+/// Upgrade for account types that have only ever had one layout
+fn no_upgrade(_from: u8, _body: &mut Vec<u8>) -> Result<()> {
+    err!(SwarmError::UnsupportedAccountVersion)
+}
+
+/// Length of `RewardEpoch` before v3 of `SwarmState` added its activity
+/// counters
+const V2_REWARD_EPOCH_LEN: usize = 5 * 8;
+
+/// Offset just past a Borsh `Option` at `offset` holding `len` bytes when set
+fn skip_option(offset: usize, len: usize, body: &[u8]) -> Result<usize> {
+    match body.get(offset) {
+        Some(0) => Ok(offset + 1),
+        Some(1) => Ok(offset + 1 + len),
+        _ => err!(SwarmError::UnsupportedAccountVersion),
+    }
+}
+
+fn upgrade_agent(from: u8, body: &mut Vec<u8>) -> Result<()> {
+    match from {
+        1 => widen_agent_counters(body),
+        2 => add_agent_decay_epoch(body),
+        3 => widen_agent_manifest(body),
+        4 => add_agent_signing_key(body),
+        5 => add_agent_pnl_stats(body),
+        _ => err!(SwarmError::UnsupportedAccountVersion),
+    }
+}
+
+/// Agent v1 -> v2: `proposals_created`, `votes_cast` and
+/// `successful_proposals` widen from u32 to u64, which absorbs the trailing
+/// padding the v1 layout needed
+fn widen_agent_counters(body: &mut Vec<u8>) -> Result<()> {
+    const COUNTERS: usize = 40;
+    const V1_PADDING: usize = 4;
+    require!(body.len() > COUNTERS + 12 + V1_PADDING, SwarmError::UnsupportedAccountVersion);
+
+    let widened: Vec<u8> = body[COUNTERS..COUNTERS + 12]
+        .chunks_exact(4)
+        .flat_map(|counter| {
+            (u32::from_le_bytes(counter.try_into().unwrap()) as u64).to_le_bytes()
+        })
+        .collect();
+    body.truncate(body.len() - V1_PADDING);
+    body.splice(COUNTERS..COUNTERS + 12, widened);
+    Ok(())
+}
+
+/// Agent v2 -> v3: `decayed_through` follows the activity counters,
+/// starting at 0
+fn add_agent_decay_epoch(body: &mut Vec<u8>) -> Result<()> {
+    const DECAYED_THROUGH: usize = 64;
+    require!(body.len() > DECAYED_THROUGH, SwarmError::UnsupportedAccountVersion);
+    body.splice(DECAYED_THROUGH..DECAYED_THROUGH, [0; 8]);
+    Ok(())
+}
+
+/// Agent v3 -> v4: `manifest` gains the byte of a format 2 header and is
+/// padded back to alignment; both trail the account
+fn widen_agent_manifest(body: &mut Vec<u8>) -> Result<()> {
+    body.extend_from_slice(&[0; 1 + 7]);
+    Ok(())
+}
+
+/// Agent v4 -> v5: `signing_key` and `guardian` follow `owner`; the
+/// signing key starts as the owner and no guardian is set
+fn add_agent_signing_key(body: &mut Vec<u8>) -> Result<()> {
+    const OWNER: usize = 136;
+    require!(body.len() > OWNER + 32, SwarmError::UnsupportedAccountVersion);
+    let owner = body[OWNER..OWNER + 32].to_vec();
+    body.splice(OWNER + 32..OWNER + 32, owner.into_iter().chain([0; 32]));
+    Ok(())
+}
+
+/// Agent v5 -> v6: the PnL attribution stats follow `decayed_through`,
+/// starting at 0
+fn add_agent_pnl_stats(body: &mut Vec<u8>) -> Result<()> {
+    const PNL_STATS: usize = 72;
+    require!(body.len() > PNL_STATS, SwarmError::UnsupportedAccountVersion);
+    body.splice(PNL_STATS..PNL_STATS, [0; 8 + 8 + 8]);
+    Ok(())
+}
+
+fn upgrade_swarm_state(from: u8, body: &mut Vec<u8>) -> Result<()> {
+    match from {
+        1 => upgrade_price_feeds(body),
+        2 => add_epoch_activity(body),
+        3 => add_committee_proposal_types(body),
+        4 => add_proposal_congestion(body),
+        5 => add_arb_report_bounty(body),
+        _ => err!(SwarmError::UnsupportedAccountVersion),
+    }
+}
+
+/// Offset of `SwarmState::rewards`, past the variable-size
+/// `voter_reward_mint`
+fn rewards_offset(body: &[u8]) -> Result<usize> {
+    let offset = skip_option(95, 32, body)?; // `version` through `voter_reward_mint`
+    Ok(offset + 1 + 2 + 8 + 8 + 2)
+}
+
+/// Offset of `SwarmState::price_feeds` given the size of `rewards`; the
+/// two `Option`s ahead of it vary in size
+fn price_feeds_offset(body: &[u8], reward_epoch_len: usize) -> Result<usize> {
+    let offset = skip_option(rewards_offset(body)? + reward_epoch_len, 32, body)?; // governance_mint
+    Ok(offset + 8 + Emission::LEN + 8 + 32 + 1)
+}
+
+/// Number of entries in the Borsh `Vec` whose length prefix is at `offset`
+fn vec_len(body: &[u8], offset: usize) -> Result<usize> {
+    body.get(offset..offset + 4)
+        .map(|len| u32::from_le_bytes(len.try_into().unwrap()) as usize)
+        .ok_or_else(|| error!(SwarmError::UnsupportedAccountVersion))
+}
+
+/// SwarmState v1 -> v2: each `PriceFeed { mint, feed_id, decimals }`
+/// becomes a Pyth-sourced feed with the old fixed staleness limit and no
+/// deviation limit
+fn upgrade_price_feeds(body: &mut Vec<u8>) -> Result<()> {
+    const V1_FEED_LEN: usize = 32 + 32 + 1;
+
+    let offset = price_feeds_offset(body, V2_REWARD_EPOCH_LEN)?;
+    let count = vec_len(body, offset)?;
+    let start = offset + 4;
+    let end = start + count * V1_FEED_LEN;
+    require!(body.len() >= end, SwarmError::UnsupportedAccountVersion);
+
+    let feeds: Vec<u8> = body[start..end]
+        .chunks_exact(V1_FEED_LEN)
+        .flat_map(|feed| {
+            let (mint, rest) = feed.split_at(32);
+            let (feed_id, decimals) = rest.split_at(32);
+            [mint, &[0], feed_id, decimals, &MAX_PRICE_AGE.to_le_bytes(), &0u16.to_le_bytes()].concat()
+        })
+        .collect();
+    body.splice(start..end, feeds);
+    body.resize(SwarmState::LEN - ACCOUNT_VERSION_OFFSET, 0);
+    Ok(())
+}
+
+/// SwarmState v2 -> v3: `RewardEpoch` gains its activity counters, zeroed
+/// for the epoch in progress
+fn add_epoch_activity(body: &mut Vec<u8>) -> Result<()> {
+    let offset = rewards_offset(body)? + V2_REWARD_EPOCH_LEN;
+    require!(body.len() > offset, SwarmError::UnsupportedAccountVersion);
+    body.splice(offset..offset, [0; RewardEpoch::LEN - V2_REWARD_EPOCH_LEN]);
+    body.resize(SwarmState::LEN - ACCOUNT_VERSION_OFFSET, 0);
+    Ok(())
+}
+
+/// SwarmState v3 -> v4: `committee_proposal_types` is inserted ahead of
+/// `bump`, with no committees
+fn add_committee_proposal_types(body: &mut Vec<u8>) -> Result<()> {
+    let offset = price_feeds_offset(body, RewardEpoch::LEN)?;
+    let offset = offset + 4 + vec_len(body, offset)? * PriceFeed::LEN + 2 + 1;
+    require!(body.len() > offset, SwarmError::UnsupportedAccountVersion);
+    body.splice(offset..offset, [0; 2]);
+    body.resize(SwarmState::LEN - ACCOUNT_VERSION_OFFSET, 0);
+    Ok(())
+}
+
+/// SwarmState v4 -> v5: `congestion` is inserted ahead of `bump` with the
+/// default threshold and half-life and no deposit
+fn add_proposal_congestion(body: &mut Vec<u8>) -> Result<()> {
+    let offset = congestion_offset(body)?;
+    let congestion = ProposalCongestion {
+        threshold: DEFAULT_CONGESTION_THRESHOLD,
+        half_life: DEFAULT_CONGESTION_HALF_LIFE,
+        decayed_at: Clock::get()?.unix_timestamp,
+        ..ProposalCongestion::default()
+    };
+    body.splice(offset..offset, congestion.try_to_vec()?);
+    body.resize(SwarmState::LEN - ACCOUNT_VERSION_OFFSET, 0);
+    Ok(())
+}
+
+/// SwarmState v5 -> v6: a zero `arb_report_bounty` is inserted ahead of
+/// `bump`, paying reputation only until the authority sets one
+fn add_arb_report_bounty(body: &mut Vec<u8>) -> Result<()> {
+    let offset = congestion_offset(body)? + ProposalCongestion::LEN;
+    require!(body.len() > offset, SwarmError::UnsupportedAccountVersion);
+    body.splice(offset..offset, 0u64.to_le_bytes());
+    body.resize(SwarmState::LEN - ACCOUNT_VERSION_OFFSET, 0);
+    Ok(())
+}
+
+/// Offset of `SwarmState::congestion`, past the variable-size `price_feeds`
+fn congestion_offset(body: &[u8]) -> Result<usize> {
+    let offset = price_feeds_offset(body, RewardEpoch::LEN)?;
+    let offset = offset + 4 + vec_len(body, offset)? * PriceFeed::LEN + 2 + 1 + 2;
+    require!(body.len() > offset, SwarmError::UnsupportedAccountVersion);
+    Ok(offset)
+}
+
+fn upgrade_risk_config(from: u8, body: &mut Vec<u8>) -> Result<()> {
+    match from {
+        1 => add_twap_guard(body),
+        2 => add_risk_engine_limits(body),
+        3 => add_circuit_breaker(body),
+        _ => err!(SwarmError::UnsupportedAccountVersion),
+    }
+}
+
+/// Offset of `RiskConfig::twap_guard`, past the variable-size limits and
+/// `allowed_mints`
+fn twap_guard_offset(body: &[u8]) -> Result<usize> {
+    let mut offset = 1; // version
+    offset = skip_option(offset, 8, body)?; // max_position_size
+    offset = skip_option(offset, 8, body)?; // max_daily_volume
+    offset = skip_option(offset, 2, body)?; // max_slippage_bps
+    Ok(offset + 4 + 32 * vec_len(body, offset)? + 8 + 8 + 8)
+}
+
+/// RiskConfig v1 -> v2: `twap_guard` is inserted ahead of `bump`, unset
+fn add_twap_guard(body: &mut Vec<u8>) -> Result<()> {
+    let offset = twap_guard_offset(body)?;
+    require!(body.len() > offset, SwarmError::UnsupportedAccountVersion);
+    body.insert(offset, 0);
+    body.resize(RiskConfig::LEN - ACCOUNT_VERSION_OFFSET, 0);
+    Ok(())
+}
+
+/// RiskConfig v2 -> v3: the concentration, venue and drawdown limits are
+/// inserted ahead of `bump`, all unset
+fn add_risk_engine_limits(body: &mut Vec<u8>) -> Result<()> {
+    let offset = skip_option(twap_guard_offset(body)?, TwapGuard::LEN, body)?;
+    require!(body.len() > offset, SwarmError::UnsupportedAccountVersion);
+    // `None`, an empty `venue_caps`, `None` and a zeroed tracker
+    body.splice(offset..offset, [0; 1 + 4 + 1 + DrawdownTracker::LEN]);
+    body.resize(RiskConfig::LEN - ACCOUNT_VERSION_OFFSET, 0);
+    Ok(())
+}
+
+/// RiskConfig v3 -> v4: the circuit breaker is inserted ahead of `bump`,
+/// unset, with no marks and trading running
+fn add_circuit_breaker(body: &mut Vec<u8>) -> Result<()> {
+    let mut offset = skip_option(twap_guard_offset(body)?, TwapGuard::LEN, body)?;
+    offset = skip_option(offset, 2, body)?; // max_concentration_bps
+    offset += 4 + VenueCap::LEN * vec_len(body, offset)?; // venue_caps
+    offset = skip_option(offset, 8, body)?; // max_drawdown
+    offset += DrawdownTracker::LEN;
+    require!(body.len() > offset, SwarmError::UnsupportedAccountVersion);
+    body.splice(offset..offset, [0; 1 + 4 + 1]);
+    body.resize(RiskConfig::LEN - ACCOUNT_VERSION_OFFSET, 0);
+    Ok(())
+}
+
+fn upgrade_proposal(from: u8, body: &mut Vec<u8>) -> Result<()> {
+    match from {
+        1 => add_proposal_tags(body),
+        _ => err!(SwarmError::UnsupportedAccountVersion),
+    }
+}
+
+/// Proposal v1 -> v2: empty `tags` follow `description`, ahead of the
+/// flags; the payload after the fixed fields moves along with them
+fn add_proposal_tags(body: &mut Vec<u8>) -> Result<()> {
+    const TAGS: usize = 424 + 32 * Proposal::MAX_VOTERS + MAX_DESCRIPTION_LENGTH;
+    require!(body.len() > TAGS, SwarmError::UnsupportedAccountVersion);
+    body.splice(TAGS..TAGS, [0; MAX_PROPOSAL_TAGS * MAX_PROPOSAL_TAG_LENGTH]);
+    Ok(())
+}
+
+fn upgrade_vote_record(from: u8, body: &mut Vec<u8>) -> Result<()> {
+    match from {
+        1 => add_vote_reasoning(body),
+        _ => err!(SwarmError::UnsupportedAccountVersion),
+    }
+}
+
+/// VoteRecord v1 -> v2: a zeroed `reasoning_hash` and empty excerpt are
+/// inserted ahead of `bump`; the reasoning of earlier votes was only logged
+fn add_vote_reasoning(body: &mut Vec<u8>) -> Result<()> {
+    const REASONING: usize = 1 + 32 + 32 + 1 + 4 + 8 + 1 + 1;
+    require!(body.len() > REASONING, SwarmError::UnsupportedAccountVersion);
+    body.splice(REASONING..REASONING, [0; 32 + 4]);
+    body.resize(VoteRecord::LEN - ACCOUNT_VERSION_OFFSET, 0);
+    Ok(())
+}
+
+fn upgrade_outcome(from: u8, body: &mut Vec<u8>) -> Result<()> {
+    match from {
+        1 => add_outcome_realized_pnl(body),
+        _ => err!(SwarmError::UnsupportedAccountVersion),
+    }
+}
+
+/// Outcome v1 -> v2: `realized_pnl` is inserted ahead of `bump`, unset
+fn add_outcome_realized_pnl(body: &mut Vec<u8>) -> Result<()> {
+    const METRICS: usize = 1 + 32 + 32 + 1;
+    let balance_deltas = METRICS + 4 + vec_len(body, METRICS)? + 8;
+    let offset = balance_deltas + 4 + BalanceDelta::LEN * vec_len(body, balance_deltas)?;
+    require!(body.len() > offset, SwarmError::UnsupportedAccountVersion);
+    body.insert(offset, 0);
+    body.resize(Outcome::LEN - ACCOUNT_VERSION_OFFSET, 0);
+    Ok(())
+}
+
+fn upgrade_listing(from: u8, body: &mut Vec<u8>) -> Result<()> {
+    match from {
+        1 => {
+            // v2 has room for a format 2 manifest; the layout is unchanged
+            body.resize(AgentListing::LEN - ACCOUNT_VERSION_OFFSET, 0);
+            Ok(())
+        }
+        _ => err!(SwarmError::UnsupportedAccountVersion),
+    }
+}
+
+fn upgrade_treasury(from: u8, body: &mut Vec<u8>) -> Result<()> {
+    match from {
+        1 => add_treasury_fees_collected(body),
+        _ => err!(SwarmError::UnsupportedAccountVersion),
+    }
+}
+
+/// Treasury v1 -> v2: `fees_collected` follows the totals, starting at 0 so
+/// only fees from then on fund the insurance fund
+fn add_treasury_fees_collected(body: &mut Vec<u8>) -> Result<()> {
+    const FEES_COLLECTED: usize = 49;
+    require!(body.len() > FEES_COLLECTED, SwarmError::UnsupportedAccountVersion);
+    body.splice(FEES_COLLECTED..FEES_COLLECTED, [0; 8]);
+    Ok(())
+}
+
+/// Current layout version and upgrade step of the account type with
+/// `discriminator`. Bumping an account's `VERSION` means registering the
+/// step from the previous layout here.
+fn schema(discriminator: &[u8]) -> Option<(u8, Upgrade)> {
+    macro_rules! schemas {
+        ($($account:ty => $upgrade:expr),* $(,)?) => {
+            $(
+                if discriminator == <$account>::DISCRIMINATOR {
+                    return Some((<$account>::VERSION, $upgrade));
+                }
+            )*
+        };
+    }
+
+    schemas!(
+        SwarmState => upgrade_swarm_state,
+        EpochRewards => no_upgrade,
+        EpochStats => no_upgrade,
+        Agent => upgrade_agent,
+        AgentIdentity => no_upgrade,
+        Membership => no_upgrade,
+        BadgeConfig => no_upgrade,
+        AgentBadge => no_upgrade,
+        Attestation => no_upgrade,
+        AgentRegistry => no_upgrade,
+        CustomAgentType => no_upgrade,
+        TwapTracker => no_upgrade,
+        Proposal => upgrade_proposal,
+        ProposalPage => no_upgrade,
+        ExecutionQueue => no_upgrade,
+        RebalanceSchedule => no_upgrade,
+        StrategyConfig => no_upgrade,
+        Portfolio => no_upgrade,
+        StandingOrder => no_upgrade,
+        DcaSchedule => no_upgrade,
+        ArbReport => no_upgrade,
+        HarvestConfig => no_upgrade,
+        InsuranceFund => no_upgrade,
+        Task => no_upgrade,
+        Coalition => no_upgrade,
+        CoalitionBallot => no_upgrade,
+        Committee => no_upgrade,
+        PayloadBuffer => no_upgrade,
+        RandomnessRequest => no_upgrade,
+        Outcome => upgrade_outcome,
+        OutcomeArchive => no_upgrade,
+        VoteRecord => upgrade_vote_record,
+        EloMatch => no_upgrade,
+        Endorsement => no_upgrade,
+        Bounty => no_upgrade,
+        PendingExposure => no_upgrade,
+        ProgramWhitelist => no_upgrade,
+        RiskConfig => upgrade_risk_config,
+        AuditLog => no_upgrade,
+        AgentBond => no_upgrade,
+        SlashRecord => no_upgrade,
+        CrossChainEmitter => no_upgrade,
+        VoteEscrow => no_upgrade,
+        Treasury => upgrade_treasury,
+        SpendTracker => no_upgrade,
+        AgentMarketplace => no_upgrade,
+        ListingIndex => no_upgrade,
+        Purchase => no_upgrade,
+        Rating => no_upgrade,
+        ListingVersion => no_upgrade,
+        Review => no_upgrade,
+        Subscription => no_upgrade,
+        AgentListing => upgrade_listing,
+        Auction => no_upgrade,
+        Bid => no_upgrade,
+    );
+    None
+}
+
+/// Upgrade any program account to its current layout (permissionless; the
+/// payer covers extra rent). Accounts from before swarms were seeded by a
+/// swarm id sit at addresses the program no longer derives and are re-seeded
+/// instead, as described in ARCHITECTURE.md.
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: any account owned by this program; its type is identified by
+    /// its discriminator and its layout by the version byte
+    #[account(mut, owner = crate::ID @ SwarmError::UnknownAccountType)]
+    pub account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account = ctx.accounts.account.to_account_info();
+
+    let (from_version, to_version, mut body) = {
+        let data = account.try_borrow_data()?;
+        require!(data.len() > ACCOUNT_VERSION_OFFSET, SwarmError::UnknownAccountType);
+        let (version, upgrade) =
+            schema(&data[..ACCOUNT_VERSION_OFFSET]).ok_or(SwarmError::UnknownAccountType)?;
+
+        let from_version = data[ACCOUNT_VERSION_OFFSET];
+        require!(from_version < version, SwarmError::AccountUpToDate);
+
+        let mut body = data[ACCOUNT_VERSION_OFFSET..].to_vec();
+        for step in from_version..version {
+            upgrade(step, &mut body)?;
+        }
+        body[0] = version;
+        (from_version, version, body)
+    };
+
+    // Newer layouts only ever grow an account
+    let new_len = ACCOUNT_VERSION_OFFSET + body.len();
+    if new_len > account.data_len() {
+        let shortfall = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(account.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: account.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        account.realloc(new_len, false)?;
+    } else {
+        body.resize(account.data_len() - ACCOUNT_VERSION_OFFSET, 0);
+    }
+    account.try_borrow_mut_data()?[ACCOUNT_VERSION_OFFSET..].copy_from_slice(&body);
+
+    emit!(AccountMigrated {
+        account: account.key(),
+        from_version,
+        to_version,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Account {} migrated from v{} to v{}", account.key(), from_version, to_version);
+
+    Ok(())
+}
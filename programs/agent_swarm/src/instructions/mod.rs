@@ -1,15 +1,101 @@
 pub mod initialize;
 pub mod register_agent;
+pub mod rotate_owner_key;
+pub mod custom_agent_type;
 pub mod create_proposal;
+pub mod payload_buffer;
 pub mod vote_proposal;
 pub mod execute_proposal;
+pub mod schedule_execution;
+pub mod rebalance_schedule;
+pub mod standing_order;
+pub mod dca;
+pub mod arb_report;
+pub mod harvest;
+pub mod insurance;
+pub mod task;
+pub mod coalition;
+pub mod committee;
+pub mod link_proposal;
+pub mod identity;
+pub mod badge;
+pub mod attestation;
 pub mod update_reputation;
 pub mod record_outcome;
+pub mod settle_vote;
+pub mod settle_elo_match;
+pub mod update_swarm_config;
+pub mod assign_executor;
+pub mod randomness;
+pub mod endorse_proposal;
+pub mod treasury;
+pub mod set_sabbatical;
+pub mod claim_voting_rewards;
+pub mod bounty;
+pub mod exposure;
+pub mod bond;
+pub mod circuit_breaker;
+pub mod slash_agent;
+pub mod cross_chain;
+pub mod rewards;
+pub mod vote_escrow;
+pub mod governance_token;
+pub mod price_feed;
+pub mod twap;
+pub mod execution_failure;
+pub mod program_whitelist;
+pub mod risk_config;
+pub mod audit_log;
+pub mod outcome_archive;
+pub mod migrate_account;
 
 pub use initialize::*;
 pub use register_agent::*;
+pub use rotate_owner_key::*;
+pub use custom_agent_type::*;
 pub use create_proposal::*;
+pub use payload_buffer::*;
 pub use vote_proposal::*;
 pub use execute_proposal::*;
+pub use schedule_execution::*;
+pub use rebalance_schedule::*;
+pub use standing_order::*;
+pub use dca::*;
+pub use arb_report::*;
+pub use harvest::*;
+pub use insurance::*;
+pub use task::*;
+pub use coalition::*;
+pub use committee::*;
+pub use link_proposal::*;
+pub use identity::*;
+pub use badge::*;
+pub use attestation::*;
 pub use update_reputation::*;
 pub use record_outcome::*;
+pub use settle_vote::*;
+pub use settle_elo_match::*;
+pub use update_swarm_config::*;
+pub use assign_executor::*;
+pub use randomness::*;
+pub use endorse_proposal::*;
+pub use treasury::*;
+pub use set_sabbatical::*;
+pub use claim_voting_rewards::*;
+pub use bounty::*;
+pub use exposure::*;
+pub use bond::*;
+pub use circuit_breaker::*;
+pub use slash_agent::*;
+pub use cross_chain::*;
+pub use rewards::*;
+pub use vote_escrow::*;
+pub use governance_token::*;
+pub use price_feed::*;
+pub use twap::*;
+pub use execution_failure::*;
+pub use program_whitelist::*;
+pub use risk_config::*;
+pub use audit_log::*;
+pub use outcome_archive::*;
+pub use migrate_account::*;
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::{OutcomeArchived, SwarmAccountCreated};
+
+/// Create the empty outcome archive (authority only)
+#[derive(Accounts)]
+pub struct InitializeOutcomeArchive<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = OutcomeArchive::LEN,
+        seeds = [OUTCOME_ARCHIVE_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub outcome_archive: Account<'info, OutcomeArchive>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == swarm_state.authority @ SwarmError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_outcome_archive(ctx: Context<InitializeOutcomeArchive>) -> Result<()> {
+    let outcome_archive = &mut ctx.accounts.outcome_archive;
+    outcome_archive.root = OutcomeArchive::empty_root();
+    outcome_archive.leaf_count = 0;
+    outcome_archive.frontier = [[0; 32]; OUTCOME_TREE_DEPTH];
+    outcome_archive.version = OutcomeArchive::VERSION;
+    outcome_archive.bump = ctx.bumps.outcome_archive;
+
+    emit!(SwarmAccountCreated {
+        account: outcome_archive.key(),
+        created_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Outcome archive initialized");
+    Ok(())
+}
+
+/// Fold an outcome past its retention period into the archive and close its
+/// account, refunding the rent to the executor that recorded it. Votes,
+/// bounties and endorsements settle against the account, so it is kept
+/// until they all have. Permissionless.
+#[derive(Accounts)]
+pub struct ArchiveOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [OUTCOME_ARCHIVE_SEED, proposal.load()?.swarm.as_ref()],
+        bump = outcome_archive.bump
+    )]
+    pub outcome_archive: Account<'info, OutcomeArchive>,
+    
+    /// Marked so the outcome cannot be recorded again
+    #[account(mut)]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        mut,
+        close = executed_by,
+        seeds = [OUTCOME_SEED, proposal.key().as_ref()],
+        bump = outcome.bump
+    )]
+    pub outcome: Account<'info, Outcome>,
+    
+    /// CHECK: rent recipient, pinned to the outcome's executor
+    #[account(mut, address = outcome.executed_by)]
+    pub executed_by: UncheckedAccount<'info>,
+}
+
+pub fn archive_outcome(ctx: Context<ArchiveOutcome>) -> Result<()> {
+    let outcome = &ctx.accounts.outcome;
+    let clock = Clock::get()?;
+
+    require!(outcome.is_archivable(clock.unix_timestamp), SwarmError::OutcomeRetained);
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    require!(proposal.is_outcome_settled(), SwarmError::OutcomeUnsettled);
+
+    let outcome_archive = &mut ctx.accounts.outcome_archive;
+    let leaf_index = outcome_archive.append(outcome.leaf()?)?;
+    proposal.outcome_archived = 1;
+
+    emit!(OutcomeArchived {
+        proposal: outcome.proposal,
+        executed_by: outcome.executed_by,
+        success: outcome.success,
+        metrics: outcome.metrics.clone(),
+        executed_at: outcome.executed_at,
+        balance_deltas: outcome.balance_deltas.clone(),
+        realized_pnl: outcome.realized_pnl,
+        bump: outcome.bump,
+        leaf_index,
+        root: outcome_archive.root,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Outcome archived at leaf {}", leaf_index);
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::{PayloadBufferClosed, PayloadBufferWritten};
+
+/// Start staging the payload of a hash-committed proposal. Anyone may stage
+/// one; `execute_proposal` only accepts the executor's own buffer.
+#[derive(Accounts)]
+#[instruction(chunk: Vec<u8>)]
+pub struct CreatePayloadBuffer<'info> {
+    #[account(
+        constraint = proposal.load()?.is_data_committed() @ SwarmError::PayloadNotCommitted,
+        constraint = !proposal.load()?.is_executed() @ SwarmError::ProposalAlreadyExecuted
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = PayloadBuffer::space(chunk.len()),
+        seeds = [PAYLOAD_BUFFER_SEED, proposal.key().as_ref(), owner.key().as_ref()],
+        bump,
+        constraint = chunk.len() <= MAX_COMMITTED_PAYLOAD_LENGTH @ SwarmError::ProposalDataTooLong
+    )]
+    pub payload_buffer: Account<'info, PayloadBuffer>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Append a chunk to a staged payload, growing the buffer by its size
+#[derive(Accounts)]
+#[instruction(chunk: Vec<u8>)]
+pub struct ExtendPayloadBuffer<'info> {
+    #[account(
+        mut,
+        seeds = [PAYLOAD_BUFFER_SEED, payload_buffer.proposal.as_ref(), owner.key().as_ref()],
+        bump = payload_buffer.bump,
+        constraint = payload_buffer.data.len() + chunk.len()
+            <= MAX_COMMITTED_PAYLOAD_LENGTH @ SwarmError::ProposalDataTooLong,
+        realloc = PayloadBuffer::space(payload_buffer.data.len() + chunk.len()),
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub payload_buffer: Account<'info, PayloadBuffer>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Owner reclaims the rent of a buffer that was never executed
+#[derive(Accounts)]
+pub struct ClosePayloadBuffer<'info> {
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ SwarmError::Unauthorized
+    )]
+    pub payload_buffer: Account<'info, PayloadBuffer>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn create_payload_buffer(ctx: Context<CreatePayloadBuffer>, chunk: Vec<u8>) -> Result<()> {
+    let payload_buffer = &mut ctx.accounts.payload_buffer;
+    payload_buffer.proposal = ctx.accounts.proposal.key();
+    payload_buffer.owner = ctx.accounts.owner.key();
+    payload_buffer.data = chunk;
+    payload_buffer.version = PayloadBuffer::VERSION;
+    payload_buffer.bump = ctx.bumps.payload_buffer;
+
+    emit!(PayloadBufferWritten {
+        buffer: payload_buffer.key(),
+        proposal: payload_buffer.proposal,
+        owner: payload_buffer.owner,
+        data_len: payload_buffer.data.len() as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Payload buffer staged with {} bytes", payload_buffer.data.len());
+    Ok(())
+}
+
+pub fn extend_payload_buffer(ctx: Context<ExtendPayloadBuffer>, chunk: Vec<u8>) -> Result<()> {
+    let payload_buffer = &mut ctx.accounts.payload_buffer;
+    payload_buffer.data.extend_from_slice(&chunk);
+
+    emit!(PayloadBufferWritten {
+        buffer: payload_buffer.key(),
+        proposal: payload_buffer.proposal,
+        owner: payload_buffer.owner,
+        data_len: payload_buffer.data.len() as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Payload buffer extended to {} bytes", payload_buffer.data.len());
+    Ok(())
+}
+
+pub fn close_payload_buffer(ctx: Context<ClosePayloadBuffer>) -> Result<()> {
+    let payload_buffer = &ctx.accounts.payload_buffer;
+
+    emit!(PayloadBufferClosed {
+        buffer: payload_buffer.key(),
+        proposal: payload_buffer.proposal,
+        owner: payload_buffer.owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Payload buffer {} closed", payload_buffer.key());
+    Ok(())
+}
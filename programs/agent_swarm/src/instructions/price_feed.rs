@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::PriceFeedSet;
+use crate::oracle::OracleSource;
+
+/// Register or remove the Pyth or Switchboard feed trades in `mint` are
+/// checked against (authority only)
+#[derive(Accounts)]
+pub struct SetPriceFeed<'info> {
+    #[account(
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    #[account(constraint = authority.key() == swarm_state.authority @ SwarmError::Unauthorized)]
+    pub authority: Signer<'info>,
+}
+
+pub fn set_price_feed(
+    ctx: Context<SetPriceFeed>,
+    source: Option<OracleSource>,
+    max_age: i64,
+    max_deviation_bps: u16,
+) -> Result<()> {
+    let swarm_state = &mut ctx.accounts.swarm_state;
+    let mint = ctx.accounts.mint.key();
+    swarm_state.price_feeds.retain(|feed| feed.mint != mint);
+
+    if let Some(source) = source {
+        require!(
+            swarm_state.price_feeds.len() < MAX_PRICE_FEEDS,
+            SwarmError::InvalidConfig
+        );
+        require!(
+            max_age > 0 && max_age <= MAX_FEED_PRICE_AGE && max_deviation_bps <= BPS_DENOMINATOR,
+            SwarmError::InvalidConfig
+        );
+        swarm_state.price_feeds.push(PriceFeed {
+            mint,
+            source,
+            decimals: ctx.accounts.mint.decimals,
+            max_age,
+            max_deviation_bps,
+        });
+        msg!("Price feed for {} registered", mint);
+    } else {
+        msg!("Price feed for {} removed", mint);
+    }
+
+    swarm_state.authority_last_active = Clock::get()?.unix_timestamp;
+
+    emit!(PriceFeedSet {
+        mint,
+        source,
+        max_age,
+        max_deviation_bps,
+        timestamp: swarm_state.authority_last_active,
+    });
+    Ok(())
+}
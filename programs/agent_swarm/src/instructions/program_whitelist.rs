@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::SwarmAccountCreated;
+
+/// Create the empty program whitelist. Entries are only added or removed by
+/// executed `RiskLimit` proposals.
+#[derive(Accounts)]
+pub struct InitializeProgramWhitelist<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = ProgramWhitelist::LEN,
+        seeds = [PROGRAM_WHITELIST_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub program_whitelist: Account<'info, ProgramWhitelist>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == swarm_state.authority @ SwarmError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_program_whitelist(ctx: Context<InitializeProgramWhitelist>) -> Result<()> {
+    let program_whitelist = &mut ctx.accounts.program_whitelist;
+    program_whitelist.programs = Vec::new();
+    program_whitelist.version = ProgramWhitelist::VERSION;
+    program_whitelist.bump = ctx.bumps.program_whitelist;
+
+    emit!(SwarmAccountCreated {
+        account: program_whitelist.key(),
+        created_by: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Program whitelist initialized");
+    Ok(())
+}
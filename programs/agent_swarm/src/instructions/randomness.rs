@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::sysvar::slot_hashes::ID as SLOT_HASHES_SYSVAR_ID;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::{RandomnessCommitted, RandomnessRevealed};
+
+/// Commit to a secret that, once revealed, seeds a proposal's randomness
+/// (any active agent, one pending commit per proposal)
+#[derive(Accounts)]
+pub struct CommitRandomness<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        constraint = proposal.load()?.swarm == swarm_state.key() @ SwarmError::SwarmMismatch,
+        constraint = proposal.load()?.randomness().is_none() @ SwarmError::RandomnessAlreadyRevealed
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == committer.key() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.is_active() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        init,
+        payer = committer,
+        space = RandomnessRequest::LEN,
+        seeds = [RANDOMNESS_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub randomness_request: Account<'info, RandomnessRequest>,
+    
+    #[account(mut)]
+    pub committer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Reveal the committed secret after the target slot, writing the
+/// proposal's randomness and refunding the request's rent
+#[derive(Accounts)]
+pub struct RevealRandomness<'info> {
+    #[account(mut)]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        mut,
+        close = committer,
+        seeds = [RANDOMNESS_SEED, proposal.key().as_ref()],
+        bump = randomness_request.bump,
+        has_one = committer @ SwarmError::Unauthorized
+    )]
+    pub randomness_request: Account<'info, RandomnessRequest>,
+    
+    #[account(mut)]
+    pub committer: Signer<'info>,
+    
+    /// CHECK: Address-constrained to the slot hashes sysvar
+    #[account(address = SLOT_HASHES_SYSVAR_ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+/// Close a commit that was never revealed in time so another can be made
+/// (permissionless; rent returns to the committer)
+#[derive(Accounts)]
+pub struct ExpireRandomness<'info> {
+    #[account(
+        mut,
+        close = committer,
+        has_one = committer @ SwarmError::Unauthorized
+    )]
+    pub randomness_request: Account<'info, RandomnessRequest>,
+    
+    /// CHECK: Rent recipient, checked against the request
+    #[account(mut)]
+    pub committer: UncheckedAccount<'info>,
+}
+
+pub fn commit_randomness(ctx: Context<CommitRandomness>, commitment: [u8; 32]) -> Result<()> {
+    let clock = Clock::get()?;
+    let randomness_request = &mut ctx.accounts.randomness_request;
+    randomness_request.proposal = ctx.accounts.proposal.key();
+    randomness_request.committer = ctx.accounts.committer.key();
+    randomness_request.commitment = commitment;
+    randomness_request.target_slot = clock.slot + RANDOMNESS_DELAY_SLOTS;
+    randomness_request.version = RandomnessRequest::VERSION;
+    randomness_request.bump = ctx.bumps.randomness_request;
+
+    emit!(RandomnessCommitted {
+        proposal: randomness_request.proposal,
+        committer: randomness_request.committer,
+        target_slot: randomness_request.target_slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Randomness committed for slot {}", randomness_request.target_slot);
+    Ok(())
+}
+
+pub fn reveal_randomness(ctx: Context<RevealRandomness>, secret: [u8; 32]) -> Result<()> {
+    let randomness_request = &ctx.accounts.randomness_request;
+    let clock = Clock::get()?;
+
+    require!(
+        keccak::hash(&secret).to_bytes() == randomness_request.commitment,
+        SwarmError::RandomnessMismatch
+    );
+    require!(clock.slot > randomness_request.target_slot, SwarmError::RandomnessNotReady);
+    require!(
+        clock.slot <= randomness_request.target_slot + RANDOMNESS_REVEAL_SLOTS,
+        SwarmError::RandomnessExpired
+    );
+    let slot_hash = slot_hash(&ctx.accounts.slot_hashes, randomness_request.target_slot)?;
+
+    let proposal_key = ctx.accounts.proposal.key();
+    let randomness = keccak::hashv(&[&secret, &slot_hash, proposal_key.as_ref()]).to_bytes();
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    require!(proposal.randomness().is_none(), SwarmError::RandomnessAlreadyRevealed);
+    proposal.randomness = randomness;
+    proposal.has_randomness = 1;
+
+    emit!(RandomnessRevealed {
+        proposal: proposal_key,
+        randomness,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Randomness revealed for proposal {}", proposal_key);
+    Ok(())
+}
+
+pub fn expire_randomness(ctx: Context<ExpireRandomness>) -> Result<()> {
+    let randomness_request = &ctx.accounts.randomness_request;
+    require!(
+        Clock::get()?.slot > randomness_request.target_slot + RANDOMNESS_REVEAL_SLOTS,
+        SwarmError::RandomnessRevealOpen
+    );
+
+    msg!("Randomness commit for proposal {} expired", randomness_request.proposal);
+    Ok(())
+}
+
+/// Hash of `slot` from the slot hashes sysvar, read in place: a u64 count,
+/// then `(slot, hash)` pairs from newest to oldest
+fn slot_hash(slot_hashes: &AccountInfo, slot: u64) -> Result<[u8; 32]> {
+    const ENTRY_LEN: usize = 8 + 32;
+    let data = slot_hashes.try_borrow_data()?;
+    let count = data
+        .get(..8)
+        .map(|len| u64::from_le_bytes(len.try_into().unwrap()) as usize)
+        .ok_or(SwarmError::RandomnessExpired)?;
+
+    data.get(8..)
+        .unwrap_or_default()
+        .chunks_exact(ENTRY_LEN)
+        .take(count)
+        .find(|entry| u64::from_le_bytes(entry[..8].try_into().unwrap()) == slot)
+        .map(|entry| entry[8..].try_into().unwrap())
+        .ok_or_else(|| error!(SwarmError::RandomnessExpired))
+}
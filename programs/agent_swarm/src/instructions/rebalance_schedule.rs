@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::RebalanceTriggered;
+use crate::ProposalType;
+
+/// Description given to proposals created by `trigger_rebalance`
+const SCHEDULED_REBALANCE_DESCRIPTION: &str = "Scheduled rebalance";
+
+/// Create the next `Rebalance` proposal of a due schedule (permissionless;
+/// the cranker pays its rent and is recorded as its proposer). The schedule
+/// was approved by vote, so the proposal is fast-tracked: it may execute
+/// without votes of its own.
+#[derive(Accounts)]
+pub struct TriggerRebalance<'info> {
+    #[account(
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        seeds = [REBALANCE_SCHEDULE_SEED, swarm_state.key().as_ref()],
+        bump = rebalance_schedule.bump
+    )]
+    pub rebalance_schedule: Account<'info, RebalanceSchedule>,
+    
+    #[account(
+        init,
+        payer = cranker,
+        space = Proposal::space(rebalance_schedule.payload().try_to_vec()?.len()),
+        seeds = [
+            PROPOSAL_SEED,
+            swarm_state.key().as_ref(),
+            swarm_state.total_proposals.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = ProposalPage::LEN,
+        seeds = [
+            PROPOSAL_PAGE_SEED,
+            swarm_state.key().as_ref(),
+            &(swarm_state.total_proposals / PROPOSAL_PAGE_SIZE).to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal_page: Account<'info, ProposalPage>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn trigger_rebalance(ctx: Context<TriggerRebalance>) -> Result<()> {
+    let clock = Clock::get()?;
+    let swarm_key = ctx.accounts.swarm_state.key();
+    let swarm_state = &mut ctx.accounts.swarm_state;
+    let rebalance_schedule = &mut ctx.accounts.rebalance_schedule;
+    require!(rebalance_schedule.is_due(clock.unix_timestamp), SwarmError::RebalanceNotDue);
+
+    let data = rebalance_schedule.payload().try_to_vec()?;
+    Proposal::write_data(&ctx.accounts.proposal.to_account_info(), 0, &data)?;
+
+    let mut proposal = ctx.accounts.proposal.load_init()?;
+    proposal.swarm = swarm_key;
+    proposal.id = swarm_state.total_proposals;
+    proposal.proposer = ctx.accounts.cranker.key();
+    proposal.proposal_type_tag = ProposalType::Rebalance.index() as u8;
+    proposal.data_len = data.len() as u16;
+    proposal.data_written = data.len() as u16;
+    proposal.set_description(SCHEDULED_REBALANCE_DESCRIPTION);
+    proposal.fast_tracked = 1;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.expires_at = clock.unix_timestamp
+        .checked_add(swarm_state.proposal_timeout)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    proposal.version = Proposal::VERSION;
+    proposal.bump = ctx.bumps.proposal;
+
+    let proposal_page = &mut ctx.accounts.proposal_page;
+    if proposal_page.proposals.is_empty() {
+        proposal_page.swarm = swarm_key;
+        proposal_page.page = proposal.id / PROPOSAL_PAGE_SIZE;
+        proposal_page.version = ProposalPage::VERSION;
+        proposal_page.bump = ctx.bumps.proposal_page;
+    }
+    proposal_page.push(ctx.accounts.proposal.key(), proposal.id);
+
+    swarm_state.total_proposals = swarm_state
+        .total_proposals
+        .checked_add(1)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    swarm_state.rewards.proposals = swarm_state.rewards.proposals.saturating_add(1);
+
+    rebalance_schedule.last_proposal = ctx.accounts.proposal.key();
+    rebalance_schedule.next_run_at = clock.unix_timestamp
+        .checked_add(rebalance_schedule.interval)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+
+    emit!(RebalanceTriggered {
+        schedule: rebalance_schedule.key(),
+        proposal: rebalance_schedule.last_proposal,
+        next_run_at: rebalance_schedule.next_run_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Scheduled rebalance proposal {} created", proposal.id);
+    Ok(())
+}
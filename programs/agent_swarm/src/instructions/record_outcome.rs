@@ -1,20 +1,40 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::constants::*;
-use crate::errors::SwarmError;
+use crate::errors::{FailedCheck, SwarmError};
+use crate::require_ctx;
+use crate::events::OutcomeRecorded;
 
 #[derive(Accounts)]
 pub struct RecordOutcome<'info> {
     #[account(
-        seeds = [AGENT_SEED, executor.key().as_ref()],
-        bump = agent.bump
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
     )]
-    pub agent: Account<'info, Agent>,
+    pub swarm_state: Account<'info, SwarmState>,
     
     #[account(
-        constraint = proposal.executed @ SwarmError::VotingInProgress
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == executor.key() @ SwarmError::Unauthorized
     )]
-    pub proposal: Account<'info, Proposal>,
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        constraint = proposal.load()?.swarm == swarm_state.key() @ SwarmError::SwarmMismatch,
+        constraint = proposal.load()?.is_executed() @ SwarmError::VotingInProgress,
+        constraint = !proposal.load()?.is_outcome_archived() @ SwarmError::OutcomeArchived
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    /// Credited with the successful proposal
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), proposal.load()?.proposer.as_ref()],
+        bump = proposer_agent.load()?.bump
+    )]
+    pub proposer_agent: AccountLoader<'info, Agent>,
     
     #[account(
         init,
@@ -36,9 +56,12 @@ pub fn record_outcome(
     success: bool,
     metrics: Vec<u8>,
 ) -> Result<()> {
-    require!(
+    require_ctx!(
         metrics.len() <= MAX_OUTCOME_METRICS_LENGTH,
-        SwarmError::ProposalDataTooLong
+        SwarmError::ProposalDataTooLong,
+        FailedCheck::MetricsLength,
+        MAX_OUTCOME_METRICS_LENGTH,
+        metrics.len()
     );
 
     let outcome = &mut ctx.accounts.outcome;
@@ -49,8 +72,24 @@ pub fn record_outcome(
     outcome.success = success;
     outcome.metrics = metrics;
     outcome.executed_at = clock.unix_timestamp;
+    outcome.balance_deltas = Vec::new();
+    outcome.realized_pnl = None;
+    outcome.version = Outcome::VERSION;
     outcome.bump = ctx.bumps.outcome;
 
+    if success {
+        ctx.accounts
+            .proposer_agent
+            .load_mut()?
+            .credit_successful_proposal(&mut ctx.accounts.swarm_state.rewards);
+    }
+
+    emit!(OutcomeRecorded {
+        proposal: outcome.proposal,
+        executed_by: outcome.executed_by,
+        success,
+        timestamp: clock.unix_timestamp,
+    });
     msg!("Outcome recorded: success={}", success);
 
     Ok(())
@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::constants::*;
-use crate::errors::SwarmError;
+use crate::errors::{FailedCheck, SwarmError};
+use crate::require_ctx;
+use crate::manifest::check_admission;
 use crate::AgentType;
+use crate::events::{AgentDeregistered, AgentRegistered};
 
 #[derive(Accounts)]
 #[instruction(agent_type: AgentType, name: String)]
@@ -14,10 +17,30 @@ pub struct RegisterAgent<'info> {
         init,
         payer = owner,
         space = Agent::LEN,
-        seeds = [AGENT_SEED, owner.key().as_ref()],
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), owner.key().as_ref()],
         bump
     )]
-    pub agent: Account<'info, Agent>,
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = AgentRegistry::LEN,
+        seeds = [AGENT_REGISTRY_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
+    /// Required for `AgentType::Custom`; only registered ids are accepted
+    #[account(
+        seeds = [
+            CUSTOM_AGENT_TYPE_SEED,
+            swarm_state.key().as_ref(),
+            &[agent_type.custom_id().unwrap_or_default()]
+        ],
+        bump = custom_agent_type.bump
+    )]
+    pub custom_agent_type: Option<Account<'info, CustomAgentType>>,
     
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -25,41 +48,130 @@ pub struct RegisterAgent<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Owner retires their agent: it stops counting towards quorum and leaves
+/// the registry. The account stays so its votes and stakes still settle.
+#[derive(Accounts)]
+pub struct DeregisterAgent<'info> {
+    #[account(
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == owner.key() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.is_active() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        mut,
+        seeds = [AGENT_REGISTRY_SEED, swarm_state.key().as_ref()],
+        bump = agent_registry.bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
+    pub owner: Signer<'info>,
+}
+
 pub fn register_agent(
     ctx: Context<RegisterAgent>,
     agent_type: AgentType,
     name: String,
+    manifest: Vec<u8>,
 ) -> Result<()> {
-    require!(
+    require_ctx!(
         name.len() <= MAX_AGENT_NAME_LENGTH,
-        SwarmError::AgentNameTooLong
+        SwarmError::AgentNameTooLong,
+        FailedCheck::AgentNameLength,
+        MAX_AGENT_NAME_LENGTH,
+        name.len()
     );
 
+    let swarm_key = ctx.accounts.swarm_state.key();
     let swarm_state = &mut ctx.accounts.swarm_state;
     
-    require!(
+    require_ctx!(
         swarm_state.active_agents < swarm_state.max_agents,
-        SwarmError::MaxAgentsReached
+        SwarmError::MaxAgentsReached,
+        FailedCheck::AgentCapacity,
+        swarm_state.max_agents,
+        swarm_state.active_agents
     );
 
-    let agent = &mut ctx.accounts.agent;
+    check_admission(&manifest, swarm_state.required_capabilities)?;
+
+    if agent_type.custom_id().is_some() {
+        require!(ctx.accounts.custom_agent_type.is_some(), SwarmError::InvalidAgentType);
+    }
+
+    let mut agent = ctx.accounts.agent.load_init()?;
     let clock = Clock::get()?;
     
+    agent.swarm = swarm_key;
     agent.owner = ctx.accounts.owner.key();
-    agent.agent_type = agent_type;
-    agent.name = name.clone();
+    agent.signing_key = agent.owner;
+    agent.guardian = Pubkey::default();
+    agent.set_agent_type(agent_type);
+    agent.set_name(&name);
     agent.reputation = INITIAL_REPUTATION;
+    agent.elo_rating = INITIAL_ELO_RATING;
     agent.proposals_created = 0;
     agent.votes_cast = 0;
     agent.successful_proposals = 0;
     agent.registered_at = clock.unix_timestamp;
     agent.last_active = clock.unix_timestamp;
-    agent.is_active = true;
+    agent.set_active(true);
+    agent.set_manifest(&manifest);
+    agent.sabbatical_until = 0;
+    agent.reward_epoch = swarm_state.rewards.epoch;
+    agent.decayed_through = swarm_state.rewards.epoch;
+    agent.reward_points = [0; REWARD_HISTORY_EPOCHS];
+    agent.version = Agent::VERSION;
     agent.bump = ctx.bumps.agent;
 
     swarm_state.active_agents += 1;
 
+    let agent_registry = &mut ctx.accounts.agent_registry;
+    if agent_registry.swarm == Pubkey::default() {
+        agent_registry.swarm = swarm_key;
+        agent_registry.version = AgentRegistry::VERSION;
+        agent_registry.bump = ctx.bumps.agent_registry;
+    }
+    agent_registry.add(ctx.accounts.agent.key(), agent_type)?;
+
+    emit!(AgentRegistered {
+        agent: ctx.accounts.agent.key(),
+        owner: agent.owner,
+        agent_type,
+        name: name.clone(),
+        timestamp: clock.unix_timestamp,
+    });
     msg!("Agent registered: {} (type: {:?})", name, agent_type);
 
     Ok(())
 }
+
+pub fn deregister_agent(ctx: Context<DeregisterAgent>) -> Result<()> {
+    let mut agent = ctx.accounts.agent.load_mut()?;
+    let swarm_state = &mut ctx.accounts.swarm_state;
+    let clock = Clock::get()?;
+
+    agent.set_active(false);
+    agent.last_active = clock.unix_timestamp;
+    swarm_state.active_agents = swarm_state.active_agents.saturating_sub(1);
+    ctx.accounts.agent_registry.remove(&ctx.accounts.agent.key())?;
+
+    emit!(AgentDeregistered {
+        agent: ctx.accounts.agent.key(),
+        owner: agent.owner,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Agent deregistered: {}", agent.name());
+
+    Ok(())
+}
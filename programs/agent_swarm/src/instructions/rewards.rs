@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::{EpochAdvanced, RewardEpochSettled, RewardsClaimed};
+use crate::instructions::treasury::pay_lamports_from_treasury;
+
+/// Permissionless crank closing a finished epoch. The rewards snapshot lets
+/// each agent claim on its own instead of one distribution over every agent;
+/// the stats snapshot drives reputation decay.
+#[derive(Accounts)]
+pub struct AdvanceEpoch<'info> {
+    #[account(
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        init,
+        payer = cranker,
+        space = EpochRewards::LEN,
+        seeds = [
+            EPOCH_REWARDS_SEED,
+            swarm_state.key().as_ref(),
+            &swarm_state.rewards.epoch.to_le_bytes()
+        ],
+        bump
+    )]
+    pub epoch_rewards: Account<'info, EpochRewards>,
+    
+    #[account(
+        init,
+        payer = cranker,
+        space = EpochStats::LEN,
+        seeds = [
+            EPOCH_STATS_SEED,
+            swarm_state.key().as_ref(),
+            &swarm_state.rewards.epoch.to_le_bytes()
+        ],
+        bump
+    )]
+    pub epoch_stats: Account<'info, EpochStats>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        mut,
+        seeds = [EPOCH_REWARDS_SEED, agent.load()?.swarm.as_ref(), &epoch.to_le_bytes()],
+        bump = epoch_rewards.bump
+    )]
+    pub epoch_rewards: Account<'info, EpochRewards>,
+    
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, agent.load()?.swarm.as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == owner.key() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, agent.load()?.swarm.as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn advance_epoch(ctx: Context<AdvanceEpoch>) -> Result<()> {
+    let swarm_key = ctx.accounts.swarm_state.key();
+    let active_agents = ctx.accounts.swarm_state.active_agents;
+    let rewards = &mut ctx.accounts.swarm_state.rewards;
+    let clock = Clock::get()?;
+
+    require!(rewards.is_over(clock.unix_timestamp), SwarmError::EpochNotOver);
+
+    let epoch_rewards = &mut ctx.accounts.epoch_rewards;
+    epoch_rewards.epoch = rewards.epoch;
+    epoch_rewards.budget = if rewards.points > 0 { rewards.budget } else { 0 };
+    epoch_rewards.points = rewards.points;
+    epoch_rewards.claimed = 0;
+    epoch_rewards.settled_at = clock.unix_timestamp;
+    epoch_rewards.version = EpochRewards::VERSION;
+    epoch_rewards.bump = ctx.bumps.epoch_rewards;
+
+    let epoch_stats = &mut ctx.accounts.epoch_stats;
+    epoch_stats.swarm = swarm_key;
+    epoch_stats.epoch = rewards.epoch;
+    epoch_stats.started_at = rewards.epoch_start;
+    epoch_stats.ended_at = clock.unix_timestamp;
+    epoch_stats.proposals = rewards.proposals;
+    epoch_stats.executions = rewards.executions;
+    epoch_stats.failures = rewards.failures;
+    epoch_stats.votes = rewards.votes;
+    epoch_stats.active_agents = active_agents;
+    epoch_stats.success_rate_bps = EpochStats::ratio_bps(
+        rewards.executions,
+        rewards.executions.saturating_add(rewards.failures),
+    );
+    epoch_stats.participation_bps = EpochStats::ratio_bps(
+        rewards.votes,
+        rewards.proposals.saturating_mul(active_agents as u64),
+    );
+    epoch_stats.version = EpochStats::VERSION;
+    epoch_stats.bump = ctx.bumps.epoch_stats;
+
+    rewards.epoch = rewards.epoch.checked_add(1).ok_or(SwarmError::ArithmeticOverflow)?;
+    rewards.epoch_start = clock.unix_timestamp;
+    rewards.points = 0;
+    rewards.proposals = 0;
+    rewards.executions = 0;
+    rewards.failures = 0;
+    rewards.votes = 0;
+
+    emit!(RewardEpochSettled {
+        epoch: epoch_rewards.epoch,
+        budget: epoch_rewards.budget,
+        points: epoch_rewards.points,
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(EpochAdvanced {
+        swarm: swarm_key,
+        epoch: epoch_stats.epoch,
+        proposals: epoch_stats.proposals,
+        executions: epoch_stats.executions,
+        failures: epoch_stats.failures,
+        votes: epoch_stats.votes,
+        success_rate_bps: epoch_stats.success_rate_bps,
+        participation_bps: epoch_stats.participation_bps,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!(
+        "Reward epoch {} settled: {} lamports across {} points",
+        epoch_rewards.epoch,
+        epoch_rewards.budget,
+        epoch_rewards.points
+    );
+    Ok(())
+}
+
+pub fn claim_rewards(ctx: Context<ClaimRewards>, epoch: u64) -> Result<()> {
+    let epoch_rewards = &mut ctx.accounts.epoch_rewards;
+    let mut agent = ctx.accounts.agent.load_mut()?;
+
+    let amount = epoch_rewards.share(agent.take_points(epoch));
+    require!(amount > 0, SwarmError::NotRewardEligible);
+
+    epoch_rewards.claimed = epoch_rewards
+        .claimed
+        .checked_add(amount)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+
+    pay_lamports_from_treasury(
+        &mut ctx.accounts.treasury,
+        &ctx.accounts.owner.to_account_info(),
+        amount,
+        Pubkey::default(), // Not tied to a single proposal
+    )?;
+
+    emit!(RewardsClaimed {
+        agent: ctx.accounts.agent.key(),
+        epoch,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Epoch {} rewards claimed: {} lamports", epoch, amount);
+    Ok(())
+}
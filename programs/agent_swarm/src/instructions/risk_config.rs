@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::SwarmAccountCreated;
+
+/// Create the swarm's risk config with no limits set. Limits are only
+/// changed by executed `RiskLimit` proposals.
+#[derive(Accounts)]
+pub struct InitializeRiskConfig<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = RiskConfig::LEN,
+        seeds = [RISK_CONFIG_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub risk_config: Account<'info, RiskConfig>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == swarm_state.authority @ SwarmError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_risk_config(ctx: Context<InitializeRiskConfig>) -> Result<()> {
+    let risk_config = &mut ctx.accounts.risk_config;
+    risk_config.max_position_size = None;
+    risk_config.max_daily_volume = None;
+    risk_config.max_slippage_bps = None;
+    risk_config.allowed_mints = Vec::new();
+    risk_config.max_tip_lamports = 0;
+    risk_config.volume_window_start = Clock::get()?.unix_timestamp;
+    risk_config.daily_volume = 0;
+    risk_config.twap_guard = None;
+    risk_config.max_concentration_bps = None;
+    risk_config.venue_caps = Vec::new();
+    risk_config.max_drawdown = None;
+    risk_config.drawdown = DrawdownTracker::default();
+    risk_config.circuit_breaker = None;
+    risk_config.value_marks = Vec::new();
+    risk_config.trading_paused = false;
+    risk_config.version = RiskConfig::VERSION;
+    risk_config.bump = ctx.bumps.risk_config;
+
+    emit!(SwarmAccountCreated {
+        account: risk_config.key(),
+        created_by: ctx.accounts.authority.key(),
+        timestamp: risk_config.volume_window_start,
+    });
+
+    msg!("Risk config initialized");
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::{AgentGuardianChanged, AgentKeyRotated};
+
+/// Agent's current signing key hands over to a new one, which co-signs so
+/// the agent can't be handed to a key nobody holds. The agent keeps its
+/// address, reputation and history, and stays named by `owner`. Coalition
+/// seats, identities and vote escrows belong to the old key and move
+/// separately.
+#[derive(Accounts)]
+pub struct RotateOwnerKey<'info> {
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, agent.load()?.swarm.as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == owner.key() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    pub owner: Signer<'info>,
+    
+    pub new_signing_key: Signer<'info>,
+    
+    /// Required once the agent has a guardian
+    pub guardian: Option<Signer<'info>>,
+}
+
+/// Agent's signing key sets or clears the guardian that must co-sign its
+/// rotations. Replacing an existing guardian takes its signature too.
+#[derive(Accounts)]
+pub struct SetAgentGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, agent.load()?.swarm.as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == owner.key() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    pub owner: Signer<'info>,
+    
+    /// Required once the agent has a guardian
+    pub guardian: Option<Signer<'info>>,
+}
+
+/// Require `guardian` to be the agent's guardian, when it has one
+fn require_guardian(agent: &Agent, guardian: Option<&Signer>) -> Result<bool> {
+    match agent.guardian() {
+        Some(expected) => {
+            require!(
+                guardian.is_some_and(|guardian| guardian.key() == expected),
+                SwarmError::GuardianSignatureRequired
+            );
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+pub fn rotate_owner_key(ctx: Context<RotateOwnerKey>) -> Result<()> {
+    let mut agent = ctx.accounts.agent.load_mut()?;
+    let new_signing_key = ctx.accounts.new_signing_key.key();
+    require_keys_neq!(new_signing_key, agent.signing_key, SwarmError::InvalidParameter);
+    let guardian_cosigned = require_guardian(&agent, ctx.accounts.guardian.as_ref())?;
+
+    let old_signing_key = agent.signing_key;
+    agent.signing_key = new_signing_key;
+
+    emit!(AgentKeyRotated {
+        agent: ctx.accounts.agent.key(),
+        owner: agent.owner,
+        old_signing_key,
+        new_signing_key,
+        guardian_cosigned,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Agent {} signing key rotated to {}", agent.name(), new_signing_key);
+
+    Ok(())
+}
+
+pub fn set_agent_guardian(ctx: Context<SetAgentGuardian>, guardian: Option<Pubkey>) -> Result<()> {
+    let mut agent = ctx.accounts.agent.load_mut()?;
+    require_guardian(&agent, ctx.accounts.guardian.as_ref())?;
+    require!(
+        guardian != Some(Pubkey::default()) && guardian != Some(agent.signing_key),
+        SwarmError::InvalidParameter
+    );
+
+    agent.guardian = guardian.unwrap_or_default();
+
+    emit!(AgentGuardianChanged {
+        agent: ctx.accounts.agent.key(),
+        guardian,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Agent {} guardian set to {:?}", agent.name(), guardian);
+
+    Ok(())
+}
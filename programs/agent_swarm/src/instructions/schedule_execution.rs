@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::ExecutionScheduled;
+
+/// Proposer holds a proposal's execution until `execute_not_before` and
+/// queues it for keepers to execute once due. Only allowed before voting
+/// starts, so voters approve the schedule along with the proposal.
+#[derive(Accounts)]
+pub struct ScheduleExecution<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        constraint = proposal.load()?.swarm == swarm_state.key() @ SwarmError::SwarmMismatch
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    /// The proposer's agent, whose signing key must sign
+    #[account(
+        seeds = [AGENT_SEED, proposal.load()?.swarm.as_ref(), proposal.load()?.proposer.as_ref()],
+        bump = proposer_agent.load()?.bump,
+        constraint = proposer_agent.load()?.signing_key == proposer.key() @ SwarmError::Unauthorized
+    )]
+    pub proposer_agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = ExecutionQueue::LEN,
+        seeds = [EXECUTION_QUEUE_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub execution_queue: Account<'info, ExecutionQueue>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn schedule_execution(ctx: Context<ScheduleExecution>, execute_not_before: i64) -> Result<()> {
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    let clock = Clock::get()?;
+
+    require!(proposal.total_voters == 0, SwarmError::VotingAlreadyStarted);
+    require!(
+        execute_not_before > clock.unix_timestamp && execute_not_before < proposal.expires_at,
+        SwarmError::InvalidParameter
+    );
+
+    let execution_queue = &mut ctx.accounts.execution_queue;
+    if execution_queue.swarm == Pubkey::default() {
+        execution_queue.swarm = ctx.accounts.swarm_state.key();
+        execution_queue.version = ExecutionQueue::VERSION;
+        execution_queue.bump = ctx.bumps.execution_queue;
+    }
+    execution_queue.push(
+        QueuedExecution {
+            proposal: ctx.accounts.proposal.key(),
+            execute_not_before,
+            expires_at: proposal.expires_at,
+        },
+        clock.unix_timestamp,
+    )?;
+    proposal.execute_not_before = execute_not_before;
+
+    emit!(ExecutionScheduled {
+        proposal: ctx.accounts.proposal.key(),
+        execute_not_before,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Proposal scheduled for execution at {}", execute_not_before);
+    Ok(())
+}
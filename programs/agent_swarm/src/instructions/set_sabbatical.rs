@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::SabbaticalSet;
+
+#[derive(Accounts)]
+pub struct SetSabbatical<'info> {
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, agent.load()?.swarm.as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == owner.key() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.is_active() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    pub owner: Signer<'info>,
+}
+
+pub fn set_sabbatical(ctx: Context<SetSabbatical>, duration: i64) -> Result<()> {
+    require!(
+        (0..=MAX_SABBATICAL_DURATION).contains(&duration),
+        SwarmError::InvalidSabbatical
+    );
+
+    let mut agent = ctx.accounts.agent.load_mut()?;
+    let clock = Clock::get()?;
+
+    agent.sabbatical_until = clock.unix_timestamp
+        .checked_add(duration)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    agent.last_active = clock.unix_timestamp;
+
+    emit!(SabbaticalSet {
+        agent: ctx.accounts.agent.key(),
+        until: agent.sabbatical_until,
+        timestamp: clock.unix_timestamp,
+    });
+    if duration == 0 {
+        msg!("Sabbatical ended: {}", agent.name());
+    } else {
+        msg!("Sabbatical started: {} until {}", agent.name(), agent.sabbatical_until);
+    }
+
+    Ok(())
+}
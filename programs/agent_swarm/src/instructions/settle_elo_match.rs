@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::EloMatchSettled;
+
+/// Permissionless crank scoring two proposals that competed for one objective.
+///
+/// The winner must have executed with a successful outcome. The loser must
+/// either have expired without execution or have a failed outcome.
+#[derive(Accounts)]
+pub struct SettleEloMatch<'info> {
+    #[account(constraint = winner_proposal.load()?.is_executed() @ SwarmError::MatchUndecided)]
+    pub winner_proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        seeds = [OUTCOME_SEED, winner_proposal.key().as_ref()],
+        bump = winner_outcome.bump,
+        constraint = winner_outcome.success @ SwarmError::MatchUndecided
+    )]
+    pub winner_outcome: Account<'info, Outcome>,
+    
+    #[account(
+        constraint = loser_proposal.load()?.swarm == winner_proposal.load()?.swarm @ SwarmError::SwarmMismatch
+    )]
+    pub loser_proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        seeds = [OUTCOME_SEED, loser_proposal.key().as_ref()],
+        bump = loser_outcome.bump
+    )]
+    pub loser_outcome: Option<Account<'info, Outcome>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            winner_proposal.load()?.swarm.as_ref(),
+            winner_proposal.load()?.proposer.as_ref()
+        ],
+        bump = winner_agent.load()?.bump
+    )]
+    pub winner_agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        mut,
+        seeds = [
+            AGENT_SEED,
+            loser_proposal.load()?.swarm.as_ref(),
+            loser_proposal.load()?.proposer.as_ref()
+        ],
+        bump = loser_agent.load()?.bump
+    )]
+    pub loser_agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        init,
+        payer = cranker,
+        space = EloMatch::LEN,
+        seeds = [ELO_MATCH_SEED, winner_proposal.key().as_ref(), loser_proposal.key().as_ref()],
+        bump
+    )]
+    pub elo_match: Account<'info, EloMatch>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn settle_elo_match(ctx: Context<SettleEloMatch>) -> Result<()> {
+    let winner_proposal = ctx.accounts.winner_proposal.load()?;
+    let loser_proposal = ctx.accounts.loser_proposal.load()?;
+    let clock = Clock::get()?;
+
+    require!(
+        winner_proposal.objective().is_some()
+            && winner_proposal.objective() == loser_proposal.objective()
+            && winner_proposal.proposer != loser_proposal.proposer,
+        SwarmError::NotCompetingProposals
+    );
+
+    let loser_lost = match &ctx.accounts.loser_outcome {
+        Some(outcome) => !outcome.success,
+        None => !loser_proposal.is_executed() && loser_proposal.is_expired(clock.unix_timestamp),
+    };
+    require!(loser_lost, SwarmError::MatchUndecided);
+
+    let mut winner_agent = ctx.accounts.winner_agent.load_mut()?;
+    let mut loser_agent = ctx.accounts.loser_agent.load_mut()?;
+    let change = elo_rating_change(winner_agent.elo_rating, loser_agent.elo_rating);
+
+    winner_agent.elo_rating = winner_agent.elo_rating.saturating_add(change).min(MAX_ELO_RATING);
+    loser_agent.elo_rating = loser_agent.elo_rating.saturating_sub(change);
+
+    let elo_match = &mut ctx.accounts.elo_match;
+    elo_match.winner = ctx.accounts.winner_proposal.key();
+    elo_match.loser = ctx.accounts.loser_proposal.key();
+    elo_match.rating_change = change;
+    elo_match.settled_at = clock.unix_timestamp;
+    elo_match.version = EloMatch::VERSION;
+    elo_match.bump = ctx.bumps.elo_match;
+
+    emit!(EloMatchSettled {
+        winner_proposal: elo_match.winner,
+        loser_proposal: elo_match.loser,
+        rating_change: change,
+        winner_rating: winner_agent.elo_rating,
+        loser_rating: loser_agent.elo_rating,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!(
+        "Elo match settled: +/-{} (winner {}, loser {})",
+        change,
+        winner_agent.elo_rating,
+        loser_agent.elo_rating
+    );
+
+    Ok(())
+}
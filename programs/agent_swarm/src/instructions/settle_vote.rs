@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::{PnlAttributed, ReputationChanged, VoteSettled};
+use crate::VoteType;
+
+/// Permissionless crank that settles one vote against the recorded outcome
+#[derive(Accounts)]
+pub struct SettleVote<'info> {
+    #[account(
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        constraint = proposal.load()?.swarm == swarm_state.key() @ SwarmError::SwarmMismatch
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        seeds = [OUTCOME_SEED, proposal.key().as_ref()],
+        bump = outcome.bump
+    )]
+    pub outcome: Account<'info, Outcome>,
+    
+    #[account(
+        mut,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), vote_record.voter.as_ref()],
+        bump = vote_record.bump,
+        constraint = !vote_record.settled @ SwarmError::VoteAlreadySettled
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), vote_record.voter.as_ref()],
+        bump = agent.load()?.bump
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    pub cranker: Signer<'info>,
+}
+
+pub fn settle_vote(ctx: Context<SettleVote>) -> Result<()> {
+    let success = ctx.accounts.outcome.success;
+    let vote_record = &mut ctx.accounts.vote_record;
+    let mut agent = ctx.accounts.agent.load_mut()?;
+    let old_reputation = agent.reputation;
+
+    if let Some(score) = vote_record.settlement_score(success) {
+        agent.update_reputation(score);
+    }
+
+    if vote_record.is_correct(success) {
+        agent.earn_points(&mut ctx.accounts.swarm_state.rewards, REWARD_POINTS_CORRECT_VOTE);
+    }
+
+    vote_record.settled = true;
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    proposal.unsettled_votes = proposal.unsettled_votes.saturating_sub(1);
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    if vote_record.vote == VoteType::Approve {
+        let weighted_votes_for = proposal.weighted_votes_for;
+        if let Some(pnl) = ctx.accounts.outcome.voter_pnl_share(vote_record.weight, weighted_votes_for) {
+            agent.attribute_vote_pnl(pnl);
+            emit!(PnlAttributed {
+                agent: ctx.accounts.agent.key(),
+                proposal: ctx.accounts.proposal.key(),
+                pnl,
+                as_proposer: false,
+                timestamp,
+            });
+        }
+    }
+    emit!(VoteSettled {
+        proposal: ctx.accounts.proposal.key(),
+        voter: vote_record.voter,
+        correct: vote_record.is_correct(success),
+        timestamp,
+    });
+    if agent.reputation != old_reputation {
+        emit!(ReputationChanged {
+            agent: ctx.accounts.agent.key(),
+            old_reputation,
+            new_reputation: agent.reputation,
+            timestamp,
+        });
+    }
+
+    msg!(
+        "Vote settled: {:?} on {} proposal, reputation {} -> {}",
+        vote_record.vote,
+        if success { "successful" } else { "failed" },
+        old_reputation,
+        agent.reputation
+    );
+
+    Ok(())
+}
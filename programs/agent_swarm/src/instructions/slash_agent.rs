@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::incinerator;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::*;
+use crate::payloads::{self, EmergencyPayload};
+use crate::ProposalType;
+
+/// Permissionless crank applying the slash carried by an executed
+/// `Emergency` proposal. Each proposal can slash once.
+#[derive(Accounts)]
+pub struct SlashAgent<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        constraint = proposal.load()?.swarm == swarm_state.key() @ SwarmError::SwarmMismatch,
+        constraint = proposal.load()?.is_executed() @ SwarmError::VotingInProgress,
+        constraint = proposal.load()?.proposal_type() == ProposalType::Emergency @ SwarmError::InvalidPayload
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        mut,
+        seeds = [BOND_SEED, swarm_state.key().as_ref(), bond.owner.as_ref()],
+        bump = bond.bump
+    )]
+    pub bond: Account<'info, AgentBond>,
+    
+    #[account(
+        init,
+        payer = cranker,
+        space = SlashRecord::LEN,
+        seeds = [SLASH_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, swarm_state.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, swarm_state.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    /// CHECK: The incinerator; lamports sent here are burned
+    #[account(mut, address = incinerator::ID)]
+    pub incinerator: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn slash_agent(ctx: Context<SlashAgent>) -> Result<()> {
+    let EmergencyPayload::Slash(payload) =
+        payloads::decode(&Proposal::load_data(&ctx.accounts.proposal)?)?
+    else {
+        return err!(SwarmError::InvalidPayload);
+    };
+    let bond = &mut ctx.accounts.bond;
+    let clock = Clock::get()?;
+
+    require_keys_eq!(bond.owner, payload.agent, SwarmError::InvalidPayload);
+
+    let (to_treasury, burned) =
+        bond.slash(payload.slash_bps, ctx.accounts.swarm_state.slash_treasury_bps)?;
+
+    bond.sub_lamports(to_treasury + burned)?;
+    ctx.accounts.treasury.add_lamports(to_treasury)?;
+    ctx.accounts.incinerator.add_lamports(burned)?;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_deposited = treasury
+        .total_deposited
+        .checked_add(to_treasury)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+
+    let slash_record = &mut ctx.accounts.slash_record;
+    slash_record.proposal = ctx.accounts.proposal.key();
+    slash_record.agent = payload.agent;
+    slash_record.to_treasury = to_treasury;
+    slash_record.burned = burned;
+    slash_record.slashed_at = clock.unix_timestamp;
+    slash_record.version = SlashRecord::VERSION;
+    slash_record.bump = ctx.bumps.slash_record;
+
+    ctx.accounts.audit_log.record(
+        slash_record.proposal,
+        AuditAction::AgentSlashed,
+        payload.agent,
+        clock.unix_timestamp,
+    );
+
+    emit!(TreasuryTransferIn {
+        schema_version: ACCOUNTING_SCHEMA_VERSION,
+        asset: NATIVE_SOL_ASSET,
+        amount: to_treasury,
+        from: bond.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(AgentSlashed {
+        agent: payload.agent,
+        proposal: slash_record.proposal,
+        to_treasury,
+        burned,
+        remaining_bond: bond.amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Agent {} slashed: {} to treasury, {} burned",
+        payload.agent,
+        to_treasury,
+        burned
+    );
+    Ok(())
+}
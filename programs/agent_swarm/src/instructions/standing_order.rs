@@ -0,0 +1,225 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::*;
+use crate::constants::*;
+use crate::cpi::SwapAccounts;
+use crate::errors::SwarmError;
+use crate::events::{StandingOrderExpired, StandingOrderTriggered};
+use crate::instructions::treasury::execute_trade;
+use crate::ProposalType;
+
+/// Fire a standing order whose trigger is met (permissionless). The order's
+/// trade runs through the same risk checks as a structured `Trade`, its
+/// spend counts against the `Strategy` cap that approved it, and the order
+/// closes, returning its rent to whoever placed it.
+#[derive(Accounts)]
+pub struct TriggerOrder<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [STANDING_ORDER_SEED, standing_order.proposal.as_ref()],
+        bump = standing_order.bump,
+        constraint = standing_order.swarm == swarm_state.key() @ SwarmError::SwarmMismatch
+    )]
+    pub standing_order: Account<'info, StandingOrder>,
+
+    /// CHECK: Receives the order's rent; must be the account that paid it
+    #[account(mut, address = standing_order.payer @ SwarmError::Unauthorized)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// CHECK: Data-less PDA owning the vault token accounts; signs the swap
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, swarm_state.key().as_ref()],
+        bump = swarm_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Vault token account of the order's input mint
+    #[account(mut)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault token account receiving the order's output
+    #[account(mut)]
+    pub output_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Venue program, checked against the route's expected program
+    /// id. The route's accounts follow as remaining accounts.
+    pub swap_program: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [RISK_CONFIG_SEED, swarm_state.key().as_ref()],
+        bump = risk_config.bump
+    )]
+    pub risk_config: Account<'info, RiskConfig>,
+
+    #[account(
+        mut,
+        seeds = [SPEND_TRACKER_SEED, swarm_state.key().as_ref()],
+        bump = spend_tracker.bump
+    )]
+    pub spend_tracker: Account<'info, SpendTracker>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = Portfolio::LEN,
+        seeds = [PORTFOLIO_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+
+    /// CHECK: Price account for the trigger's base mint, or its
+    /// `TwapTracker` when the trigger has a TWAP window; parsed in
+    /// `PriceCondition::price`
+    pub trigger_price_base: UncheckedAccount<'info>,
+
+    /// CHECK: Price account for the trigger's quote mint
+    pub trigger_price_quote: UncheckedAccount<'info>,
+
+    /// CHECK: Pyth price update for the trade's input mint, required when
+    /// the risk config checks trade prices
+    pub price_update_in: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Pyth price update for the trade's output mint
+    pub price_update_out: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: `TwapTracker` of the trade's input mint, required when the
+    /// risk config sets a `twap_guard`
+    pub twap_tracker_in: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: `TwapTracker` of the trade's output mint
+    pub twap_tracker_out: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn trigger_order<'info>(ctx: Context<'_, '_, '_, 'info, TriggerOrder<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let swarm_state = &ctx.accounts.swarm_state;
+    let standing_order = &ctx.accounts.standing_order;
+    require!(
+        clock.unix_timestamp <= standing_order.expires_at,
+        SwarmError::StandingOrderExpired
+    );
+
+    let trigger = standing_order.trigger;
+    let price = trigger.price(
+        &swarm_state.key(),
+        swarm_state,
+        &ctx.accounts.trigger_price_base,
+        &ctx.accounts.trigger_price_quote,
+        clock.unix_timestamp,
+    )?;
+    msg!("Trigger price {} against threshold {}", price, trigger.threshold);
+    require!(trigger.is_met(price), SwarmError::PriceConditionNotMet);
+
+    let trade = standing_order.trade.clone();
+    let prices = (
+        ctx.accounts.price_update_in.as_deref(),
+        ctx.accounts.price_update_out.as_deref(),
+    );
+    let risk_config = &mut ctx.accounts.risk_config;
+    risk_config.check_trade(
+        swarm_state,
+        &trade,
+        ctx.accounts.swap_program.key,
+        prices,
+        (
+            ctx.accounts.twap_tracker_in.as_deref(),
+            ctx.accounts.twap_tracker_out.as_deref(),
+        ),
+        clock.unix_timestamp,
+    )?;
+    ctx.accounts
+        .spend_tracker
+        .record_spend(&ProposalType::Strategy, trade.amount, clock.unix_timestamp)?;
+
+    let (spent, received) = execute_trade(
+        swarm_state,
+        &mut ctx.accounts.treasury_token_account,
+        &mut ctx.accounts.output_token_account,
+        &SwapAccounts {
+            authority: &ctx.accounts.vault_authority,
+            swap_program: &ctx.accounts.swap_program,
+            route_accounts: ctx.remaining_accounts,
+        },
+        standing_order.proposal,
+        &trade,
+    )?;
+
+    let portfolio = &mut ctx.accounts.portfolio;
+    if portfolio.swarm == Pubkey::default() {
+        portfolio.swarm = swarm_state.key();
+        portfolio.version = Portfolio::VERSION;
+        portfolio.bump = ctx.bumps.portfolio;
+    }
+    for (mint, token_account) in [
+        (trade.mint_in, &ctx.accounts.treasury_token_account),
+        (trade.mint_out, &ctx.accounts.output_token_account),
+    ] {
+        portfolio.record_balance(mint, token_account.amount, standing_order.proposal, clock.unix_timestamp)?;
+    }
+    if let (Some(price_update_in), Some(price_update_out)) = prices {
+        risk_config.record_trade_result(
+            swarm_state,
+            price_update_in,
+            price_update_out,
+            &trade,
+            (spent, received),
+            clock.unix_timestamp,
+        )?;
+    }
+
+    emit!(StandingOrderTriggered {
+        order: standing_order.key(),
+        keeper: ctx.accounts.keeper.key(),
+        price,
+        spent,
+        received,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Standing order fired: {} in, {} out", spent, received);
+    Ok(())
+}
+
+/// Close a standing order that lapsed unfired (permissionless), returning
+/// its rent to whoever placed it
+#[derive(Accounts)]
+pub struct CloseExpiredOrder<'info> {
+    #[account(
+        mut,
+        close = payer,
+        seeds = [STANDING_ORDER_SEED, standing_order.proposal.as_ref()],
+        bump = standing_order.bump
+    )]
+    pub standing_order: Account<'info, StandingOrder>,
+
+    /// CHECK: Receives the order's rent; must be the account that paid it
+    #[account(mut, address = standing_order.payer @ SwarmError::Unauthorized)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+pub fn close_expired_order(ctx: Context<CloseExpiredOrder>) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp > ctx.accounts.standing_order.expires_at,
+        SwarmError::StandingOrderActive
+    );
+
+    emit!(StandingOrderExpired {
+        order: ctx.accounts.standing_order.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Expired standing order closed");
+    Ok(())
+}
@@ -0,0 +1,281 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::{FailedCheck, SwarmError};
+use crate::events::{ReputationChanged, TaskClaimed, TaskClosed, TaskCompleted, TaskCreated};
+use crate::{require_ctx, AgentType};
+
+/// Consensus agent hands out a unit of work to the swarm
+#[derive(Accounts)]
+#[instruction(task_id: u64)]
+pub struct CreateTask<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), creator_agent.load()?.owner.as_ref()],
+        bump = creator_agent.load()?.bump,
+        constraint = creator_agent.load()?.signing_key == creator.key() @ SwarmError::Unauthorized,
+        constraint = creator_agent.load()?.is_active() @ SwarmError::Unauthorized,
+        constraint = creator_agent.load()?.agent_type() == AgentType::Consensus @ SwarmError::InvalidAgentType
+    )]
+    pub creator_agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = Task::LEN,
+        seeds = [
+            TASK_SEED,
+            swarm_state.key().as_ref(),
+            creator_agent.load()?.owner.as_ref(),
+            &task_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub task: Account<'info, Task>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Analytics or Execution agent takes an open task, or one whose previous
+/// claim lapsed
+#[derive(Accounts)]
+pub struct ClaimTask<'info> {
+    #[account(
+        mut,
+        seeds = [TASK_SEED, task.swarm.as_ref(), task.creator.as_ref(), &task.task_id.to_le_bytes()],
+        bump = task.bump
+    )]
+    pub task: Account<'info, Task>,
+    
+    #[account(
+        seeds = [AGENT_SEED, task.swarm.as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == claimant.key() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.is_active() @ SwarmError::Unauthorized,
+        constraint = matches!(
+            agent.load()?.agent_type(),
+            AgentType::Analytics | AgentType::Execution
+        ) @ SwarmError::InvalidAgentType
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    pub claimant: Signer<'info>,
+}
+
+/// Claimant submits the hash of its result
+#[derive(Accounts)]
+pub struct CompleteTask<'info> {
+    #[account(mut)]
+    pub task: Account<'info, Task>,
+    
+    /// The claiming agent, whose signing key must sign
+    #[account(
+        seeds = [AGENT_SEED, task.swarm.as_ref(), task.claimant.unwrap_or_default().as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == claimant.key() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    pub claimant: Signer<'info>,
+}
+
+/// Creator accepts a completed task, crediting the claimant's reputation
+/// and reclaiming the task's rent
+#[derive(Accounts)]
+pub struct AcceptTask<'info> {
+    #[account(mut, close = creator)]
+    pub task: Account<'info, Task>,
+    
+    /// The creating agent, whose signing key must sign
+    #[account(
+        seeds = [AGENT_SEED, task.swarm.as_ref(), task.creator.as_ref()],
+        bump = creator_agent.load()?.bump,
+        constraint = creator_agent.load()?.signing_key == creator.key() @ SwarmError::Unauthorized
+    )]
+    pub creator_agent: AccountLoader<'info, Agent>,
+    
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, task.swarm.as_ref(), task.claimant.unwrap_or_default().as_ref()],
+        bump = claimant_agent.load()?.bump
+    )]
+    pub claimant_agent: AccountLoader<'info, Agent>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+/// Creator withdraws a task nobody is working on, or rejects a completed
+/// one without credit
+#[derive(Accounts)]
+pub struct CancelTask<'info> {
+    #[account(mut, close = creator)]
+    pub task: Account<'info, Task>,
+    
+    /// The creating agent, whose signing key must sign
+    #[account(
+        seeds = [AGENT_SEED, task.swarm.as_ref(), task.creator.as_ref()],
+        bump = creator_agent.load()?.bump,
+        constraint = creator_agent.load()?.signing_key == creator.key() @ SwarmError::Unauthorized
+    )]
+    pub creator_agent: AccountLoader<'info, Agent>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+pub fn create_task(
+    ctx: Context<CreateTask>,
+    task_id: u64,
+    kind: TaskKind,
+    target: Pubkey,
+    description: String,
+    reputation_reward: u16,
+    claim_timeout: i64,
+) -> Result<()> {
+    require_ctx!(
+        description.len() <= MAX_DESCRIPTION_LENGTH,
+        SwarmError::DescriptionTooLong,
+        FailedCheck::DescriptionLength,
+        MAX_DESCRIPTION_LENGTH,
+        description.len()
+    );
+    require!(
+        reputation_reward <= MAX_TASK_REPUTATION_REWARD && claim_timeout >= MIN_TASK_CLAIM_TIMEOUT,
+        SwarmError::InvalidParameter
+    );
+
+    let clock = Clock::get()?;
+    let task = &mut ctx.accounts.task;
+    task.swarm = ctx.accounts.swarm_state.key();
+    task.creator = ctx.accounts.creator_agent.load()?.owner;
+    task.task_id = task_id;
+    task.kind = kind;
+    task.target = target;
+    task.description = description;
+    task.reputation_reward = reputation_reward;
+    task.claim_timeout = claim_timeout;
+    task.status = TaskStatus::Open;
+    task.claimant = None;
+    task.created_at = clock.unix_timestamp;
+    task.version = Task::VERSION;
+    task.bump = ctx.bumps.task;
+
+    emit!(TaskCreated {
+        task: task.key(),
+        swarm: task.swarm,
+        creator: task.creator,
+        kind,
+        target,
+        reputation_reward,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Task {} created: {:?} {}", task_id, kind, target);
+
+    Ok(())
+}
+
+pub fn claim_task(ctx: Context<ClaimTask>) -> Result<()> {
+    let clock = Clock::get()?;
+    let task = &mut ctx.accounts.task;
+
+    require!(task.is_claimable(clock.unix_timestamp), SwarmError::TaskNotClaimable);
+
+    task.status = TaskStatus::Claimed;
+    task.claimant = Some(ctx.accounts.agent.load()?.owner);
+    task.claimed_at = clock.unix_timestamp;
+
+    emit!(TaskClaimed {
+        task: task.key(),
+        claimant: task.claimant.unwrap_or_default(),
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Task {} claimed by {}", task.task_id, task.claimant.unwrap_or_default());
+
+    Ok(())
+}
+
+pub fn complete_task(ctx: Context<CompleteTask>, result_hash: [u8; 32]) -> Result<()> {
+    let clock = Clock::get()?;
+    let task = &mut ctx.accounts.task;
+
+    // A lapsed claim can still be completed as long as nobody took it over
+    require!(task.status == TaskStatus::Claimed, SwarmError::TaskNotClaimable);
+
+    task.status = TaskStatus::Completed;
+    task.result_hash = result_hash;
+    task.completed_at = clock.unix_timestamp;
+
+    emit!(TaskCompleted {
+        task: task.key(),
+        claimant: task.claimant.unwrap_or_default(),
+        result_hash,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Task {} completed", task.task_id);
+
+    Ok(())
+}
+
+pub fn accept_task(ctx: Context<AcceptTask>) -> Result<()> {
+    let task = &ctx.accounts.task;
+    require!(task.status == TaskStatus::Completed, SwarmError::TaskNotCompleted);
+
+    let clock = Clock::get()?;
+    let mut agent = ctx.accounts.claimant_agent.load_mut()?;
+    let old_reputation = agent.reputation;
+    agent.reputation = agent
+        .reputation
+        .saturating_add(task.reputation_reward)
+        .min(MAX_REPUTATION);
+    agent.last_active = clock.unix_timestamp;
+
+    emit!(ReputationChanged {
+        agent: ctx.accounts.claimant_agent.key(),
+        old_reputation,
+        new_reputation: agent.reputation,
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(TaskClosed {
+        task: task.key(),
+        claimant: task.claimant,
+        accepted: true,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!(
+        "Task {} accepted: reputation {} -> {}",
+        task.task_id,
+        old_reputation,
+        agent.reputation
+    );
+
+    Ok(())
+}
+
+pub fn cancel_task(ctx: Context<CancelTask>) -> Result<()> {
+    let task = &ctx.accounts.task;
+    let clock = Clock::get()?;
+
+    require!(
+        task.status != TaskStatus::Claimed || task.is_claimable(clock.unix_timestamp),
+        SwarmError::TaskClaimActive
+    );
+
+    emit!(TaskClosed {
+        task: task.key(),
+        claimant: task.claimant,
+        accepted: false,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Task {} cancelled", task.task_id);
+
+    Ok(())
+}
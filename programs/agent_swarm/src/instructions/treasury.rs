@@ -0,0 +1,1021 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::constants::*;
+use crate::cpi::{
+    drift, jito, marinade, meteora, solend, token_extensions, whirlpool, CPIHelper, SwapAccounts,
+    TokenTransferAccounts,
+};
+use crate::errors::SwarmError;
+use crate::events::*;
+use crate::payloads::{
+    BalanceCheck, LendingAction, LendingPayload, LiquidityPayload, PerpOrderPayload, ProtocolCall, StakingPayload, SwapRoute, TradePayload, TreasuryTokenTransferPayload,
+    TreasuryTransferPayload, WhirlpoolPayload,
+};
+use anchor_lang::solana_program::instruction::AccountMeta;
+use crate::ProposalType;
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Treasury::LEN,
+        seeds = [TREASURY_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = SpendTracker::LEN,
+        seeds = [SPEND_TRACKER_SEED, swarm_state.key().as_ref()],
+        bump
+    )]
+    pub spend_tracker: Account<'info, SpendTracker>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == swarm_state.authority @ SwarmError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Set the per-epoch spend cap for one proposal type (authority only)
+#[derive(Accounts)]
+pub struct SetSpendCap<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        seeds = [SPEND_TRACKER_SEED, swarm_state.key().as_ref()],
+        bump = spend_tracker.bump
+    )]
+    pub spend_tracker: Account<'info, SpendTracker>,
+    
+    #[account(constraint = authority.key() == swarm_state.authority @ SwarmError::Unauthorized)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, treasury.swarm.as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the swarm's vault token account for `mint`: the associated token
+/// account of the vault authority PDA, so only executed proposals can move
+/// the balance. Token-2022 mints with a transfer hook need the hook program
+/// on the swarm's whitelist.
+#[derive(Accounts)]
+pub struct CreateVaultTokenAccount<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    /// CHECK: Data-less PDA that owns and signs for the vault token accounts
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, swarm_state.key().as_ref()],
+        bump = swarm_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = vault_authority,
+        associated_token::token_program = token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Required when the mint has a transfer hook
+    #[account(
+        seeds = [PROGRAM_WHITELIST_SEED, swarm_state.key().as_ref()],
+        bump = program_whitelist.bump
+    )]
+    pub program_whitelist: Option<Account<'info, ProgramWhitelist>>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposit SPL or Token-2022 tokens into a vault token account. A mint's
+/// transfer hook accounts follow as remaining accounts.
+#[derive(Accounts)]
+pub struct DepositTreasuryTokens<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = swarm_state.vault_authority,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = depositor_token_account.owner == depositor.key() @ SwarmError::Unauthorized
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// Required when the mint has a transfer hook
+    #[account(
+        seeds = [PROGRAM_WHITELIST_SEED, swarm_state.key().as_ref()],
+        bump = program_whitelist.bump
+    )]
+    pub program_whitelist: Option<Account<'info, ProgramWhitelist>>,
+    
+    pub depositor: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.swarm = ctx.accounts.swarm_state.key();
+    treasury.total_deposited = 0;
+    treasury.total_withdrawn = 0;
+    treasury.version = Treasury::VERSION;
+    treasury.bump = ctx.bumps.treasury;
+
+    let spend_tracker = &mut ctx.accounts.spend_tracker;
+    spend_tracker.caps = [NO_SPEND_CAP; MAX_PROPOSAL_TYPES];
+    spend_tracker.spent = [0; MAX_PROPOSAL_TYPES];
+    spend_tracker.epoch_start = Clock::get()?.unix_timestamp;
+    spend_tracker.epoch_length = DEFAULT_SPEND_EPOCH_LENGTH;
+    spend_tracker.version = SpendTracker::VERSION;
+    spend_tracker.bump = ctx.bumps.spend_tracker;
+
+    emit!(SwarmAccountCreated {
+        account: treasury.key(),
+        created_by: ctx.accounts.authority.key(),
+        timestamp: spend_tracker.epoch_start,
+    });
+    msg!("Treasury initialized");
+    Ok(())
+}
+
+pub fn set_spend_cap(
+    ctx: Context<SetSpendCap>,
+    proposal_type: ProposalType,
+    cap: u64,
+) -> Result<()> {
+    let spend_tracker = &mut ctx.accounts.spend_tracker;
+    spend_tracker.caps[proposal_type.index()] = cap;
+
+    emit!(SpendCapSet {
+        proposal_type: proposal_type.clone(),
+        cap,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("Spend cap for {:?} set to {}", proposal_type, cap);
+    Ok(())
+}
+
+pub fn deposit_treasury(ctx: Context<DepositTreasury>, lamports: u64) -> Result<()> {
+    require!(lamports > 0, SwarmError::InvalidParameter);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_deposited = treasury
+        .total_deposited
+        .checked_add(lamports)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+
+    emit!(TreasuryTransferIn {
+        schema_version: ACCOUNTING_SCHEMA_VERSION,
+        asset: NATIVE_SOL_ASSET,
+        amount: lamports,
+        from: ctx.accounts.depositor.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Treasury deposit: {} lamports", lamports);
+    Ok(())
+}
+
+pub fn create_vault_token_account(ctx: Context<CreateVaultTokenAccount>) -> Result<()> {
+    token_extensions::require_allowed_hook(
+        &ctx.accounts.mint.to_account_info(),
+        ctx.accounts.program_whitelist.as_deref(),
+    )?;
+
+    emit!(SwarmAccountCreated {
+        account: ctx.accounts.vault_token_account.key(),
+        created_by: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!(
+        "Vault token account created: {} (mint {})",
+        ctx.accounts.vault_token_account.key(),
+        ctx.accounts.mint.key()
+    );
+    Ok(())
+}
+
+pub fn deposit_treasury_tokens<'info>(
+    ctx: Context<'_, '_, '_, 'info, DepositTreasuryTokens<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, SwarmError::InvalidParameter);
+    token_extensions::require_allowed_hook(
+        &ctx.accounts.mint.to_account_info(),
+        ctx.accounts.program_whitelist.as_deref(),
+    )?;
+
+    // Transfer fees are withheld from what the vault receives
+    let received = CPIHelper::transfer_tokens_checked(
+        &TokenTransferAccounts {
+            from: &ctx.accounts.depositor_token_account,
+            to: &ctx.accounts.treasury_token_account,
+            mint: &ctx.accounts.mint,
+            authority: &ctx.accounts.depositor.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            hook_accounts: ctx.remaining_accounts,
+        },
+        amount,
+        &[],
+    )?;
+
+    emit!(TreasuryTransferIn {
+        schema_version: ACCOUNTING_SCHEMA_VERSION,
+        asset: ctx.accounts.treasury_token_account.mint,
+        amount: received,
+        from: ctx.accounts.depositor_token_account.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Treasury token deposit: {} of mint {}",
+        received,
+        ctx.accounts.treasury_token_account.mint
+    );
+    Ok(())
+}
+
+/// Move lamports out of the treasury for an executed `TreasuryTransfer`
+pub fn transfer_from_treasury<'info>(
+    treasury: &mut Account<'info, Treasury>,
+    recipient: &AccountInfo<'info>,
+    proposal: Pubkey,
+    payload: &TreasuryTransferPayload,
+) -> Result<()> {
+    require_keys_eq!(recipient.key(), payload.recipient, SwarmError::InvalidPayload);
+
+    pay_lamports_from_treasury(treasury, recipient, payload.lamports, proposal)?;
+
+    msg!("Treasury transfer: {} lamports to {}", payload.lamports, payload.recipient);
+    Ok(())
+}
+
+/// Vault token account a treasury token payout is drawn from, with the
+/// accounts that sign and check the transfer
+pub struct TreasuryTokenSource<'a, 'info> {
+    pub swarm_state: &'a Account<'info, SwarmState>,
+    pub vault_authority: &'a AccountInfo<'info>,
+    pub treasury_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    /// Extra accounts for the mint's Token-2022 transfer hook, if any
+    pub hook_accounts: &'a [AccountInfo<'info>],
+}
+
+/// Move SPL or Token-2022 tokens out of a treasury token account for an
+/// executed `TreasuryTokenTransfer`
+pub fn transfer_tokens_from_treasury<'info>(
+    source: &TreasuryTokenSource<'_, 'info>,
+    recipient_token_account: &InterfaceAccount<'info, TokenAccount>,
+    proposal: Pubkey,
+    payload: &TreasuryTokenTransferPayload,
+) -> Result<()> {
+    require_keys_eq!(source.treasury_token_account.mint, payload.mint, SwarmError::InvalidPayload);
+    require_keys_eq!(
+        recipient_token_account.key(),
+        payload.recipient_token_account,
+        SwarmError::InvalidPayload
+    );
+
+    pay_tokens_from_treasury(source, recipient_token_account, payload.amount, proposal)?;
+
+    msg!(
+        "Treasury token transfer: {} of mint {} to {}",
+        payload.amount,
+        payload.mint,
+        payload.recipient_token_account
+    );
+    Ok(())
+}
+
+/// Swap treasury tokens for an executed structured `Trade`, returning the
+/// amounts spent and received. Both token accounts belong to the vault
+/// authority, which signs the venue CPI as `venue.authority`.
+pub fn execute_trade<'info>(
+    swarm_state: &Account<'info, SwarmState>,
+    treasury_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    output_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    venue: &SwapAccounts<'_, 'info>,
+    proposal: Pubkey,
+    payload: &TradePayload,
+) -> Result<(u64, u64)> {
+    require_keys_eq!(
+        treasury_token_account.owner,
+        swarm_state.vault_authority,
+        SwarmError::Unauthorized
+    );
+    require_keys_eq!(
+        output_token_account.owner,
+        swarm_state.vault_authority,
+        SwarmError::Unauthorized
+    );
+    require_keys_eq!(treasury_token_account.mint, payload.mint_in, SwarmError::InvalidPayload);
+    require_keys_eq!(output_token_account.mint, payload.mint_out, SwarmError::InvalidPayload);
+    require!(
+        treasury_token_account.amount >= payload.amount,
+        SwarmError::InsufficientTreasuryFunds
+    );
+
+    let swarm_key = swarm_state.key();
+    let bump = [swarm_state.vault_authority_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_AUTHORITY_SEED, swarm_key.as_ref(), &bump]];
+
+    let (spent, received) = match &payload.route {
+        SwapRoute::Jupiter { data } => CPIHelper::jupiter_swap(
+            treasury_token_account,
+            output_token_account,
+            venue,
+            data,
+            payload.amount,
+            payload.min_out,
+            signer_seeds,
+        )?,
+        SwapRoute::Raydium => CPIHelper::raydium_swap(
+            treasury_token_account,
+            output_token_account,
+            venue,
+            payload.amount,
+            payload.min_out,
+            signer_seeds,
+        )?,
+    };
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(TreasuryTransferOut {
+        schema_version: ACCOUNTING_SCHEMA_VERSION,
+        asset: payload.mint_in,
+        amount: spent,
+        to: venue.swap_program.key(),
+        proposal,
+        timestamp,
+    });
+    emit!(TreasuryTransferIn {
+        schema_version: ACCOUNTING_SCHEMA_VERSION,
+        asset: payload.mint_out,
+        amount: received,
+        from: venue.swap_program.key(),
+        timestamp,
+    });
+
+    Ok((spent, received))
+}
+
+/// Perform an executed structured `Strategy` proposal's calls in order, each
+/// on a whitelisted program and signed by the vault authority. Any failing
+/// call fails the whole execution.
+pub fn execute_strategy<'info>(
+    swarm_state: &Account<'info, SwarmState>,
+    program_whitelist: &ProgramWhitelist,
+    vault_authority: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    calls: Vec<ProtocolCall>,
+) -> Result<()> {
+    let swarm_key = swarm_state.key();
+    let bump = [swarm_state.vault_authority_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_AUTHORITY_SEED, swarm_key.as_ref(), &bump]];
+
+    for call in calls {
+        let target_program = remaining_account(remaining_accounts, call.program_index)?;
+        require!(
+            *target_program.key != crate::ID && program_whitelist.contains(target_program.key),
+            SwarmError::UntrustedProgram
+        );
+
+        let accounts = call
+            .accounts
+            .iter()
+            .map(|account| remaining_account(remaining_accounts, account.index).cloned())
+            .collect::<Result<Vec<_>>>()?;
+        let metas = call
+            .accounts
+            .iter()
+            .zip(&accounts)
+            .map(|(account, info)| AccountMeta {
+                pubkey: *info.key,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect();
+
+        CPIHelper::execute_protocol_call(
+            vault_authority,
+            target_program,
+            call.data,
+            metas,
+            &accounts,
+            signer_seeds,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Perform an executed lending `Strategy` proposal against the vault's Solend
+/// obligation. The lending program comes first in `remaining_accounts`,
+/// followed by the instruction's accounts.
+///
+/// Withdrawals and borrows must start from a refreshed obligation at or
+/// above the swarm's minimum health factor; Solend bounds where they end.
+pub fn execute_lending<'info>(
+    swarm_state: &Account<'info, SwarmState>,
+    vault_authority: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    payload: LendingPayload,
+) -> Result<()> {
+    let (lending_program, accounts) = remaining_accounts
+        .split_first()
+        .ok_or(SwarmError::MissingExecutionAccount)?;
+
+    if payload.action != LendingAction::Deposit {
+        let layout = solend::layout(payload.action);
+        let obligation = solend::Obligation::load(
+            accounts
+                .get(layout.obligation_slot)
+                .ok_or(SwarmError::MissingExecutionAccount)?,
+        )?;
+        require!(!obligation.stale, SwarmError::ObligationUnhealthy);
+        if let Some(health_factor_bps) = obligation.health_factor_bps() {
+            require!(
+                health_factor_bps >= swarm_state.min_health_factor_bps as u128,
+                SwarmError::ObligationUnhealthy
+            );
+        }
+    }
+
+    let swarm_key = swarm_state.key();
+    let bump = [swarm_state.vault_authority_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_AUTHORITY_SEED, swarm_key.as_ref(), &bump]];
+
+    CPIHelper::solend_call(
+        vault_authority,
+        lending_program,
+        accounts,
+        payload.action,
+        payload.amount,
+        signer_seeds,
+    )
+}
+
+/// Perform an executed staking `Strategy` proposal through Marinade. The
+/// Marinade program comes first in `remaining_accounts`, followed by the
+/// instruction's accounts.
+///
+/// Marinade moves SOL through system accounts, so the vault authority stands
+/// in for the treasury: stakes and ticket rent are moved to it first, and
+/// claimed SOL is swept from it back into the treasury.
+pub fn execute_staking<'info>(
+    swarm_state: &Account<'info, SwarmState>,
+    treasury: &mut Account<'info, Treasury>,
+    vault_authority: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    remaining_accounts: &[AccountInfo<'info>],
+    proposal: Pubkey,
+    payload: StakingPayload,
+) -> Result<()> {
+    let (marinade_program, accounts) = remaining_accounts
+        .split_first()
+        .ok_or(SwarmError::MissingExecutionAccount)?;
+
+    let order_proposal = match payload {
+        StakingPayload::Claim { order_proposal } => order_proposal,
+        _ => proposal,
+    };
+    let (ticket, ticket_bump) = Pubkey::find_program_address(
+        &[STAKE_TICKET_SEED, order_proposal.as_ref()],
+        &crate::ID,
+    );
+
+    let swarm_key = swarm_state.key();
+    let bump = [swarm_state.vault_authority_bump];
+    let vault_seeds: &[&[u8]] = &[VAULT_AUTHORITY_SEED, swarm_key.as_ref(), &bump];
+
+    match payload {
+        StakingPayload::Deposit { lamports } => {
+            pay_lamports_from_treasury(treasury, vault_authority, lamports, proposal)?;
+        }
+        StakingPayload::OrderUnstake { .. } => {
+            // Marinade writes the ticket into an account we create for it
+            let ticket_account = accounts.get(4).ok_or(SwarmError::MissingExecutionAccount)?;
+            require_keys_eq!(ticket_account.key(), ticket, SwarmError::InvalidPayload);
+
+            let rent = Rent::get()?.minimum_balance(marinade::TICKET_ACCOUNT_LEN);
+            pay_lamports_from_treasury(treasury, vault_authority, rent, proposal)?;
+            let ticket_bump = [ticket_bump];
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    system_program.to_account_info(),
+                    system_program::CreateAccount {
+                        from: vault_authority.clone(),
+                        to: ticket_account.clone(),
+                    },
+                    &[vault_seeds, &[STAKE_TICKET_SEED, order_proposal.as_ref(), &ticket_bump]],
+                ),
+                rent,
+                marinade::TICKET_ACCOUNT_LEN as u64,
+                &marinade::PROGRAM_ID,
+            )?;
+        }
+        StakingPayload::Claim { .. } => {}
+    }
+
+    let vault_before = vault_authority.lamports();
+    CPIHelper::marinade_call(
+        vault_authority,
+        marinade_program,
+        accounts,
+        &payload,
+        &ticket,
+        &[vault_seeds],
+    )?;
+
+    if let StakingPayload::Claim { .. } = payload {
+        let claimed = vault_authority.lamports().saturating_sub(vault_before);
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: vault_authority.clone(),
+                    to: treasury.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            claimed,
+        )?;
+        treasury.total_deposited = treasury
+            .total_deposited
+            .checked_add(claimed)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+
+        emit!(TreasuryTransferIn {
+            schema_version: ACCOUNTING_SCHEMA_VERSION,
+            asset: NATIVE_SOL_ASSET,
+            amount: claimed,
+            from: ticket,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+/// Place an executed perp `Strategy` proposal's order on Drift. The Drift
+/// program comes first in `remaining_accounts`, followed by the order's
+/// accounts.
+///
+/// Orders that can grow the position need a limit for the market in
+/// `PendingExposure`, and the position with every open order filled must
+/// stay within it.
+pub fn execute_perp_order<'info>(
+    swarm_state: &Account<'info, SwarmState>,
+    pending_exposure: &PendingExposure,
+    vault_authority: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    payload: PerpOrderPayload,
+) -> Result<()> {
+    let (drift_program, accounts) = remaining_accounts
+        .split_first()
+        .ok_or(SwarmError::MissingExecutionAccount)?;
+
+    if !payload.reduce_only {
+        let max_base_amount = pending_exposure
+            .perp_limit(payload.market_index)
+            .ok_or(SwarmError::ExposureLimitExceeded)?;
+        let user = accounts.get(1).ok_or(SwarmError::MissingExecutionAccount)?;
+        let position = drift::load_perp_position(user, vault_authority.key, payload.market_index)?;
+        require!(
+            position.worst_case_size().saturating_add(payload.base_asset_amount) <= max_base_amount,
+            SwarmError::ExposureLimitExceeded
+        );
+    }
+
+    let swarm_key = swarm_state.key();
+    let bump = [swarm_state.vault_authority_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_AUTHORITY_SEED, swarm_key.as_ref(), &bump]];
+
+    CPIHelper::drift_place_perp_order(
+        vault_authority,
+        drift_program,
+        accounts,
+        &payload,
+        signer_seeds,
+    )
+}
+
+/// Perform an executed liquidity `Strategy` proposal on a vault-owned Meteora
+/// DLMM position. The DLMM program comes first in `remaining_accounts`,
+/// followed by the instruction's accounts.
+///
+/// Opening a position moves its rent from the treasury to the vault
+/// authority, which pays for it; closing one returns the rent straight to
+/// the treasury.
+pub fn execute_liquidity<'info>(
+    swarm_state: &Account<'info, SwarmState>,
+    treasury: &mut Account<'info, Treasury>,
+    vault_authority: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    proposal: Pubkey,
+    payload: LiquidityPayload,
+) -> Result<()> {
+    let (dlmm_program, accounts) = remaining_accounts
+        .split_first()
+        .ok_or(SwarmError::MissingExecutionAccount)?;
+
+    if let LiquidityPayload::OpenPosition { .. } = payload {
+        let rent = Rent::get()?.minimum_balance(meteora::POSITION_ACCOUNT_LEN);
+        pay_lamports_from_treasury(treasury, vault_authority, rent, proposal)?;
+    }
+
+    let swarm_key = swarm_state.key();
+    let bump = [swarm_state.vault_authority_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_AUTHORITY_SEED, swarm_key.as_ref(), &bump]];
+
+    let treasury_before = treasury.to_account_info().lamports();
+    CPIHelper::meteora_dlmm_call(
+        vault_authority,
+        dlmm_program,
+        accounts,
+        &payload,
+        &treasury.key(),
+        signer_seeds,
+    )?;
+
+    if let LiquidityPayload::ClosePosition = payload {
+        let refunded = treasury.to_account_info().lamports().saturating_sub(treasury_before);
+        treasury.total_deposited = treasury
+            .total_deposited
+            .checked_add(refunded)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+
+        emit!(TreasuryTransferIn {
+            schema_version: ACCOUNTING_SCHEMA_VERSION,
+            asset: NATIVE_SOL_ASSET,
+            amount: refunded,
+            from: accounts[0].key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+/// Perform an executed Whirlpool `Strategy` proposal on a vault-owned Orca
+/// position. The Whirlpool program comes first in `remaining_accounts`,
+/// followed by the instruction's accounts.
+///
+/// Opening a position moves its rent from the treasury to the vault
+/// authority, which funds it; closing one returns the rent straight to the
+/// treasury.
+pub fn execute_whirlpool<'info>(
+    swarm_state: &Account<'info, SwarmState>,
+    treasury: &mut Account<'info, Treasury>,
+    vault_authority: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    proposal: Pubkey,
+    payload: WhirlpoolPayload,
+) -> Result<()> {
+    let (whirlpool_program, accounts) = remaining_accounts
+        .split_first()
+        .ok_or(SwarmError::MissingExecutionAccount)?;
+
+    if let WhirlpoolPayload::OpenPosition { .. } = payload {
+        let rent = whirlpool::open_position_rent(&Rent::get()?);
+        pay_lamports_from_treasury(treasury, vault_authority, rent, proposal)?;
+    }
+
+    let swarm_key = swarm_state.key();
+    let bump = [swarm_state.vault_authority_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_AUTHORITY_SEED, swarm_key.as_ref(), &bump]];
+
+    let treasury_before = treasury.to_account_info().lamports();
+    CPIHelper::whirlpool_call(
+        vault_authority,
+        whirlpool_program,
+        accounts,
+        &payload,
+        &treasury.key(),
+        signer_seeds,
+    )?;
+
+    if let WhirlpoolPayload::ClosePosition = payload {
+        let refunded = treasury.to_account_info().lamports().saturating_sub(treasury_before);
+        treasury.total_deposited = treasury
+            .total_deposited
+            .checked_add(refunded)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+
+        emit!(TreasuryTransferIn {
+            schema_version: ACCOUNTING_SCHEMA_VERSION,
+            asset: NATIVE_SOL_ASSET,
+            amount: refunded,
+            from: accounts[2].key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+/// Collect the fees of each of a harvest's `positions`, returning the
+/// remaining accounts left for its swap. `remaining_accounts` start with the
+/// Whirlpool program, followed per position, in order, by `collect_fees`'
+/// accounts and the position's lower and upper tick arrays.
+pub fn collect_harvest<'a, 'info>(
+    swarm_state: &Account<'info, SwarmState>,
+    vault_authority: &AccountInfo<'info>,
+    positions: &[Pubkey],
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<&'a [AccountInfo<'info>]> {
+    if positions.is_empty() {
+        return Ok(remaining_accounts);
+    }
+    let (whirlpool_program, accounts) = remaining_accounts
+        .split_first()
+        .ok_or(SwarmError::MissingExecutionAccount)?;
+    let stride = whirlpool::COLLECT_FEES_ACCOUNT_COUNT + 2;
+    require!(
+        accounts.len() >= positions.len() * stride,
+        SwarmError::MissingExecutionAccount
+    );
+    let (collect_accounts, rest) = accounts.split_at(positions.len() * stride);
+
+    let swarm_key = swarm_state.key();
+    let bump = [swarm_state.vault_authority_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_AUTHORITY_SEED, swarm_key.as_ref(), &bump]];
+
+    for (position, accounts) in positions.iter().zip(collect_accounts.chunks(stride)) {
+        require_keys_eq!(*accounts[2].key, *position, SwarmError::InvalidPayload);
+        CPIHelper::whirlpool_call(
+            vault_authority,
+            whirlpool_program,
+            accounts,
+            &WhirlpoolPayload::CollectFees,
+            // Collecting moves no rent
+            &Pubkey::default(),
+            signer_seeds,
+        )?;
+    }
+    Ok(rest)
+}
+
+/// Balances of the vault token accounts a `Strategy`'s balance checks name,
+/// read before it executes
+pub fn snapshot_balances(
+    swarm_state: &SwarmState,
+    remaining_accounts: &[AccountInfo],
+    checks: &[BalanceCheck],
+) -> Result<Vec<u64>> {
+    checks
+        .iter()
+        .map(|check| {
+            vault_token_amount(
+                swarm_state,
+                remaining_account(remaining_accounts, check.account_index)?,
+            )
+        })
+        .collect()
+}
+
+/// Measure each checked vault balance's change since `snapshot_balances`.
+/// Also returns whether any change fell outside its check's tolerance.
+pub fn measure_balance_deltas(
+    swarm_state: &SwarmState,
+    remaining_accounts: &[AccountInfo],
+    checks: &[BalanceCheck],
+    before: &[u64],
+) -> Result<(Vec<BalanceDelta>, bool)> {
+    let mut deltas = Vec::with_capacity(checks.len());
+    let mut deviated = false;
+
+    for (check, before) in checks.iter().zip(before) {
+        let account = remaining_account(remaining_accounts, check.account_index)?;
+        let after = vault_token_amount(swarm_state, account)?;
+        let measured = i64::try_from(after as i128 - *before as i128)
+            .map_err(|_| error!(SwarmError::ArithmeticOverflow))?;
+
+        let deviation = (measured as i128 - check.expected_delta as i128).unsigned_abs();
+        if deviation > check.tolerance as u128 {
+            msg!(
+                "Balance of {} moved {} against an expected {}",
+                account.key,
+                measured,
+                check.expected_delta
+            );
+            deviated = true;
+        }
+
+        deltas.push(BalanceDelta {
+            token_account: *account.key,
+            expected: check.expected_delta,
+            measured,
+        });
+    }
+
+    Ok((deltas, deviated))
+}
+
+fn vault_token_amount(swarm_state: &SwarmState, account: &AccountInfo) -> Result<u64> {
+    Ok(vault_token_account(swarm_state, account)?.amount)
+}
+
+/// Read `account` as a token account owned by the swarm's vault authority
+pub fn vault_token_account(swarm_state: &SwarmState, account: &AccountInfo) -> Result<TokenAccount> {
+    require!(
+        *account.owner == anchor_spl::token::ID || *account.owner == anchor_spl::token_2022::ID,
+        SwarmError::Unauthorized
+    );
+    let token_account = TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    require_keys_eq!(
+        token_account.owner,
+        swarm_state.vault_authority,
+        SwarmError::Unauthorized
+    );
+    Ok(token_account)
+}
+
+fn remaining_account<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    index: u8,
+) -> Result<&'a AccountInfo<'info>> {
+    remaining_accounts
+        .get(index as usize)
+        .ok_or_else(|| error!(SwarmError::MissingExecutionAccount))
+}
+
+/// Tip a Jito tip account from the treasury so a time-sensitive execution
+/// lands, within the risk config's cap
+pub fn pay_execution_tip<'info>(
+    risk_config: &RiskConfig,
+    treasury: &mut Account<'info, Treasury>,
+    tip_account: &AccountInfo<'info>,
+    lamports: u64,
+    proposal: Pubkey,
+) -> Result<()> {
+    require!(lamports <= risk_config.max_tip_lamports, SwarmError::TipExceedsCap);
+    require!(jito::is_tip_account(tip_account.key), SwarmError::InvalidTipAccount);
+
+    msg!("Tipping {} lamports to {}", lamports, tip_account.key);
+    pay_lamports_from_treasury(treasury, tip_account, lamports, proposal)
+}
+
+/// Debit the treasury and credit `recipient`.
+///
+/// The treasury is owned by this program, so it is debited directly rather
+/// than through a System Program CPI. The rent-exempt minimum is preserved.
+pub fn pay_lamports_from_treasury<'info>(
+    treasury: &mut Account<'info, Treasury>,
+    recipient: &AccountInfo<'info>,
+    lamports: u64,
+    proposal: Pubkey,
+) -> Result<()> {
+    let rent_floor = Rent::get()?.minimum_balance(Treasury::LEN);
+    let available = treasury.get_lamports().saturating_sub(rent_floor);
+    require!(lamports <= available, SwarmError::InsufficientTreasuryFunds);
+
+    treasury.sub_lamports(lamports)?;
+    recipient.add_lamports(lamports)?;
+
+    treasury.total_withdrawn = treasury
+        .total_withdrawn
+        .checked_add(lamports)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+
+    emit!(TreasuryTransferOut {
+        schema_version: ACCOUNTING_SCHEMA_VERSION,
+        asset: NATIVE_SOL_ASSET,
+        amount: lamports,
+        to: recipient.key(),
+        proposal,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Transfer tokens out of a vault token account, signed by the vault
+/// authority. `amount` leaves the vault; the recipient gets it less any
+/// Token-2022 transfer fee.
+pub fn pay_tokens_from_treasury<'info>(
+    source: &TreasuryTokenSource<'_, 'info>,
+    recipient_token_account: &InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+    proposal: Pubkey,
+) -> Result<()> {
+    let TreasuryTokenSource {
+        swarm_state,
+        vault_authority,
+        treasury_token_account,
+        mint,
+        token_program,
+        hook_accounts,
+    } = *source;
+    require_keys_eq!(
+        treasury_token_account.owner,
+        swarm_state.vault_authority,
+        SwarmError::Unauthorized
+    );
+    require_keys_eq!(
+        recipient_token_account.mint,
+        treasury_token_account.mint,
+        SwarmError::InvalidPayload
+    );
+    require!(
+        treasury_token_account.amount >= amount,
+        SwarmError::InsufficientTreasuryFunds
+    );
+
+    let swarm_key = swarm_state.key();
+    let bump = [swarm_state.vault_authority_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_AUTHORITY_SEED, swarm_key.as_ref(), &bump]];
+
+    CPIHelper::transfer_tokens_checked(
+        &TokenTransferAccounts {
+            from: treasury_token_account,
+            to: recipient_token_account,
+            mint,
+            authority: vault_authority,
+            token_program,
+            hook_accounts,
+        },
+        amount,
+        signer_seeds,
+    )?;
+
+    emit!(TreasuryTransferOut {
+        schema_version: ACCOUNTING_SCHEMA_VERSION,
+        asset: treasury_token_account.mint,
+        amount,
+        to: recipient_token_account.key(),
+        proposal,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
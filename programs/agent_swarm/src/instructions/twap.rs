@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::events::TwapObserved;
+use crate::oracle::OraclePrice;
+
+/// Crank the oracle price of `mint` into its `TwapTracker`, creating the
+/// tracker on first use. Any active agent may crank; each observation must
+/// be newer than the last.
+#[derive(Accounts)]
+pub struct RecordTwapObservation<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == cranker.key() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.is_active() @ SwarmError::Unauthorized
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = TwapTracker::LEN,
+        seeds = [TWAP_TRACKER_SEED, swarm_state.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub twap_tracker: Account<'info, TwapTracker>,
+    
+    /// CHECK: Pyth or Switchboard price account of the mint's registered
+    /// feed; parsed and checked in `OraclePrice::load`
+    pub price_account: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn record_twap_observation(ctx: Context<RecordTwapObservation>) -> Result<()> {
+    let swarm_key = ctx.accounts.swarm_state.key();
+    let mint = ctx.accounts.mint.key();
+    let feed = ctx.accounts
+        .swarm_state
+        .price_feed(&mint)
+        .ok_or(SwarmError::MissingPriceFeed)?;
+
+    let clock = Clock::get()?;
+    let oracle_price = OraclePrice::load(&ctx.accounts.price_account, feed, clock.unix_timestamp)?;
+    let price = oracle_price.rescale(TWAP_PRICE_EXPONENT)?;
+
+    let twap_tracker = &mut ctx.accounts.twap_tracker;
+    if twap_tracker.swarm == Pubkey::default() {
+        twap_tracker.swarm = swarm_key;
+        twap_tracker.mint = mint;
+        twap_tracker.version = TwapTracker::VERSION;
+        twap_tracker.bump = ctx.bumps.twap_tracker;
+    }
+    twap_tracker.observe(price, oracle_price.publish_time)?;
+
+    emit!(TwapObserved {
+        swarm: swarm_key,
+        mint,
+        price,
+        published_at: oracle_price.publish_time,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("TWAP observation for {}: {}", mint, price);
+    Ok(())
+}
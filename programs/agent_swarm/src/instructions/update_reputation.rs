@@ -1,44 +1,76 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::constants::*;
-use crate::errors::SwarmError;
+use crate::errors::{FailedCheck, SwarmError};
+use crate::events::ReputationChanged;
+use crate::require_ctx;
 
 #[derive(Accounts)]
 pub struct UpdateReputation<'info> {
     #[account(
-        seeds = [SWARM_SEED],
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
         bump = swarm_state.bump
     )]
     pub swarm_state: Account<'info, SwarmState>,
     
     #[account(
         mut,
-        seeds = [AGENT_SEED, agent.owner.as_ref()],
-        bump = agent.bump
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump
     )]
-    pub agent: Account<'info, Agent>,
+    pub agent: AccountLoader<'info, Agent>,
     
     #[account(constraint = authority.key() == swarm_state.authority @ SwarmError::Unauthorized)]
     pub authority: Signer<'info>,
 }
 
+/// Permissionless crank decaying the reputation of an agent that sat out a
+/// finished epoch in which the swarm had proposals to act on
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct DecayReputation<'info> {
+    #[account(
+        seeds = [EPOCH_STATS_SEED, agent.load()?.swarm.as_ref(), &epoch.to_le_bytes()],
+        bump = epoch_stats.bump
+    )]
+    pub epoch_stats: Account<'info, EpochStats>,
+    
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, agent.load()?.swarm.as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump
+    )]
+    pub agent: AccountLoader<'info, Agent>,
+}
+
 pub fn update_reputation(
     ctx: Context<UpdateReputation>,
     performance_score: u16,
 ) -> Result<()> {
-    require!(
+    require_ctx!(
         performance_score <= 1000,
-        SwarmError::InvalidReputationScore
+        SwarmError::InvalidReputationScore,
+        FailedCheck::PerformanceScore,
+        1000,
+        performance_score
     );
 
-    let agent = &mut ctx.accounts.agent;
+    let mut agent = ctx.accounts.agent.load_mut()?;
     let old_reputation = agent.reputation;
     
     agent.update_reputation(performance_score);
     
     let clock = Clock::get()?;
     agent.last_active = clock.unix_timestamp;
+    ctx.accounts.swarm_state.authority_last_active = clock.unix_timestamp;
 
+    emit!(ReputationChanged {
+        agent: ctx.accounts.agent.key(),
+        old_reputation,
+        new_reputation: agent.reputation,
+        timestamp: clock.unix_timestamp,
+    });
     msg!(
         "Agent reputation updated: {} -> {} (performance: {})",
         old_reputation,
@@ -48,3 +80,37 @@ pub fn update_reputation(
 
     Ok(())
 }
+
+pub fn decay_reputation(ctx: Context<DecayReputation>, epoch: u64) -> Result<()> {
+    let epoch_stats = &ctx.accounts.epoch_stats;
+    let mut agent = ctx.accounts.agent.load_mut()?;
+
+    // Epochs are checked in order, each at most once, and only count against
+    // an agent that has not been active since the epoch began
+    require!(
+        epoch >= agent.decayed_through
+            && epoch_stats.proposals > 0
+            && agent.last_active < epoch_stats.started_at
+            && !agent.is_exempt_from_decay(epoch_stats.started_at),
+        SwarmError::NotDecayEligible
+    );
+
+    let old_reputation = agent.reputation;
+    agent.reputation = agent.reputation.saturating_sub(REPUTATION_DECAY_PER_EPOCH);
+    agent.decayed_through = epoch + 1;
+
+    emit!(ReputationChanged {
+        agent: ctx.accounts.agent.key(),
+        old_reputation,
+        new_reputation: agent.reputation,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!(
+        "Agent reputation decayed for epoch {}: {} -> {}",
+        epoch,
+        old_reputation,
+        agent.reputation
+    );
+
+    Ok(())
+}
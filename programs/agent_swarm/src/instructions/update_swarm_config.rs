@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::manifest::KNOWN_CAPABILITIES;
+use crate::ExecutionMode;
+use crate::events::SwarmConfigUpdated;
+
+/// Optional swarm parameter updates; `None` leaves a value unchanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct SwarmConfigParams {
+    pub elo_blend_bps: Option<u16>,
+    pub execution_guard: Option<bool>,
+    pub execution_mode: Option<ExecutionMode>,
+    pub executor_window: Option<i64>,
+    pub required_capabilities: Option<u32>,
+    pub min_description_length: Option<u16>,
+    pub require_typed_payloads: Option<bool>,
+    pub voter_reward: Option<u64>,
+    /// `Some(None)` switches voter rewards back to SOL
+    pub voter_reward_mint: Option<Option<Pubkey>>,
+    pub enforce_exposure_limits: Option<bool>,
+    pub slash_treasury_bps: Option<u16>,
+    pub authority_inactivity_period: Option<i64>,
+    pub marketplace_fee_bps: Option<u16>,
+    /// Lamports shared by agents' performance points each reward epoch
+    pub reward_budget: Option<u64>,
+    pub reward_epoch_length: Option<i64>,
+    pub governance_mint: Option<Option<Pubkey>>,
+    pub ve_max_boost_power: Option<u64>,
+    pub emission_per_epoch: Option<u64>,
+    pub emission_max_supply: Option<u64>,
+    /// 0 lets sellers claim immediately and disables refunds
+    pub sale_dispute_window: Option<i64>,
+    pub min_health_factor_bps: Option<u16>,
+    pub max_execution_attempts: Option<u8>,
+    /// Lamports paid into the treasury per proposal before congestion; 0
+    /// makes proposing free
+    pub proposal_deposit: Option<u64>,
+    /// Recent proposals per doubling of the deposit; 0 never escalates
+    pub congestion_threshold: Option<u16>,
+    pub congestion_half_life: Option<i64>,
+    /// Lamports paid per `ArbReport` an executed trade follows
+    pub arb_report_bounty: Option<u64>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSwarmConfig<'info> {
+    #[account(
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, swarm_state.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    #[account(constraint = authority.key() == swarm_state.authority @ SwarmError::Unauthorized)]
+    pub authority: Signer<'info>,
+}
+
+pub fn update_swarm_config(
+    ctx: Context<UpdateSwarmConfig>,
+    params: SwarmConfigParams,
+) -> Result<()> {
+    let swarm_state = &mut ctx.accounts.swarm_state;
+
+    if let Some(elo_blend_bps) = params.elo_blend_bps {
+        require!(elo_blend_bps <= BPS_DENOMINATOR, SwarmError::InvalidConfig);
+        swarm_state.elo_blend_bps = elo_blend_bps;
+    }
+
+    if let Some(execution_guard) = params.execution_guard {
+        swarm_state.execution_guard = execution_guard;
+    }
+
+    if let Some(execution_mode) = params.execution_mode {
+        swarm_state.execution_mode = execution_mode;
+    }
+
+    if let Some(executor_window) = params.executor_window {
+        require!(
+            executor_window > 0 && executor_window <= swarm_state.proposal_timeout,
+            SwarmError::InvalidConfig
+        );
+        swarm_state.executor_window = executor_window;
+    }
+
+    if let Some(required_capabilities) = params.required_capabilities {
+        require!(
+            required_capabilities & !KNOWN_CAPABILITIES == 0,
+            SwarmError::InvalidConfig
+        );
+        swarm_state.required_capabilities = required_capabilities;
+    }
+
+    if let Some(min_description_length) = params.min_description_length {
+        require!(
+            min_description_length as usize <= MAX_DESCRIPTION_LENGTH,
+            SwarmError::InvalidConfig
+        );
+        swarm_state.min_description_length = min_description_length;
+    }
+
+    if let Some(require_typed_payloads) = params.require_typed_payloads {
+        swarm_state.require_typed_payloads = require_typed_payloads;
+    }
+
+    if let Some(voter_reward) = params.voter_reward {
+        swarm_state.voter_reward = voter_reward;
+    }
+
+    if let Some(voter_reward_mint) = params.voter_reward_mint {
+        swarm_state.voter_reward_mint = voter_reward_mint;
+    }
+
+    if let Some(enforce_exposure_limits) = params.enforce_exposure_limits {
+        swarm_state.enforce_exposure_limits = enforce_exposure_limits;
+    }
+
+    if let Some(slash_treasury_bps) = params.slash_treasury_bps {
+        require!(slash_treasury_bps <= BPS_DENOMINATOR, SwarmError::InvalidConfig);
+        swarm_state.slash_treasury_bps = slash_treasury_bps;
+    }
+
+    if let Some(authority_inactivity_period) = params.authority_inactivity_period {
+        require!(authority_inactivity_period > 0, SwarmError::InvalidConfig);
+        swarm_state.authority_inactivity_period = authority_inactivity_period;
+    }
+
+    if let Some(marketplace_fee_bps) = params.marketplace_fee_bps {
+        require!(marketplace_fee_bps <= BPS_DENOMINATOR, SwarmError::InvalidConfig);
+        swarm_state.marketplace_fee_bps = marketplace_fee_bps;
+    }
+
+    if let Some(reward_budget) = params.reward_budget {
+        swarm_state.rewards.budget = reward_budget;
+    }
+
+    if let Some(reward_epoch_length) = params.reward_epoch_length {
+        require!(reward_epoch_length > 0, SwarmError::InvalidConfig);
+        swarm_state.rewards.epoch_length = reward_epoch_length;
+    }
+
+    if let Some(governance_mint) = params.governance_mint {
+        swarm_state.governance_mint = governance_mint;
+    }
+
+    if let Some(ve_max_boost_power) = params.ve_max_boost_power {
+        swarm_state.ve_max_boost_power = ve_max_boost_power;
+    }
+
+    if let Some(emission_per_epoch) = params.emission_per_epoch {
+        swarm_state.emission.per_epoch = emission_per_epoch;
+    }
+
+    if let Some(emission_max_supply) = params.emission_max_supply {
+        swarm_state.emission.max_supply = emission_max_supply;
+    }
+
+    if let Some(sale_dispute_window) = params.sale_dispute_window {
+        require!(sale_dispute_window >= 0, SwarmError::InvalidConfig);
+        swarm_state.sale_dispute_window = sale_dispute_window;
+    }
+
+    if let Some(min_health_factor_bps) = params.min_health_factor_bps {
+        require!(min_health_factor_bps >= BPS_DENOMINATOR, SwarmError::InvalidConfig);
+        swarm_state.min_health_factor_bps = min_health_factor_bps;
+    }
+
+    if let Some(max_execution_attempts) = params.max_execution_attempts {
+        require!(max_execution_attempts > 0, SwarmError::InvalidConfig);
+        swarm_state.max_execution_attempts = max_execution_attempts;
+    }
+
+    if let Some(proposal_deposit) = params.proposal_deposit {
+        swarm_state.congestion.base_deposit = proposal_deposit;
+    }
+
+    if let Some(congestion_threshold) = params.congestion_threshold {
+        swarm_state.congestion.threshold = congestion_threshold;
+    }
+
+    if let Some(congestion_half_life) = params.congestion_half_life {
+        require!(congestion_half_life > 0, SwarmError::InvalidConfig);
+        // Settle the decay owed under the old half-life first
+        let current_time = Clock::get()?.unix_timestamp;
+        swarm_state.congestion.decay(current_time);
+        swarm_state.congestion.half_life = congestion_half_life;
+        swarm_state.congestion.decayed_at = current_time;
+    }
+
+    if let Some(arb_report_bounty) = params.arb_report_bounty {
+        swarm_state.arb_report_bounty = arb_report_bounty;
+    }
+
+    swarm_state.authority_last_active = Clock::get()?.unix_timestamp;
+    ctx.accounts.audit_log.record(
+        ctx.accounts.authority.key(),
+        AuditAction::SwarmConfigUpdated,
+        swarm_state.key(),
+        swarm_state.authority_last_active,
+    );
+
+    emit!(SwarmConfigUpdated {
+        params,
+        timestamp: swarm_state.authority_last_active,
+    });
+    msg!("Swarm config updated");
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::constants::*;
+use crate::cpi::CPIHelper;
+use crate::errors::SwarmError;
+use crate::events::{LockExtended, LockedTokensWithdrawn, TokensLocked};
+
+/// Lock governance tokens for a vote weight boost that decays with the
+/// remaining lock time. Tokens sit in an associated token account owned by
+/// the `VoteEscrow` PDA.
+#[derive(Accounts)]
+pub struct LockTokens<'info> {
+    #[account(
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        constraint = Some(mint.key()) == swarm_state.governance_mint @ SwarmError::InvalidParameter
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = VoteEscrow::LEN,
+        seeds = [VOTE_ESCROW_SEED, swarm_state.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub vote_escrow: Account<'info, VoteEscrow>,
+    
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = vote_escrow
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ SwarmError::Unauthorized
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    #[account(
+        mut,
+        seeds = [VOTE_ESCROW_SEED, vote_escrow.swarm.as_ref(), owner.key().as_ref()],
+        bump = vote_escrow.bump
+    )]
+    pub vote_escrow: Account<'info, VoteEscrow>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawLockedTokens<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [VOTE_ESCROW_SEED, vote_escrow.swarm.as_ref(), owner.key().as_ref()],
+        bump = vote_escrow.bump
+    )]
+    pub vote_escrow: Account<'info, VoteEscrow>,
+    
+    #[account(
+        mut,
+        associated_token::mint = vote_escrow.mint,
+        associated_token::authority = vote_escrow
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ SwarmError::Unauthorized
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn lock_tokens(ctx: Context<LockTokens>, amount: u64, duration: i64) -> Result<()> {
+    require!(amount > 0, SwarmError::InvalidParameter);
+    require!(
+        (MIN_VOTE_ESCROW_LOCK..=MAX_VOTE_ESCROW_LOCK).contains(&duration),
+        SwarmError::InvalidLockDuration
+    );
+
+    CPIHelper::transfer_tokens(
+        &ctx.accounts.owner_token_account,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.token_program,
+        amount,
+        &[],
+    )?;
+
+    let clock = Clock::get()?;
+    let vote_escrow = &mut ctx.accounts.vote_escrow;
+    vote_escrow.swarm = ctx.accounts.swarm_state.key();
+    vote_escrow.owner = ctx.accounts.owner.key();
+    vote_escrow.mint = ctx.accounts.mint.key();
+    vote_escrow.amount = amount;
+    vote_escrow.locked_at = clock.unix_timestamp;
+    vote_escrow.lock_end = clock.unix_timestamp
+        .checked_add(duration)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    vote_escrow.version = VoteEscrow::VERSION;
+    vote_escrow.bump = ctx.bumps.vote_escrow;
+
+    emit!(TokensLocked {
+        owner: vote_escrow.owner,
+        mint: vote_escrow.mint,
+        amount,
+        lock_end: vote_escrow.lock_end,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Locked {} tokens until {}", amount, vote_escrow.lock_end);
+    Ok(())
+}
+
+pub fn extend_lock(ctx: Context<ExtendLock>, lock_end: i64) -> Result<()> {
+    let vote_escrow = &mut ctx.accounts.vote_escrow;
+    let clock = Clock::get()?;
+
+    require!(
+        lock_end > vote_escrow.lock_end
+            && lock_end <= clock.unix_timestamp.saturating_add(MAX_VOTE_ESCROW_LOCK),
+        SwarmError::InvalidLockDuration
+    );
+
+    vote_escrow.lock_end = lock_end;
+
+    emit!(LockExtended {
+        owner: vote_escrow.owner,
+        lock_end,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Lock extended until {}", lock_end);
+    Ok(())
+}
+
+pub fn withdraw_locked_tokens(ctx: Context<WithdrawLockedTokens>) -> Result<()> {
+    let vote_escrow = &ctx.accounts.vote_escrow;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= vote_escrow.lock_end,
+        SwarmError::TokensStillLocked
+    );
+
+    let swarm_key = vote_escrow.swarm;
+    let owner_key = vote_escrow.owner;
+    let bump = [vote_escrow.bump];
+    let signer_seeds: &[&[&[u8]]] =
+        &[&[VOTE_ESCROW_SEED, swarm_key.as_ref(), owner_key.as_ref(), &bump]];
+
+    CPIHelper::transfer_tokens(
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.owner_token_account,
+        &vote_escrow.to_account_info(),
+        &ctx.accounts.token_program,
+        ctx.accounts.escrow_token_account.amount,
+        signer_seeds,
+    )?;
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_token_account.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: vote_escrow.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    emit!(LockedTokensWithdrawn {
+        owner: owner_key,
+        amount: vote_escrow.amount,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Withdrew {} locked tokens", vote_escrow.amount);
+    Ok(())
+}
@@ -1,29 +1,62 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::constants::*;
-use crate::errors::SwarmError;
+use crate::errors::{with_context, FailedCheck, SwarmError};
+use crate::require_ctx;
 use crate::VoteType;
+use crate::events::VoteCast;
 
 #[derive(Accounts)]
 pub struct VoteProposal<'info> {
     #[account(
-        seeds = [SWARM_SEED],
+        mut,
+        seeds = [SWARM_SEED, swarm_state.swarm_id.to_le_bytes().as_ref()],
         bump = swarm_state.bump
     )]
     pub swarm_state: Account<'info, SwarmState>,
     
     #[account(
         mut,
-        seeds = [AGENT_SEED, voter.key().as_ref()],
-        bump = agent.bump,
-        constraint = agent.is_active @ SwarmError::Unauthorized
+        seeds = [AGENT_SEED, swarm_state.key().as_ref(), agent.load()?.owner.as_ref()],
+        bump = agent.load()?.bump,
+        constraint = agent.load()?.signing_key == voter.key() @ SwarmError::Unauthorized,
+        constraint = agent.load()?.is_active() @ SwarmError::Unauthorized
     )]
-    pub agent: Account<'info, Agent>,
+    pub agent: AccountLoader<'info, Agent>,
     
-    #[account(mut)]
-    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        constraint = proposal.load()?.swarm == swarm_state.key() @ SwarmError::SwarmMismatch
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    
+    #[account(
+        init,
+        payer = voter,
+        space = VoteRecord::LEN,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), agent.load()?.owner.as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
     
+    #[account(mut)]
     pub voter: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Boosts the vote weight when present
+    #[account(
+        seeds = [VOTE_ESCROW_SEED, swarm_state.key().as_ref(), voter.key().as_ref()],
+        bump = vote_escrow.bump
+    )]
+    pub vote_escrow: Option<Account<'info, VoteEscrow>>,
+    
+    /// Required for proposals scoped to a committee, whose members alone vote
+    #[account(
+        seeds = [COMMITTEE_SEED, swarm_state.key().as_ref(), &[committee.committee_id]],
+        bump = committee.bump
+    )]
+    pub committee: Option<Account<'info, Committee>>,
 }
 
 pub fn vote_proposal(
@@ -31,39 +64,93 @@ pub fn vote_proposal(
     vote: VoteType,
     reasoning: String,
 ) -> Result<()> {
-    require!(
+    require_ctx!(
         reasoning.len() <= MAX_REASONING_LENGTH,
-        SwarmError::ReasoningTooLong
+        SwarmError::ReasoningTooLong,
+        FailedCheck::ReasoningLength,
+        MAX_REASONING_LENGTH,
+        reasoning.len()
     );
 
-    let proposal = &mut ctx.accounts.proposal;
-    let agent = &mut ctx.accounts.agent;
+    let mut proposal = ctx.accounts.proposal.load_mut()?;
+    let mut agent = ctx.accounts.agent.load_mut()?;
     let clock = Clock::get()?;
 
     require!(
-        !proposal.executed,
+        !proposal.is_executed(),
         SwarmError::ProposalAlreadyExecuted
     );
 
-    require!(
+    require_ctx!(
         !proposal.is_expired(clock.unix_timestamp),
-        SwarmError::ProposalExpired
+        SwarmError::ProposalExpired,
+        FailedCheck::ProposalExpiry,
+        proposal.expires_at,
+        clock.unix_timestamp
     );
 
+    require!(proposal.is_data_complete(), SwarmError::ProposalDataIncomplete);
+
     require!(
-        !proposal.has_voted(&ctx.accounts.voter.key()),
+        !proposal.has_voted(&agent.owner),
         SwarmError::AlreadyVoted
     );
 
-    let vote_weight = agent.vote_weight();
+    if let Some(committee) = Committee::for_proposal(&proposal, ctx.accounts.committee.as_deref())? {
+        require!(committee.admits(agent.agent_type()), SwarmError::InvalidAgentType);
+    }
+
+    // Agents on sabbatical carry no vote weight
+    require!(
+        !agent.is_on_sabbatical(clock.unix_timestamp),
+        SwarmError::OnSabbatical
+    );
+
+    let swarm_state = &mut ctx.accounts.swarm_state;
+    let mut vote_weight = agent.blended_vote_weight(swarm_state.elo_blend_bps);
+    if let Some(vote_escrow) = ctx.accounts.vote_escrow.as_ref() {
+        let boost_bps = vote_escrow.boost_bps(swarm_state.ve_max_boost_power, clock.unix_timestamp);
+        vote_weight = u32::try_from(
+            vote_weight as u64 * (BPS_DENOMINATOR + boost_bps) as u64 / BPS_DENOMINATOR as u64,
+        )
+        .map_err(|_| error!(SwarmError::ArithmeticOverflow))?;
+    }
     proposal
-        .record_vote(&ctx.accounts.voter.key(), vote.clone(), vote_weight)
-        .map_err(|_| SwarmError::MaxAgentsReached)?;
+        .record_vote(&agent.owner, vote.clone(), vote_weight)
+        .map_err(|_| with_context(
+            SwarmError::MaxAgentsReached,
+            FailedCheck::VoterCapacity,
+            Proposal::MAX_VOTERS as i64,
+            proposal.voters().len() as i64,
+        ))?;
+    proposal.unsettled_votes += 1;
+
+    let vote_record = &mut ctx.accounts.vote_record;
+    vote_record.proposal = ctx.accounts.proposal.key();
+    vote_record.voter = agent.owner;
+    vote_record.vote = vote.clone();
+    vote_record.weight = vote_weight;
+    vote_record.voted_at = clock.unix_timestamp;
+    vote_record.settled = false;
+    vote_record.reward_claimed = false;
+    vote_record.set_reasoning(&reasoning);
+    vote_record.version = VoteRecord::VERSION;
+    vote_record.bump = ctx.bumps.vote_record;
 
-    agent.votes_cast += 1;
+    agent.votes_cast = agent.votes_cast.saturating_add(1);
+    swarm_state.rewards.votes = swarm_state.rewards.votes.saturating_add(1);
     agent.last_active = clock.unix_timestamp;
 
-    msg!("Vote recorded: {:?} with weight {} - {}", vote, vote_weight, reasoning);
+    emit!(VoteCast {
+        proposal: ctx.accounts.proposal.key(),
+        voter: agent.owner,
+        vote: vote.clone(),
+        weight: vote_weight,
+        reasoning_hash: vote_record.reasoning_hash,
+        reasoning,
+        timestamp: clock.unix_timestamp,
+    });
+    msg!("Vote recorded: {:?} with weight {}", vote, vote_weight);
 
     Ok(())
 }
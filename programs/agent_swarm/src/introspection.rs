@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_lang::Discriminator;
+use crate::cpi::solend;
+use crate::errors::SwarmError;
+
+/// Ed25519 signature verification program ID
+pub const ED25519_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+/// Compute Budget program ID (compile-time constant — no runtime unwrap)
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// Kamino Lend program ID (compile-time constant — no runtime unwrap)
+pub const KAMINO_LEND_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("KLend2g3cP87fffoy8q1mQqGKjrxjC8boSyAYavgmjD");
+
+/// marginfi v2 program ID (compile-time constant — no runtime unwrap)
+pub const MARGINFI_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("MFv2hWf31Z9kbCa1snEPYctwafyhdvnV7FZnsebVacA");
+
+const KAMINO_FLASH_BORROW_DISCRIMINATOR: [u8; 8] = [135, 231, 52, 167, 7, 52, 212, 193];
+const MARGINFI_START_FLASHLOAN_DISCRIMINATOR: [u8; 8] = [14, 131, 33, 220, 81, 186, 180, 107];
+
+/// Ed25519 instruction data: signature count, padding, then one 14-byte
+/// offsets record per signature
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+/// Offsets instruction index meaning the ed25519 instruction's own data
+const ED25519_SELF_INDEX: u16 = u16::MAX;
+
+/// Programs allowed to appear in the same transaction as a guarded execution
+pub fn is_trusted_program(program_id: &Pubkey) -> bool {
+    *program_id == crate::ID || *program_id == COMPUTE_BUDGET_PROGRAM_ID
+}
+
+/// Reject the transaction if any instruction other than the current one
+/// targets an untrusted program.
+///
+/// A proposal's swap CPIs can be sandwiched by instructions placed before or
+/// after `execute_proposal` in the same transaction (price pushes, balance
+/// manipulation). Walking the instructions sysvar lets the swarm refuse to
+/// execute inside such a transaction.
+pub fn assert_no_untrusted_instructions(instructions_sysvar: &AccountInfo) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    let mut index = 0;
+
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        if index != current_index && !is_trusted_program(&instruction.program_id) {
+            msg!(
+                "Untrusted instruction {} targets program {}",
+                index,
+                instruction.program_id
+            );
+            return err!(SwarmError::UntrustedInstruction);
+        }
+        index += 1;
+    }
+
+    Ok(())
+}
+
+/// Whether `instruction` takes a flash loan from a known lender
+pub fn is_flash_borrow(instruction: &Instruction) -> bool {
+    let data = &instruction.data;
+    match instruction.program_id {
+        id if id == solend::PROGRAM_ID => {
+            data.first() == Some(&solend::FLASH_BORROW_RESERVE_LIQUIDITY_TAG)
+        }
+        id if id == KAMINO_LEND_PROGRAM_ID => data.starts_with(&KAMINO_FLASH_BORROW_DISCRIMINATOR),
+        id if id == MARGINFI_PROGRAM_ID => data.starts_with(&MARGINFI_START_FLASHLOAN_DISCRIMINATOR),
+        _ => false,
+    }
+}
+
+/// Reject an execution reached through another program, a transaction that
+/// executes more than one proposal, and one that takes a flash loan before
+/// executing.
+///
+/// Borrowed liquidity can move the pools and oracles a proposal trades
+/// against and be repaid in the same transaction. Lenders' borrow
+/// instructions don't reliably name the borrowed mint, so any flash loan is
+/// refused rather than only those against the vault's mints.
+pub fn assert_no_reentry(instructions_sysvar: &AccountInfo) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    let current = load_instruction_at_checked(current_index, instructions_sysvar)?;
+    require_keys_eq!(current.program_id, crate::ID, SwarmError::ExecutionViaCpi);
+
+    let mut index = 0;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        if index != current_index
+            && instruction.program_id == crate::ID
+            && instruction.data.starts_with(&crate::instruction::ExecuteProposal::DISCRIMINATOR)
+        {
+            msg!("Instruction {} executes another proposal", index);
+            return err!(SwarmError::DuplicateExecution);
+        }
+        if index < current_index && is_flash_borrow(&instruction) {
+            msg!("Instruction {} takes a flash loan from {}", index, instruction.program_id);
+            return err!(SwarmError::FlashLoanInTransaction);
+        }
+        index += 1;
+    }
+
+    Ok(())
+}
+
+/// Require the instruction just before the current one to be an ed25519
+/// program check of a single signature by `signer` over `message`.
+///
+/// The ed25519 program fails the whole transaction on a bad signature, so
+/// finding it with the expected key and message proves the signature. Its
+/// offsets must point into its own data; otherwise the key and message it
+/// checked could be read from another instruction than the one inspected.
+pub fn assert_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    require!(current_index > 0, SwarmError::InvalidSignature);
+    let instruction = load_instruction_at_checked(current_index - 1, instructions_sysvar)?;
+    require_keys_eq!(instruction.program_id, ED25519_PROGRAM_ID, SwarmError::InvalidSignature);
+
+    let data = &instruction.data;
+    require!(
+        data.len() >= ED25519_OFFSETS_START + ED25519_OFFSETS_LEN && data[0] == 1,
+        SwarmError::InvalidSignature
+    );
+    let offset = |field: usize| {
+        let at = ED25519_OFFSETS_START + 2 * field;
+        u16::from_le_bytes([data[at], data[at + 1]])
+    };
+    // signature, public key and message instruction indices
+    require!(
+        [offset(1), offset(3), offset(6)].iter().all(|&index| index == ED25519_SELF_INDEX),
+        SwarmError::InvalidSignature
+    );
+
+    let key_start = offset(2) as usize;
+    let message_start = offset(4) as usize;
+    let signed_key = data.get(key_start..key_start + 32);
+    let signed_message = data.get(message_start..message_start + offset(5) as usize);
+    require!(
+        signed_key == Some(signer.as_ref()) && signed_message == Some(message),
+        SwarmError::InvalidSignature
+    );
+
+    Ok(())
+}
@@ -5,8 +5,21 @@ pub mod state;
 pub mod errors;
 pub mod constants;
 pub mod cpi;
+pub mod events;
+pub mod introspection;
+pub mod oracle;
+pub mod manifest;
+pub mod marketplace;
+pub mod auction;
+pub mod payloads;
+pub mod pda;
+pub mod risk;
 
 use instructions::*;
+use marketplace::*;
+use auction::*;
+use oracle::{OracleSource, PriceCondition};
+use state::{BadgeKind, CoalitionRules, TaskKind};
 
 declare_id!("56Vy8e8V4E6UZnsa6uDRg8HFiPwroz6nRKh7rm9xAfeK");
 
@@ -19,14 +32,15 @@ declare_id!("56Vy8e8V4E6UZnsa6uDRg8HFiPwroz6nRKh7rm9xAfeK");
 pub mod agent_swarm {
     use super::*;
 
-    /// Initialize the agent swarm with configuration parameters
+    /// Initialize swarm `swarm_id` with configuration parameters
     pub fn initialize(
         ctx: Context<Initialize>,
+        swarm_id: u64,
         max_agents: u8,
         min_votes_required: u8,
         proposal_timeout: i64,
     ) -> Result<()> {
-        instructions::initialize(ctx, max_agents, min_votes_required, proposal_timeout)
+        instructions::initialize(ctx, swarm_id, max_agents, min_votes_required, proposal_timeout)
     }
 
     /// Register a new agent in the swarm
@@ -34,8 +48,36 @@ pub mod agent_swarm {
         ctx: Context<RegisterAgent>,
         agent_type: AgentType,
         name: String,
+        manifest: Vec<u8>,
     ) -> Result<()> {
-        instructions::register_agent(ctx, agent_type, name)
+        instructions::register_agent(ctx, agent_type, name, manifest)
+    }
+
+    /// Define what an `AgentType::Custom` id means in this swarm (authority
+    /// co-signs)
+    pub fn register_custom_agent_type(
+        ctx: Context<RegisterCustomAgentType>,
+        id: u8,
+        name: String,
+        schema_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::register_custom_agent_type(ctx, id, name, schema_hash)
+    }
+
+    /// Retire an agent from the swarm (owner only)
+    pub fn deregister_agent(ctx: Context<DeregisterAgent>) -> Result<()> {
+        instructions::deregister_agent(ctx)
+    }
+
+    /// Replace an agent's signing key, keeping its account and history; the
+    /// new key and any guardian co-sign
+    pub fn rotate_owner_key(ctx: Context<RotateOwnerKey>) -> Result<()> {
+        instructions::rotate_owner_key(ctx)
+    }
+
+    /// Set or clear the guardian co-signing an agent's key rotations
+    pub fn set_agent_guardian(ctx: Context<SetAgentGuardian>, guardian: Option<Pubkey>) -> Result<()> {
+        instructions::set_agent_guardian(ctx, guardian)
     }
 
     /// Create a new proposal for agent coordination
@@ -44,8 +86,44 @@ pub mod agent_swarm {
         proposal_type: ProposalType,
         data: Vec<u8>,
         description: String,
+        objective: Option<[u8; 32]>,
+        total_data_len: u16,
+        data_hash: Option<[u8; 32]>,
+        condition: Option<PriceCondition>,
+        tags: Vec<String>,
     ) -> Result<()> {
-        instructions::create_proposal(ctx, proposal_type, data, description)
+        instructions::create_proposal(
+            ctx,
+            proposal_type,
+            data,
+            description,
+            objective,
+            total_data_len,
+            data_hash,
+            condition,
+            tags,
+        )
+    }
+
+    /// Upload more of a proposal's payload, growing the account to fit
+    /// (proposer only, before voting opens)
+    pub fn extend_proposal_data(ctx: Context<ExtendProposalData>, chunk: Vec<u8>) -> Result<()> {
+        instructions::extend_proposal_data(ctx, chunk)
+    }
+
+    /// Stage the payload of a hash-committed proposal for execution
+    pub fn create_payload_buffer(ctx: Context<CreatePayloadBuffer>, chunk: Vec<u8>) -> Result<()> {
+        instructions::create_payload_buffer(ctx, chunk)
+    }
+
+    /// Upload more of a staged payload, growing the buffer to fit
+    pub fn extend_payload_buffer(ctx: Context<ExtendPayloadBuffer>, chunk: Vec<u8>) -> Result<()> {
+        instructions::extend_payload_buffer(ctx, chunk)
+    }
+
+    /// Reclaim the rent of a staged payload that was not executed
+    pub fn close_payload_buffer(ctx: Context<ClosePayloadBuffer>) -> Result<()> {
+        instructions::close_payload_buffer(ctx)
     }
 
     /// Vote on an existing proposal
@@ -58,7 +136,9 @@ pub mod agent_swarm {
     }
 
     /// Execute an approved proposal
-    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+    ) -> Result<()> {
         instructions::execute_proposal(ctx)
     }
 
@@ -70,6 +150,11 @@ pub mod agent_swarm {
         instructions::update_reputation(ctx, performance_score)
     }
 
+    /// Decay the reputation of an agent idle through a finished epoch (permissionless)
+    pub fn decay_reputation(ctx: Context<DecayReputation>, epoch: u64) -> Result<()> {
+        instructions::decay_reputation(ctx, epoch)
+    }
+
     /// Record execution outcome for learning
     pub fn record_outcome(
         ctx: Context<RecordOutcome>,
@@ -78,6 +163,612 @@ pub mod agent_swarm {
     ) -> Result<()> {
         instructions::record_outcome(ctx, success, metrics)
     }
+
+    /// Record a failed execution attempt, reported by the assigned executor
+    /// or the proposer; the proposal fails once the swarm's maximum attempts
+    /// are reached
+    pub fn report_execution_failure(
+        ctx: Context<ReportExecutionFailure>,
+        error_code: u32,
+    ) -> Result<()> {
+        instructions::report_execution_failure(ctx, error_code)
+    }
+
+    /// Close a failed proposal, returning its rent to the proposer
+    pub fn close_failed_proposal(ctx: Context<CloseFailedProposal>) -> Result<()> {
+        instructions::close_failed_proposal(ctx)
+    }
+
+    /// Fold an outcome past its retention period into the outcome archive
+    /// and close its account
+    pub fn archive_outcome(ctx: Context<ArchiveOutcome>) -> Result<()> {
+        instructions::archive_outcome(ctx)
+    }
+
+    /// Settle a vote against the recorded outcome, adjusting the voter's reputation
+    pub fn settle_vote(ctx: Context<SettleVote>) -> Result<()> {
+        instructions::settle_vote(ctx)
+    }
+
+    /// Score two proposals that competed for the same objective
+    pub fn settle_elo_match(ctx: Context<SettleEloMatch>) -> Result<()> {
+        instructions::settle_elo_match(ctx)
+    }
+
+    /// Update tunable swarm parameters (authority only)
+    pub fn update_swarm_config(
+        ctx: Context<UpdateSwarmConfig>,
+        params: SwarmConfigParams,
+    ) -> Result<()> {
+        instructions::update_swarm_config(ctx, params)
+    }
+
+    /// Assign the agent expected to execute a proposal
+    pub fn assign_executor(ctx: Context<AssignExecutor>) -> Result<()> {
+        instructions::assign_executor(ctx)
+    }
+
+    /// Hold a proposal's execution until a set time and queue it for keepers
+    /// (proposer only, before voting starts)
+    pub fn schedule_execution(ctx: Context<ScheduleExecution>, execute_not_before: i64) -> Result<()> {
+        instructions::schedule_execution(ctx, execute_not_before)
+    }
+
+    /// Make a proposal wait on a proposal in another swarm being approved
+    /// there (proposer only, before voting starts)
+    pub fn link_proposal(ctx: Context<LinkProposal>) -> Result<()> {
+        instructions::link_proposal(ctx)
+    }
+
+    /// Create the next fast-tracked proposal of a due rebalance schedule
+    /// (permissionless)
+    pub fn trigger_rebalance(ctx: Context<TriggerRebalance>) -> Result<()> {
+        instructions::trigger_rebalance(ctx)
+    }
+
+    /// Fire a standing order whose price trigger is met (permissionless)
+    pub fn trigger_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, TriggerOrder<'info>>,
+    ) -> Result<()> {
+        instructions::trigger_order(ctx)
+    }
+
+    /// Close a standing order that lapsed unfired (permissionless)
+    pub fn close_expired_order(ctx: Context<CloseExpiredOrder>) -> Result<()> {
+        instructions::close_expired_order(ctx)
+    }
+
+    /// Swap the next due leg of a DCA schedule (permissionless)
+    pub fn execute_dca_leg<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteDcaLeg<'info>>,
+    ) -> Result<()> {
+        instructions::execute_dca_leg(ctx)
+    }
+
+    /// Collect fees from the registered positions and swap a harvest worth
+    /// no more than the crank threshold into the base asset (permissionless)
+    pub fn crank_harvest<'info>(
+        ctx: Context<'_, '_, '_, 'info, CrankHarvest<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::crank_harvest(ctx, amount)
+    }
+
+    /// Sweep the insurance fund's share of new protocol fees out of the
+    /// treasury (permissionless)
+    pub fn fund_insurance(ctx: Context<FundInsurance>) -> Result<()> {
+        instructions::fund_insurance(ctx)
+    }
+
+    /// Mark the portfolio's oracle value for the circuit breaker, pausing
+    /// trading on a deep enough drawdown (permissionless)
+    pub fn mark_portfolio_value<'info>(
+        ctx: Context<'_, '_, '_, 'info, MarkPortfolioValue<'info>>,
+    ) -> Result<()> {
+        instructions::mark_portfolio_value(ctx)
+    }
+
+    /// Report an arbitrage route ahead of the trade (Arbitrage agents only)
+    pub fn submit_arb_report(
+        ctx: Context<SubmitArbReport>,
+        route_hash: [u8; 32],
+        buy_venue: Pubkey,
+        sell_venue: Pubkey,
+        expected_edge_bps: u16,
+    ) -> Result<()> {
+        instructions::submit_arb_report(ctx, route_hash, buy_venue, sell_venue, expected_edge_bps)
+    }
+
+    /// Close a credited or expired arbitrage report (permissionless)
+    pub fn close_arb_report(ctx: Context<CloseArbReport>) -> Result<()> {
+        instructions::close_arb_report(ctx)
+    }
+
+    /// Hand out an analysis or monitoring task (Consensus agents only)
+    pub fn create_task(
+        ctx: Context<CreateTask>,
+        task_id: u64,
+        kind: TaskKind,
+        target: Pubkey,
+        description: String,
+        reputation_reward: u16,
+        claim_timeout: i64,
+    ) -> Result<()> {
+        instructions::create_task(ctx, task_id, kind, target, description, reputation_reward, claim_timeout)
+    }
+
+    /// Claim an open or lapsed task (Analytics and Execution agents)
+    pub fn claim_task(ctx: Context<ClaimTask>) -> Result<()> {
+        instructions::claim_task(ctx)
+    }
+
+    /// Submit the hash of a claimed task's result (claimant only)
+    pub fn complete_task(ctx: Context<CompleteTask>, result_hash: [u8; 32]) -> Result<()> {
+        instructions::complete_task(ctx, result_hash)
+    }
+
+    /// Accept a completed task, crediting the claimant's reputation (creator only)
+    pub fn accept_task(ctx: Context<AcceptTask>) -> Result<()> {
+        instructions::accept_task(ctx)
+    }
+
+    /// Close a task without credit unless a live claim holds it (creator only)
+    pub fn cancel_task(ctx: Context<CancelTask>) -> Result<()> {
+        instructions::cancel_task(ctx)
+    }
+
+    /// Create or reconfigure a committee that alone handles the given
+    /// proposal types with its own quorum (authority only)
+    pub fn configure_committee(
+        ctx: Context<ConfigureCommittee>,
+        committee_id: u8,
+        name: String,
+        agent_types: Vec<AgentType>,
+        proposal_types: Vec<ProposalType>,
+        min_votes: u8,
+    ) -> Result<()> {
+        instructions::configure_committee(ctx, committee_id, name, agent_types, proposal_types, min_votes)
+    }
+
+    /// Form a coalition of agents with its own rules (Governance agents only)
+    pub fn create_coalition(
+        ctx: Context<CreateCoalition>,
+        coalition_id: u64,
+        name: String,
+        rules: CoalitionRules,
+    ) -> Result<()> {
+        instructions::create_coalition(ctx, coalition_id, name, rules)
+    }
+
+    /// Join a coalition, with the founder's signature unless membership is open
+    pub fn join_coalition(ctx: Context<JoinCoalition>) -> Result<()> {
+        instructions::join_coalition(ctx)
+    }
+
+    /// Leave a coalition
+    pub fn leave_coalition(ctx: Context<LeaveCoalition>) -> Result<()> {
+        instructions::leave_coalition(ctx)
+    }
+
+    /// Change a coalition's internal rules (founder only)
+    pub fn update_coalition_rules(
+        ctx: Context<UpdateCoalitionRules>,
+        rules: CoalitionRules,
+    ) -> Result<()> {
+        instructions::update_coalition_rules(ctx, rules)
+    }
+
+    /// Add SOL to a coalition's shared treasury
+    pub fn deposit_coalition_funds(ctx: Context<DepositCoalitionFunds>, lamports: u64) -> Result<()> {
+        instructions::deposit_coalition_funds(ctx, lamports)
+    }
+
+    /// Pay SOL from a coalition's treasury, signed by the rules' share of members
+    pub fn withdraw_coalition_funds(ctx: Context<WithdrawCoalitionFunds>, lamports: u64) -> Result<()> {
+        instructions::withdraw_coalition_funds(ctx, lamports)
+    }
+
+    /// Record a member's internal vote on how its coalition should vote
+    pub fn coalition_vote(ctx: Context<CoalitionVote>, vote: VoteType) -> Result<()> {
+        instructions::coalition_vote(ctx, vote)
+    }
+
+    /// Cast a coalition's decided position for its members (permissionless)
+    pub fn cast_coalition_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CastCoalitionVote<'info>>,
+    ) -> Result<()> {
+        instructions::cast_coalition_vote(ctx)
+    }
+
+    /// Commit to a secret seeding a proposal's randomness (any active agent)
+    pub fn commit_randomness(ctx: Context<CommitRandomness>, commitment: [u8; 32]) -> Result<()> {
+        instructions::commit_randomness(ctx, commitment)
+    }
+
+    /// Reveal a randomness commit, mixing in the target slot's hash (committer only)
+    pub fn reveal_randomness(ctx: Context<RevealRandomness>, secret: [u8; 32]) -> Result<()> {
+        instructions::reveal_randomness(ctx, secret)
+    }
+
+    /// Close an unrevealed randomness commit after its reveal window (permissionless)
+    pub fn expire_randomness(ctx: Context<ExpireRandomness>) -> Result<()> {
+        instructions::expire_randomness(ctx)
+    }
+
+    /// Assign the Execution agent drawn by a proposal's randomness (permissionless)
+    pub fn assign_random_executor(ctx: Context<AssignRandomExecutor>) -> Result<()> {
+        instructions::assign_random_executor(ctx)
+    }
+
+    /// Stake reputation behind another agent's proposal
+    pub fn endorse_proposal(ctx: Context<EndorseProposal>, stake: u16) -> Result<()> {
+        instructions::endorse_proposal(ctx, stake)
+    }
+
+    /// Return an endorsement stake, adjusted by the proposal outcome
+    pub fn settle_endorsement(ctx: Context<SettleEndorsement>) -> Result<()> {
+        instructions::settle_endorsement(ctx)
+    }
+
+    /// Create the caller's cross-swarm identity, carrying one reputation
+    /// score into every swarm it links
+    pub fn create_agent_identity(ctx: Context<CreateAgentIdentity>) -> Result<()> {
+        instructions::create_agent_identity(ctx)
+    }
+
+    /// Link the caller's agent in a swarm to its identity, adopting the
+    /// identity's reputation
+    pub fn link_membership(ctx: Context<LinkMembership>) -> Result<()> {
+        instructions::link_membership(ctx)
+    }
+
+    /// Fold a swarm's reputation changes into the agent's identity and
+    /// refresh that swarm's copy (permissionless)
+    pub fn sync_reputation(ctx: Context<SyncReputation>) -> Result<()> {
+        instructions::sync_reputation(ctx)
+    }
+
+    /// Sync and detach a swarm's agent from the caller's identity
+    pub fn unlink_membership(ctx: Context<UnlinkMembership>) -> Result<()> {
+        instructions::unlink_membership(ctx)
+    }
+
+    /// Set the swarm's badge milestones and Bubblegum tree (authority only)
+    pub fn configure_badges(
+        ctx: Context<ConfigureBadges>,
+        merkle_tree: Pubkey,
+        votes_milestone: u64,
+        proposals_milestone: u64,
+        top_reputation_enabled: bool,
+        uri_prefix: String,
+    ) -> Result<()> {
+        instructions::configure_badges(
+            ctx,
+            merkle_tree,
+            votes_milestone,
+            proposals_milestone,
+            top_reputation_enabled,
+            uri_prefix,
+        )
+    }
+
+    /// Mint an earned achievement badge to an agent's owner as a compressed NFT
+    pub fn award_badge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AwardBadge<'info>>,
+        kind: BadgeKind,
+        epoch: u64,
+    ) -> Result<()> {
+        instructions::award_badge(ctx, kind, epoch)
+    }
+
+    /// Record an agent's ed25519 signature over an off-chain output,
+    /// verified by the ed25519 instruction just before this one
+    pub fn attest_output(
+        ctx: Context<AttestOutput>,
+        payload_hash: [u8; 32],
+        model_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::attest_output(ctx, payload_hash, model_hash)
+    }
+
+    /// Cite an attested output as a proposal's basis (proposer only, before
+    /// voting starts)
+    pub fn cite_attestation(ctx: Context<CiteAttestation>) -> Result<()> {
+        instructions::cite_attestation(ctx)
+    }
+
+    /// Announce planned downtime; `duration` of 0 ends a sabbatical early
+    pub fn set_sabbatical(ctx: Context<SetSabbatical>, duration: i64) -> Result<()> {
+        instructions::set_sabbatical(ctx, duration)
+    }
+
+    /// Claim the treasury reward for an approving vote on a successful proposal
+    pub fn claim_voting_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimVotingRewards<'info>>,
+    ) -> Result<()> {
+        instructions::claim_voting_rewards(ctx)
+    }
+
+    /// Bond SOL behind the caller's agent
+    pub fn post_bond(ctx: Context<PostBond>, lamports: u64) -> Result<()> {
+        instructions::post_bond(ctx, lamports)
+    }
+
+    /// Start the unbonding period for the caller's bond
+    pub fn request_unbond(ctx: Context<RequestUnbond>) -> Result<()> {
+        instructions::request_unbond(ctx)
+    }
+
+    /// Withdraw a bond once its unbonding period has passed
+    pub fn withdraw_bond(ctx: Context<WithdrawBond>) -> Result<()> {
+        instructions::withdraw_bond(ctx)
+    }
+
+    /// Apply the bond slash carried by an executed Emergency proposal
+    pub fn slash_agent(ctx: Context<SlashAgent>) -> Result<()> {
+        instructions::slash_agent(ctx)
+    }
+
+    /// Post the Wormhole message carried by an executed CrossChain proposal
+    pub fn post_cross_chain_message(ctx: Context<PostCrossChainMessage>) -> Result<()> {
+        instructions::post_cross_chain_message(ctx)
+    }
+
+    /// Close a finished epoch into its `EpochRewards` and `EpochStats`
+    /// accounts and start the next (permissionless)
+    pub fn advance_epoch(ctx: Context<AdvanceEpoch>) -> Result<()> {
+        instructions::advance_epoch(ctx)
+    }
+
+    /// Claim the caller's performance rewards for one settled epoch
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, epoch: u64) -> Result<()> {
+        instructions::claim_rewards(ctx, epoch)
+    }
+
+    /// Create the swarm-owned governance token mint (authority only)
+    pub fn create_governance_mint(ctx: Context<CreateGovernanceMint>) -> Result<()> {
+        instructions::create_governance_mint(ctx)
+    }
+
+    /// Mint the current reward epoch's governance token emission to the treasury
+    pub fn mint_emissions(ctx: Context<MintEmissions>) -> Result<()> {
+        instructions::mint_emissions(ctx)
+    }
+
+    /// Lock governance tokens in a vote escrow for boosted vote weight
+    pub fn lock_tokens(ctx: Context<LockTokens>, amount: u64, duration: i64) -> Result<()> {
+        instructions::lock_tokens(ctx, amount, duration)
+    }
+
+    /// Push a vote escrow's unlock time further out
+    pub fn extend_lock(ctx: Context<ExtendLock>, lock_end: i64) -> Result<()> {
+        instructions::extend_lock(ctx, lock_end)
+    }
+
+    /// Withdraw vote-escrowed tokens after the lock expires
+    pub fn withdraw_locked_tokens(ctx: Context<WithdrawLockedTokens>) -> Result<()> {
+        instructions::withdraw_locked_tokens(ctx)
+    }
+
+    /// Create the swarm treasury (authority only)
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        instructions::initialize_treasury(ctx)
+    }
+
+    /// Deposit SOL into the swarm treasury
+    pub fn deposit_treasury(ctx: Context<DepositTreasury>, lamports: u64) -> Result<()> {
+        instructions::deposit_treasury(ctx, lamports)
+    }
+
+    /// Cap how much one proposal type may move out of the treasury per epoch
+    pub fn set_spend_cap(
+        ctx: Context<SetSpendCap>,
+        proposal_type: ProposalType,
+        cap: u64,
+    ) -> Result<()> {
+        instructions::set_spend_cap(ctx, proposal_type, cap)
+    }
+
+    /// Create the vault authority's associated token account for a mint
+    pub fn create_vault_token_account(ctx: Context<CreateVaultTokenAccount>) -> Result<()> {
+        instructions::create_vault_token_account(ctx)
+    }
+
+    /// Deposit SPL or Token-2022 tokens into the swarm treasury
+    pub fn deposit_treasury_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositTreasuryTokens<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::deposit_treasury_tokens(ctx, amount)
+    }
+
+    /// Escrow a SOL or SPL bounty for the agent that executes a proposal
+    pub fn attach_bounty(ctx: Context<AttachBounty>, amount: u64) -> Result<()> {
+        instructions::attach_bounty(ctx, amount)
+    }
+
+    /// Pay out a bounty after a successful recorded outcome
+    pub fn claim_bounty(ctx: Context<ClaimBounty>) -> Result<()> {
+        instructions::claim_bounty(ctx)
+    }
+
+    /// Return a bounty to its funder after expiry or a failed outcome
+    pub fn refund_bounty(ctx: Context<RefundBounty>) -> Result<()> {
+        instructions::refund_bounty(ctx)
+    }
+
+    /// Create the pending trade exposure account (authority only)
+    pub fn initialize_pending_exposure(ctx: Context<InitializePendingExposure>) -> Result<()> {
+        instructions::initialize_pending_exposure(ctx)
+    }
+
+    /// Create the `Strategy` program whitelist (authority only)
+    pub fn initialize_program_whitelist(ctx: Context<InitializeProgramWhitelist>) -> Result<()> {
+        instructions::initialize_program_whitelist(ctx)
+    }
+
+    /// Create the swarm's trading risk config (authority only)
+    pub fn initialize_risk_config(ctx: Context<InitializeRiskConfig>) -> Result<()> {
+        instructions::initialize_risk_config(ctx)
+    }
+
+    /// Create the log of the swarm's privileged actions (authority only)
+    pub fn initialize_audit_log(ctx: Context<InitializeAuditLog>) -> Result<()> {
+        instructions::initialize_audit_log(ctx)
+    }
+
+    /// Create the merkle archive for old outcomes (authority only)
+    pub fn initialize_outcome_archive(ctx: Context<InitializeOutcomeArchive>) -> Result<()> {
+        instructions::initialize_outcome_archive(ctx)
+    }
+
+    /// Set or clear the pending exposure limit for a mint (authority only)
+    pub fn set_exposure_limit(
+        ctx: Context<SetExposureLimit>,
+        mint: Pubkey,
+        max_amount: Option<u64>,
+    ) -> Result<()> {
+        instructions::set_exposure_limit(ctx, mint, max_amount)
+    }
+
+    /// Register (or with `None` remove) the oracle feed pricing a mint (authority only)
+    pub fn set_price_feed(
+        ctx: Context<SetPriceFeed>,
+        source: Option<OracleSource>,
+        max_age: i64,
+        max_deviation_bps: u16,
+    ) -> Result<()> {
+        instructions::set_price_feed(ctx, source, max_age, max_deviation_bps)
+    }
+
+    /// Fold the current oracle price of a mint into its TWAP (any active agent)
+    pub fn record_twap_observation(ctx: Context<RecordTwapObservation>) -> Result<()> {
+        instructions::record_twap_observation(ctx)
+    }
+
+    /// Reserve an approved Trade against the swarm's aggregate exposure limits
+    pub fn reserve_trade_exposure(ctx: Context<ReserveTradeExposure>) -> Result<()> {
+        instructions::reserve_trade_exposure(ctx)
+    }
+
+    /// Upgrade a program account to its current layout version, reallocating
+    /// it if the new layout is larger (permissionless)
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::migrate_account(ctx)
+    }
+
+    /// Initialize the agent marketplace
+    pub fn initialize_marketplace(ctx: Context<InitializeMarketplace>) -> Result<()> {
+        marketplace::initialize_marketplace(ctx)
+    }
+
+    /// List an agent in the marketplace
+    pub fn list_agent(ctx: Context<ListAgent>, params: ListAgentParams) -> Result<()> {
+        marketplace::list_agent(ctx, params)
+    }
+
+    /// Update an existing marketplace listing; `price_mint: Some(None)`
+    /// switches it back to lamports
+    pub fn update_listing(
+        ctx: Context<UpdateListing>,
+        description: Option<String>,
+        price: Option<u64>,
+        price_mint: Option<Option<Pubkey>>,
+        manifest: Option<Vec<u8>>,
+        category: Option<ListingCategory>,
+        tags: Option<Vec<String>>,
+    ) -> Result<()> {
+        marketplace::update_listing(ctx, description, price, price_mint, manifest, category, tags)
+    }
+
+    /// Remove a listing and clear its slot in the listing index
+    pub fn delist_agent(ctx: Context<DelistAgent>) -> Result<()> {
+        marketplace::delist_agent(ctx)
+    }
+
+    /// Hand a listing, and its future sale proceeds, to a new owner
+    pub fn transfer_listing(ctx: Context<TransferListing>, new_owner: Pubkey) -> Result<()> {
+        marketplace::transfer_listing(ctx, new_owner)
+    }
+
+    /// Release a new version of a listing, recorded in its version history
+    pub fn publish_version(
+        ctx: Context<PublishVersion>,
+        version: String,
+        changelog_hash: [u8; 32],
+    ) -> Result<()> {
+        marketplace::publish_version(ctx, version, changelog_hash)
+    }
+
+    /// Download (purchase) a listed agent
+    pub fn download_agent<'info>(ctx: Context<'_, '_, '_, 'info, DownloadAgent<'info>>) -> Result<()> {
+        marketplace::download_agent(ctx)
+    }
+
+    /// Collect an escrowed sale once its refund window has closed
+    pub fn claim_sale_proceeds<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimSaleProceeds<'info>>,
+    ) -> Result<()> {
+        marketplace::claim_sale_proceeds(ctx)
+    }
+
+    /// Undo a purchase and recover its payment during the refund window
+    pub fn request_refund<'info>(ctx: Context<'_, '_, '_, 'info, RequestRefund<'info>>) -> Result<()> {
+        marketplace::request_refund(ctx)
+    }
+
+    /// Set a listing's subscription price and period (owner only)
+    pub fn set_subscription_terms(
+        ctx: Context<UpdateListing>,
+        period_price: u64,
+        period_length: i64,
+    ) -> Result<()> {
+        marketplace::set_subscription_terms(ctx, period_price, period_length)
+    }
+
+    /// Subscribe to a listing for one period
+    pub fn subscribe(ctx: Context<Subscribe>) -> Result<()> {
+        marketplace::subscribe(ctx)
+    }
+
+    /// Pay for another subscription period
+    pub fn renew_subscription(ctx: Context<RenewSubscription>) -> Result<()> {
+        marketplace::renew_subscription(ctx)
+    }
+
+    /// Put a listing's ownership up for auction (owner only)
+    pub fn start_auction(
+        ctx: Context<StartAuction>,
+        reserve_price: u64,
+        duration: i64,
+    ) -> Result<()> {
+        auction::start_auction(ctx, reserve_price, duration)
+    }
+
+    /// Escrow a bid above the current highest bid
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+        auction::place_bid(ctx, amount)
+    }
+
+    /// Withdraw an outbid or losing bid
+    pub fn cancel_bid(ctx: Context<CancelBid>) -> Result<()> {
+        auction::cancel_bid(ctx)
+    }
+
+    /// Pay the seller and hand the listing to the highest bidder
+    pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+        auction::settle_auction(ctx)
+    }
+
+    /// Rate a listed agent (1-5 stars)
+    pub fn rate_agent(ctx: Context<RateAgent>, rating: u8) -> Result<()> {
+        marketplace::rate_agent(ctx, rating)
+    }
+
+    /// Review a purchased agent with up to 280 bytes of text and a 1-5 star rating
+    pub fn review_agent(ctx: Context<ReviewAgent>, stars: u8, text: String) -> Result<()> {
+        marketplace::review_agent(ctx, stars, text)
+    }
 }
 
 /// Agent types in the swarm
@@ -96,14 +787,79 @@ pub enum AgentType {
     Custom(u8),     // Custom community-defined agent types (0-255)
 }
 
+impl AgentType {
+    /// Borsh size of the largest variant, `Custom(u8)`
+    pub const LEN: usize = 2;
+
+    /// Id of a `Custom` type, which must have a registered `CustomAgentType`
+    pub fn custom_id(&self) -> Option<u8> {
+        match self {
+            AgentType::Custom(id) => Some(*id),
+            _ => None,
+        }
+    }
+}
+
 /// Proposal types that agents can create
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
 pub enum ProposalType {
     Rebalance,      // Rebalance portfolio allocation
     Trade,          // Execute a specific trade
     RiskLimit,      // Update risk parameters
     Strategy,       // Change investment strategy
     Emergency,      // Emergency action (higher priority)
+    TreasuryTransfer, // Move SOL out of the swarm treasury
+    TreasuryTokenTransfer, // Move SPL tokens out of the swarm treasury
+    AuthorityHandover, // Replace an inactive swarm authority (supermajority)
+    CrossChain,     // Post a Wormhole message from the swarm to another chain
+    DcaSchedule,    // Buy into a position in fixed legs over time
+    Harvest,        // Collect LP fees and swap them into the base asset
+    Insurance,      // Set insurance fund terms or pay a claim to the treasury
+}
+
+impl ProposalType {
+    /// Stable index used for per-type tables (spend caps, bitmasks)
+    pub fn index(&self) -> usize {
+        match self {
+            ProposalType::Rebalance => 0,
+            ProposalType::Trade => 1,
+            ProposalType::RiskLimit => 2,
+            ProposalType::Strategy => 3,
+            ProposalType::Emergency => 4,
+            ProposalType::TreasuryTransfer => 5,
+            ProposalType::TreasuryTokenTransfer => 6,
+            ProposalType::AuthorityHandover => 7,
+            ProposalType::CrossChain => 8,
+            ProposalType::DcaSchedule => 9,
+            ProposalType::Harvest => 10,
+            ProposalType::Insurance => 11,
+        }
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        Some(match index {
+            0 => ProposalType::Rebalance,
+            1 => ProposalType::Trade,
+            2 => ProposalType::RiskLimit,
+            3 => ProposalType::Strategy,
+            4 => ProposalType::Emergency,
+            5 => ProposalType::TreasuryTransfer,
+            6 => ProposalType::TreasuryTokenTransfer,
+            7 => ProposalType::AuthorityHandover,
+            8 => ProposalType::CrossChain,
+            9 => ProposalType::DcaSchedule,
+            10 => ProposalType::Harvest,
+            11 => ProposalType::Insurance,
+            _ => return None,
+        })
+    }
+}
+
+/// Who may execute an approved proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ExecutionMode {
+    Open,           // Any active agent may execute (liveness-optimized)
+    Permissioned,   // Assigned executor only within the window, then any agent
 }
 
 /// Vote types
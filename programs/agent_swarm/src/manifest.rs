@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use crate::errors::SwarmError;
+use crate::ProposalType;
+
+/// Binary agent manifest shared by marketplace listings and Agent accounts.
+///
+/// Layout (little-endian):
+///
+/// | bytes | field                                   |
+/// |-------|-----------------------------------------|
+/// | 2     | magic `b"AM"`                           |
+/// | 1     | manifest format version                 |
+/// | 3     | agent version (major, minor, patch)     |
+/// | 4     | capability bitflags (`CAP_*`)           |
+/// | 2     | supported proposal types (bit per type) |
+/// | 1     | required oracle count                   |
+/// | 32*n  | required oracle feed pubkeys            |
+///
+/// Format 1 manifests, whose proposal type mask is a single byte, are still
+/// accepted.
+pub const MANIFEST_MAGIC: [u8; 2] = *b"AM";
+pub const MANIFEST_FORMAT_VERSION: u8 = 2;
+pub const MANIFEST_HEADER_LENGTH: usize = 13;
+const V1_MANIFEST_HEADER_LENGTH: usize = 12;
+pub const MAX_MANIFEST_ORACLES: usize = 4;
+pub const MAX_MANIFEST_LENGTH: usize = MANIFEST_HEADER_LENGTH + 32 * MAX_MANIFEST_ORACLES;
+
+pub const CAP_TRADING: u32 = 1 << 0;
+pub const CAP_ANALYTICS: u32 = 1 << 1;
+pub const CAP_RISK: u32 = 1 << 2;
+pub const CAP_LEARNING: u32 = 1 << 3;
+pub const CAP_GOVERNANCE: u32 = 1 << 4;
+pub const CAP_SECURITY: u32 = 1 << 5;
+pub const CAP_LIQUIDITY: u32 = 1 << 6;
+pub const CAP_ARBITRAGE: u32 = 1 << 7;
+pub const CAP_EXECUTION: u32 = 1 << 8;
+pub const KNOWN_CAPABILITIES: u32 = (1 << 9) - 1;
+
+/// Bit assigned to each proposal type in `supported_proposal_types`
+pub fn proposal_type_bit(proposal_type: &ProposalType) -> u16 {
+    1 << proposal_type.index()
+}
+
+pub const KNOWN_PROPOSAL_TYPES: u16 = (1 << 12) - 1; // Rebalance through Insurance
+
+/// Decoded agent manifest
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AgentManifest {
+    pub version: [u8; 3],
+    pub capabilities: u32,
+    pub supported_proposal_types: u16,
+    pub required_oracles: Vec<Pubkey>,
+}
+
+impl AgentManifest {
+    /// Parse and validate a manifest. Trailing bytes, unknown flags, and
+    /// unsupported format versions are rejected.
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        require!(
+            bytes.len() >= V1_MANIFEST_HEADER_LENGTH && bytes.len() <= MAX_MANIFEST_LENGTH,
+            SwarmError::InvalidManifest
+        );
+        require!(bytes[0..2] == MANIFEST_MAGIC, SwarmError::InvalidManifest);
+
+        let version = [bytes[3], bytes[4], bytes[5]];
+        let capabilities = u32::from_le_bytes([bytes[6], bytes[7], bytes[8], bytes[9]]);
+        let (supported_proposal_types, header_length) = match bytes[2] {
+            1 => (bytes[10] as u16, V1_MANIFEST_HEADER_LENGTH),
+            MANIFEST_FORMAT_VERSION if bytes.len() >= MANIFEST_HEADER_LENGTH => {
+                (u16::from_le_bytes([bytes[10], bytes[11]]), MANIFEST_HEADER_LENGTH)
+            }
+            _ => return err!(SwarmError::InvalidManifest),
+        };
+        let oracle_count = bytes[header_length - 1] as usize;
+
+        require!(
+            capabilities != 0 && capabilities & !KNOWN_CAPABILITIES == 0,
+            SwarmError::InvalidManifest
+        );
+        require!(
+            supported_proposal_types != 0 && supported_proposal_types & !KNOWN_PROPOSAL_TYPES == 0,
+            SwarmError::InvalidManifest
+        );
+        require!(
+            oracle_count <= MAX_MANIFEST_ORACLES
+                && bytes.len() == header_length + 32 * oracle_count,
+            SwarmError::InvalidManifest
+        );
+
+        let required_oracles = bytes[header_length..]
+            .chunks_exact(32)
+            .map(|chunk| Pubkey::try_from(chunk).map_err(|_| error!(SwarmError::InvalidManifest)))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            version,
+            capabilities,
+            supported_proposal_types,
+            required_oracles,
+        })
+    }
+
+    /// Encode back to the binary layout
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(MANIFEST_HEADER_LENGTH + 32 * self.required_oracles.len());
+        bytes.extend_from_slice(&MANIFEST_MAGIC);
+        bytes.push(MANIFEST_FORMAT_VERSION);
+        bytes.extend_from_slice(&self.version);
+        bytes.extend_from_slice(&self.capabilities.to_le_bytes());
+        bytes.extend_from_slice(&self.supported_proposal_types.to_le_bytes());
+        bytes.push(self.required_oracles.len() as u8);
+        for oracle in &self.required_oracles {
+            bytes.extend_from_slice(oracle.as_ref());
+        }
+        bytes
+    }
+
+    /// Whether the agent provides every capability the swarm requires
+    pub fn is_compatible(&self, required_capabilities: u32) -> bool {
+        self.capabilities & required_capabilities == required_capabilities
+    }
+
+    pub fn supports(&self, proposal_type: &ProposalType) -> bool {
+        self.supported_proposal_types & proposal_type_bit(proposal_type) != 0
+    }
+}
+
+/// Validate raw manifest bytes against the swarm's admission requirements.
+/// An empty manifest is only acceptable when the swarm requires nothing.
+pub fn check_admission(manifest: &[u8], required_capabilities: u32) -> Result<()> {
+    if manifest.is_empty() {
+        require!(required_capabilities == 0, SwarmError::IncompatibleManifest);
+        return Ok(());
+    }
+
+    let parsed = AgentManifest::parse(manifest)?;
+    require!(
+        parsed.is_compatible(required_capabilities),
+        SwarmError::IncompatibleManifest
+    );
+    Ok(())
+}
@@ -1,63 +1,341 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
 use crate::constants::*;
+use crate::cpi::{token_extensions, CPIHelper, TokenTransferAccounts};
 use crate::errors::SwarmError;
+use crate::events::*;
+use crate::state::{ProgramWhitelist, SwarmState, Treasury};
+use crate::manifest::{AgentManifest, MAX_MANIFEST_LENGTH};
 use crate::AgentType;
 
+pub const MAX_LISTING_TAGS: usize = 5;
+pub const MAX_TAG_LENGTH: usize = 16;
+pub const LISTING_CATEGORY_COUNT: usize = 7;
+pub const MIN_SUBSCRIPTION_PERIOD: i64 = 86400; // 1 day
+pub const MAX_REVIEW_LENGTH: usize = 280;
+pub const LISTING_INDEX_PAGE_SIZE: u64 = 32;
+pub const MAX_ROYALTY_BPS: u16 = 2500;
+
+/// Discovery category for a listing
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ListingCategory {
+    Trading,
+    Analytics,
+    Risk,
+    Security,
+    Liquidity,
+    Governance,
+    Other,
+}
+
+impl ListingCategory {
+    /// Slot in `AgentMarketplace::category_counts`
+    pub fn index(&self) -> usize {
+        match self {
+            ListingCategory::Trading => 0,
+            ListingCategory::Analytics => 1,
+            ListingCategory::Risk => 2,
+            ListingCategory::Security => 3,
+            ListingCategory::Liquidity => 4,
+            ListingCategory::Governance => 5,
+            ListingCategory::Other => 6,
+        }
+    }
+}
+
 /// Agent Marketplace - Allows community to list and discover agents
 #[account]
 pub struct AgentMarketplace {
+    pub version: u8,
     pub authority: Pubkey,
     pub total_listings: u64,
+    pub category_counts: [u64; LISTING_CATEGORY_COUNT],
+    pub next_index: u64,        // Index position of the next listing
     pub bump: u8,
 }
 
 impl AgentMarketplace {
-    pub const LEN: usize = 8 + 32 + 8 + 1;
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + 1 + 32 + 8 + 8 * LISTING_CATEGORY_COUNT + 8 + 1;
+}
+
+/// One page of listing pubkeys in listing order, so clients can paginate
+/// without `getProgramAccounts`. Delisted entries become `Pubkey::default()`
+/// so positions never shift.
+#[account]
+pub struct ListingIndex {
+    pub version: u8,
+    pub page: u64,
+    pub listings: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl ListingIndex {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        8 +  // page
+        (4 + 32 * LISTING_INDEX_PAGE_SIZE as usize) + // listings
+        1;   // bump
+}
+
+fn validate_tags(tags: &[String]) -> Result<()> {
+    require!(tags.len() <= MAX_LISTING_TAGS, SwarmError::InvalidParameter);
+    require!(
+        tags.iter().all(|tag| !tag.is_empty() && tag.len() <= MAX_TAG_LENGTH),
+        SwarmError::InvalidParameter
+    );
+    Ok(())
+}
+
+/// Proof that `buyer` paid for `listing`. Repeat downloads are free.
+///
+/// The payment is escrowed until `refund_deadline`; the buyer may take it
+/// back before then, the seller claims it afterwards. SOL is held on this
+/// account, SPL payments in a token account owned by it.
+#[account]
+pub struct Purchase {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,         // Listing owner at the time of sale
+    pub mint: Option<Pubkey>,   // None = paid in lamports
+    pub price_paid: u64,
+    pub escrowed: u64,          // Lamports still held for the seller
+    pub purchased_at: i64,
+    pub refund_deadline: i64,
+    pub bump: u8,
+}
+
+impl Purchase {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        32 + // listing
+        32 + // buyer
+        32 + // seller
+        (1 + 32) + // mint
+        8 +  // price_paid
+        8 +  // escrowed
+        8 +  // purchased_at
+        8 +  // refund_deadline
+        1;   // bump
+
+    /// The buyer may take the escrowed payment back until `refund_deadline`
+    pub fn is_refundable(&self, current_time: i64) -> bool {
+        self.escrowed > 0 && current_time < self.refund_deadline
+    }
+}
+
+/// A purchaser's star rating of a listing; re-rating replaces it
+#[account]
+pub struct Rating {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub rater: Pubkey,
+    pub stars: u8,
+    pub rated_at: i64,
+    pub bump: u8,
+}
+
+impl Rating {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        32 + // listing
+        32 + // rater
+        1 +  // stars
+        8 +  // rated_at
+        1;   // bump
+}
+
+/// Immutable record of one release of a listing, so buyers can pin to or
+/// audit a specific version
+#[account]
+pub struct ListingVersion {
+    /// Account layout version; `version` is the released agent version
+    pub layout_version: u8,
+    pub listing: Pubkey,
+    pub version: String,
+    pub changelog_hash: [u8; 32],
+    pub released_at: i64,
+    pub bump: u8,
+}
+
+impl ListingVersion {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // layout_version
+        32 + // listing
+        (4 + 16) + // version
+        32 + // changelog_hash
+        8 +  // released_at
+        1;   // bump
+}
+
+/// A purchaser's written review. Its stars are also recorded in their
+/// `Rating`, so reviewing and rating never count twice.
+#[account]
+pub struct Review {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub reviewer: Pubkey,
+    pub stars: u8,
+    pub text: String,
+    pub reviewed_at: i64,
+    pub bump: u8,
+}
+
+impl Review {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        32 + // listing
+        32 + // reviewer
+        1 +  // stars
+        (4 + MAX_REVIEW_LENGTH) + // text
+        8 +  // reviewed_at
+        1;   // bump
+}
+
+/// Recurring access to a listing, valid until `expires_at`
+#[account]
+pub struct Subscription {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub subscriber: Pubkey,
+    pub expires_at: i64,
+    pub total_paid: u64,
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        32 + // listing
+        32 + // subscriber
+        8 +  // expires_at
+        8 +  // total_paid
+        1;   // bump
+    
+    pub fn is_active(&self, current_time: i64) -> bool {
+        current_time < self.expires_at
+    }
 }
 
 /// Agent marketplace listing
 #[account]
 pub struct AgentListing {
+    /// Account layout version; `version` is the released agent version
+    pub layout_version: u8,
     pub agent: Pubkey,
     pub owner: Pubkey,
+    pub creator: Pubkey,        // Original lister; part of the PDA seeds
+
     pub agent_type: AgentType,
     pub name: String,
     pub description: String,
     pub version: String,
-    pub price: u64,             // Price in lamports (0 = free)
+    pub price: u64,             // Price in lamports or `price_mint` base units (0 = free)
+    pub price_mint: Option<Pubkey>, // None = priced in lamports
     pub downloads: u64,
     pub rating: u16,            // Rating * 100 (e.g., 450 = 4.50)
     pub total_ratings: u64,
+    pub rating_sum: u64,        // Sum of stars * 100 across all ratings
     pub verified: bool,
     pub listed_at: i64,
     pub updated_at: i64,
+    pub manifest: Vec<u8>,
+    pub category: ListingCategory,
+    pub tags: Vec<String>,
+    pub period_price: u64,      // Subscription price per period (0 = no subscriptions)
+    pub period_length: i64,     // Subscription period in seconds
+    pub index: u64,             // Position in the `ListingIndex` pages
+    pub refunds_open_until: i64, // Latest refund deadline of any sale
+    pub royalty_bps: u16,       // Creator's cut of resales; fixed at listing time
     pub bump: u8,
 }
 
 impl AgentListing {
+    /// v2 made room for format 2 manifests
+    pub const VERSION: u8 = 2;
     pub const LEN: usize = 8 + // discriminator
+        1 +  // layout_version
         32 + // agent
         32 + // owner
-        2 +  // agent_type
+        32 + // creator
+        AgentType::LEN + // agent_type
         (4 + 64) + // name
         (4 + 256) + // description
         (4 + 16) + // version
         8 +  // price
+        (1 + 32) + // price_mint
         8 +  // downloads
         2 +  // rating
         8 +  // total_ratings
+        8 +  // rating_sum
         1 +  // verified
         8 +  // listed_at
         8 +  // updated_at
+        (4 + MAX_MANIFEST_LENGTH) + // manifest
+        1 +  // category
+        (4 + MAX_LISTING_TAGS * (4 + MAX_TAG_LENGTH)) + // tags
+        8 +  // period_price
+        8 +  // period_length
+        8 +  // index
+        8 +  // refunds_open_until
+        2 +  // royalty_bps
         1;   // bump
+
+    /// Add a rating of `stars` to the average, replacing the rater's
+    /// `previous_stars` (0 when they had not rated yet) instead of counting
+    /// them twice
+    pub fn record_rating(&mut self, previous_stars: u8, stars: u8) -> Result<()> {
+        if previous_stars == 0 {
+            self.total_ratings = self.total_ratings.saturating_add(1);
+        } else {
+            self.rating_sum = self.rating_sum.saturating_sub(previous_stars as u64 * 100);
+        }
+        self.rating_sum = self
+            .rating_sum
+            .checked_add(stars as u64 * 100)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        self.rating = (self.rating_sum / self.total_ratings) as u16;
+        Ok(())
+    }
+
+    /// Drop one rating of `stars` from the average
+    pub fn remove_rating(&mut self, stars: u8) {
+        self.total_ratings = self.total_ratings.saturating_sub(1);
+        self.rating_sum = self.rating_sum.saturating_sub(stars as u64 * 100);
+        self.rating = self.rating_sum.checked_div(self.total_ratings).unwrap_or(0) as u16;
+    }
+}
+
+/// Details of a new marketplace listing
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ListAgentParams {
+    pub agent_type: AgentType,
+    pub name: String,
+    pub description: String,
+    pub version: String,
+    pub price: u64,             // Price in lamports or `price_mint` base units (0 = free)
+    pub price_mint: Option<Pubkey>, // None = priced in lamports
+    pub manifest: Vec<u8>,
+    pub category: ListingCategory,
+    pub tags: Vec<String>,
+    pub royalty_bps: u16,       // Creator's cut of resales; fixed at listing time
 }
 
 /// Agent listing metadata
 #[derive(Accounts)]
-#[instruction(name: String)]
+#[instruction(params: ListAgentParams)]
 pub struct ListAgent<'info> {
     #[account(
-        seeds = [b"marketplace"],
+        mut,
+        seeds = [MARKETPLACE_SEED],
         bump = marketplace.bump
     )]
     pub marketplace: Account<'info, AgentMarketplace>,
@@ -66,11 +344,32 @@ pub struct ListAgent<'info> {
         init,
         payer = owner,
         space = AgentListing::LEN,
-        seeds = [b"listing", owner.key().as_ref(), name.as_bytes()],
+        seeds = [LISTING_SEED, owner.key().as_ref(), params.name.as_bytes()],
         bump
     )]
     pub listing: Account<'info, AgentListing>,
     
+    #[account(
+        init,
+        payer = owner,
+        space = ListingVersion::LEN,
+        seeds = [LISTING_VERSION_SEED, listing.key().as_ref(), params.version.as_bytes()],
+        bump
+    )]
+    pub listing_version: Account<'info, ListingVersion>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = ListingIndex::LEN,
+        seeds = [
+            LISTING_INDEX_SEED,
+            &(marketplace.next_index / LISTING_INDEX_PAGE_SIZE).to_le_bytes()
+        ],
+        bump
+    )]
+    pub listing_index: Account<'info, ListingIndex>,
+    
     pub agent: Signer<'info>,
     
     #[account(mut)]
@@ -84,7 +383,14 @@ pub struct ListAgent<'info> {
 pub struct UpdateListing<'info> {
     #[account(
         mut,
-        seeds = [b"listing", owner.key().as_ref(), listing.name.as_bytes()],
+        seeds = [MARKETPLACE_SEED],
+        bump = marketplace.bump
+    )]
+    pub marketplace: Account<'info, AgentMarketplace>,
+    
+    #[account(
+        mut,
+        seeds = [LISTING_SEED, listing.creator.as_ref(), listing.name.as_bytes()],
         bump = listing.bump,
         constraint = listing.owner == owner.key() @ SwarmError::Unauthorized
     )]
@@ -94,83 +400,525 @@ pub struct UpdateListing<'info> {
     pub owner: Signer<'info>,
 }
 
+/// Remove a listing once no sale can still be refunded
+#[derive(Accounts)]
+pub struct DelistAgent<'info> {
+    #[account(
+        mut,
+        seeds = [MARKETPLACE_SEED],
+        bump = marketplace.bump
+    )]
+    pub marketplace: Account<'info, AgentMarketplace>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [LISTING_SEED, listing.creator.as_ref(), listing.name.as_bytes()],
+        bump = listing.bump,
+        constraint = listing.owner == owner.key() @ SwarmError::Unauthorized
+    )]
+    pub listing: Account<'info, AgentListing>,
+    
+    #[account(
+        mut,
+        seeds = [LISTING_INDEX_SEED, &(listing.index / LISTING_INDEX_PAGE_SIZE).to_le_bytes()],
+        bump = listing_index.bump
+    )]
+    pub listing_index: Account<'info, ListingIndex>,
+    
+    /// CHECK: Must be empty; a running auction settles ownership itself
+    #[account(seeds = [AUCTION_SEED, listing.key().as_ref()], bump)]
+    pub auction: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Hand a listing to a new owner. The PDA is seeded by the original
+/// creator, so it keeps its address.
+#[derive(Accounts)]
+pub struct TransferListing<'info> {
+    #[account(
+        mut,
+        seeds = [LISTING_SEED, listing.creator.as_ref(), listing.name.as_bytes()],
+        bump = listing.bump,
+        constraint = listing.owner == owner.key() @ SwarmError::Unauthorized
+    )]
+    pub listing: Account<'info, AgentListing>,
+    
+    /// CHECK: Must be empty; a running auction settles ownership itself
+    #[account(seeds = [AUCTION_SEED, listing.key().as_ref()], bump)]
+    pub auction: UncheckedAccount<'info>,
+    
+    pub owner: Signer<'info>,
+}
+
+/// Release a new version of a listing
+#[derive(Accounts)]
+#[instruction(version: String)]
+pub struct PublishVersion<'info> {
+    #[account(
+        mut,
+        seeds = [LISTING_SEED, listing.creator.as_ref(), listing.name.as_bytes()],
+        bump = listing.bump,
+        constraint = listing.owner == owner.key() @ SwarmError::Unauthorized
+    )]
+    pub listing: Account<'info, AgentListing>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = ListingVersion::LEN,
+        seeds = [LISTING_VERSION_SEED, listing.key().as_ref(), version.as_bytes()],
+        bump
+    )]
+    pub listing_version: Account<'info, ListingVersion>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
 /// Download/purchase agent
 #[derive(Accounts)]
 pub struct DownloadAgent<'info> {
     #[account(mut)]
     pub listing: Account<'info, AgentListing>,
     
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = Purchase::LEN,
+        seeds = [PURCHASE_SEED, listing.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub purchase: Account<'info, Purchase>,
+    
     #[account(mut)]
     pub buyer: Signer<'info>,
     
+    #[account(
+        seeds = [SWARM_SEED, MARKETPLACE_SWARM_ID.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    /// Required when the listing has a `price_mint`; a transfer hook's
+    /// accounts follow as remaining accounts
+    #[account(
+        mut,
+        constraint = buyer_token_account.owner == buyer.key() @ SwarmError::Unauthorized
+    )]
+    pub buyer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Token account owned by the purchase PDA, created by the buyer beforehand
+    #[account(
+        mut,
+        constraint = escrow_token_account.owner == purchase.key() @ SwarmError::Unauthorized
+    )]
+    pub escrow_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Required when the listing's mint has a transfer hook
+    #[account(
+        seeds = [PROGRAM_WHITELIST_SEED, swarm_state.key().as_ref()],
+        bump = program_whitelist.bump
+    )]
+    pub program_whitelist: Option<Account<'info, ProgramWhitelist>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Release an escrowed sale to the seller once the refund window closes
+#[derive(Accounts)]
+pub struct ClaimSaleProceeds<'info> {
+    #[account(
+        mut,
+        seeds = [PURCHASE_SEED, purchase.listing.as_ref(), purchase.buyer.as_ref()],
+        bump = purchase.bump,
+        constraint = purchase.seller == seller.key() @ SwarmError::Unauthorized
+    )]
+    pub purchase: Account<'info, Purchase>,
+    
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    #[account(
+        seeds = [SWARM_SEED, MARKETPLACE_SWARM_ID.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    /// Receives the protocol fee
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, swarm_state.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// CHECK: Refunded the escrow token account's rent; checked against `purchase.buyer`
+    #[account(mut, address = purchase.buyer)]
+    pub buyer: Option<UncheckedAccount<'info>>,
+    
+    /// A transfer hook's accounts follow as remaining accounts
+    #[account(mut)]
+    pub escrow_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = seller_token_account.owner == seller.key() @ SwarmError::Unauthorized
+    )]
+    pub seller_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Receives the protocol fee for token sales
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == swarm_state.vault_authority @ SwarmError::Unauthorized
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Written when Token-2022 transfer fees withheld in the escrow are
+    /// harvested before it closes
+    #[account(mut)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+/// Undo a purchase during its refund window
+#[derive(Accounts)]
+pub struct RequestRefund<'info> {
+    #[account(mut, address = purchase.listing)]
+    pub listing: Account<'info, AgentListing>,
+    
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [PURCHASE_SEED, listing.key().as_ref(), buyer.key().as_ref()],
+        bump = purchase.bump
+    )]
+    pub purchase: Account<'info, Purchase>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    /// CHECK: the buyer's `Rating`, if they rated; removed from the
+    /// listing's average and closed with the refund
+    #[account(
+        mut,
+        seeds = [RATING_SEED, listing.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub rating: UncheckedAccount<'info>,
+    
+    /// CHECK: the buyer's `Review`, if they reviewed; closed with the refund
+    #[account(
+        mut,
+        seeds = [REVIEW_SEED, listing.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub review: UncheckedAccount<'info>,
+    
+    /// A transfer hook's accounts follow as remaining accounts
+    #[account(mut)]
+    pub escrow_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = buyer_token_account.owner == buyer.key() @ SwarmError::Unauthorized
+    )]
+    pub buyer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+/// Start or renew a subscription to a listing
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    #[account(constraint = listing.period_price > 0 @ SwarmError::SubscriptionsDisabled)]
+    pub listing: Account<'info, AgentListing>,
+    
+    #[account(
+        init,
+        payer = subscriber,
+        space = Subscription::LEN,
+        seeds = [SUBSCRIPTION_SEED, listing.key().as_ref(), subscriber.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+    
     /// CHECK: Owner receives payment
+    #[account(mut, address = listing.owner @ SwarmError::Unauthorized)]
+    pub owner: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [SWARM_SEED, MARKETPLACE_SWARM_ID.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, swarm_state.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RenewSubscription<'info> {
+    #[account(constraint = listing.period_price > 0 @ SwarmError::SubscriptionsDisabled)]
+    pub listing: Account<'info, AgentListing>,
+    
+    #[account(
+        mut,
+        seeds = [SUBSCRIPTION_SEED, listing.key().as_ref(), subscriber.key().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
     #[account(mut)]
+    pub subscriber: Signer<'info>,
+    
+    /// CHECK: Owner receives payment
+    #[account(mut, address = listing.owner @ SwarmError::Unauthorized)]
     pub owner: AccountInfo<'info>,
     
+    #[account(
+        seeds = [SWARM_SEED, MARKETPLACE_SWARM_ID.to_le_bytes().as_ref()],
+        bump = swarm_state.bump
+    )]
+    pub swarm_state: Account<'info, SwarmState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, swarm_state.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
     pub system_program: Program<'info, System>,
 }
 
-/// Rate agent
+/// Rate agent; only purchasers may rate, once each
 #[derive(Accounts)]
 pub struct RateAgent<'info> {
     #[account(mut)]
     pub listing: Account<'info, AgentListing>,
     
+    #[account(
+        seeds = [PURCHASE_SEED, listing.key().as_ref(), rater.key().as_ref()],
+        bump = purchase.bump
+    )]
+    pub purchase: Account<'info, Purchase>,
+    
+    #[account(
+        init_if_needed,
+        payer = rater,
+        space = Rating::LEN,
+        seeds = [RATING_SEED, listing.key().as_ref(), rater.key().as_ref()],
+        bump
+    )]
+    pub rating: Account<'info, Rating>,
+    
+    #[account(mut)]
     pub rater: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Review agent; only purchasers may review, once each
+#[derive(Accounts)]
+pub struct ReviewAgent<'info> {
+    #[account(mut)]
+    pub listing: Account<'info, AgentListing>,
+    
+    #[account(
+        seeds = [PURCHASE_SEED, listing.key().as_ref(), reviewer.key().as_ref()],
+        bump = purchase.bump
+    )]
+    pub purchase: Account<'info, Purchase>,
+    
+    #[account(
+        init_if_needed,
+        payer = reviewer,
+        space = Rating::LEN,
+        seeds = [RATING_SEED, listing.key().as_ref(), reviewer.key().as_ref()],
+        bump
+    )]
+    pub rating: Account<'info, Rating>,
+    
+    #[account(
+        init_if_needed,
+        payer = reviewer,
+        space = Review::LEN,
+        seeds = [REVIEW_SEED, listing.key().as_ref(), reviewer.key().as_ref()],
+        bump
+    )]
+    pub review: Account<'info, Review>,
+    
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 pub fn initialize_marketplace(ctx: Context<InitializeMarketplace>) -> Result<()> {
     let marketplace = &mut ctx.accounts.marketplace;
     marketplace.authority = ctx.accounts.authority.key();
     marketplace.total_listings = 0;
+    marketplace.category_counts = [0; LISTING_CATEGORY_COUNT];
+    marketplace.next_index = 0;
+    marketplace.version = AgentMarketplace::VERSION;
     marketplace.bump = ctx.bumps.marketplace;
     
+    emit!(SwarmAccountCreated {
+        account: marketplace.key(),
+        created_by: marketplace.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     msg!("Agent Marketplace initialized");
     Ok(())
 }
 
-pub fn list_agent(
-    ctx: Context<ListAgent>,
-    agent_type: AgentType,
-    name: String,
-    description: String,
-    version: String,
-    price: u64,
-) -> Result<()> {
+pub fn list_agent(ctx: Context<ListAgent>, params: ListAgentParams) -> Result<()> {
+    let ListAgentParams {
+        agent_type,
+        name,
+        description,
+        version,
+        price,
+        price_mint,
+        manifest,
+        category,
+        tags,
+        royalty_bps,
+    } = params;
     require!(name.len() <= 64, SwarmError::AgentNameTooLong);
     require!(description.len() <= 256, SwarmError::DescriptionTooLong);
     require!(version.len() <= 16, SwarmError::InvalidParameter);
+    AgentManifest::parse(&manifest)?;
+    validate_tags(&tags)?;
+    require!(royalty_bps <= MAX_ROYALTY_BPS, SwarmError::InvalidParameter);
     
     let listing = &mut ctx.accounts.listing;
     let clock = Clock::get()?;
     
     listing.agent = ctx.accounts.agent.key();
     listing.owner = ctx.accounts.owner.key();
+    listing.creator = ctx.accounts.owner.key();
     listing.agent_type = agent_type;
     listing.name = name.clone();
     listing.description = description;
     listing.version = version;
     listing.price = price;
+    listing.price_mint = price_mint;
     listing.downloads = 0;
     listing.rating = 0;
     listing.total_ratings = 0;
+    listing.rating_sum = 0;
     listing.verified = false;
     listing.listed_at = clock.unix_timestamp;
     listing.updated_at = clock.unix_timestamp;
+    listing.manifest = manifest;
+    listing.category = category;
+    listing.tags = tags;
+    listing.period_price = 0;
+    listing.period_length = 0;
+    listing.index = ctx.accounts.marketplace.next_index;
+    listing.refunds_open_until = 0;
+    listing.royalty_bps = royalty_bps;
+    listing.layout_version = AgentListing::VERSION;
     listing.bump = ctx.bumps.listing;
     
+    let listing_version = &mut ctx.accounts.listing_version;
+    listing_version.listing = listing.key();
+    listing_version.version = listing.version.clone();
+    listing_version.changelog_hash = [0; 32];
+    listing_version.released_at = clock.unix_timestamp;
+    listing_version.layout_version = ListingVersion::VERSION;
+    listing_version.bump = ctx.bumps.listing_version;
+    
+    let listing_index = &mut ctx.accounts.listing_index;
+    if listing_index.listings.is_empty() {
+        listing_index.page = listing.index / LISTING_INDEX_PAGE_SIZE;
+        listing_index.version = ListingIndex::VERSION;
+        listing_index.bump = ctx.bumps.listing_index;
+    }
+    listing_index.listings.push(listing.key());
+    
     let marketplace = &mut ctx.accounts.marketplace;
-    marketplace.total_listings += 1;
+    marketplace.total_listings = marketplace.total_listings.saturating_add(1);
+    marketplace.next_index = marketplace
+        .next_index
+        .checked_add(1)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    let counts = &mut marketplace.category_counts;
+    counts[category.index()] = counts[category.index()].saturating_add(1);
+    
+    emit!(ListingCreated {
+        listing: listing.key(),
+        agent: listing.agent,
+        owner: listing.owner,
+        agent_type,
+        category,
+        price,
+        price_mint,
+        timestamp: clock.unix_timestamp,
+    });
     
     msg!("Agent listed: {} (type: {:?})", name, agent_type);
     Ok(())
 }
 
+pub fn delist_agent(ctx: Context<DelistAgent>) -> Result<()> {
+    let listing = &ctx.accounts.listing;
+    
+    require!(ctx.accounts.auction.data_is_empty(), SwarmError::AuctionActive);
+    require!(
+        Clock::get()?.unix_timestamp >= listing.refunds_open_until,
+        SwarmError::RefundWindowOpen
+    );
+    
+    let slot = (listing.index % LISTING_INDEX_PAGE_SIZE) as usize;
+    ctx.accounts.listing_index.listings[slot] = Pubkey::default();
+    
+    let marketplace = &mut ctx.accounts.marketplace;
+    marketplace.total_listings = marketplace.total_listings.saturating_sub(1);
+    let counts = &mut marketplace.category_counts;
+    counts[listing.category.index()] = counts[listing.category.index()].saturating_sub(1);
+    
+    emit!(ListingDelisted {
+        listing: listing.key(),
+        owner: listing.owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Agent delisted: {}", listing.name);
+    Ok(())
+}
+
 pub fn update_listing(
     ctx: Context<UpdateListing>,
     description: Option<String>,
-    version: Option<String>,
     price: Option<u64>,
+    price_mint: Option<Option<Pubkey>>,
+    manifest: Option<Vec<u8>>,
+    category: Option<ListingCategory>,
+    tags: Option<Vec<String>>,
 ) -> Result<()> {
     let listing = &mut ctx.accounts.listing;
     let clock = Clock::get()?;
@@ -180,57 +928,648 @@ pub fn update_listing(
         listing.description = desc;
     }
     
-    if let Some(ver) = version {
-        require!(ver.len() <= 16, SwarmError::InvalidParameter);
-        listing.version = ver;
-    }
-    
     if let Some(p) = price {
         listing.price = p;
     }
     
+    if let Some(mint) = price_mint {
+        listing.price_mint = mint;
+    }
+    
+    if let Some(m) = manifest {
+        AgentManifest::parse(&m)?;
+        listing.manifest = m;
+    }
+    
+    if let Some(c) = category {
+        let marketplace = &mut ctx.accounts.marketplace;
+        let counts = &mut marketplace.category_counts;
+        counts[listing.category.index()] = counts[listing.category.index()].saturating_sub(1);
+        counts[c.index()] = counts[c.index()].saturating_add(1);
+        listing.category = c;
+    }
+    
+    if let Some(t) = tags {
+        validate_tags(&t)?;
+        listing.tags = t;
+    }
+    
     listing.updated_at = clock.unix_timestamp;
     
+    emit!(ListingUpdated {
+        listing: listing.key(),
+        price: listing.price,
+        price_mint: listing.price_mint,
+        category: listing.category,
+        timestamp: clock.unix_timestamp,
+    });
+    
     msg!("Listing updated: {}", listing.name);
     Ok(())
 }
 
-pub fn download_agent(ctx: Context<DownloadAgent>) -> Result<()> {
+pub fn transfer_listing(ctx: Context<TransferListing>, new_owner: Pubkey) -> Result<()> {
+    require!(ctx.accounts.auction.data_is_empty(), SwarmError::AuctionActive);
+    
+    let listing = &mut ctx.accounts.listing;
+    let previous_owner = listing.owner;
+    listing.owner = new_owner;
+    listing.updated_at = Clock::get()?.unix_timestamp;
+    
+    emit!(ListingTransferred {
+        listing: listing.key(),
+        previous_owner,
+        new_owner,
+        timestamp: listing.updated_at,
+    });
+    
+    msg!("Listing {} transferred to {}", listing.name, new_owner);
+    Ok(())
+}
+
+pub fn publish_version(
+    ctx: Context<PublishVersion>,
+    version: String,
+    changelog_hash: [u8; 32],
+) -> Result<()> {
+    require!(!version.is_empty() && version.len() <= 16, SwarmError::InvalidParameter);
+    
+    let clock = Clock::get()?;
+    let listing = &mut ctx.accounts.listing;
+    listing.version = version.clone();
+    listing.updated_at = clock.unix_timestamp;
+    
+    let listing_version = &mut ctx.accounts.listing_version;
+    listing_version.listing = listing.key();
+    listing_version.version = version;
+    listing_version.changelog_hash = changelog_hash;
+    listing_version.released_at = clock.unix_timestamp;
+    listing_version.layout_version = ListingVersion::VERSION;
+    listing_version.bump = ctx.bumps.listing_version;
+    
+    emit!(VersionPublished {
+        listing: listing.key(),
+        version: listing.version.clone(),
+        changelog_hash,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Released {} v{}", listing.name, listing.version);
+    Ok(())
+}
+
+pub fn download_agent<'info>(ctx: Context<'_, '_, '_, 'info, DownloadAgent<'info>>) -> Result<()> {
     let listing = &mut ctx.accounts.listing;
     
-    // Transfer payment if not free
+    if ctx.accounts.purchase.purchased_at != 0 {
+        msg!("Agent already purchased: {}", listing.name);
+        return Ok(());
+    }
+    
+    // Hold the payment on the receipt until the refund window closes. Only
+    // what arrives after a Token-2022 transfer fee is escrowed.
+    let mut escrowed = listing.price;
     if listing.price > 0 {
-        let ix = anchor_lang::solana_program::system_instruction::transfer(
-            &ctx.accounts.buyer.key(),
-            &listing.owner,
-            listing.price,
-        );
+        match listing.price_mint {
+            Some(mint) => {
+                let (
+                    Some(buyer_token_account),
+                    Some(escrow_token_account),
+                    Some(mint_account),
+                    Some(token_program),
+                ) = (
+                    ctx.accounts.buyer_token_account.as_ref(),
+                    ctx.accounts.escrow_token_account.as_ref(),
+                    ctx.accounts.mint.as_ref(),
+                    ctx.accounts.token_program.as_ref(),
+                ) else {
+                    return err!(SwarmError::MissingExecutionAccount);
+                };
+                require_keys_eq!(mint_account.key(), mint, SwarmError::InvalidParameter);
+                token_extensions::require_allowed_hook(
+                    &mint_account.to_account_info(),
+                    ctx.accounts.program_whitelist.as_deref(),
+                )?;
+                
+                escrowed = CPIHelper::transfer_tokens_checked(
+                    &TokenTransferAccounts {
+                        from: buyer_token_account,
+                        to: escrow_token_account,
+                        mint: mint_account,
+                        authority: &ctx.accounts.buyer.to_account_info(),
+                        token_program,
+                        hook_accounts: ctx.remaining_accounts,
+                    },
+                    listing.price,
+                    &[],
+                )?;
+            }
+            None => {
+                system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: ctx.accounts.buyer.to_account_info(),
+                            to: ctx.accounts.purchase.to_account_info(),
+                        },
+                    ),
+                    listing.price,
+                )?;
+            }
+        }
+    }
+    
+    listing.downloads = listing.downloads.saturating_add(1);
+    
+    let clock = Clock::get()?;
+    let purchase = &mut ctx.accounts.purchase;
+    purchase.listing = listing.key();
+    purchase.buyer = ctx.accounts.buyer.key();
+    purchase.seller = listing.owner;
+    purchase.mint = listing.price_mint;
+    purchase.price_paid = listing.price;
+    purchase.escrowed = escrowed;
+    purchase.purchased_at = clock.unix_timestamp;
+    purchase.refund_deadline = clock.unix_timestamp
+        .checked_add(ctx.accounts.swarm_state.sale_dispute_window)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    purchase.version = Purchase::VERSION;
+    purchase.bump = ctx.bumps.purchase;
+    
+    listing.refunds_open_until = listing.refunds_open_until.max(purchase.refund_deadline);
+    
+    emit!(AgentPurchased {
+        listing: purchase.listing,
+        buyer: purchase.buyer,
+        seller: purchase.seller,
+        mint: purchase.mint,
+        price_paid: purchase.price_paid,
+        refund_deadline: purchase.refund_deadline,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Agent downloaded: {} (total: {})", listing.name, listing.downloads);
+    Ok(())
+}
+
+pub fn claim_sale_proceeds<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimSaleProceeds<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let purchase = &mut ctx.accounts.purchase;
+    
+    require!(clock.unix_timestamp >= purchase.refund_deadline, SwarmError::RefundWindowOpen);
+    require!(purchase.escrowed > 0, SwarmError::SaleAlreadySettled);
+    
+    let amount = purchase.escrowed;
+    let fee = (amount as u128 * ctx.accounts.swarm_state.marketplace_fee_bps as u128
+        / BPS_DENOMINATOR as u128) as u64;
+    
+    purchase.escrowed = 0;
+    
+    let Some(mint) = purchase.mint else {
+        purchase.sub_lamports(amount)?;
+        ctx.accounts.seller.add_lamports(amount - fee)?;
+        
+        if fee > 0 {
+            let treasury = &mut ctx.accounts.treasury;
+            treasury.add_lamports(fee)?;
+            treasury.record_fee(fee)?;
+            
+            emit!(FeePaid {
+                schema_version: ACCOUNTING_SCHEMA_VERSION,
+                asset: NATIVE_SOL_ASSET,
+                amount: fee,
+                payer: purchase.buyer,
+                recipient: treasury.key(),
+                proposal: None,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        
+        emit!(SaleProceedsClaimed {
+            purchase: purchase.key(),
+            seller: purchase.seller,
+            mint: None,
+            amount: amount - fee,
+            fee,
+            timestamp: clock.unix_timestamp,
+        });
         
-        anchor_lang::solana_program::program::invoke(
-            &ix,
-            &[
-                ctx.accounts.buyer.to_account_info(),
-                ctx.accounts.owner.to_account_info(),
-            ],
+        msg!("Sale proceeds claimed: {} lamports", amount - fee);
+        return Ok(());
+    };
+    
+    let (
+        Some(buyer),
+        Some(escrow_token_account),
+        Some(seller_token_account),
+        Some(treasury_token_account),
+        Some(mint_account),
+        Some(token_program),
+    ) = (
+        ctx.accounts.buyer.as_ref(),
+        ctx.accounts.escrow_token_account.as_ref(),
+        ctx.accounts.seller_token_account.as_ref(),
+        ctx.accounts.treasury_token_account.as_ref(),
+        ctx.accounts.mint.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+    ) else {
+        return err!(SwarmError::MissingExecutionAccount);
+    };
+    
+    let bump = [purchase.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        PURCHASE_SEED,
+        purchase.listing.as_ref(),
+        purchase.buyer.as_ref(),
+        &bump,
+    ]];
+    
+    let payout = TokenTransferAccounts {
+        from: escrow_token_account,
+        to: seller_token_account,
+        mint: mint_account,
+        authority: &purchase.to_account_info(),
+        token_program,
+        hook_accounts: ctx.remaining_accounts,
+    };
+    CPIHelper::transfer_tokens_checked(&payout, amount - fee, signer_seeds)?;
+    
+    if fee > 0 {
+        CPIHelper::transfer_tokens_checked(
+            &TokenTransferAccounts { to: treasury_token_account, ..payout },
+            fee,
+            signer_seeds,
         )?;
+        
+        emit!(FeePaid {
+            schema_version: ACCOUNTING_SCHEMA_VERSION,
+            asset: mint,
+            amount: fee,
+            payer: purchase.buyer,
+            recipient: treasury_token_account.key(),
+            proposal: None,
+            timestamp: clock.unix_timestamp,
+        });
     }
     
-    listing.downloads += 1;
+    close_purchase_escrow(purchase, escrow_token_account, buyer, mint_account, token_program, mint)?;
     
-    msg!("Agent downloaded: {} (total: {})", listing.name, listing.downloads);
+    emit!(SaleProceedsClaimed {
+        purchase: purchase.key(),
+        seller: purchase.seller,
+        mint: Some(mint),
+        amount: amount - fee,
+        fee,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Sale proceeds claimed: {} of mint {}", amount - fee, mint);
     Ok(())
 }
 
-pub fn rate_agent(ctx: Context<RateAgent>, rating: u8) -> Result<()> {
-    require!(rating >= 1 && rating <= 5, SwarmError::InvalidParameter);
+pub fn request_refund<'info>(ctx: Context<'_, '_, '_, 'info, RequestRefund<'info>>) -> Result<()> {
+    let purchase = &ctx.accounts.purchase;
+    
+    require!(purchase.escrowed > 0, SwarmError::SaleAlreadySettled);
+    require!(
+        purchase.is_refundable(Clock::get()?.unix_timestamp),
+        SwarmError::RefundWindowClosed
+    );
+    
+    // `close = buyer` returns escrowed lamports together with the rent
+    if let Some(mint) = purchase.mint {
+        let (
+            Some(escrow_token_account),
+            Some(buyer_token_account),
+            Some(mint_account),
+            Some(token_program),
+        ) = (
+            ctx.accounts.escrow_token_account.as_ref(),
+            ctx.accounts.buyer_token_account.as_ref(),
+            ctx.accounts.mint.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+        ) else {
+            return err!(SwarmError::MissingExecutionAccount);
+        };
+        
+        let bump = [purchase.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            PURCHASE_SEED,
+            purchase.listing.as_ref(),
+            purchase.buyer.as_ref(),
+            &bump,
+        ]];
+        
+        CPIHelper::transfer_tokens_checked(
+            &TokenTransferAccounts {
+                from: escrow_token_account,
+                to: buyer_token_account,
+                mint: mint_account,
+                authority: &purchase.to_account_info(),
+                token_program,
+                hook_accounts: ctx.remaining_accounts,
+            },
+            purchase.escrowed,
+            signer_seeds,
+        )?;
+        
+        close_purchase_escrow(
+            purchase,
+            escrow_token_account,
+            &ctx.accounts.buyer.to_account_info(),
+            mint_account,
+            token_program,
+            mint,
+        )?;
+    }
     
     let listing = &mut ctx.accounts.listing;
+    listing.downloads = listing.downloads.saturating_sub(1);
+    withdraw_rating(
+        listing,
+        &ctx.accounts.rating,
+        &ctx.accounts.review,
+        &ctx.accounts.buyer.to_account_info(),
+    )?;
+    
+    emit!(PurchaseRefunded {
+        purchase: purchase.key(),
+        buyer: purchase.buyer,
+        mint: purchase.mint,
+        amount: purchase.escrowed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     
-    // Update running average rating
-    let current_total = (listing.rating as u64) * listing.total_ratings;
-    let new_total = current_total + (rating as u64 * 100);
-    listing.total_ratings += 1;
-    listing.rating = (new_total / listing.total_ratings) as u16;
+    msg!("Purchase of {} refunded: {}", listing.name, purchase.escrowed);
+    Ok(())
+}
+
+/// Take a refunded buyer's rating out of the listing's average and close
+/// their `Rating` and `Review`, when they left them
+fn withdraw_rating(
+    listing: &mut AgentListing,
+    rating: &AccountInfo,
+    review: &AccountInfo,
+    buyer: &AccountInfo,
+) -> Result<()> {
+    if *rating.owner == crate::ID {
+        let stars = Rating::try_deserialize(&mut &rating.try_borrow_data()?[..])?.stars;
+        listing.remove_rating(stars);
+        close_program_account(rating, buyer)?;
+    }
+    if *review.owner == crate::ID {
+        Review::try_deserialize(&mut &review.try_borrow_data()?[..])?;
+        close_program_account(review, buyer)?;
+    }
+    Ok(())
+}
+
+/// Close an account of this program, returning its rent to `destination`
+fn close_program_account(account: &AccountInfo, destination: &AccountInfo) -> Result<()> {
+    destination.add_lamports(account.lamports())?;
+    account.sub_lamports(account.lamports())?;
+    account.assign(&system_program::ID);
+    account.realloc(0, false)?;
+    Ok(())
+}
+
+/// Close an emptied SPL escrow, returning its rent to the buyer who funded it.
+/// Token-2022 transfer fees withheld in it go to the mint first.
+fn close_purchase_escrow<'info>(
+    purchase: &Account<'info, Purchase>,
+    escrow_token_account: &InterfaceAccount<'info, TokenAccount>,
+    buyer: &AccountInfo<'info>,
+    mint_account: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    mint: Pubkey,
+) -> Result<()> {
+    require_keys_eq!(escrow_token_account.owner, purchase.key(), SwarmError::Unauthorized);
+    require_keys_eq!(escrow_token_account.mint, mint, SwarmError::InvalidParameter);
+    
+    token_extensions::harvest_withheld_fees(
+        &token_program.to_account_info(),
+        &mint_account.to_account_info(),
+        &escrow_token_account.to_account_info(),
+    )?;
+    
+    let bump = [purchase.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        PURCHASE_SEED,
+        purchase.listing.as_ref(),
+        purchase.buyer.as_ref(),
+        &bump,
+    ]];
+    
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow_token_account.to_account_info(),
+            destination: buyer.clone(),
+            authority: purchase.to_account_info(),
+        },
+        signer_seeds,
+    ))
+}
+
+/// Set the per-period subscription price; a price of 0 stops new subscriptions
+pub fn set_subscription_terms(
+    ctx: Context<UpdateListing>,
+    period_price: u64,
+    period_length: i64,
+) -> Result<()> {
+    require!(
+        period_price == 0 || period_length >= MIN_SUBSCRIPTION_PERIOD,
+        SwarmError::InvalidParameter
+    );
+    
+    let listing = &mut ctx.accounts.listing;
+    listing.period_price = period_price;
+    listing.period_length = period_length;
+    listing.updated_at = Clock::get()?.unix_timestamp;
+    
+    emit!(SubscriptionTermsSet {
+        listing: listing.key(),
+        period_price,
+        period_length,
+        timestamp: listing.updated_at,
+    });
+    
+    msg!("Subscription terms for {}: {} per {}s", listing.name, period_price, period_length);
+    Ok(())
+}
+
+pub fn subscribe(ctx: Context<Subscribe>) -> Result<()> {
+    let listing = &ctx.accounts.listing;
+    let clock = Clock::get()?;
+    
+    pay_listing_owner(
+        &ctx.accounts.subscriber,
+        &ctx.accounts.owner,
+        &mut ctx.accounts.treasury,
+        &ctx.accounts.swarm_state,
+        &ctx.accounts.system_program,
+        listing.period_price,
+    )?;
+    
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.listing = listing.key();
+    subscription.subscriber = ctx.accounts.subscriber.key();
+    subscription.expires_at = clock.unix_timestamp
+        .checked_add(listing.period_length)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    subscription.total_paid = listing.period_price;
+    subscription.version = Subscription::VERSION;
+    subscription.bump = ctx.bumps.subscription;
+    
+    emit!(Subscribed {
+        listing: subscription.listing,
+        subscriber: subscription.subscriber,
+        amount: listing.period_price,
+        expires_at: subscription.expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Subscribed to {} until {}", listing.name, subscription.expires_at);
+    Ok(())
+}
+
+pub fn renew_subscription(ctx: Context<RenewSubscription>) -> Result<()> {
+    let listing = &ctx.accounts.listing;
+    let clock = Clock::get()?;
+    
+    pay_listing_owner(
+        &ctx.accounts.subscriber,
+        &ctx.accounts.owner,
+        &mut ctx.accounts.treasury,
+        &ctx.accounts.swarm_state,
+        &ctx.accounts.system_program,
+        listing.period_price,
+    )?;
+    
+    // Renewing early extends the current period; a lapsed one restarts now
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.expires_at = subscription
+        .expires_at
+        .max(clock.unix_timestamp)
+        .checked_add(listing.period_length)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    subscription.total_paid = subscription
+        .total_paid
+        .checked_add(listing.period_price)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    
+    emit!(SubscriptionRenewed {
+        listing: subscription.listing,
+        subscriber: subscription.subscriber,
+        amount: listing.period_price,
+        expires_at: subscription.expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Subscription to {} renewed until {}", listing.name, subscription.expires_at);
+    Ok(())
+}
+
+/// Pay `amount` from `buyer` to the listing owner, less the protocol fee
+/// routed to the treasury
+fn pay_listing_owner<'info>(
+    buyer: &Signer<'info>,
+    owner: &AccountInfo<'info>,
+    treasury: &mut Account<'info, Treasury>,
+    swarm_state: &SwarmState,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    let fee = (amount as u128 * swarm_state.marketplace_fee_bps as u128
+        / BPS_DENOMINATOR as u128) as u64;
+    
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: buyer.to_account_info(),
+                to: owner.clone(),
+            },
+        ),
+        amount - fee,
+    )?;
+    
+    if fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: buyer.to_account_info(),
+                    to: treasury.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+        
+        treasury.record_fee(fee)?;
+        
+        emit!(FeePaid {
+            schema_version: ACCOUNTING_SCHEMA_VERSION,
+            asset: NATIVE_SOL_ASSET,
+            amount: fee,
+            payer: buyer.key(),
+            recipient: treasury.key(),
+            proposal: None,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+    
+    Ok(())
+}
+
+/// Record `stars` from `rater` and re-average the listing
+fn apply_rating(
+    listing: &mut AgentListing,
+    listing_key: Pubkey,
+    rating_account: &mut Rating,
+    rater: Pubkey,
+    stars: u8,
+    bump: u8,
+) -> Result<()> {
+    require!((1..=5).contains(&stars), SwarmError::InvalidParameter);
+    listing.record_rating(rating_account.stars, stars)?;
+    
+    rating_account.listing = listing_key;
+    rating_account.rater = rater;
+    rating_account.stars = stars;
+    rating_account.rated_at = Clock::get()?.unix_timestamp;
+    rating_account.version = Rating::VERSION;
+    rating_account.bump = bump;
+    
+    Ok(())
+}
+
+pub fn rate_agent(ctx: Context<RateAgent>, rating: u8) -> Result<()> {
+    let listing_key = ctx.accounts.listing.key();
+    let listing = &mut ctx.accounts.listing;
+    
+    apply_rating(
+        listing,
+        listing_key,
+        &mut ctx.accounts.rating,
+        ctx.accounts.rater.key(),
+        rating,
+        ctx.bumps.rating,
+    )?;
+    
+    emit!(AgentRated {
+        listing: listing_key,
+        rater: ctx.accounts.rater.key(),
+        stars: rating,
+        average: listing.rating,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     
     msg!(
         "Agent rated: {} - {} stars (avg: {:.2})",
@@ -242,13 +1581,52 @@ pub fn rate_agent(ctx: Context<RateAgent>, rating: u8) -> Result<()> {
     Ok(())
 }
 
+pub fn review_agent(ctx: Context<ReviewAgent>, stars: u8, text: String) -> Result<()> {
+    require!(
+        !text.is_empty() && text.len() <= MAX_REVIEW_LENGTH,
+        SwarmError::ReviewTooLong
+    );
+    
+    let listing_key = ctx.accounts.listing.key();
+    let reviewer = ctx.accounts.reviewer.key();
+    let listing = &mut ctx.accounts.listing;
+    
+    apply_rating(
+        listing,
+        listing_key,
+        &mut ctx.accounts.rating,
+        reviewer,
+        stars,
+        ctx.bumps.rating,
+    )?;
+    
+    let review = &mut ctx.accounts.review;
+    review.listing = listing_key;
+    review.reviewer = reviewer;
+    review.stars = stars;
+    review.text = text;
+    review.reviewed_at = Clock::get()?.unix_timestamp;
+    review.version = Review::VERSION;
+    review.bump = ctx.bumps.review;
+    
+    emit!(AgentReviewed {
+        listing: listing_key,
+        reviewer,
+        stars,
+        timestamp: review.reviewed_at,
+    });
+    
+    msg!("Agent reviewed: {} - {} stars", listing.name, stars);
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct InitializeMarketplace<'info> {
     #[account(
         init,
         payer = authority,
         space = AgentMarketplace::LEN,
-        seeds = [b"marketplace"],
+        seeds = [MARKETPLACE_SEED],
         bump
     )]
     pub marketplace: Account<'info, AgentMarketplace>,
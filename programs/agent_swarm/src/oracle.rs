@@ -0,0 +1,419 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::payloads::TradePayload;
+use crate::state::{PriceFeed, SwarmState, TwapGuard, TwapTracker};
+
+/// Pyth Solana receiver program ID (compile-time constant — no runtime unwrap)
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Switchboard V2 program ID, owner of aggregator accounts
+pub const SWITCHBOARD_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
+
+/// Anchor discriminator of the receiver's `PriceUpdateV2` account
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Anchor discriminator of Switchboard's `AggregatorAccountData` account
+const AGGREGATOR_DISCRIMINATOR: [u8; 8] = [217, 230, 65, 101, 201, 162, 27, 125];
+
+/// Where a registered feed's prices come from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OracleSource {
+    /// Pyth pull-oracle feed, read from a posted `PriceUpdateV2` account
+    Pyth { feed_id: [u8; 32] },
+    /// Switchboard aggregator account, for mints Pyth does not cover
+    Switchboard { aggregator: Pubkey },
+}
+
+impl OracleSource {
+    pub const LEN: usize = 1 + 32;
+}
+
+/// Wormhole verification of a posted price update
+#[derive(AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+/// Pyth pull-oracle `PriceUpdateV2` account, minus its discriminator
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct PriceUpdate {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+    pub posted_slot: u64,
+}
+
+impl PriceUpdate {
+    /// Read a fully verified, positive price for `feed_id`
+    pub fn load(account: &AccountInfo, feed_id: &[u8; 32]) -> Result<Self> {
+        require_keys_eq!(*account.owner, PYTH_RECEIVER_PROGRAM_ID, SwarmError::InvalidPriceFeed);
+
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() > 8 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+            SwarmError::InvalidPriceFeed
+        );
+        let update = Self::deserialize(&mut &data[8..])
+            .map_err(|_| error!(SwarmError::InvalidPriceFeed))?;
+
+        require!(
+            update.verification_level == VerificationLevel::Full
+                && update.feed_id == *feed_id
+                && update.price > 0,
+            SwarmError::InvalidPriceFeed
+        );
+
+        Ok(update)
+    }
+}
+
+/// Switchboard's fixed-point decimal: `mantissa * 10^-scale`
+#[derive(AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SwitchboardDecimal {
+    pub mantissa: i128,
+    pub scale: u32,
+}
+
+/// Leading fields of Switchboard's packed `AggregatorAccountData`, minus
+/// its discriminator, up to the latest confirmed round's result
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct AggregatorHeader {
+    pub name: [u8; 32],
+    pub metadata: [u8; 128],
+    pub _reserved1: [u8; 32],
+    pub queue_pubkey: Pubkey,
+    pub oracle_request_batch_size: u32,
+    pub min_oracle_results: u32,
+    pub min_job_results: u32,
+    pub min_update_delay_seconds: u32,
+    pub start_after: i64,
+    pub variance_threshold: SwitchboardDecimal,
+    pub force_report_period: i64,
+    pub expiration: i64,
+    pub consecutive_failure_count: u64,
+    pub next_allowed_update_time: i64,
+    pub is_locked: bool,
+    pub crank_pubkey: Pubkey,
+    pub num_success: u32,
+    pub num_error: u32,
+    pub is_closed: bool,
+    pub round_open_slot: u64,
+    pub round_open_timestamp: i64,
+    pub result: SwitchboardDecimal,
+    pub std_deviation: SwitchboardDecimal,
+}
+
+impl AggregatorHeader {
+    /// Read the latest confirmed round of `aggregator`
+    pub fn load(account: &AccountInfo, aggregator: &Pubkey) -> Result<Self> {
+        require_keys_eq!(account.key(), *aggregator, SwarmError::InvalidPriceFeed);
+        require_keys_eq!(*account.owner, SWITCHBOARD_PROGRAM_ID, SwarmError::InvalidPriceFeed);
+
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() > 8 && data[..8] == AGGREGATOR_DISCRIMINATOR,
+            SwarmError::InvalidPriceFeed
+        );
+        let header = Self::deserialize(&mut &data[8..])
+            .map_err(|_| error!(SwarmError::InvalidPriceFeed))?;
+
+        require!(
+            header.num_success >= header.min_oracle_results.max(1)
+                && header.result.mantissa > 0,
+            SwarmError::InvalidPriceFeed
+        );
+
+        Ok(header)
+    }
+}
+
+/// A price as `price * 10^exponent`, with its uncertainty on the same scale
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    /// Read `feed`'s price from `account`, rejecting it when older than the
+    /// feed's `max_age` or less certain than its `max_deviation_bps`
+    pub fn load(account: &AccountInfo, feed: &PriceFeed, current_time: i64) -> Result<Self> {
+        let price = match feed.source {
+            OracleSource::Pyth { feed_id } => {
+                let update = PriceUpdate::load(account, &feed_id)?;
+                OraclePrice {
+                    price: update.price,
+                    conf: update.conf,
+                    exponent: update.exponent,
+                    publish_time: update.publish_time,
+                }
+            }
+            OracleSource::Switchboard { aggregator } => {
+                let header = AggregatorHeader::load(account, &aggregator)?;
+                Self::from_switchboard(&header)?
+            }
+        };
+
+        require!(
+            current_time.saturating_sub(price.publish_time) <= feed.max_age,
+            SwarmError::StalePrice
+        );
+        require!(
+            feed.max_deviation_bps == 0
+                || price.conf as u128 * BPS_DENOMINATOR as u128
+                    <= price.price as u128 * feed.max_deviation_bps as u128,
+            SwarmError::OracleDeviationExceeded
+        );
+
+        Ok(price)
+    }
+
+    /// Time-weighted average over `window` seconds from the swarm's tracker
+    /// of `feed.mint`, whose last observation must be within the feed's
+    /// `max_age`
+    pub fn load_twap(
+        account: &AccountInfo,
+        swarm: &Pubkey,
+        feed: &PriceFeed,
+        window: i64,
+        current_time: i64,
+    ) -> Result<Self> {
+        require_keys_eq!(*account.owner, crate::ID, SwarmError::InvalidPriceFeed);
+        let tracker = TwapTracker::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        require!(
+            tracker.swarm == *swarm && tracker.mint == feed.mint,
+            SwarmError::InvalidPriceFeed
+        );
+        require!(
+            current_time.saturating_sub(tracker.last_observed_at) <= feed.max_age,
+            SwarmError::StalePrice
+        );
+
+        Ok(OraclePrice {
+            price: tracker.twap(window)?,
+            conf: 0,
+            exponent: TWAP_PRICE_EXPONENT,
+            publish_time: tracker.last_observed_at,
+        })
+    }
+
+    /// The price as a mantissa of `10^exponent`
+    pub fn rescale(&self, exponent: i32) -> Result<i64> {
+        let power = 10i64
+            .checked_pow((self.exponent - exponent).unsigned_abs())
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        if self.exponent >= exponent {
+            self.price.checked_mul(power).ok_or_else(|| error!(SwarmError::ArithmeticOverflow))
+        } else {
+            Ok(self.price / power)
+        }
+    }
+
+    /// Rescale a Switchboard round onto an i64 mantissa, dropping precision
+    /// a price does not need
+    fn from_switchboard(header: &AggregatorHeader) -> Result<Self> {
+        let (mut price, mut deviation, mut scale) = (
+            header.result.mantissa,
+            header.std_deviation.mantissa.unsigned_abs(),
+            header.result.scale as i64,
+        );
+        // Bring the deviation onto the result's scale
+        let shift = header.std_deviation.scale as i64 - scale;
+        for _ in 0..shift.unsigned_abs() {
+            deviation = if shift > 0 { deviation / 10 } else { deviation.saturating_mul(10) };
+        }
+        while price > i64::MAX as i128 {
+            price /= 10;
+            deviation /= 10;
+            scale -= 1;
+        }
+
+        Ok(OraclePrice {
+            price: price as i64,
+            conf: u64::try_from(deviation).unwrap_or(u64::MAX),
+            exponent: i32::try_from(-scale).map_err(|_| error!(SwarmError::InvalidPriceFeed))?,
+            publish_time: header.round_open_timestamp,
+        })
+    }
+}
+
+/// `amount_in` base units of one token valued in base units of another, at
+/// the two oracle prices
+pub fn fair_amount_out(
+    amount_in: u64,
+    price_in: &OraclePrice,
+    decimals_in: u8,
+    price_out: &OraclePrice,
+    decimals_out: u8,
+) -> Result<u64> {
+    let scale = price_in.exponent - price_out.exponent + decimals_out as i32 - decimals_in as i32;
+    let power = 10u128
+        .checked_pow(scale.unsigned_abs())
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+
+    let mut numerator = (amount_in as u128)
+        .checked_mul(price_in.price as u128)
+        .ok_or(SwarmError::ArithmeticOverflow)?;
+    let mut denominator = price_out.price as u128;
+    if scale >= 0 {
+        numerator = numerator.checked_mul(power).ok_or(SwarmError::ArithmeticOverflow)?;
+    } else {
+        denominator = denominator.checked_mul(power).ok_or(SwarmError::ArithmeticOverflow)?;
+    }
+
+    u64::try_from(numerator / denominator).map_err(|_| error!(SwarmError::ArithmeticOverflow))
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PriceComparison {
+    Below,
+    Above,
+}
+
+/// Execution gate on a pair price, e.g. "only if SOL/USDC < 150". The price
+/// is in `quote_mint` base units per whole `base_mint` token, read from the
+/// swarm's registered feeds when the proposal executes.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PriceCondition {
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub comparison: PriceComparison,
+    pub threshold: u64,
+    /// Compare the mints' TWAPs over this many seconds instead of their
+    /// spot prices; 0 uses spot prices
+    pub twap_window: i64,
+}
+
+impl PriceCondition {
+    /// Both mints need a registered feed for the condition to be checkable
+    pub fn validate(&self, swarm_state: &SwarmState) -> Result<()> {
+        require!(
+            self.base_mint != self.quote_mint && self.threshold > 0,
+            SwarmError::InvalidParameter
+        );
+        require!(
+            (0..=MAX_TWAP_WINDOW).contains(&self.twap_window),
+            SwarmError::InvalidParameter
+        );
+        require!(
+            swarm_state.price_feed(&self.base_mint).is_some()
+                && swarm_state.price_feed(&self.quote_mint).is_some(),
+            SwarmError::MissingPriceFeed
+        );
+        Ok(())
+    }
+
+    /// Pair price from the feeds' current prices or, with a `twap_window`,
+    /// from the mints' `TwapTracker`s passed in their place
+    pub fn price(
+        &self,
+        swarm: &Pubkey,
+        swarm_state: &SwarmState,
+        base_price_account: &AccountInfo,
+        quote_price_account: &AccountInfo,
+        current_time: i64,
+    ) -> Result<u64> {
+        let feed_base = swarm_state
+            .price_feed(&self.base_mint)
+            .ok_or(SwarmError::MissingPriceFeed)?;
+        let feed_quote = swarm_state
+            .price_feed(&self.quote_mint)
+            .ok_or(SwarmError::MissingPriceFeed)?;
+
+        let load = |account: &AccountInfo, feed: &PriceFeed| match self.twap_window {
+            0 => OraclePrice::load(account, feed, current_time),
+            window => OraclePrice::load_twap(account, swarm, feed, window, current_time),
+        };
+
+        let one_token = 10u64
+            .checked_pow(feed_base.decimals as u32)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        fair_amount_out(
+            one_token,
+            &load(base_price_account, feed_base)?,
+            feed_base.decimals,
+            &load(quote_price_account, feed_quote)?,
+            feed_quote.decimals,
+        )
+    }
+
+    pub fn is_met(&self, price: u64) -> bool {
+        match self.comparison {
+            PriceComparison::Below => price < self.threshold,
+            PriceComparison::Above => price > self.threshold,
+        }
+    }
+}
+
+/// Reject a trade whose `min_out` sits further below the oracle-implied
+/// output than `max_slippage_bps` allows
+pub fn check_trade_slippage(
+    swarm_state: &SwarmState,
+    max_slippage_bps: u16,
+    price_update_in: &AccountInfo,
+    price_update_out: &AccountInfo,
+    payload: &TradePayload,
+    current_time: i64,
+) -> Result<()> {
+    let feed_in = swarm_state
+        .price_feed(&payload.mint_in)
+        .ok_or(SwarmError::MissingPriceFeed)?;
+    let feed_out = swarm_state
+        .price_feed(&payload.mint_out)
+        .ok_or(SwarmError::MissingPriceFeed)?;
+
+    let price_in = OraclePrice::load(price_update_in, feed_in, current_time)?;
+    let price_out = OraclePrice::load(price_update_out, feed_out, current_time)?;
+
+    let fair_out = fair_amount_out(
+        payload.amount,
+        &price_in,
+        feed_in.decimals,
+        &price_out,
+        feed_out.decimals,
+    )?;
+    let floor = fair_out as u128
+        * (BPS_DENOMINATOR - max_slippage_bps) as u128
+        / BPS_DENOMINATOR as u128;
+
+    msg!("Oracle check: min_out {} against fair {} (floor {})", payload.min_out, fair_out, floor);
+    require!(payload.min_out as u128 >= floor, SwarmError::OracleSlippageExceeded);
+    Ok(())
+}
+
+/// Reject a trade side whose oracle price strays from its TWAP by more than
+/// the risk config's `TwapGuard` allows
+pub fn check_twap_deviation(
+    swarm: &Pubkey,
+    swarm_state: &SwarmState,
+    guard: &TwapGuard,
+    price_update: &AccountInfo,
+    twap_tracker: &AccountInfo,
+    mint: &Pubkey,
+    current_time: i64,
+) -> Result<()> {
+    let feed = swarm_state.price_feed(mint).ok_or(SwarmError::MissingPriceFeed)?;
+    let spot = OraclePrice::load(price_update, feed, current_time)?.rescale(TWAP_PRICE_EXPONENT)?;
+    let twap = OraclePrice::load_twap(twap_tracker, swarm, feed, guard.window, current_time)?.price;
+
+    msg!("TWAP check for {}: spot {} against TWAP {}", mint, spot, twap);
+    require!(
+        spot.abs_diff(twap) as u128 * BPS_DENOMINATOR as u128
+            <= twap as u128 * guard.max_deviation_bps as u128,
+        SwarmError::TwapDeviationExceeded
+    );
+    Ok(())
+}
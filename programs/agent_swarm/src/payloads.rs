@@ -0,0 +1,859 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::oracle::PriceCondition;
+use crate::state::{CircuitBreaker, TwapGuard};
+use crate::ProposalType;
+use solana_program::keccak;
+
+/// Borsh payload carried in `Proposal.data` for `ProposalType::TreasuryTransfer`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TreasuryTransferPayload {
+    pub recipient: Pubkey,
+    pub lamports: u64,
+}
+
+/// Borsh payload for `ProposalType::Trade`: the pair being traded, its size
+/// and how to route it. Executing a structured trade swaps out of the treasury.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TradePayload {
+    pub mint_in: Pubkey,
+    pub mint_out: Pubkey,
+    pub amount: u64,
+    pub min_out: u64,
+    pub route: SwapRoute,
+    /// Paid from the treasury to a Jito tip account so the execution lands
+    /// promptly; 0 for none
+    pub tip_lamports: u64,
+}
+
+/// Venue a `Trade` executes through
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum SwapRoute {
+    /// Jupiter v6 route instruction data, as returned by the Jupiter API;
+    /// its accounts are passed as remaining accounts
+    Jupiter { data: Vec<u8> },
+    /// Raydium AMM V4 `swap_base_in`; the pool's accounts are passed as
+    /// remaining accounts
+    Raydium,
+}
+
+impl TradePayload {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.mint_in != Pubkey::default()
+                && self.mint_out != Pubkey::default()
+                && self.mint_in != self.mint_out,
+            SwarmError::InvalidPayload
+        );
+        require!(self.amount > 0 && self.min_out > 0, SwarmError::InvalidPayload);
+        match &self.route {
+            SwapRoute::Jupiter { data } => require!(!data.is_empty(), SwarmError::InvalidPayload),
+            SwapRoute::Raydium => {}
+        }
+        Ok(())
+    }
+
+    /// Identifies the trade's pair and route, as cited by an `ArbReport`
+    pub fn route_hash(&self) -> Result<[u8; 32]> {
+        Ok(keccak::hashv(&[
+            self.mint_in.as_ref(),
+            self.mint_out.as_ref(),
+            &self.route.try_to_vec()?,
+        ])
+        .to_bytes())
+    }
+}
+
+/// Structured `Strategy` proposal, executed against the swarm's vaults and
+/// signed by the vault authority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct StrategyPayload {
+    pub action: StrategyAction,
+    /// Vault token balance changes the action should produce, measured
+    /// after it runs and written to the proposal's `Outcome`
+    pub balance_checks: Vec<BalanceCheck>,
+    /// Fail execution when a measured delta falls outside its tolerance,
+    /// rather than recording an unsuccessful outcome
+    pub fail_on_deviation: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum StrategyAction {
+    /// An ordered list of instructions on whitelisted programs, performed in
+    /// one transaction so e.g. withdraw -> swap -> deposit either all land
+    /// or none do
+    Calls { calls: Vec<ProtocolCall> },
+    /// A Solend deposit, withdrawal or borrow on the vault's obligation
+    Lending(LendingPayload),
+    /// Stake treasury SOL into mSOL, or unstake it through Marinade's
+    /// delayed-unstake tickets
+    Staking(StakingPayload),
+    /// Open, adjust or close a Drift perp position held by the vault
+    Perp(PerpOrderPayload),
+    /// Provide or withdraw liquidity in Meteora DLMM bins from the vaults
+    Liquidity(LiquidityPayload),
+    /// Provide or withdraw liquidity in an Orca Whirlpool position owned
+    /// by the vault
+    Whirlpool(WhirlpoolPayload),
+    /// Replace the swarm's `StrategyConfig` parameters
+    Parameters(StrategyParameters),
+    /// Place a stop-loss or take-profit `StandingOrder`
+    StandingOrder(StandingOrderTerms),
+}
+
+/// A trade approved ahead of time, fired by anyone through `trigger_order`
+/// once the pair price crosses its trigger
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct StandingOrderTerms {
+    /// E.g. "SOL/USDC below 120" for a stop-loss selling SOL
+    pub trigger: PriceCondition,
+    pub trade: TradePayload,
+    /// The order lapses unfired after this time
+    pub expires_at: i64,
+}
+
+impl StandingOrderTerms {
+    pub fn validate(&self) -> Result<()> {
+        self.trade.validate()?;
+        // Keepers firing the order pay their own way
+        require!(self.trade.tip_lamports == 0, SwarmError::InvalidPayload);
+        if let SwapRoute::Jupiter { data } = &self.trade.route {
+            require!(data.len() <= MAX_ORDER_ROUTE_DATA, SwarmError::InvalidPayload);
+        }
+        require!(
+            self.trigger.base_mint != self.trigger.quote_mint && self.trigger.threshold > 0,
+            SwarmError::InvalidPayload
+        );
+        Ok(())
+    }
+}
+
+/// Parameters agents run the swarm's strategy with
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct StrategyParameters {
+    pub allocations: Vec<TargetAllocation>,
+    /// Seconds of history signals are computed over
+    pub lookback_windows: Vec<i64>,
+    /// Signal levels, in the order the strategy defines
+    pub thresholds: Vec<i64>,
+}
+
+impl StrategyParameters {
+    pub fn validate(&self) -> Result<()> {
+        validate_allocations(&self.allocations)?;
+        require!(
+            self.lookback_windows.len() <= MAX_STRATEGY_LOOKBACKS
+                && self.lookback_windows.iter().all(|window| *window > 0),
+            SwarmError::InvalidPayload
+        );
+        require!(
+            self.thresholds.len() <= MAX_STRATEGY_THRESHOLDS,
+            SwarmError::InvalidPayload
+        );
+        Ok(())
+    }
+}
+
+/// One instruction of a `StrategyAction::Calls`. Accounts, including the target
+/// program, are referenced by index into `execute_proposal`'s remaining
+/// accounts so calls can share them.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ProtocolCall {
+    pub program_index: u8,
+    pub accounts: Vec<ProtocolCallAccount>,
+    pub data: Vec<u8>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ProtocolCallAccount {
+    pub index: u8,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct LendingPayload {
+    pub action: LendingAction,
+    /// Liquidity to deposit or borrow, or collateral to withdraw
+    pub amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LendingAction {
+    Deposit,
+    Withdraw,
+    Borrow,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum StakingPayload {
+    /// Stake treasury lamports for mSOL held in a vault token account
+    Deposit { lamports: u64 },
+    /// Burn vault mSOL for a ticket redeemable once the epoch turns. The
+    /// ticket is a PDA of this proposal.
+    OrderUnstake { msol_amount: u64 },
+    /// Redeem the ticket ordered by `order_proposal` back into the treasury
+    Claim { order_proposal: Pubkey },
+}
+
+/// Meteora DLMM action on a vault-owned position. Positions are DLMM PDAs
+/// of the pair, the vault authority and their bin range.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum LiquidityPayload {
+    /// Create the vault's position spanning `width` bins from `lower_bin_id`,
+    /// with its rent paid from the treasury
+    OpenPosition { lower_bin_id: i32, width: i32 },
+    /// Deposit vault tokens into the position's bins; each bin's
+    /// distribution is its share of the amount in basis points
+    AddLiquidity { amount_x: u64, amount_y: u64, bins: Vec<BinLiquidity> },
+    /// Withdraw a share of each listed bin back to the vault token accounts
+    RemoveLiquidity { bins: Vec<BinReduction> },
+    /// Close an emptied position, returning its rent to the treasury
+    ClosePosition,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BinLiquidity {
+    pub bin_id: i32,
+    pub distribution_x: u16,
+    pub distribution_y: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BinReduction {
+    pub bin_id: i32,
+    pub bps_to_remove: u16,
+}
+
+impl LiquidityPayload {
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            LiquidityPayload::OpenPosition { width, .. } => require!(
+                *width > 0 && *width as usize <= MAX_LIQUIDITY_BINS,
+                SwarmError::InvalidPayload
+            ),
+            LiquidityPayload::AddLiquidity { amount_x, amount_y, bins } => {
+                require!(
+                    (*amount_x > 0 || *amount_y > 0)
+                        && !bins.is_empty()
+                        && bins.len() <= MAX_LIQUIDITY_BINS,
+                    SwarmError::InvalidPayload
+                );
+                let total_x: u32 = bins.iter().map(|bin| bin.distribution_x as u32).sum();
+                let total_y: u32 = bins.iter().map(|bin| bin.distribution_y as u32).sum();
+                require!(
+                    total_x <= BPS_DENOMINATOR as u32 && total_y <= BPS_DENOMINATOR as u32,
+                    SwarmError::InvalidPayload
+                );
+            }
+            LiquidityPayload::RemoveLiquidity { bins } => require!(
+                !bins.is_empty()
+                    && bins.len() <= MAX_LIQUIDITY_BINS
+                    && bins
+                        .iter()
+                        .all(|bin| bin.bps_to_remove > 0 && bin.bps_to_remove <= BPS_DENOMINATOR),
+                SwarmError::InvalidPayload
+            ),
+            LiquidityPayload::ClosePosition => {}
+        }
+        Ok(())
+    }
+}
+
+/// Orca Whirlpool action on a vault-owned position. The position's NFT is
+/// held in the vault authority's associated token account for its mint,
+/// which makes the vault authority the position's authority.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum WhirlpoolPayload {
+    /// Open a position over `[tick_lower_index, tick_upper_index)`, with its
+    /// rent paid from the treasury. The position mint is a fresh keypair
+    /// that signs the executing transaction.
+    OpenPosition { tick_lower_index: i32, tick_upper_index: i32 },
+    /// Deposit up to the given vault token amounts for `liquidity_amount`
+    IncreaseLiquidity { liquidity_amount: u128, token_max_a: u64, token_max_b: u64 },
+    /// Withdraw `liquidity_amount` back to the vault token accounts
+    DecreaseLiquidity { liquidity_amount: u128, token_min_a: u64, token_min_b: u64 },
+    /// Bring the position's fees up to date and collect them into the vault
+    CollectFees,
+    /// Close an emptied position, burning its NFT and returning its rent to
+    /// the treasury
+    ClosePosition,
+}
+
+impl WhirlpoolPayload {
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            WhirlpoolPayload::OpenPosition { tick_lower_index, tick_upper_index } => require!(
+                *tick_lower_index < *tick_upper_index
+                    && *tick_lower_index >= -MAX_WHIRLPOOL_TICK_INDEX
+                    && *tick_upper_index <= MAX_WHIRLPOOL_TICK_INDEX,
+                SwarmError::InvalidPayload
+            ),
+            WhirlpoolPayload::IncreaseLiquidity { liquidity_amount, token_max_a, token_max_b } => {
+                require!(
+                    *liquidity_amount > 0 && (*token_max_a > 0 || *token_max_b > 0),
+                    SwarmError::InvalidPayload
+                )
+            }
+            WhirlpoolPayload::DecreaseLiquidity { liquidity_amount, .. } => {
+                require!(*liquidity_amount > 0, SwarmError::InvalidPayload)
+            }
+            WhirlpoolPayload::CollectFees | WhirlpoolPayload::ClosePosition => {}
+        }
+        Ok(())
+    }
+}
+
+/// Drift perp market order. Reduce-only orders shrink or close a position
+/// and never grow it; others are checked against the market's position
+/// limit.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PerpOrderPayload {
+    pub market_index: u16,
+    pub direction: PerpDirection,
+    pub base_asset_amount: u64,
+    /// Worst fill price, in Drift's price precision
+    pub limit_price: u64,
+    pub reduce_only: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PerpDirection {
+    Long,
+    Short,
+}
+
+/// Expected change in a vault token account, referenced by index into
+/// `execute_proposal`'s remaining accounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct BalanceCheck {
+    pub account_index: u8,
+    pub expected_delta: i64,
+    /// Largest accepted distance between the measured and expected delta
+    pub tolerance: u64,
+}
+
+impl StrategyPayload {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.balance_checks.len() <= MAX_BALANCE_CHECKS,
+            SwarmError::InvalidPayload
+        );
+        // Setting parameters or placing an order moves no balances
+        if let StrategyAction::Parameters(_) | StrategyAction::StandingOrder(_) = self.action {
+            require!(self.balance_checks.is_empty(), SwarmError::InvalidPayload);
+        }
+        self.action.validate()
+    }
+}
+
+impl StrategyAction {
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            StrategyAction::Calls { calls } => require!(
+                !calls.is_empty() && calls.len() <= MAX_STRATEGY_CALLS,
+                SwarmError::InvalidPayload
+            ),
+            StrategyAction::Lending(payload) => {
+                require!(payload.amount > 0, SwarmError::InvalidPayload)
+            }
+            StrategyAction::Staking(payload) => match payload {
+                StakingPayload::Deposit { lamports: amount }
+                | StakingPayload::OrderUnstake { msol_amount: amount } => {
+                    require!(*amount > 0, SwarmError::InvalidPayload)
+                }
+                StakingPayload::Claim { .. } => {}
+            },
+            StrategyAction::Perp(payload) => require!(
+                payload.base_asset_amount > 0 && payload.limit_price > 0,
+                SwarmError::InvalidPayload
+            ),
+            StrategyAction::Liquidity(payload) => payload.validate()?,
+            StrategyAction::Whirlpool(payload) => payload.validate()?,
+            StrategyAction::Parameters(parameters) => parameters.validate()?,
+            StrategyAction::StandingOrder(terms) => terms.validate()?,
+        }
+        Ok(())
+    }
+}
+
+/// Borsh payload for `ProposalType::Rebalance`: target portfolio weights
+/// for off-chain executors, published when the proposal executes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RebalancePayload {
+    pub allocations: Vec<TargetAllocation>,
+    /// Repeat these allocations on a schedule, replacing any current one
+    pub schedule: Option<RebalanceScheduleTerms>,
+    /// Trades executors should make to reach the allocations. When any are
+    /// given, execution checks them against the deltas the `Portfolio`
+    /// needs at oracle prices.
+    pub trades: Vec<RebalanceTrade>,
+}
+
+/// One swap of a rebalance: `amount` base units of `mint_in` sold for
+/// `mint_out`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RebalanceTrade {
+    pub mint_in: Pubkey,
+    pub mint_out: Pubkey,
+    pub amount: u64,
+}
+
+/// How often a scheduled rebalance recurs and where executors should trade
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RebalanceScheduleTerms {
+    /// Seconds between rebalances; 0 stops the current schedule
+    pub interval: i64,
+    /// Swap programs executors should prefer, most preferred first
+    pub venues: Vec<Pubkey>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TargetAllocation {
+    pub mint: Pubkey,
+    pub weight_bps: u16,
+}
+
+/// Distinct mints whose weights sum to the whole portfolio
+fn validate_allocations(allocations: &[TargetAllocation]) -> Result<()> {
+    require!(
+        !allocations.is_empty() && allocations.len() <= MAX_REBALANCE_ALLOCATIONS,
+        SwarmError::InvalidPayload
+    );
+    let total: u32 = allocations.iter().map(|a| a.weight_bps as u32).sum();
+    require!(total == BPS_DENOMINATOR as u32, SwarmError::InvalidPayload);
+    for (i, allocation) in allocations.iter().enumerate() {
+        require!(
+            allocations[..i].iter().all(|other| other.mint != allocation.mint),
+            SwarmError::InvalidPayload
+        );
+    }
+    Ok(())
+}
+
+impl RebalancePayload {
+    pub fn validate(&self) -> Result<()> {
+        validate_allocations(&self.allocations)?;
+        if let Some(schedule) = &self.schedule {
+            require!(
+                schedule.interval == 0 || schedule.interval >= MIN_REBALANCE_INTERVAL,
+                SwarmError::InvalidPayload
+            );
+            require!(
+                schedule.venues.len() <= MAX_REBALANCE_VENUES
+                    && !schedule.venues.contains(&Pubkey::default()),
+                SwarmError::InvalidPayload
+            );
+        }
+        require!(self.trades.len() <= MAX_REBALANCE_TRADES, SwarmError::InvalidPayload);
+        for trade in &self.trades {
+            require!(
+                trade.amount > 0
+                    && trade.mint_in != Pubkey::default()
+                    && trade.mint_in != trade.mint_out
+                    && self.allocations.iter().any(|a| a.mint == trade.mint_out),
+                SwarmError::InvalidPayload
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Borsh payload for `ProposalType::RiskLimit`: sets (or with `None`
+/// removes) one limit held in the `PendingExposure` account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum RiskLimitPayload {
+    /// Pending-trade exposure limit for one input mint
+    Exposure { mint: Pubkey, max_amount: Option<u64> },
+    /// Largest perp position, in base units, in one Drift market
+    PerpPosition { market_index: u16, max_base_amount: Option<u64> },
+    /// Add a program to, or remove it from, the `Strategy` call whitelist
+    AllowProgram { program: Pubkey, allowed: bool },
+    /// Largest single structured trade
+    MaxPositionSize { max_amount: Option<u64> },
+    /// Largest total traded per day
+    MaxDailyVolume { max_amount: Option<u64> },
+    /// Largest accepted discount to the oracle price; `None` skips the check
+    MaxSlippage { max_slippage_bps: Option<u16> },
+    /// Add a mint to, or remove it from, the mints trades may touch
+    AllowMint { mint: Pubkey, allowed: bool },
+    /// Largest Jito tip one execution may pay; 0 disables tips
+    MaxExecutionTip { max_lamports: u64 },
+    /// Largest gap between a trade's oracle prices and their TWAPs; `None`
+    /// skips the check
+    TwapGuard { guard: Option<TwapGuard> },
+    /// Largest target weight one mint may have in a rebalance
+    MaxConcentration { max_weight_bps: Option<u16> },
+    /// Largest volume one swap program may carry per day; `None` lifts the cap
+    VenueCap { program: Pubkey, max_volume: Option<u64> },
+    /// Largest fall from peak of trades' oracle-valued results, measured
+    /// from when it is set
+    MaxDrawdown { max_drawdown: Option<u64> },
+    /// Drawdown of the marked portfolio value that pauses trading and
+    /// rebalancing; `None` disables the breaker
+    CircuitBreaker { breaker: Option<CircuitBreaker> },
+}
+
+/// Borsh payload for `ProposalType::TreasuryTokenTransfer`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TreasuryTokenTransferPayload {
+    pub mint: Pubkey,
+    pub recipient_token_account: Pubkey,
+    pub amount: u64,
+}
+
+/// Borsh payload for `ProposalType::Emergency`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum EmergencyPayload {
+    /// Slash an agent's bond, applied by `slash_agent`
+    Slash(SlashPayload),
+    /// Lift a circuit breaker pause; needs a supermajority
+    ResumeTrading,
+}
+
+/// Slash carried by an `Emergency` proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct SlashPayload {
+    /// Owner of the bonded agent
+    pub agent: Pubkey,
+    /// Fraction of the bond to slash
+    pub slash_bps: u16,
+    /// Jito tip paid from the treasury when the proposal executes; 0 for none
+    pub tip_lamports: u64,
+}
+
+/// Borsh payload for `ProposalType::AuthorityHandover`. The new authority is
+/// typically a governance program PDA or multisig rather than a single key.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct AuthorityHandoverPayload {
+    pub new_authority: Pubkey,
+}
+
+/// Borsh payload for `ProposalType::CrossChain`: a call for a contract on
+/// another chain, delivered as a Wormhole message emitted by the swarm
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CrossChainPayload {
+    /// Wormhole chain id of the destination (e.g. 2 for Ethereum)
+    pub target_chain: u16,
+    /// Destination contract, left-padded to 32 bytes for EVM addresses
+    pub target_address: [u8; 32],
+    /// Wormhole batching nonce
+    pub nonce: u32,
+    /// Call data the destination decodes, e.g. a Safe module transaction
+    pub payload: Vec<u8>,
+}
+
+impl CrossChainPayload {
+    /// Body of the Wormhole message: target chain (big-endian), target
+    /// address, the proposal that authorized it, then the call data
+    pub fn message(&self, proposal: &Pubkey) -> Vec<u8> {
+        [
+            &self.target_chain.to_be_bytes()[..],
+            &self.target_address,
+            proposal.as_ref(),
+            &self.payload,
+        ]
+        .concat()
+    }
+}
+
+/// Borsh payload for `ProposalType::DcaSchedule`: buy `mint_out` with
+/// `amount_per_leg` of `mint_in` every `interval` seconds until
+/// `total_budget` is spent. Anyone may crank a due leg with
+/// `execute_dca_leg`, which swaps through Raydium at no worse than
+/// `max_slippage_bps` from the oracle price.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct DcaSchedulePayload {
+    pub mint_in: Pubkey,
+    pub mint_out: Pubkey,
+    pub amount_per_leg: u64,
+    pub interval: i64,
+    pub total_budget: u64,
+    pub max_slippage_bps: u16,
+}
+
+/// Borsh payload for `ProposalType::Harvest`: collect fees from the
+/// registered Whirlpool positions, then optionally swap one reward mint into
+/// the base asset. `terms`, when set, first replace the swarm's
+/// `HarvestConfig`; smaller harvests can then be cranked permissionlessly
+/// with `crank_harvest`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct HarvestPayload {
+    pub terms: Option<HarvestTerms>,
+    pub swap: Option<HarvestSwap>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct HarvestTerms {
+    /// Asset harvested rewards are swapped into
+    pub base_mint: Pubkey,
+    /// Vault-owned Whirlpool positions fees are collected from
+    pub positions: Vec<Pubkey>,
+    /// Largest oracle value, in quote units scaled by
+    /// 10^-`TWAP_PRICE_EXPONENT`, one crank may swap; zero disables the
+    /// crank's swaps
+    pub crank_threshold: u64,
+    /// Shortest time between harvests for the crank
+    pub crank_interval: i64,
+    /// Slippage from the oracle price a cranked swap accepts
+    pub max_slippage_bps: u16,
+}
+
+/// Rewards of `mint_in` to swap into the base asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct HarvestSwap {
+    pub mint_in: Pubkey,
+    pub amount: u64,
+    pub min_out: u64,
+    pub route: SwapRoute,
+}
+
+impl HarvestPayload {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(terms) = &self.terms {
+            require!(
+                terms.base_mint != Pubkey::default()
+                    && terms.positions.len() <= MAX_HARVEST_POSITIONS
+                    && terms.crank_interval >= 0
+                    && terms.max_slippage_bps <= BPS_DENOMINATOR,
+                SwarmError::InvalidPayload
+            );
+            for (i, position) in terms.positions.iter().enumerate() {
+                require!(
+                    *position != Pubkey::default() && !terms.positions[..i].contains(position),
+                    SwarmError::InvalidPayload
+                );
+            }
+        }
+        if let Some(swap) = &self.swap {
+            require!(
+                swap.mint_in != Pubkey::default() && swap.amount > 0 && swap.min_out > 0,
+                SwarmError::InvalidPayload
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Borsh payload for `ProposalType::Insurance`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum InsurancePayload {
+    /// Set the share of protocol fees the fund takes and the loss a claim
+    /// must show, creating the fund if needed
+    Terms { fee_share_bps: u16, loss_threshold_bps: u16 },
+    /// Reimburse the treasury `lamports` for the executed `Strategy`
+    /// `loss_proposal`. The claim proposal must cite an attestation, made
+    /// after the loss, of the protocol failure behind it.
+    Claim { loss_proposal: Pubkey, lamports: u64 },
+}
+
+impl InsurancePayload {
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            InsurancePayload::Terms { fee_share_bps, loss_threshold_bps } => require!(
+                *fee_share_bps <= BPS_DENOMINATOR
+                    && *loss_threshold_bps > 0
+                    && *loss_threshold_bps <= BPS_DENOMINATOR,
+                SwarmError::InvalidPayload
+            ),
+            InsurancePayload::Claim { loss_proposal, lamports } => require!(
+                *loss_proposal != Pubkey::default() && *lamports > 0,
+                SwarmError::InvalidPayload
+            ),
+        }
+        Ok(())
+    }
+}
+
+/// Decode a proposal payload, rejecting trailing bytes
+pub fn decode<T: AnchorDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_from_slice(data).map_err(|_| error!(SwarmError::InvalidPayload))
+}
+
+/// Typed view of `Proposal.data`, one variant per `ProposalType`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ProposalPayload {
+    Rebalance(RebalancePayload),
+    Trade(TradePayload),
+    RiskLimit(RiskLimitPayload),
+    Strategy(StrategyPayload),
+    Emergency(EmergencyPayload),
+    TreasuryTransfer(TreasuryTransferPayload),
+    TreasuryTokenTransfer(TreasuryTokenTransferPayload),
+    AuthorityHandover(AuthorityHandoverPayload),
+    CrossChain(CrossChainPayload),
+    DcaSchedule(DcaSchedulePayload),
+    Harvest(HarvestPayload),
+    Insurance(InsurancePayload),
+}
+
+impl ProposalPayload {
+    /// Decode `data` as the payload for `proposal_type`
+    pub fn decode(proposal_type: &ProposalType, data: &[u8]) -> Result<Self> {
+        Ok(match proposal_type {
+            ProposalType::Rebalance => ProposalPayload::Rebalance(decode(data)?),
+            ProposalType::Trade => ProposalPayload::Trade(decode(data)?),
+            ProposalType::RiskLimit => ProposalPayload::RiskLimit(decode(data)?),
+            ProposalType::Strategy => ProposalPayload::Strategy(decode(data)?),
+            ProposalType::Emergency => ProposalPayload::Emergency(decode(data)?),
+            ProposalType::TreasuryTransfer => ProposalPayload::TreasuryTransfer(decode(data)?),
+            ProposalType::TreasuryTokenTransfer => {
+                ProposalPayload::TreasuryTokenTransfer(decode(data)?)
+            }
+            ProposalType::AuthorityHandover => ProposalPayload::AuthorityHandover(decode(data)?),
+            ProposalType::CrossChain => ProposalPayload::CrossChain(decode(data)?),
+            ProposalType::DcaSchedule => ProposalPayload::DcaSchedule(decode(data)?),
+            ProposalType::Harvest => ProposalPayload::Harvest(decode(data)?),
+            ProposalType::Insurance => ProposalPayload::Insurance(decode(data)?),
+        })
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            ProposalPayload::Rebalance(payload) => payload.validate()?,
+            ProposalPayload::Trade(payload) => payload.validate()?,
+            ProposalPayload::RiskLimit(RiskLimitPayload::Exposure { mint, .. }) => {
+                require!(*mint != Pubkey::default(), SwarmError::InvalidPayload);
+            }
+            ProposalPayload::RiskLimit(RiskLimitPayload::AllowProgram { program, .. }) => {
+                require!(
+                    *program != Pubkey::default() && *program != crate::ID,
+                    SwarmError::InvalidPayload
+                );
+            }
+            ProposalPayload::RiskLimit(RiskLimitPayload::MaxSlippage { max_slippage_bps }) => {
+                require!(
+                    max_slippage_bps.unwrap_or(0) <= BPS_DENOMINATOR,
+                    SwarmError::InvalidPayload
+                );
+            }
+            ProposalPayload::RiskLimit(RiskLimitPayload::AllowMint { mint, .. }) => {
+                require!(*mint != Pubkey::default(), SwarmError::InvalidPayload);
+            }
+            ProposalPayload::RiskLimit(RiskLimitPayload::MaxConcentration { max_weight_bps }) => {
+                require!(
+                    (1..=BPS_DENOMINATOR).contains(&max_weight_bps.unwrap_or(BPS_DENOMINATOR)),
+                    SwarmError::InvalidPayload
+                );
+            }
+            ProposalPayload::RiskLimit(RiskLimitPayload::VenueCap { program, .. }) => {
+                require!(*program != Pubkey::default(), SwarmError::InvalidPayload);
+            }
+            ProposalPayload::RiskLimit(RiskLimitPayload::TwapGuard { guard: Some(guard) }) => {
+                require!(
+                    guard.window > 0
+                        && guard.window <= MAX_TWAP_WINDOW
+                        && guard.max_deviation_bps <= BPS_DENOMINATOR,
+                    SwarmError::InvalidPayload
+                );
+            }
+            ProposalPayload::RiskLimit(RiskLimitPayload::CircuitBreaker { breaker: Some(breaker) }) => {
+                require!(
+                    (MIN_CIRCUIT_BREAKER_WINDOW..=MAX_CIRCUIT_BREAKER_WINDOW).contains(&breaker.window)
+                        && (1..=BPS_DENOMINATOR).contains(&breaker.max_drawdown_bps),
+                    SwarmError::InvalidPayload
+                );
+            }
+            ProposalPayload::RiskLimit(
+                RiskLimitPayload::PerpPosition { .. }
+                | RiskLimitPayload::MaxPositionSize { .. }
+                | RiskLimitPayload::MaxDailyVolume { .. }
+                | RiskLimitPayload::MaxExecutionTip { .. }
+                | RiskLimitPayload::TwapGuard { guard: None }
+                | RiskLimitPayload::MaxDrawdown { .. }
+                | RiskLimitPayload::CircuitBreaker { breaker: None },
+            ) => {}
+            ProposalPayload::Strategy(payload) => payload.validate()?,
+            ProposalPayload::Emergency(EmergencyPayload::Slash(payload)) => {
+                require!(
+                    payload.slash_bps > 0 && payload.slash_bps <= BPS_DENOMINATOR,
+                    SwarmError::InvalidPayload
+                );
+            }
+            ProposalPayload::Emergency(EmergencyPayload::ResumeTrading) => {}
+            ProposalPayload::TreasuryTransfer(payload) => {
+                require!(payload.lamports > 0, SwarmError::InvalidPayload);
+            }
+            ProposalPayload::TreasuryTokenTransfer(payload) => {
+                require!(payload.amount > 0, SwarmError::InvalidPayload);
+            }
+            ProposalPayload::AuthorityHandover(payload) => {
+                require!(
+                    payload.new_authority != Pubkey::default(),
+                    SwarmError::InvalidPayload
+                );
+            }
+            ProposalPayload::CrossChain(payload) => {
+                // Wormhole chain 0 is unset and 1 is Solana itself
+                require!(
+                    payload.target_chain > 1
+                        && payload.target_address != [0; 32]
+                        && !payload.payload.is_empty()
+                        && payload.payload.len() <= MAX_CROSS_CHAIN_PAYLOAD_LENGTH,
+                    SwarmError::InvalidPayload
+                );
+            }
+            ProposalPayload::DcaSchedule(payload) => {
+                require!(
+                    payload.mint_in != Pubkey::default()
+                        && payload.mint_out != Pubkey::default()
+                        && payload.mint_in != payload.mint_out,
+                    SwarmError::InvalidPayload
+                );
+                require!(
+                    payload.amount_per_leg > 0
+                        && payload.total_budget >= payload.amount_per_leg
+                        && payload.interval >= MIN_DCA_INTERVAL
+                        && payload.max_slippage_bps <= BPS_DENOMINATOR,
+                    SwarmError::InvalidPayload
+                );
+            }
+            ProposalPayload::Harvest(payload) => payload.validate()?,
+            ProposalPayload::Insurance(payload) => payload.validate()?,
+        }
+        Ok(())
+    }
+}
+
+/// Whether `proposal_type` may carry free-form data that only signals intent.
+/// Types that move funds or authority always need their typed payload.
+pub fn allows_free_form(proposal_type: &ProposalType) -> bool {
+    matches!(
+        proposal_type,
+        ProposalType::Rebalance
+            | ProposalType::Trade
+            | ProposalType::RiskLimit
+            | ProposalType::Strategy
+            | ProposalType::Emergency
+    )
+}
+
+/// Whether proposals of `proposal_type` may store only a hash of their
+/// payload. Its payload is then only available at execution, so types read
+/// earlier (exposure reservations, slashing) cannot.
+pub fn allows_commitment(proposal_type: &ProposalType) -> bool {
+    matches!(proposal_type, ProposalType::Strategy)
+}
+
+/// Decode the typed payload of a proposal, or `None` for free-form data on a
+/// type that allows it
+pub fn decode_proposal(proposal_type: &ProposalType, data: &[u8]) -> Result<Option<ProposalPayload>> {
+    match ProposalPayload::decode(proposal_type, data) {
+        Ok(payload) => Ok(Some(payload)),
+        Err(_) if allows_free_form(proposal_type) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Check that `data` is well-formed for `proposal_type`. Free-form data is
+/// only accepted when `require_typed` is off.
+pub fn validate(proposal_type: &ProposalType, data: &[u8], require_typed: bool) -> Result<()> {
+    match decode_proposal(proposal_type, data)? {
+        Some(payload) => payload.validate(),
+        None => {
+            require!(!require_typed, SwarmError::InvalidPayload);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,358 @@
+//! Deterministic PDA derivation for every program account.
+//!
+//! The program constraints and off-chain clients both derive addresses from
+//! these functions, so the seeds only live in one place. Clients depend on
+//! this crate with the `no-entrypoint` feature to use them. Python clients
+//! use the mirror in `sdk/python/agent_swarm_sdk/pda.py`, which must change
+//! alongside this module.
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// Configuration account of swarm `swarm_id`
+pub fn find_swarm_address(swarm_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SWARM_SEED, &swarm_id.to_le_bytes()], &crate::ID)
+}
+
+/// Agent account owned by `owner` in `swarm`
+pub fn find_agent_address(swarm: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AGENT_SEED, swarm.as_ref(), owner.as_ref()], &crate::ID)
+}
+
+/// Registry enumerating every agent of `swarm`
+pub fn find_agent_registry_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AGENT_REGISTRY_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Registration of `AgentType::Custom(id)` in `swarm`
+pub fn find_custom_agent_type_address(swarm: &Pubkey, id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CUSTOM_AGENT_TYPE_SEED, swarm.as_ref(), &[id]], &crate::ID)
+}
+
+/// Scheduled proposals of `swarm` awaiting keepers
+pub fn find_execution_queue_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EXECUTION_QUEUE_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Recurring rebalance approved in `swarm`
+pub fn find_rebalance_schedule_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REBALANCE_SCHEDULE_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Strategy parameters adopted by `swarm`'s `Strategy` proposals
+pub fn find_strategy_config_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STRATEGY_CONFIG_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Treasury holdings and target weights of `swarm`
+pub fn find_portfolio_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PORTFOLIO_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Standing order placed by the `Strategy` proposal `proposal`
+pub fn find_standing_order_address(proposal: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STANDING_ORDER_SEED, proposal.as_ref()], &crate::ID)
+}
+
+/// DCA schedule created by the `DcaSchedule` proposal `proposal`
+pub fn find_dca_schedule_address(proposal: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DCA_SCHEDULE_SEED, proposal.as_ref()], &crate::ID)
+}
+
+/// Arbitrage report of the route hashing to `route_hash` in `swarm`
+pub fn find_arb_report_address(swarm: &Pubkey, route_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ARB_REPORT_SEED, swarm.as_ref(), route_hash], &crate::ID)
+}
+
+/// Harvest settings and registered positions of `swarm`
+pub fn find_harvest_config_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[HARVEST_CONFIG_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Insurance fund reimbursing `swarm`'s treasury
+pub fn find_insurance_fund_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INSURANCE_FUND_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Task `task_id` created by `creator` in `swarm`
+pub fn find_task_address(swarm: &Pubkey, creator: &Pubkey, task_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TASK_SEED, swarm.as_ref(), creator.as_ref(), &task_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Coalition `coalition_id` formed by `founder` in `swarm`
+pub fn find_coalition_address(swarm: &Pubkey, founder: &Pubkey, coalition_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[COALITION_SEED, swarm.as_ref(), founder.as_ref(), &coalition_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Internal ballot of `coalition` on `proposal`
+pub fn find_coalition_ballot_address(coalition: &Pubkey, proposal: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[COALITION_BALLOT_SEED, coalition.as_ref(), proposal.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Committee `committee_id` of `swarm`
+pub fn find_committee_address(swarm: &Pubkey, committee_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COMMITTEE_SEED, swarm.as_ref(), &[committee_id]], &crate::ID)
+}
+
+/// Cross-swarm identity of agents owned by `owner`
+pub fn find_agent_identity_address(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AGENT_IDENTITY_SEED, owner.as_ref()], &crate::ID)
+}
+
+/// Link between `identity` and its agent in `swarm`
+pub fn find_membership_address(identity: &Pubkey, swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MEMBERSHIP_SEED, identity.as_ref(), swarm.as_ref()], &crate::ID)
+}
+
+/// Badge rules of `swarm`, also the delegate of its badge tree
+pub fn find_badge_config_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BADGE_CONFIG_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Receipt of `agent`'s badge of `kind_index`; `epoch` is 0 for milestones
+pub fn find_agent_badge_address(agent: &Pubkey, kind_index: u8, epoch: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[BADGE_SEED, agent.as_ref(), &[kind_index], &epoch.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// `agent`'s attestation of the output hashing to `payload_hash`
+pub fn find_attestation_address(agent: &Pubkey, payload_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ATTESTATION_SEED, agent.as_ref(), payload_hash], &crate::ID)
+}
+
+/// Wormhole sequence tracker of `swarm`
+pub fn find_cross_chain_emitter_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CROSS_CHAIN_EMITTER_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Wormhole message account posted for an executed CrossChain `proposal`
+pub fn find_cross_chain_message_address(proposal: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CROSS_CHAIN_MESSAGE_SEED, proposal.as_ref()], &crate::ID)
+}
+
+/// Pending randomness commit for `proposal`
+pub fn find_randomness_request_address(proposal: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RANDOMNESS_SEED, proposal.as_ref()], &crate::ID)
+}
+
+/// Proposal number `proposal_id` in `swarm` (its `total_proposals` at creation)
+pub fn find_proposal_address(swarm: &Pubkey, proposal_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROPOSAL_SEED, swarm.as_ref(), &proposal_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Page `page` of `swarm`'s proposal registry, holding proposals
+/// `page * PROPOSAL_PAGE_SIZE` onwards
+pub fn find_proposal_page_address(swarm: &Pubkey, page: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROPOSAL_PAGE_SEED, swarm.as_ref(), &page.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Execution outcome recorded for `proposal`
+pub fn find_outcome_address(proposal: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OUTCOME_SEED, proposal.as_ref()], &crate::ID)
+}
+
+/// Vote cast by `voter` on `proposal`
+pub fn find_vote_record_address(proposal: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOTE_RECORD_SEED, proposal.as_ref(), voter.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Settled Elo match between two competing proposals
+pub fn find_elo_match_address(winner: &Pubkey, loser: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ELO_MATCH_SEED, winner.as_ref(), loser.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Reputation stake by `endorser` behind `proposal`
+pub fn find_endorsement_address(proposal: &Pubkey, endorser: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ENDORSEMENT_SEED, proposal.as_ref(), endorser.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Payload of hash-committed `proposal` staged by `owner`
+pub fn find_payload_buffer_address(proposal: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PAYLOAD_BUFFER_SEED, proposal.as_ref(), owner.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// SOL treasury of `swarm`
+pub fn find_treasury_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Data-less signer owning `swarm`'s vault token accounts
+pub fn find_vault_authority_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_AUTHORITY_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Programs `swarm`'s `Strategy` calls may target
+pub fn find_program_whitelist_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_WHITELIST_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Ring of `swarm`'s recent privileged actions
+pub fn find_audit_log_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AUDIT_LOG_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Trading limits set by `swarm`'s `RiskLimit` proposals
+pub fn find_risk_config_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RISK_CONFIG_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Time-weighted average price of `mint` in `swarm`
+pub fn find_twap_tracker_address(swarm: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TWAP_TRACKER_SEED, swarm.as_ref(), mint.as_ref()], &crate::ID)
+}
+
+/// Merkle root over `swarm`'s archived outcomes
+pub fn find_outcome_archive_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OUTCOME_ARCHIVE_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Per-epoch spend tracker of `swarm`'s treasury
+pub fn find_spend_tracker_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SPEND_TRACKER_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Bounty escrowed by `funder` on `proposal`
+pub fn find_bounty_address(proposal: &Pubkey, funder: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[BOUNTY_SEED, proposal.as_ref(), funder.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// `swarm`'s approved-but-unexecuted trade exposure
+pub fn find_pending_exposure_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PENDING_EXPOSURE_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// SOL bond posted by agent `owner` in `swarm`
+pub fn find_bond_address(swarm: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BOND_SEED, swarm.as_ref(), owner.as_ref()], &crate::ID)
+}
+
+/// Slash applied by `proposal`
+pub fn find_slash_record_address(proposal: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SLASH_SEED, proposal.as_ref()], &crate::ID)
+}
+
+/// `swarm`'s settled performance reward epoch `epoch`
+pub fn find_epoch_rewards_address(swarm: &Pubkey, epoch: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[EPOCH_REWARDS_SEED, swarm.as_ref(), &epoch.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Activity recorded for `epoch` of `swarm`
+pub fn find_epoch_stats_address(swarm: &Pubkey, epoch: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[EPOCH_STATS_SEED, swarm.as_ref(), &epoch.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// `swarm` governance tokens locked by `owner`
+pub fn find_vote_escrow_address(swarm: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOTE_ESCROW_SEED, swarm.as_ref(), owner.as_ref()], &crate::ID)
+}
+
+/// Governance token mint of `swarm`
+pub fn find_governance_mint_address(swarm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GOVERNANCE_MINT_SEED, swarm.as_ref()], &crate::ID)
+}
+
+/// Agent marketplace singleton
+pub fn find_marketplace_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MARKETPLACE_SEED], &crate::ID)
+}
+
+/// Marketplace listing `name` originally published by `creator`
+pub fn find_listing_address(creator: &Pubkey, name: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LISTING_SEED, creator.as_ref(), name.as_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Page `page` of the marketplace's listing index
+pub fn find_listing_index_address(page: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LISTING_INDEX_SEED, &page.to_le_bytes()], &crate::ID)
+}
+
+/// Release `version` of `listing`
+pub fn find_listing_version_address(listing: &Pubkey, version: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LISTING_VERSION_SEED, listing.as_ref(), version.as_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Rating left by `rater` on `listing`
+pub fn find_rating_address(listing: &Pubkey, rater: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[RATING_SEED, listing.as_ref(), rater.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Written review left by `reviewer` on `listing`
+pub fn find_review_address(listing: &Pubkey, reviewer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REVIEW_SEED, listing.as_ref(), reviewer.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Subscription held by `subscriber` on `listing`
+pub fn find_subscription_address(listing: &Pubkey, subscriber: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SUBSCRIPTION_SEED, listing.as_ref(), subscriber.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Running ownership auction for `listing`
+pub fn find_auction_address(listing: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AUCTION_SEED, listing.as_ref()], &crate::ID)
+}
+
+/// Escrowed bid by `bidder` in `auction`
+pub fn find_bid_address(auction: &Pubkey, bidder: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BID_SEED, auction.as_ref(), bidder.as_ref()], &crate::ID)
+}
+
+/// Purchase receipt for `buyer` on `listing`
+pub fn find_purchase_address(listing: &Pubkey, buyer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PURCHASE_SEED, listing.as_ref(), buyer.as_ref()],
+        &crate::ID,
+    )
+}
@@ -0,0 +1,380 @@
+//! Risk engine for structured `Trade` and `Rebalance` proposals.
+//!
+//! Every limit lives on the swarm's `RiskConfig` and changes only through
+//! executed `RiskLimit` proposals: allowed mints, position size, daily and
+//! per-venue volume, concentration of rebalance weights, and the drawdown
+//! of trades' oracle-valued results. `execute_proposal` checks a trade
+//! before its swap and records its result after, and checks a rebalance's
+//! trades against the deltas its `Portfolio` needs. A circuit breaker on
+//! the marked portfolio value pauses both until an `Emergency` proposal
+//! resumes them.
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::{FailedCheck, SwarmError};
+use crate::oracle::{check_trade_slippage, check_twap_deviation, OraclePrice};
+use crate::payloads::{RebalancePayload, RebalanceTrade, TradePayload};
+use crate::require_ctx;
+use crate::state::{
+    CircuitBreaker, DrawdownTracker, Portfolio, RiskConfig, SwarmState, ValueMark, VenueCap,
+};
+
+impl RiskConfig {
+    pub fn is_mint_allowed(&self, mint: &Pubkey) -> bool {
+        self.allowed_mints.is_empty() || self.allowed_mints.contains(mint)
+    }
+
+    pub fn set_mint(&mut self, mint: Pubkey, allowed: bool) -> Result<()> {
+        self.allowed_mints.retain(|allowed| *allowed != mint);
+        if allowed {
+            require!(self.allowed_mints.len() < MAX_ALLOWED_MINTS, SwarmError::InvalidConfig);
+            self.allowed_mints.push(mint);
+        }
+        Ok(())
+    }
+
+    /// Cap the volume `program` may carry, or with `None` lift its cap
+    pub fn set_venue_cap(&mut self, program: Pubkey, max_volume: Option<u64>) -> Result<()> {
+        match (self.venue_caps.iter_mut().find(|cap| cap.program == program), max_volume) {
+            (Some(cap), Some(max_volume)) => cap.max_volume = max_volume,
+            (Some(_), None) => self.venue_caps.retain(|cap| cap.program != program),
+            (None, Some(max_volume)) => {
+                require!(self.venue_caps.len() < MAX_VENUE_CAPS, SwarmError::InvalidConfig);
+                self.venue_caps.push(VenueCap { program, max_volume, volume: 0 });
+            }
+            (None, None) => {}
+        }
+        Ok(())
+    }
+
+    /// Set the drawdown limit, measuring from the current result so past
+    /// losses do not count against it
+    pub fn set_max_drawdown(&mut self, max_drawdown: Option<u64>) {
+        self.max_drawdown = max_drawdown;
+        self.drawdown.peak_pnl = self.drawdown.cumulative_pnl;
+    }
+
+    /// Set the circuit breaker, forgetting the marks taken under the old one
+    pub fn set_circuit_breaker(&mut self, breaker: Option<CircuitBreaker>) {
+        self.circuit_breaker = breaker;
+        self.value_marks.clear();
+    }
+
+    /// Record `value` as the portfolio's mark, keeping the marks inside the
+    /// breaker's window, and pause trading when it falls further below the
+    /// window's highest mark than the breaker allows. Marks are spaced so
+    /// `MAX_VALUE_MARKS` of them span the window. Returns the peak and the
+    /// drawdown from it in bps.
+    pub fn mark_value(&mut self, value: u64, current_time: i64) -> Result<(u64, u64)> {
+        let breaker = self.circuit_breaker.ok_or(SwarmError::CircuitBreakerNotConfigured)?;
+        if let Some(last) = self.value_marks.last() {
+            require!(
+                current_time >= last.marked_at.saturating_add(breaker.window / MAX_VALUE_MARKS as i64),
+                SwarmError::ValueMarkTooSoon
+            );
+        }
+        let window_start = current_time.saturating_sub(breaker.window);
+        self.value_marks.retain(|mark| mark.marked_at > window_start);
+        if self.value_marks.len() == MAX_VALUE_MARKS {
+            self.value_marks.remove(0);
+        }
+        self.value_marks.push(ValueMark { value, marked_at: current_time });
+
+        let peak = self.value_marks.iter().map(|mark| mark.value).max().unwrap_or(value);
+        let drawdown_bps = if peak == 0 {
+            0
+        } else {
+            ((peak - value) as u128 * BPS_DENOMINATOR as u128 / peak as u128) as u64
+        };
+        if drawdown_bps > breaker.max_drawdown_bps as u64 {
+            self.trading_paused = true;
+        }
+        Ok((peak, drawdown_bps))
+    }
+
+    /// Lift a pause, restarting the window so the marks that tripped the
+    /// breaker cannot trip it again
+    pub fn resume_trading(&mut self) {
+        self.trading_paused = false;
+        self.value_marks.clear();
+    }
+
+    /// Whether trades must carry oracle prices for both mints
+    pub fn requires_trade_prices(&self) -> bool {
+        self.max_slippage_bps.is_some() || self.twap_guard.is_some() || self.max_drawdown.is_some()
+    }
+
+    /// Check a trade routed through `venue` against every pre-trade limit
+    /// and count it towards the window's volume
+    pub fn record_trade(
+        &mut self,
+        payload: &TradePayload,
+        venue: &Pubkey,
+        current_time: i64,
+    ) -> Result<()> {
+        require!(!self.trading_paused, SwarmError::TradingPaused);
+        require!(
+            self.is_mint_allowed(&payload.mint_in) && self.is_mint_allowed(&payload.mint_out),
+            SwarmError::MintNotAllowed
+        );
+        if let Some(max_position_size) = self.max_position_size {
+            require!(payload.amount <= max_position_size, SwarmError::PositionLimitExceeded);
+        }
+
+        if current_time.saturating_sub(self.volume_window_start) >= RISK_VOLUME_WINDOW {
+            self.volume_window_start = current_time;
+            self.daily_volume = 0;
+            for cap in &mut self.venue_caps {
+                cap.volume = 0;
+            }
+        }
+        let daily_volume = self
+            .daily_volume
+            .checked_add(payload.amount)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        if let Some(max_daily_volume) = self.max_daily_volume {
+            require!(daily_volume <= max_daily_volume, SwarmError::DailyVolumeExceeded);
+        }
+        if let Some(cap) = self.venue_caps.iter_mut().find(|cap| cap.program == *venue) {
+            let volume = cap
+                .volume
+                .checked_add(payload.amount)
+                .ok_or(SwarmError::ArithmeticOverflow)?;
+            require!(volume <= cap.max_volume, SwarmError::VenueCapExceeded);
+            cap.volume = volume;
+        }
+
+        self.daily_volume = daily_volume;
+        Ok(())
+    }
+
+    /// Every pre-trade check of a structured trade routed through `venue`:
+    /// the limits `record_trade` counts, oracle slippage and the TWAP guard.
+    /// `prices` and `twap_trackers` hold the input then output mint's
+    /// accounts, needed when the config sets a check reading them.
+    pub fn check_trade<'info>(
+        &mut self,
+        swarm_state: &Account<SwarmState>,
+        payload: &TradePayload,
+        venue: &Pubkey,
+        prices: (Option<&AccountInfo<'info>>, Option<&AccountInfo<'info>>),
+        twap_trackers: (Option<&AccountInfo<'info>>, Option<&AccountInfo<'info>>),
+        current_time: i64,
+    ) -> Result<()> {
+        self.record_trade(payload, venue, current_time)?;
+        let (Some(price_update_in), Some(price_update_out)) = prices else {
+            require!(!self.requires_trade_prices(), SwarmError::MissingExecutionAccount);
+            return Ok(());
+        };
+        if let Some(max_slippage_bps) = self.max_slippage_bps {
+            check_trade_slippage(
+                swarm_state,
+                max_slippage_bps,
+                price_update_in,
+                price_update_out,
+                payload,
+                current_time,
+            )?;
+        }
+        if let Some(guard) = self.twap_guard {
+            let (Some(twap_in), Some(twap_out)) = twap_trackers else {
+                return err!(SwarmError::MissingExecutionAccount);
+            };
+            for (price_update, twap_tracker, mint) in [
+                (price_update_in, twap_in, &payload.mint_in),
+                (price_update_out, twap_out, &payload.mint_out),
+            ] {
+                check_twap_deviation(
+                    &swarm_state.key(),
+                    swarm_state,
+                    &guard,
+                    price_update,
+                    twap_tracker,
+                    mint,
+                    current_time,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Value a completed trade's `(spent, received)` at oracle prices and add
+    /// its result to the drawdown, rejecting it when the fall from peak
+    /// exceeds the limit
+    pub fn record_trade_result(
+        &mut self,
+        swarm_state: &SwarmState,
+        price_update_in: &AccountInfo,
+        price_update_out: &AccountInfo,
+        payload: &TradePayload,
+        (spent, received): (u64, u64),
+        current_time: i64,
+    ) -> Result<()> {
+        let Some(max_drawdown) = self.max_drawdown else {
+            return Ok(());
+        };
+        let pnl = trade_pnl(
+            swarm_state,
+            price_update_in,
+            price_update_out,
+            payload,
+            (spent, received),
+            current_time,
+        )?;
+
+        self.drawdown.record(pnl)?;
+        msg!("Trade result {} leaves drawdown at {}", pnl, self.drawdown.drawdown());
+        require!(self.drawdown.drawdown() <= max_drawdown, SwarmError::DrawdownLimitExceeded);
+        Ok(())
+    }
+
+    pub fn check_rebalance(&self, payload: &RebalancePayload) -> Result<()> {
+        require!(!self.trading_paused, SwarmError::TradingPaused);
+        require!(
+            payload
+                .allocations
+                .iter()
+                .all(|allocation| self.is_mint_allowed(&allocation.mint)),
+            SwarmError::MintNotAllowed
+        );
+        if let Some(max_concentration_bps) = self.max_concentration_bps {
+            require!(
+                payload
+                    .allocations
+                    .iter()
+                    .all(|allocation| allocation.weight_bps <= max_concentration_bps),
+                SwarmError::ConcentrationLimitExceeded
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Oracle value of what a completed trade received less what it spent, in
+/// quote units scaled by 10^-`TWAP_PRICE_EXPONENT`
+pub fn trade_pnl(
+    swarm_state: &SwarmState,
+    price_update_in: &AccountInfo,
+    price_update_out: &AccountInfo,
+    payload: &TradePayload,
+    (spent, received): (u64, u64),
+    current_time: i64,
+) -> Result<i64> {
+    let spent_value = UnitPrice::load(swarm_state, price_update_in, &payload.mint_in, current_time)?
+        .value(spent)?;
+    let received_value = UnitPrice::load(swarm_state, price_update_out, &payload.mint_out, current_time)?
+        .value(received)?;
+    received_value
+        .checked_sub(spent_value)
+        .ok_or_else(|| error!(SwarmError::ArithmeticOverflow))
+}
+
+impl DrawdownTracker {
+    pub fn record(&mut self, pnl: i64) -> Result<()> {
+        self.cumulative_pnl = self
+            .cumulative_pnl
+            .checked_add(pnl)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        self.peak_pnl = self.peak_pnl.max(self.cumulative_pnl);
+        Ok(())
+    }
+
+    /// Fall of the cumulative result from its peak
+    pub fn drawdown(&self) -> u64 {
+        self.peak_pnl.abs_diff(self.cumulative_pnl)
+    }
+}
+
+impl Portfolio {
+    /// Each holding's price, from `price_updates` given in `holdings` order
+    /// and read through the feed registered for the holding's mint
+    pub fn unit_prices(
+        &self,
+        swarm_state: &SwarmState,
+        price_updates: &[AccountInfo],
+        current_time: i64,
+    ) -> Result<Vec<UnitPrice>> {
+        require!(price_updates.len() >= self.holdings.len(), SwarmError::MissingExecutionAccount);
+        self.holdings
+            .iter()
+            .zip(price_updates)
+            .map(|(holding, price_update)| {
+                UnitPrice::load(swarm_state, price_update, &holding.mint, current_time)
+            })
+            .collect()
+    }
+
+    /// Check that `trades` move every holding to within
+    /// `REBALANCE_TOLERANCE_BPS` of the portfolio's value from its target
+    /// weight, valuing holdings at `prices` given in `holdings` order
+    pub fn check_rebalance_trades(&self, prices: &[UnitPrice], trades: &[RebalanceTrade]) -> Result<()> {
+        require!(prices.len() == self.holdings.len(), SwarmError::MissingExecutionAccount);
+        let position = |mint: &Pubkey| {
+            self.holdings
+                .iter()
+                .position(|holding| holding.mint == *mint)
+                .ok_or_else(|| error!(SwarmError::InvalidPayload))
+        };
+
+        let mut values = Vec::with_capacity(self.holdings.len());
+        for (holding, price) in self.holdings.iter().zip(prices) {
+            values.push(price.value(holding.amount)? as i128);
+        }
+        let mut flows = vec![0i128; self.holdings.len()];
+        for trade in trades {
+            let (sold, bought) = (position(&trade.mint_in)?, position(&trade.mint_out)?);
+            let value = prices[sold].value(trade.amount)? as i128;
+            flows[sold] -= value;
+            flows[bought] += value;
+        }
+
+        let total: i128 = values.iter().sum();
+        let tolerance = total * REBALANCE_TOLERANCE_BPS as i128 / BPS_DENOMINATOR as i128;
+        for ((holding, value), flow) in self.holdings.iter().zip(values).zip(flows) {
+            let delta =
+                total * holding.target_weight_bps as i128 / BPS_DENOMINATOR as i128 - value;
+            require_ctx!(
+                (flow - delta).abs() <= tolerance,
+                SwarmError::RebalanceMismatch,
+                FailedCheck::RebalanceDelta,
+                delta,
+                flow,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// An oracle price per whole token, as `price * 10^TWAP_PRICE_EXPONENT`
+/// quote units, with the mint's decimals
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct UnitPrice {
+    pub price: i64,
+    pub decimals: u8,
+}
+
+impl UnitPrice {
+    /// `mint`'s price from `price_update`, read through its registered feed
+    pub fn load(
+        swarm_state: &SwarmState,
+        price_update: &AccountInfo,
+        mint: &Pubkey,
+        current_time: i64,
+    ) -> Result<Self> {
+        let feed = swarm_state.price_feed(mint).ok_or(SwarmError::MissingPriceFeed)?;
+        Ok(UnitPrice {
+            price: OraclePrice::load(price_update, feed, current_time)?.rescale(TWAP_PRICE_EXPONENT)?,
+            decimals: feed.decimals,
+        })
+    }
+
+    /// Value of `amount` base units, in quote units scaled by
+    /// 10^-`TWAP_PRICE_EXPONENT`
+    pub fn value(&self, amount: u64) -> Result<i64> {
+        let scale = 10u128
+            .checked_pow(self.decimals as u32)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        let value = amount as u128 * self.price.max(0) as u128 / scale;
+        i64::try_from(value).map_err(|_| error!(SwarmError::ArithmeticOverflow))
+    }
+}
@@ -1,10 +1,21 @@
 use anchor_lang::prelude::*;
-use crate::{AgentType, ProposalType, VoteType};
+use crate::{AgentType, ExecutionMode, ProposalType, VoteType};
 use crate::constants::*;
+use crate::errors::SwarmError;
+use crate::manifest::MAX_MANIFEST_LENGTH;
+use crate::oracle::{OracleSource, PriceComparison, PriceCondition};
+use crate::payloads::{
+    HarvestSwap, HarvestTerms, RebalancePayload, RebalanceScheduleTerms, StrategyParameters,
+    TargetAllocation, TradePayload,
+};
+use solana_program::keccak;
 
 /// Main swarm configuration and state
 #[account]
 pub struct SwarmState {
+    pub version: u8,
+    /// Seeds this swarm's address; one deployment hosts many swarms
+    pub swarm_id: u64,
     pub authority: Pubkey,
     pub max_agents: u8,
     pub active_agents: u8,
@@ -12,11 +23,55 @@ pub struct SwarmState {
     pub proposal_timeout: i64,
     pub total_proposals: u64,
     pub executed_proposals: u64,
+    pub elo_blend_bps: u16,
+    pub execution_guard: bool,
+    pub execution_mode: ExecutionMode,
+    pub executor_window: i64,
+    pub required_capabilities: u32,
+    pub min_description_length: u16,
+    pub require_typed_payloads: bool,
+    pub voter_reward: u64,
+    pub voter_reward_mint: Option<Pubkey>,
+    pub enforce_exposure_limits: bool,
+    pub slash_treasury_bps: u16,
+    pub authority_last_active: i64,
+    pub authority_inactivity_period: i64,
+    pub marketplace_fee_bps: u16,
+    pub rewards: RewardEpoch,
+    pub governance_mint: Option<Pubkey>,
+    /// Vote-escrow power that earns the maximum boost; 0 disables boosting
+    pub ve_max_boost_power: u64,
+    pub emission: Emission,
+    /// How long marketplace payments stay refundable before the seller is paid
+    pub sale_dispute_window: i64,
+    /// PDA owning the swarm's vault token accounts and signing for them
+    pub vault_authority: Pubkey,
+    pub vault_authority_bump: u8,
+    pub price_feeds: Vec<PriceFeed>,
+    /// Lowest lending health factor (liquidation threshold over debt) an
+    /// obligation may have before a withdrawal or borrow
+    pub min_health_factor_bps: u16,
+    /// Reported execution failures after which a proposal is marked failed
+    pub max_execution_attempts: u8,
+    /// Bit `ProposalType::index()` set for each type a `Committee` handles
+    /// in place of the whole swarm
+    pub committee_proposal_types: u16,
+    pub congestion: ProposalCongestion,
+    /// Lamports the treasury pays an Arbitrage agent whose `ArbReport` an
+    /// executed trade follows; 0 pays reputation only
+    pub arb_report_bounty: u64,
     pub bump: u8,
 }
 
 impl SwarmState {
+    /// v2 added the oracle source, staleness and deviation to `price_feeds`;
+    /// v3 added the activity counters to `rewards`; v4 added
+    /// `committee_proposal_types`; v5 added `congestion`; v6 added
+    /// `arb_report_bounty`
+    pub const VERSION: u8 = 6;
     pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        8 +   // swarm_id
         32 +  // authority
         1 +   // max_agents
         1 +   // active_agents
@@ -24,38 +79,395 @@ impl SwarmState {
         8 +   // proposal_timeout
         8 +   // total_proposals
         8 +   // executed_proposals
+        2 +   // elo_blend_bps
+        1 +   // execution_guard
+        1 +   // execution_mode
+        8 +   // executor_window
+        4 +   // required_capabilities
+        2 +   // min_description_length
+        1 +   // require_typed_payloads
+        8 +   // voter_reward
+        (1 + 32) + // voter_reward_mint
+        1 +   // enforce_exposure_limits
+        2 +   // slash_treasury_bps
+        8 +   // authority_last_active
+        8 +   // authority_inactivity_period
+        2 +   // marketplace_fee_bps
+        RewardEpoch::LEN + // rewards
+        (1 + 32) + // governance_mint
+        8 +   // ve_max_boost_power
+        Emission::LEN + // emission
+        8 +   // sale_dispute_window
+        32 +  // vault_authority
+        1 +   // vault_authority_bump
+        (4 + MAX_PRICE_FEEDS * PriceFeed::LEN) + // price_feeds
+        2 +   // min_health_factor_bps
+        1 +   // max_execution_attempts
+        2 +   // committee_proposal_types
+        ProposalCongestion::LEN + // congestion
+        8 +   // arb_report_bounty
         1;    // bump
+
+    /// Whether the authority has been idle long enough for an
+    /// `AuthorityHandover` proposal to replace it
+    pub fn is_authority_inactive(&self, current_time: i64) -> bool {
+        current_time.saturating_sub(self.authority_last_active) >= self.authority_inactivity_period
+    }
+
+    pub fn price_feed(&self, mint: &Pubkey) -> Option<&PriceFeed> {
+        self.price_feeds.iter().find(|feed| feed.mint == *mint)
+    }
+}
+
+/// The swarm's current epoch. Agents earn points and the swarm counts its
+/// activity during an epoch; advancing it snapshots the budget and points
+/// into an `EpochRewards` account and the activity into an `EpochStats` one.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct RewardEpoch {
+    pub epoch: u64,
+    pub epoch_start: i64,
+    pub epoch_length: i64,
+    pub budget: u64,
+    pub points: u64,
+    pub proposals: u64,
+    pub executions: u64,
+    /// Proposals that ran out of execution attempts
+    pub failures: u64,
+    pub votes: u64,
+}
+
+impl RewardEpoch {
+    pub const LEN: usize = 8 + // epoch
+        8 +   // epoch_start
+        8 +   // epoch_length
+        8 +   // budget
+        8 +   // points
+        8 +   // proposals
+        8 +   // executions
+        8 +   // failures
+        8;    // votes
+
+    pub fn is_over(&self, current_time: i64) -> bool {
+        current_time.saturating_sub(self.epoch_start) >= self.epoch_length
+    }
+}
+
+/// Settled reward epoch. Agents claim their share from it lazily.
+#[account]
+pub struct EpochRewards {
+    pub version: u8,
+    pub epoch: u64,
+    pub budget: u64,
+    pub points: u64,
+    pub claimed: u64,
+    pub settled_at: i64,
+    pub bump: u8,
+}
+
+impl EpochRewards {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        8 +   // epoch
+        8 +   // budget
+        8 +   // points
+        8 +   // claimed
+        8 +   // settled_at
+        1;    // bump
+
+    /// Lamports owed for `points` earned in this epoch
+    pub fn share(&self, points: u32) -> u64 {
+        if self.points == 0 {
+            return 0;
+        }
+        (self.budget as u128 * points as u128 / self.points as u128) as u64
+    }
 }
 
-/// Individual agent registration and reputation
+/// Activity of one finished epoch, recorded when it is advanced
 #[account]
+pub struct EpochStats {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub epoch: u64,
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub proposals: u64,
+    pub executions: u64,
+    pub failures: u64,
+    pub votes: u64,
+    /// Active agents when the epoch ended
+    pub active_agents: u8,
+    /// Executions out of proposals that either executed or failed
+    pub success_rate_bps: u16,
+    /// Votes out of one vote per active agent on every proposal, capped
+    pub participation_bps: u16,
+    pub bump: u8,
+}
+
+impl EpochStats {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        8 +   // epoch
+        8 +   // started_at
+        8 +   // ended_at
+        8 +   // proposals
+        8 +   // executions
+        8 +   // failures
+        8 +   // votes
+        1 +   // active_agents
+        2 +   // success_rate_bps
+        2 +   // participation_bps
+        1;    // bump
+
+    /// `part` out of `whole`, in basis points capped at `BPS_DENOMINATOR`
+    pub fn ratio_bps(part: u64, whole: u64) -> u16 {
+        if whole == 0 {
+            return 0;
+        }
+        (part as u128 * BPS_DENOMINATOR as u128 / whole as u128).min(BPS_DENOMINATOR as u128) as u16
+    }
+}
+
+/// Governance token emission schedule: `per_epoch` tokens minted to the
+/// treasury once per reward epoch until `max_supply` is reached
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct Emission {
+    pub per_epoch: u64,
+    pub max_supply: u64,
+    pub last_epoch: u64,
+}
+
+impl Emission {
+    pub const LEN: usize = 8 + // per_epoch
+        8 +   // max_supply
+        8;    // last_epoch
+}
+
+/// Congestion pricing for `create_proposal`. Recent proposal volume halves
+/// every `half_life` seconds, and every `threshold` proposals of it double
+/// the deposit a proposer pays into the treasury. A `base_deposit` of 0
+/// makes proposing free.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ProposalCongestion {
+    pub base_deposit: u64,
+    pub threshold: u16,
+    pub half_life: i64,
+    /// Proposals created recently, decayed through `decayed_at`
+    pub volume: u32,
+    pub decayed_at: i64,
+}
+
+impl ProposalCongestion {
+    pub const LEN: usize = 8 + // base_deposit
+        2 +   // threshold
+        8 +   // half_life
+        4 +   // volume
+        8;    // decayed_at
+
+    /// Halve `volume` once per whole `half_life` elapsed; the remainder
+    /// carries over so frequent calls decay no slower than rare ones
+    pub fn decay(&mut self, current_time: i64) {
+        if self.half_life <= 0 {
+            return;
+        }
+        let half_lives = current_time.saturating_sub(self.decayed_at) / self.half_life;
+        if half_lives <= 0 {
+            return;
+        }
+        self.volume = self.volume.checked_shr(half_lives.min(32) as u32).unwrap_or(0);
+        self.decayed_at = self
+            .decayed_at
+            .saturating_add(half_lives.saturating_mul(self.half_life));
+    }
+
+    /// Deposit for the next proposal at the current volume
+    pub fn deposit(&self) -> u64 {
+        if self.threshold == 0 {
+            return self.base_deposit;
+        }
+        let doublings = (self.volume / self.threshold as u32).min(MAX_CONGESTION_DOUBLINGS);
+        self.base_deposit.saturating_mul(1 << doublings)
+    }
+
+    /// Decay, then price and count a new proposal
+    pub fn record(&mut self, current_time: i64) -> u64 {
+        self.decay(current_time);
+        let deposit = self.deposit();
+        self.volume = self.volume.saturating_add(1);
+        deposit
+    }
+}
+
+/// Individual agent registration and reputation.
+///
+/// Zero-copy: fields are ordered so the layout has no implicit padding, with
+/// `version` first like every other account. Variable-length values are
+/// fixed arrays with a length.
+#[account(zero_copy)]
 pub struct Agent {
-    pub owner: Pubkey,
-    pub agent_type: AgentType,
-    pub name: String,
+    pub version: u8,
+    pub bump: u8,
     pub reputation: u16,
-    pub proposals_created: u32,
-    pub votes_cast: u32,
-    pub successful_proposals: u32,
+    pub elo_rating: u16,
+    pub manifest_len: u16,
     pub registered_at: i64,
     pub last_active: i64,
-    pub is_active: bool,
-    pub bump: u8,
+    pub sabbatical_until: i64,
+    pub reward_epoch: u64,
+    pub proposals_created: u64,
+    pub votes_cast: u64,
+    pub successful_proposals: u64,
+    /// Epochs before this one have been checked for reputation decay
+    pub decayed_through: u64,
+    /// Oracle-valued PnL realized by this agent's executed proposals, in
+    /// quote units scaled by 10^-`TWAP_PRICE_EXPONENT`
+    pub proposal_pnl: i64,
+    /// This agent's vote-weighted share of the PnL realized by proposals
+    /// it voted to approve
+    pub vote_pnl: i64,
+    /// Outcomes whose PnL has been attributed to this agent
+    pub attributed_outcomes: u64,
+    pub reward_points: [u32; REWARD_HISTORY_EPOCHS],
+    /// `SwarmState` the agent is registered with
+    pub swarm: Pubkey,
+    /// Registering key; seeds the agent and names it in proposals, votes
+    /// and bonds even after its signing key is rotated
+    pub owner: Pubkey,
+    /// Key that signs for the agent; starts as `owner`
+    pub signing_key: Pubkey,
+    /// Must co-sign key rotations and guardian changes;
+    /// `Pubkey::default()` when unset
+    pub guardian: Pubkey,
+    /// Borsh encoding of the `AgentType`
+    pub agent_type_tag: [u8; AgentType::LEN],
+    pub name: [u8; MAX_AGENT_NAME_LENGTH],
+    pub name_len: u8,
+    pub active: u8,
+    pub manifest: [u8; MAX_MANIFEST_LENGTH],
+    pub _padding: [u8; 7],
 }
 
 impl Agent {
-    pub const LEN: usize = 8 + // discriminator
-        32 +  // owner
-        1 +   // agent_type
-        (4 + MAX_AGENT_NAME_LENGTH) + // name
-        2 +   // reputation
-        4 +   // proposals_created
-        4 +   // votes_cast
-        4 +   // successful_proposals
-        8 +   // registered_at
-        8 +   // last_active
-        1 +   // is_active
-        1;    // bump
+    /// v2 widened the activity counters from u32 to u64; v3 added
+    /// `decayed_through`; v4 made room for format 2 manifests; v5 added
+    /// `signing_key` and `guardian`; v6 added the PnL attribution stats
+    pub const VERSION: u8 = 6;
+    pub const LEN: usize = 8 + std::mem::size_of::<Agent>();
+
+    pub fn agent_type(&self) -> AgentType {
+        AgentType::deserialize(&mut &self.agent_type_tag[..])
+            .expect("agent type is written by set_agent_type")
+    }
+
+    pub fn set_agent_type(&mut self, agent_type: AgentType) {
+        self.agent_type_tag = [0; AgentType::LEN];
+        let encoded = agent_type.try_to_vec().expect("agent type fits in AgentType::LEN");
+        self.agent_type_tag[..encoded.len()].copy_from_slice(&encoded);
+    }
+
+    pub fn name(&self) -> &str {
+        std::str::from_utf8(&self.name[..self.name_len as usize]).unwrap_or_default()
+    }
+
+    /// Callers check the length against `MAX_AGENT_NAME_LENGTH`
+    pub fn set_name(&mut self, name: &str) {
+        self.name = [0; MAX_AGENT_NAME_LENGTH];
+        self.name[..name.len()].copy_from_slice(name.as_bytes());
+        self.name_len = name.len() as u8;
+    }
+
+    pub fn manifest(&self) -> &[u8] {
+        &self.manifest[..self.manifest_len as usize]
+    }
+
+    /// Callers check the length against `MAX_MANIFEST_LENGTH`
+    pub fn set_manifest(&mut self, manifest: &[u8]) {
+        self.manifest = [0; MAX_MANIFEST_LENGTH];
+        self.manifest[..manifest.len()].copy_from_slice(manifest);
+        self.manifest_len = manifest.len() as u16;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active != 0
+    }
+
+    pub fn guardian(&self) -> Option<Pubkey> {
+        (self.guardian != Pubkey::default()).then_some(self.guardian)
+    }
+
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active as u8;
+    }
+
+    /// Check if the agent announced downtime covering `current_time`
+    pub fn is_on_sabbatical(&self, current_time: i64) -> bool {
+        current_time < self.sabbatical_until
+    }
+
+    /// Reputation decay and stale-marking must skip agents on sabbatical
+    pub fn is_exempt_from_decay(&self, current_time: i64) -> bool {
+        self.is_on_sabbatical(current_time)
+    }
+
+    /// Move the points ring forward to `epoch`, clearing slots that now
+    /// belong to epochs with no points yet
+    pub fn advance_reward_epoch(&mut self, epoch: u64) {
+        if epoch <= self.reward_epoch {
+            return;
+        }
+        let cleared = (epoch - self.reward_epoch).min(REWARD_HISTORY_EPOCHS as u64);
+        for offset in 0..cleared {
+            self.reward_points[((epoch - offset) % REWARD_HISTORY_EPOCHS as u64) as usize] = 0;
+        }
+        self.reward_epoch = epoch;
+    }
+
+    /// Points earned in `epoch`, if it is still within the history ring
+    pub fn points_for(&self, epoch: u64) -> u32 {
+        if epoch > self.reward_epoch || self.reward_epoch - epoch >= REWARD_HISTORY_EPOCHS as u64 {
+            return 0;
+        }
+        self.reward_points[(epoch % REWARD_HISTORY_EPOCHS as u64) as usize]
+    }
+
+    /// Credit performance points in the current epoch
+    pub fn earn_points(&mut self, rewards: &mut RewardEpoch, points: u32) {
+        self.advance_reward_epoch(rewards.epoch);
+        let slot = (rewards.epoch % REWARD_HISTORY_EPOCHS as u64) as usize;
+        self.reward_points[slot] = self.reward_points[slot].saturating_add(points);
+        rewards.points = rewards.points.saturating_add(points as u64);
+    }
+
+    /// Credit a proposal of this agent's that executed as intended
+    pub fn credit_successful_proposal(&mut self, rewards: &mut RewardEpoch) {
+        self.successful_proposals = self.successful_proposals.saturating_add(1);
+        self.earn_points(rewards, REWARD_POINTS_SUCCESSFUL_PROPOSAL);
+    }
+
+    /// Attribute `pnl` realized by one of this agent's proposals
+    pub fn attribute_proposal_pnl(&mut self, pnl: i64) {
+        self.proposal_pnl = self.proposal_pnl.saturating_add(pnl);
+        self.attributed_outcomes = self.attributed_outcomes.saturating_add(1);
+    }
+
+    /// Attribute this agent's share of `pnl` realized by a proposal it
+    /// approved
+    pub fn attribute_vote_pnl(&mut self, pnl: i64) {
+        self.vote_pnl = self.vote_pnl.saturating_add(pnl);
+        self.attributed_outcomes = self.attributed_outcomes.saturating_add(1);
+    }
+
+    /// Remove and return the points earned in `epoch` so they pay out once
+    pub fn take_points(&mut self, epoch: u64) -> u32 {
+        let points = self.points_for(epoch);
+        if points > 0 {
+            self.reward_points[(epoch % REWARD_HISTORY_EPOCHS as u64) as usize] = 0;
+        }
+        points
+    }
 
     /// Calculate weighted vote based on reputation
     pub fn vote_weight(&self) -> u32 {
@@ -64,6 +476,17 @@ impl Agent {
         1000 + (self.reputation as u32 * 10)
     }
 
+    /// Vote weight with the agent's Elo rating blended in.
+    /// `elo_blend_bps` of the weight comes from Elo, the rest from reputation.
+    pub fn blended_vote_weight(&self, elo_blend_bps: u16) -> u32 {
+        let reputation_weight = self.vote_weight() as u64;
+        let elo_weight = 1000 + (self.elo_rating as u64 * 10);
+        let blend = elo_blend_bps.min(BPS_DENOMINATOR) as u64;
+
+        ((reputation_weight * (BPS_DENOMINATOR as u64 - blend) + elo_weight * blend)
+            / BPS_DENOMINATOR as u64) as u32
+    }
+
     /// Update reputation after proposal outcome
     pub fn update_reputation(&mut self, performance_score: u16) {
         let new_reputation = if performance_score > 500 {
@@ -78,117 +501,2700 @@ impl Agent {
     }
 }
 
-/// Proposal for agent coordination
+/// An owner's reputation across every swarm it serves in. Each swarm's
+/// `Agent` keeps its own working copy; `sync_reputation` folds the changes
+/// each swarm made back into this score and hands it out again.
 #[account]
-pub struct Proposal {
-    pub proposer: Pubkey,
-    pub proposal_type: ProposalType,
-    pub data: Vec<u8>,
-    pub description: String,
+pub struct AgentIdentity {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub reputation: u16,
+    /// Linked `Membership`s
+    pub memberships: u16,
     pub created_at: i64,
-    pub expires_at: i64,
-    pub executed: bool,
-    pub executed_at: i64,
-    pub votes_for: u32,
-    pub votes_against: u32,
-    pub votes_abstain: u32,
-    pub weighted_votes_for: u64,
-    pub weighted_votes_against: u64,
-    pub total_voters: u8,
-    pub voters: Vec<Pubkey>,
     pub bump: u8,
 }
 
-impl Proposal {
+impl AgentIdentity {
+    pub const VERSION: u8 = 1;
     pub const LEN: usize = 8 + // discriminator
-        32 +  // proposer
-        1 +   // proposal_type
-        (4 + MAX_PROPOSAL_DATA_LENGTH) + // data
-        (4 + MAX_DESCRIPTION_LENGTH) +   // description
+        1 +   // version
+        32 +  // owner
+        2 +   // reputation
+        2 +   // memberships
         8 +   // created_at
-        8 +   // expires_at
-        1 +   // executed
-        8 +   // executed_at
-        4 +   // votes_for
-        4 +   // votes_against
-        4 +   // votes_abstain
-        8 +   // weighted_votes_for
-        8 +   // weighted_votes_against
-        1 +   // total_voters
-        (4 + 32 * 20) + // voters (max 20 agents)
         1;    // bump
+}
 
-    /// Check if proposal has reached quorum
-    /// Uses integer math (no floats) — requires > 50% of agents.
-    pub fn has_quorum(&self, min_votes: u8, total_agents: u8) -> bool {
-        self.total_voters >= min_votes &&
-        // Integer ceiling division: (total_agents + 1) / 2  gives majority threshold
-        self.total_voters as u16 >= ((total_agents as u16 + 1) / 2)
+/// Link between an `AgentIdentity` and its `Agent` in one swarm
+#[account]
+pub struct Membership {
+    pub version: u8,
+    pub identity: Pubkey,
+    pub swarm: Pubkey,
+    pub agent: Pubkey,
+    /// Identity reputation the agent was last given; the agent's drift from
+    /// it is what this swarm has earned or lost since
+    pub synced_reputation: u16,
+    pub synced_at: i64,
+    pub bump: u8,
+}
+
+impl Membership {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // identity
+        32 +  // swarm
+        32 +  // agent
+        2 +   // synced_reputation
+        8 +   // synced_at
+        1;    // bump
+
+    /// Apply the agent's drift since the last sync to the identity and give
+    /// the agent the identity's new score
+    pub fn sync(&mut self, identity: &mut AgentIdentity, agent: &mut Agent, current_time: i64) {
+        let drift = agent.reputation as i32 - self.synced_reputation as i32;
+        identity.reputation = (identity.reputation as i32 + drift)
+            .clamp(MIN_REPUTATION as i32, MAX_REPUTATION as i32) as u16;
+        agent.reputation = identity.reputation;
+        self.synced_reputation = identity.reputation;
+        self.synced_at = current_time;
     }
+}
 
-    /// Check if proposal is approved (weighted voting)
-    pub fn is_approved(&self) -> bool {
-        // Weighted votes for must exceed votes against
-        self.weighted_votes_for > self.weighted_votes_against &&
-        self.votes_for > self.votes_against
+/// Achievement an agent can be awarded a compressed NFT badge for
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BadgeKind {
+    Votes,         // Cast `votes_milestone` votes
+    Proposals,     // Had `proposals_milestone` proposals succeed
+    TopReputation, // Held the swarm's top reputation during an epoch
+}
+
+impl BadgeKind {
+    pub fn index(&self) -> u8 {
+        match self {
+            BadgeKind::Votes => 0,
+            BadgeKind::Proposals => 1,
+            BadgeKind::TopReputation => 2,
+        }
     }
 
-    /// Check if proposal is expired
-    pub fn is_expired(&self, current_time: i64) -> bool {
-        current_time > self.expires_at
+    /// Badge name, within Bubblegum's 32-byte limit
+    pub fn name(&self) -> &'static str {
+        match self {
+            BadgeKind::Votes => "Swarm Voter",
+            BadgeKind::Proposals => "Swarm Proposer",
+            BadgeKind::TopReputation => "Swarm Top Reputation",
+        }
     }
 
-    /// Check if agent has already voted
-    pub fn has_voted(&self, agent: &Pubkey) -> bool {
-        self.voters.contains(agent)
+    /// Suffix appended to the config's `uri_prefix` for the badge's metadata
+    pub fn uri_suffix(&self) -> &'static str {
+        match self {
+            BadgeKind::Votes => "votes.json",
+            BadgeKind::Proposals => "proposals.json",
+            BadgeKind::TopReputation => "top-reputation.json",
+        }
     }
+}
 
-    /// Maximum voters that can be stored (matches LEN calculation)
-    pub const MAX_VOTERS: usize = 20;
+/// Governance-set badge rules of a swarm. The PDA is the delegate of the
+/// Bubblegum tree badges are minted into.
+#[account]
+pub struct BadgeConfig {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub merkle_tree: Pubkey,
+    /// Votes that earn the `Votes` badge; 0 disables it
+    pub votes_milestone: u64,
+    /// Successful proposals that earn the `Proposals` badge; 0 disables it
+    pub proposals_milestone: u64,
+    pub top_reputation_enabled: bool,
+    /// Next epoch whose `TopReputation` badge is still unclaimed
+    pub next_top_reputation_epoch: u64,
+    pub uri_prefix: String,
+    pub badges_minted: u64,
+    pub bump: u8,
+}
 
-    /// Record a vote. Returns Err if voter capacity is exceeded.
-    pub fn record_vote(&mut self, agent: &Pubkey, vote: VoteType, weight: u32) -> std::result::Result<(), &'static str> {
-        if self.voters.len() >= Self::MAX_VOTERS {
-            return Err("maximum voter capacity reached");
-        }
+impl BadgeConfig {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        32 +  // merkle_tree
+        8 +   // votes_milestone
+        8 +   // proposals_milestone
+        1 +   // top_reputation_enabled
+        8 +   // next_top_reputation_epoch
+        (4 + MAX_BADGE_URI_PREFIX_LENGTH) + // uri_prefix
+        8 +   // badges_minted
+        1;    // bump
 
-        match vote {
-            VoteType::Approve => {
-                self.votes_for += 1;
-                self.weighted_votes_for += weight as u64;
+    /// Whether `agent` has reached `kind`'s milestone. `TopReputation` is
+    /// checked against the other agents by the caller.
+    pub fn is_earned(&self, kind: BadgeKind, agent: &Agent) -> bool {
+        match kind {
+            BadgeKind::Votes => {
+                self.votes_milestone > 0 && agent.votes_cast >= self.votes_milestone
             }
-            VoteType::Reject => {
-                self.votes_against += 1;
-                self.weighted_votes_against += weight as u64;
+            BadgeKind::Proposals => {
+                self.proposals_milestone > 0
+                    && agent.successful_proposals >= self.proposals_milestone
             }
-            VoteType::Abstain => {
-                self.votes_abstain += 1;
+            BadgeKind::TopReputation => self.top_reputation_enabled,
+        }
+    }
+}
+
+/// Receipt of a badge minted to an agent, so each is awarded once
+#[account]
+pub struct AgentBadge {
+    pub version: u8,
+    pub agent: Pubkey,
+    pub owner: Pubkey,
+    pub kind: BadgeKind,
+    /// Epoch of a `TopReputation` badge; 0 for milestones
+    pub epoch: u64,
+    pub merkle_tree: Pubkey,
+    pub awarded_at: i64,
+    pub bump: u8,
+}
+
+impl AgentBadge {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // agent
+        32 +  // owner
+        1 +   // kind
+        8 +   // epoch
+        32 +  // merkle_tree
+        8 +   // awarded_at
+        1;    // bump
+}
+
+/// An agent's ed25519 signature over an off-chain output, verified
+/// on-chain. Proposals cite it so voters can check which model output they
+/// are based on.
+#[account]
+pub struct Attestation {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub agent: Pubkey,
+    /// The agent's signing key at attestation time
+    pub signer: Pubkey,
+    /// Hash of the off-chain analysis payload
+    pub payload_hash: [u8; 32],
+    /// Hash identifying the model that produced the payload
+    pub model_hash: [u8; 32],
+    pub slot: u64,
+    pub attested_at: i64,
+    pub bump: u8,
+}
+
+impl Attestation {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        32 +  // agent
+        32 +  // signer
+        32 +  // payload_hash
+        32 +  // model_hash
+        8 +   // slot
+        8 +   // attested_at
+        1;    // bump
+
+    /// Bytes the signing key signs: the agent account, then both hashes.
+    /// Naming the agent keeps a signature from being replayed for the
+    /// key's agents in other swarms.
+    pub fn message(agent: &Pubkey, payload_hash: &[u8; 32], model_hash: &[u8; 32]) -> [u8; 96] {
+        let mut message = [0u8; 96];
+        message[..32].copy_from_slice(agent.as_ref());
+        message[32..64].copy_from_slice(payload_hash);
+        message[64..].copy_from_slice(model_hash);
+        message
+    }
+}
+
+/// `RegistryEntry::flags` bit set while the agent is registered
+pub const REGISTRY_FLAG_ACTIVE: u8 = 1 << 0;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RegistryEntry {
+    pub agent: Pubkey,
+    pub agent_type: AgentType,
+    pub flags: u8,
+}
+
+impl RegistryEntry {
+    pub const LEN: usize = 32 + // agent
+        AgentType::LEN + // agent_type
+        1;    // flags
+
+    pub fn is_active(&self) -> bool {
+        self.flags & REGISTRY_FLAG_ACTIVE != 0
+    }
+}
+
+/// Every agent of a swarm with its type and status, so quorum-by-type and
+/// sortition can enumerate agents on-chain. Deregistered entries keep their
+/// slot, inactive, until a new agent reuses it, so the list never outgrows
+/// `max_agents`.
+#[account]
+pub struct AgentRegistry {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub entries: Vec<RegistryEntry>,
+    pub bump: u8,
+}
+
+impl AgentRegistry {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        (4 + MAX_AGENTS as usize * RegistryEntry::LEN) + // entries
+        1;    // bump
+
+    pub fn add(&mut self, agent: Pubkey, agent_type: AgentType) -> Result<()> {
+        let entry = RegistryEntry { agent, agent_type, flags: REGISTRY_FLAG_ACTIVE };
+        match self.entries.iter_mut().find(|entry| !entry.is_active()) {
+            Some(slot) => *slot = entry,
+            None => {
+                require!(self.entries.len() < MAX_AGENTS as usize, SwarmError::MaxAgentsReached);
+                self.entries.push(entry);
             }
         }
+        Ok(())
+    }
 
-        self.voters.push(*agent);
-        self.total_voters += 1;
+    pub fn remove(&mut self, agent: &Pubkey) -> Result<()> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.is_active() && entry.agent == *agent)
+            .ok_or(SwarmError::AgentNotFound)?;
+        entry.flags &= !REGISTRY_FLAG_ACTIVE;
         Ok(())
     }
+
+    pub fn active(&self) -> impl Iterator<Item = &RegistryEntry> {
+        self.entries.iter().filter(|entry| entry.is_active())
+    }
+
+    pub fn count_of_type(&self, agent_type: AgentType) -> usize {
+        self.active().filter(|entry| entry.agent_type == agent_type).count()
+    }
+
+    /// Active agent of `agent_type` picked uniformly by `randomness`
+    pub fn draw(&self, agent_type: AgentType, randomness: &[u8; 32]) -> Option<Pubkey> {
+        let count = self.count_of_type(agent_type);
+        if count == 0 {
+            return None;
+        }
+        let seed = keccak::hashv(&[b"executor", randomness]).to_bytes();
+        let index = u64::from_le_bytes(seed[..8].try_into().unwrap()) % count as u64;
+        self.active()
+            .filter(|entry| entry.agent_type == agent_type)
+            .nth(index as usize)
+            .map(|entry| entry.agent)
+    }
 }
 
-/// Execution outcome for learning
+/// Pending commit-reveal randomness for a proposal. The committer's secret
+/// is mixed with the hash of a slot that had not happened at commit time, so
+/// neither the committer nor that slot's leader controls the result alone.
 #[account]
-pub struct Outcome {
+pub struct RandomnessRequest {
+    pub version: u8,
     pub proposal: Pubkey,
-    pub executed_by: Pubkey,
-    pub success: bool,
-    pub metrics: Vec<u8>,
-    pub executed_at: i64,
+    pub committer: Pubkey,
+    /// Keccak hash of the committer's secret
+    pub commitment: [u8; 32],
+    /// Slot whose hash is mixed in; the secret is revealed after it
+    pub target_slot: u64,
     pub bump: u8,
 }
 
-impl Outcome {
+impl RandomnessRequest {
+    pub const VERSION: u8 = 1;
     pub const LEN: usize = 8 + // discriminator
+        1 +   // version
         32 +  // proposal
-        32 +  // executed_by
-        1 +   // success
-        (4 + MAX_OUTCOME_METRICS_LENGTH) + // metrics
-        8 +   // executed_at
+        32 +  // committer
+        32 +  // commitment
+        8 +   // target_slot
+        1;    // bump
+}
+
+/// Meaning of an `AgentType::Custom` id within a swarm
+#[account]
+pub struct CustomAgentType {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub id: u8,
+    pub name: String,
+    /// Hash of the off-chain schema describing the type's role and manifest
+    pub schema_hash: [u8; 32],
+    pub creator: Pubkey,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl CustomAgentType {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        1 +   // id
+        (4 + MAX_AGENT_TYPE_NAME_LENGTH) + // name
+        32 +  // schema_hash
+        32 +  // creator
+        8 +   // created_at
         1;    // bump
 }
+
+/// Proposal for agent coordination.
+///
+/// Zero-copy, so votes touch the counters without deserializing the
+/// description and voter list. Fields are ordered so the layout has no
+/// implicit padding, with `version` first like every other account. The
+/// payload is stored after the fixed fields and the account is sized to it.
+#[account(zero_copy)]
+pub struct Proposal {
+    pub version: u8,
+    pub bump: u8,
+    /// Declared payload length
+    pub data_len: u16,
+    /// Payload bytes uploaded so far; voting opens once all are written
+    pub data_written: u16,
+    pub description_len: u16,
+    /// Swarm's `total_proposals` at creation; seeds the proposal and places
+    /// it in the swarm's `ProposalPage`s
+    pub id: u64,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub executed_at: i64,
+    pub assigned_at: i64,
+    /// `PriceCondition::threshold`, when `condition_comparison` is set
+    pub condition_threshold: u64,
+    /// `PriceCondition::twap_window`
+    pub condition_twap_window: i64,
+    /// Earliest execution time of a scheduled proposal; 0 when unscheduled
+    pub execute_not_before: i64,
+    pub weighted_votes_for: u64,
+    pub weighted_votes_against: u64,
+    pub votes_for: u32,
+    pub votes_against: u32,
+    pub votes_abstain: u32,
+    /// Error code of the most recently reported failed execution, once
+    /// `execution_attempts` is non-zero
+    pub last_failure_code: u32,
+    /// `SwarmState` the proposal was created in
+    pub swarm: Pubkey,
+    pub proposer: Pubkey,
+    /// `Pubkey::default()` when no executor is assigned
+    pub assigned_executor: Pubkey,
+    pub condition_base_mint: Pubkey,
+    pub condition_quote_mint: Pubkey,
+    /// Proposal in another swarm that must be approved there before this
+    /// one executes; `Pubkey::default()` when unlinked
+    pub linked_proposal: Pubkey,
+    /// `Attestation` of the off-chain output the proposal is based on;
+    /// `Pubkey::default()` when none is cited
+    pub attestation: Pubkey,
+    pub objective: [u8; 32],
+    /// Keccak hash of the payload when `data_committed`; the payload itself
+    /// is staged in a `PayloadBuffer` at execution
+    pub data_hash: [u8; 32],
+    /// Revealed commit-reveal randomness, once `has_randomness`
+    pub randomness: [u8; 32],
+    pub voters: [Pubkey; Proposal::MAX_VOTERS],
+    pub description: [u8; MAX_DESCRIPTION_LENGTH],
+    /// Topic tags, zero-padded; unused slots are all zero
+    pub tags: [[u8; MAX_PROPOSAL_TAG_LENGTH]; MAX_PROPOSAL_TAGS],
+    /// `ProposalType::index()` of the proposal's type
+    pub proposal_type_tag: u8,
+    pub has_objective: u8,
+    /// At most `MAX_VOTERS`
+    pub total_voters: u8,
+    pub executed: u8,
+    /// Out of execution attempts; the proposal can no longer execute
+    pub failed: u8,
+    pub execution_attempts: u8,
+    /// Outcome was folded into the `OutcomeArchive` and its account closed
+    pub outcome_archived: u8,
+    /// Only `data_hash` is stored; `data_len` is zero
+    pub data_committed: u8,
+    /// 0 for an unconditional proposal, else `PriceComparison` index + 1
+    pub condition_comparison: u8,
+    pub has_randomness: u8,
+    /// `assigned_executor` was drawn from `randomness` and is final
+    pub executor_drawn: u8,
+    /// Created by a vote-approved schedule; needs no votes of its own
+    pub fast_tracked: u8,
+    /// 0 when the whole swarm votes, else the scoped `Committee`'s id + 1
+    pub committee: u8,
+    /// Bounties still escrowed against the proposal
+    pub open_bounties: u8,
+    /// Endorsements not yet settled
+    pub open_endorsements: u8,
+    /// `VoteRecord`s not yet settled against the outcome; at most
+    /// `MAX_VOTERS`
+    pub unsettled_votes: u8,
+}
+
+impl Proposal {
+    /// v2 added `tags`
+    pub const VERSION: u8 = 2;
+    pub const LEN: usize = 8 + std::mem::size_of::<Proposal>();
+
+    pub fn proposal_type(&self) -> ProposalType {
+        ProposalType::from_index(self.proposal_type_tag as usize)
+            .expect("proposal type is written from a ProposalType")
+    }
+
+    /// Account size holding `data_len` payload bytes
+    pub fn space(data_len: usize) -> usize {
+        Self::LEN + data_len
+    }
+
+    pub fn is_data_complete(&self) -> bool {
+        self.data_written == self.data_len
+    }
+
+    /// Copy out the payload written so far. Must not be called while the
+    /// proposal is loaded mutably.
+    pub fn load_data(proposal: &AccountLoader<Proposal>) -> Result<Vec<u8>> {
+        let data_written = proposal.load()?.data_written as usize;
+        let info = proposal.to_account_info();
+        let account_data = info.try_borrow_data()?;
+        account_data
+            .get(Self::LEN..Self::LEN + data_written)
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| error!(SwarmError::ProposalDataTooLong))
+    }
+
+    /// Write `bytes` into the payload at `offset`. The account must already
+    /// be sized for them and the proposal not be loaded; callers advance
+    /// `data_written`.
+    pub fn write_data(proposal: &AccountInfo, offset: usize, bytes: &[u8]) -> Result<()> {
+        let start = Self::LEN + offset;
+        let mut account_data = proposal.try_borrow_mut_data()?;
+        account_data
+            .get_mut(start..start + bytes.len())
+            .ok_or(SwarmError::ProposalDataTooLong)?
+            .copy_from_slice(bytes);
+        Ok(())
+    }
+
+    pub fn description(&self) -> &str {
+        std::str::from_utf8(&self.description[..self.description_len as usize]).unwrap_or_default()
+    }
+
+    /// Callers check the length against `MAX_DESCRIPTION_LENGTH`
+    pub fn set_description(&mut self, description: &str) {
+        self.description[..description.len()].copy_from_slice(description.as_bytes());
+        self.description_len = description.len() as u16;
+    }
+
+    /// Check proposal tags: at most `MAX_PROPOSAL_TAGS`, each 1 to
+    /// `MAX_PROPOSAL_TAG_LENGTH` ASCII letters, digits, `-` or `_`, and no
+    /// tag twice ignoring case
+    pub fn validate_tags(tags: &[String]) -> Result<()> {
+        require!(tags.len() <= MAX_PROPOSAL_TAGS, SwarmError::TooManyProposalTags);
+        for (index, tag) in tags.iter().enumerate() {
+            require!(
+                (1..=MAX_PROPOSAL_TAG_LENGTH).contains(&tag.len())
+                    && tag.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_'),
+                SwarmError::InvalidProposalTag
+            );
+            require!(
+                !tags[..index].iter().any(|earlier| earlier.eq_ignore_ascii_case(tag)),
+                SwarmError::InvalidProposalTag
+            );
+        }
+        Ok(())
+    }
+
+    pub fn tags(&self) -> Vec<String> {
+        self.tags
+            .iter()
+            .map(|tag| {
+                let len = tag.iter().position(|byte| *byte == 0).unwrap_or(tag.len());
+                String::from_utf8_lossy(&tag[..len]).into_owned()
+            })
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    }
+
+    /// Callers check the tags with `validate_tags`
+    pub fn set_tags(&mut self, tags: &[String]) {
+        self.tags = [[0; MAX_PROPOSAL_TAG_LENGTH]; MAX_PROPOSAL_TAGS];
+        for (slot, tag) in self.tags.iter_mut().zip(tags) {
+            slot[..tag.len()].copy_from_slice(tag.as_bytes());
+        }
+    }
+
+    pub fn objective(&self) -> Option<[u8; 32]> {
+        (self.has_objective != 0).then_some(self.objective)
+    }
+
+    pub fn voters(&self) -> &[Pubkey] {
+        &self.voters[..self.total_voters as usize]
+    }
+
+    /// Price the proposal waits for before it may execute
+    pub fn condition(&self) -> Option<PriceCondition> {
+        let comparison = match self.condition_comparison {
+            1 => PriceComparison::Below,
+            2 => PriceComparison::Above,
+            _ => return None,
+        };
+        Some(PriceCondition {
+            base_mint: self.condition_base_mint,
+            quote_mint: self.condition_quote_mint,
+            comparison,
+            threshold: self.condition_threshold,
+            twap_window: self.condition_twap_window,
+        })
+    }
+
+    pub fn set_condition(&mut self, condition: Option<PriceCondition>) {
+        let Some(condition) = condition else {
+            self.condition_comparison = 0;
+            return;
+        };
+        self.condition_base_mint = condition.base_mint;
+        self.condition_quote_mint = condition.quote_mint;
+        self.condition_threshold = condition.threshold;
+        self.condition_twap_window = condition.twap_window;
+        self.condition_comparison = condition.comparison as u8 + 1;
+    }
+
+    pub fn assigned_executor(&self) -> Option<Pubkey> {
+        (self.assigned_executor != Pubkey::default()).then_some(self.assigned_executor)
+    }
+
+    pub fn last_failure_code(&self) -> Option<u32> {
+        (self.execution_attempts > 0).then_some(self.last_failure_code)
+    }
+
+    pub fn is_executed(&self) -> bool {
+        self.executed != 0
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.failed != 0
+    }
+
+    /// Bounties or endorsements that still need the proposal account to be
+    /// paid out
+    pub fn has_open_claims(&self) -> bool {
+        self.open_bounties > 0 || self.open_endorsements > 0
+    }
+
+    /// A failed proposal can be closed once its bounties are refunded and
+    /// endorsements settled
+    pub fn is_closable(&self) -> bool {
+        self.is_failed() && !self.has_open_claims()
+    }
+
+    /// The outcome can be archived once nothing settles against it any more
+    pub fn is_outcome_settled(&self) -> bool {
+        self.unsettled_votes == 0 && !self.has_open_claims()
+    }
+
+    /// A bounty goes back to its funder once the recorded outcome failed or,
+    /// without an outcome, the proposal failed or expired unexecuted
+    pub fn is_bounty_refundable(&self, outcome_success: Option<bool>, current_time: i64) -> bool {
+        match outcome_success {
+            Some(success) => !success,
+            None => {
+                self.is_failed() || (!self.is_executed() && self.is_expired(current_time))
+            }
+        }
+    }
+
+    pub fn is_data_committed(&self) -> bool {
+        self.data_committed != 0
+    }
+
+    pub fn linked_proposal(&self) -> Option<Pubkey> {
+        (self.linked_proposal != Pubkey::default()).then_some(self.linked_proposal)
+    }
+
+    pub fn attestation(&self) -> Option<Pubkey> {
+        (self.attestation != Pubkey::default()).then_some(self.attestation)
+    }
+
+    pub fn execute_not_before(&self) -> Option<i64> {
+        (self.execute_not_before != 0).then_some(self.execute_not_before)
+    }
+
+    pub fn randomness(&self) -> Option<[u8; 32]> {
+        (self.has_randomness != 0).then_some(self.randomness)
+    }
+
+    pub fn is_fast_tracked(&self) -> bool {
+        self.fast_tracked != 0
+    }
+
+    pub fn is_executor_drawn(&self) -> bool {
+        self.executor_drawn != 0
+    }
+
+    pub fn is_outcome_archived(&self) -> bool {
+        self.outcome_archived != 0
+    }
+
+    /// Committee the proposal was scoped to at creation
+    pub fn committee_id(&self) -> Option<u8> {
+        self.committee.checked_sub(1)
+    }
+
+    /// Check if proposal has reached quorum
+    /// Uses integer math (no floats) — requires > 50% of agents.
+    pub fn has_quorum(&self, min_votes: u8, total_agents: u8) -> bool {
+        if self.is_fast_tracked() {
+            return true;
+        }
+        self.total_voters >= min_votes &&
+        // Integer ceiling division: (total_agents + 1) / 2  gives majority threshold
+        self.total_voters as u16 >= ((total_agents as u16 + 1) / 2)
+    }
+
+    /// Check if proposal is approved (weighted voting)
+    pub fn is_approved(&self) -> bool {
+        if self.is_fast_tracked() {
+            return true;
+        }
+        // A weighted tie is settled by a coin flip once randomness is revealed
+        if self.weighted_votes_for == self.weighted_votes_against && self.weighted_votes_for > 0 {
+            return self
+                .randomness()
+                .is_some_and(|randomness| keccak::hashv(&[b"tie", &randomness]).0[0] & 1 == 1);
+        }
+        // Weighted votes for must exceed votes against
+        self.weighted_votes_for > self.weighted_votes_against &&
+        self.votes_for > self.votes_against
+    }
+
+    /// Check if at least `SUPERMAJORITY_BPS` of the weighted vote approves
+    pub fn has_supermajority(&self) -> bool {
+        let total = self.weighted_votes_for as u128 + self.weighted_votes_against as u128;
+        total > 0
+            && self.weighted_votes_for as u128 * BPS_DENOMINATOR as u128
+                >= total * SUPERMAJORITY_BPS as u128
+    }
+
+    /// Check if proposal is expired
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        current_time > self.expires_at
+    }
+
+    /// Check whether `executor` may execute at `current_time`.
+    /// In permissioned mode the assigned executor has exclusivity until the
+    /// window lapses, after which execution falls back to any agent.
+    pub fn can_execute(
+        &self,
+        executor: &Pubkey,
+        mode: ExecutionMode,
+        window: i64,
+        current_time: i64,
+    ) -> bool {
+        match (mode, self.assigned_executor()) {
+            (ExecutionMode::Permissioned, Some(assigned)) => {
+                assigned == *executor
+                    || current_time >= self.assigned_at.saturating_add(window)
+            }
+            _ => true,
+        }
+    }
+
+    /// Nominate `executor`, opening its exclusive window. A proposal takes
+    /// one nomination, so the window cannot be restarted.
+    pub fn nominate_executor(&mut self, executor: Pubkey, current_time: i64) -> Result<()> {
+        require!(!self.is_executor_drawn(), SwarmError::ExecutorAlreadyDrawn);
+        require!(self.assigned_executor().is_none(), SwarmError::ExecutorAlreadyAssigned);
+        self.assigned_executor = executor;
+        self.assigned_at = current_time;
+        Ok(())
+    }
+
+    /// Count a failed execution reported by `reporter`, which must be the
+    /// assigned executor or the proposer. Returns whether the proposal ran
+    /// out of attempts and is now failed.
+    pub fn record_failure(&mut self, reporter: &Pubkey, error_code: u32, max_attempts: u8) -> Result<bool> {
+        require!(
+            self.assigned_executor() == Some(*reporter) || self.proposer == *reporter,
+            SwarmError::NotAssignedExecutor
+        );
+        self.execution_attempts = self.execution_attempts.saturating_add(1);
+        self.last_failure_code = error_code;
+        if self.execution_attempts >= max_attempts {
+            self.failed = 1;
+        }
+        Ok(self.is_failed())
+    }
+
+    /// Check if agent has already voted
+    pub fn has_voted(&self, agent: &Pubkey) -> bool {
+        self.voters().contains(agent)
+    }
+
+    /// Maximum voters that can be stored
+    pub const MAX_VOTERS: usize = 20;
+
+    /// Record a vote. Returns Err if voter capacity is exceeded.
+    pub fn record_vote(&mut self, agent: &Pubkey, vote: VoteType, weight: u32) -> std::result::Result<(), &'static str> {
+        if self.total_voters as usize >= Self::MAX_VOTERS {
+            return Err("maximum voter capacity reached");
+        }
+
+        match vote {
+            VoteType::Approve => {
+                self.votes_for = self.votes_for.saturating_add(1);
+                self.weighted_votes_for = self.weighted_votes_for.saturating_add(weight as u64);
+            }
+            VoteType::Reject => {
+                self.votes_against = self.votes_against.saturating_add(1);
+                self.weighted_votes_against = self.weighted_votes_against.saturating_add(weight as u64);
+            }
+            VoteType::Abstain => {
+                self.votes_abstain = self.votes_abstain.saturating_add(1);
+            }
+        }
+
+        // Bounded by `MAX_VOTERS` above, so `total_voters` cannot wrap
+        self.voters[self.total_voters as usize] = *agent;
+        self.total_voters += 1;
+        Ok(())
+    }
+}
+
+/// One page of a swarm's proposal pubkeys in creation order, with a status
+/// bit per entry, so clients can find open proposals without
+/// `getProgramAccounts`. Closed proposals become `Pubkey::default()` so
+/// positions never shift.
+#[account]
+pub struct ProposalPage {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub page: u64,
+    pub proposals: Vec<Pubkey>,
+    /// Bit `i` is set while `proposals[i]` is neither executed nor failed
+    pub open: u64,
+    /// Bit `i` is set once `proposals[i]` executed
+    pub executed: u64,
+    /// Bit `i` is set once `proposals[i]` ran out of execution attempts
+    pub failed: u64,
+    pub bump: u8,
+}
+
+impl ProposalPage {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        8 +   // page
+        (4 + 32 * PROPOSAL_PAGE_SIZE as usize) + // proposals
+        8 +   // open
+        8 +   // executed
+        8 +   // failed
+        1;    // bump
+
+    fn bit(proposal_id: u64) -> u64 {
+        1 << (proposal_id % PROPOSAL_PAGE_SIZE)
+    }
+
+    pub fn push(&mut self, proposal: Pubkey, proposal_id: u64) {
+        self.proposals.push(proposal);
+        self.open |= Self::bit(proposal_id);
+    }
+
+    pub fn mark_executed(&mut self, proposal_id: u64) {
+        self.open &= !Self::bit(proposal_id);
+        self.executed |= Self::bit(proposal_id);
+    }
+
+    pub fn mark_failed(&mut self, proposal_id: u64) {
+        self.open &= !Self::bit(proposal_id);
+        self.failed |= Self::bit(proposal_id);
+    }
+
+    pub fn remove(&mut self, proposal_id: u64) {
+        self.proposals[(proposal_id % PROPOSAL_PAGE_SIZE) as usize] = Pubkey::default();
+        self.open &= !Self::bit(proposal_id);
+        self.executed &= !Self::bit(proposal_id);
+        self.failed &= !Self::bit(proposal_id);
+    }
+}
+
+/// Payload of a hash-committed proposal, uploaded in chunks by an executor
+/// and consumed by `execute_proposal`
+#[account]
+pub struct PayloadBuffer {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+    pub bump: u8,
+}
+
+impl PayloadBuffer {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // proposal
+        32 +  // owner
+        4 +   // data (length prefix)
+        1;    // bump
+
+    /// Account size holding `data_len` payload bytes
+    pub fn space(data_len: usize) -> usize {
+        Self::LEN + data_len
+    }
+
+    pub fn matches(&self, data_hash: &[u8; 32]) -> bool {
+        keccak::hash(&self.data).to_bytes() == *data_hash
+    }
+}
+
+/// Execution outcome for learning
+#[account]
+pub struct Outcome {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub executed_by: Pubkey,
+    pub success: bool,
+    pub metrics: Vec<u8>,
+    pub executed_at: i64,
+    /// Vault balance changes measured after a `Strategy` with balance checks
+    pub balance_deltas: Vec<BalanceDelta>,
+    /// Oracle-valued PnL of an executed `Trade`, in quote units scaled by
+    /// 10^-`TWAP_PRICE_EXPONENT`; `None` when it was not measured
+    pub realized_pnl: Option<i64>,
+    pub bump: u8,
+}
+
+impl Outcome {
+    /// v2 added `realized_pnl`
+    pub const VERSION: u8 = 2;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // proposal
+        32 +  // executed_by
+        1 +   // success
+        (4 + MAX_OUTCOME_METRICS_LENGTH) + // metrics
+        8 +   // executed_at
+        (4 + MAX_BALANCE_CHECKS * BalanceDelta::LEN) + // balance_deltas
+        (1 + 8) + // realized_pnl
+        1;    // bump
+
+    pub fn is_archivable(&self, current_time: i64) -> bool {
+        current_time >= self.executed_at.saturating_add(OUTCOME_RETENTION_PERIOD)
+    }
+
+    /// Share of the realized PnL attributed to a voter who approved with
+    /// `weight` of the proposal's `weighted_votes_for`
+    pub fn voter_pnl_share(&self, weight: u32, weighted_votes_for: u64) -> Option<i64> {
+        let pnl = self.realized_pnl?;
+        if weighted_votes_for == 0 {
+            return None;
+        }
+        Some((pnl as i128 * weight as i128 / weighted_votes_for as i128) as i64)
+    }
+
+    /// Leaf committed to the `OutcomeArchive`: the keccak hash of the
+    /// outcome's Borsh encoding, which indexers rebuild from `OutcomeArchived`
+    pub fn leaf(&self) -> Result<[u8; 32]> {
+        Ok(keccak::hash(&self.try_to_vec()?).to_bytes())
+    }
+
+    /// Largest shortfall of a measured balance change against its expected
+    /// change, relative to the expected change
+    pub fn worst_shortfall_bps(&self) -> u64 {
+        self.balance_deltas
+            .iter()
+            .filter(|delta| delta.expected != 0)
+            .map(|delta| {
+                let shortfall = (delta.expected as i128 - delta.measured as i128).max(0);
+                (shortfall * BPS_DENOMINATOR as i128 / (delta.expected as i128).abs())
+                    .min(u64::MAX as i128) as u64
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Append-only keccak merkle tree over archived outcomes. Only the root and
+/// the rightmost filled node of each level are stored; leaves live in
+/// `OutcomeArchived` events.
+#[account]
+pub struct OutcomeArchive {
+    pub version: u8,
+    pub root: [u8; 32],
+    pub leaf_count: u64,
+    /// Left sibling at each level for the next append
+    pub frontier: [[u8; 32]; OUTCOME_TREE_DEPTH],
+    pub bump: u8,
+}
+
+impl OutcomeArchive {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // root
+        8 +   // leaf_count
+        32 * OUTCOME_TREE_DEPTH + // frontier
+        1;    // bump
+
+    /// Root of a tree with no leaves; missing leaves hash as zero
+    pub fn empty_root() -> [u8; 32] {
+        (0..OUTCOME_TREE_DEPTH).fold([0; 32], |zero, _| keccak::hashv(&[&zero, &zero]).to_bytes())
+    }
+
+    /// Append `leaf`, returning its index
+    pub fn append(&mut self, leaf: [u8; 32]) -> Result<u64> {
+        let index = self.leaf_count;
+        require!(index < 1 << OUTCOME_TREE_DEPTH, SwarmError::OutcomeArchiveFull);
+
+        let mut node = leaf;
+        let mut zero = [0; 32];
+        for level in 0..OUTCOME_TREE_DEPTH {
+            node = if (index >> level) & 1 == 0 {
+                self.frontier[level] = node;
+                keccak::hashv(&[&node, &zero]).to_bytes()
+            } else {
+                keccak::hashv(&[&self.frontier[level], &node]).to_bytes()
+            };
+            zero = keccak::hashv(&[&zero, &zero]).to_bytes();
+        }
+
+        self.root = node;
+        self.leaf_count = index + 1;
+        Ok(index)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct BalanceDelta {
+    pub token_account: Pubkey,
+    pub expected: i64,
+    pub measured: i64,
+}
+
+impl BalanceDelta {
+    pub const LEN: usize = 32 + // token_account
+        8 +   // expected
+        8;    // measured
+}
+
+/// Persisted record of a single agent's vote, settled once the outcome is known
+#[account]
+pub struct VoteRecord {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub vote: VoteType,
+    pub weight: u32,
+    pub voted_at: i64,
+    pub settled: bool,
+    pub reward_claimed: bool,
+    /// Keccak hash of the full reasoning, which `VoteCast` carries
+    pub reasoning_hash: [u8; 32],
+    /// Leading `MAX_REASONING_EXCERPT_LENGTH` bytes of the reasoning
+    pub reasoning_excerpt: String,
+    pub bump: u8,
+}
+
+impl VoteRecord {
+    /// v2 added the reasoning hash and excerpt
+    pub const VERSION: u8 = 2;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // proposal
+        32 +  // voter
+        1 +   // vote
+        4 +   // weight
+        8 +   // voted_at
+        1 +   // settled
+        1 +   // reward_claimed
+        32 +  // reasoning_hash
+        (4 + MAX_REASONING_EXCERPT_LENGTH) + // reasoning_excerpt
+        1;    // bump
+
+    /// Keep the hash of `reasoning` and as much of its start as fits the
+    /// excerpt, cut at a character boundary
+    pub fn set_reasoning(&mut self, reasoning: &str) {
+        self.reasoning_hash = keccak::hash(reasoning.as_bytes()).to_bytes();
+        let mut end = reasoning.len().min(MAX_REASONING_EXCERPT_LENGTH);
+        while !reasoning.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.reasoning_excerpt = reasoning[..end].to_string();
+    }
+
+    /// Performance score applied to the voter once the outcome is recorded.
+    /// Voters on the side the outcome vindicated gain reputation, the others
+    /// lose it; abstentions are left untouched.
+    pub fn is_correct(&self, success: bool) -> bool {
+        self.settlement_score(success) == Some(VOTE_SETTLEMENT_REWARD_SCORE)
+    }
+
+    pub fn settlement_score(&self, success: bool) -> Option<u16> {
+        match (&self.vote, success) {
+            (VoteType::Approve, true) | (VoteType::Reject, false) => Some(VOTE_SETTLEMENT_REWARD_SCORE),
+            (VoteType::Approve, false) | (VoteType::Reject, true) => Some(VOTE_SETTLEMENT_PENALTY_SCORE),
+            (VoteType::Abstain, _) => None,
+        }
+    }
+}
+
+/// Settled head-to-head result between two proposals competing for the same
+/// objective. Its existence prevents a pair from being scored twice.
+#[account]
+pub struct EloMatch {
+    pub version: u8,
+    pub winner: Pubkey,
+    pub loser: Pubkey,
+    pub rating_change: u16,
+    pub settled_at: i64,
+    pub bump: u8,
+}
+
+impl EloMatch {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // winner
+        32 +  // loser
+        2 +   // rating_change
+        8 +   // settled_at
+        1;    // bump
+}
+
+/// Expected score (per mille) of the higher-rated side, indexed by rating
+/// difference in steps of 50. Integer lookup instead of 1/(1+10^(-d/400)).
+const ELO_EXPECTED_PER_MILLE: [u32; 17] = [
+    500, 571, 640, 703, 760, 808, 849, 882, 909, 930, 947, 960, 969, 977, 983, 987, 990,
+];
+
+/// Expected score (per mille) for a player rated `rating` against `opponent`
+pub fn elo_expected_score(rating: u16, opponent: u16) -> u32 {
+    let diff = (rating as i32 - opponent as i32).unsigned_abs().min(800);
+    let index = (diff / 50) as usize;
+    let base = ELO_EXPECTED_PER_MILLE[index];
+    let expected = match ELO_EXPECTED_PER_MILLE.get(index + 1) {
+        // Linear interpolation between table steps
+        Some(next) => base + (next - base) * (diff % 50) / 50,
+        None => base,
+    };
+
+    if rating >= opponent { expected } else { 1000 - expected }
+}
+
+/// Rating points transferred from loser to winner
+pub fn elo_rating_change(winner_rating: u16, loser_rating: u16) -> u16 {
+    let expected = elo_expected_score(winner_rating, loser_rating);
+    (ELO_K_FACTOR as u32 * (1000 - expected) / 1000) as u16
+}
+
+/// Reputation staked by one agent behind another agent's proposal
+#[account]
+pub struct Endorsement {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub endorser: Pubkey,
+    pub stake: u16,
+    pub endorsed_at: i64,
+    pub settled: bool,
+    pub bump: u8,
+}
+
+impl Endorsement {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // proposal
+        32 +  // endorser
+        2 +   // stake
+        8 +   // endorsed_at
+        1 +   // settled
+        1;    // bump
+
+    /// Reputation returned to the endorser. `None` means the proposal never
+    /// ran (expired unexecuted), so the full stake is refunded.
+    pub fn payout(&self, success: Option<bool>) -> u16 {
+        let stake = self.stake as u32;
+        let payout = match success {
+            Some(true) => stake + stake * ENDORSEMENT_REWARD_BPS as u32 / BPS_DENOMINATOR as u32,
+            Some(false) => stake - stake * ENDORSEMENT_SLASH_BPS as u32 / BPS_DENOMINATOR as u32,
+            None => stake,
+        };
+        payout.min(MAX_REPUTATION as u32) as u16
+    }
+}
+
+/// Reward escrowed by `funder` for the agent that successfully executes
+/// `proposal`. `mint` is `None` for SOL bounties.
+#[account]
+pub struct Bounty {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub funder: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Bounty {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // proposal
+        32 +  // funder
+        (1 + 32) + // mint
+        8 +   // amount
+        8 +   // created_at
+        1;    // bump
+}
+
+/// Oracle feed pricing `mint`, registered by the authority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PriceFeed {
+    pub mint: Pubkey,
+    pub source: OracleSource,
+    pub decimals: u8,
+    /// Oldest accepted price, in seconds
+    pub max_age: i64,
+    /// Widest accepted confidence interval (Pyth) or standard deviation
+    /// (Switchboard) relative to the price; 0 skips the check
+    pub max_deviation_bps: u16,
+}
+
+impl PriceFeed {
+    pub const LEN: usize = 32 + // mint
+        OracleSource::LEN + // source
+        1 +   // decimals
+        8 +   // max_age
+        2;    // max_deviation_bps
+}
+
+/// Recurring rebalance approved by an executed `Rebalance` proposal.
+/// `trigger_rebalance` turns it into a fast-tracked `Rebalance` proposal
+/// each time `interval` elapses.
+#[account]
+pub struct RebalanceSchedule {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub allocations: Vec<TargetAllocation>,
+    /// Swap programs executors should prefer, most preferred first
+    pub venues: Vec<Pubkey>,
+    /// Seconds between rebalances; 0 when stopped
+    pub interval: i64,
+    pub next_run_at: i64,
+    /// Proposal that approved the schedule
+    pub source_proposal: Pubkey,
+    /// Proposal created by the latest trigger
+    pub last_proposal: Pubkey,
+    pub bump: u8,
+}
+
+impl RebalanceSchedule {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        (4 + MAX_REBALANCE_ALLOCATIONS * (32 + 2)) + // allocations
+        (4 + MAX_REBALANCE_VENUES * 32) + // venues
+        8 +   // interval
+        8 +   // next_run_at
+        32 +  // source_proposal
+        32 +  // last_proposal
+        1;    // bump
+
+    /// Adopt `allocations` on `terms`, first running one interval from now
+    pub fn set(
+        &mut self,
+        allocations: &[TargetAllocation],
+        terms: &RebalanceScheduleTerms,
+        source_proposal: Pubkey,
+        current_time: i64,
+    ) -> Result<()> {
+        self.allocations = allocations.to_vec();
+        self.venues = terms.venues.clone();
+        self.interval = terms.interval;
+        self.next_run_at = current_time
+            .checked_add(terms.interval)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        self.source_proposal = source_proposal;
+        Ok(())
+    }
+
+    pub fn is_due(&self, current_time: i64) -> bool {
+        self.interval > 0 && current_time >= self.next_run_at
+    }
+
+    /// Payload of the `Rebalance` proposal each trigger creates
+    pub fn payload(&self) -> RebalancePayload {
+        RebalancePayload { allocations: self.allocations.clone(), schedule: None, trades: Vec::new() }
+    }
+}
+
+/// Strategy parameters agents run with, replaced as a whole by executed
+/// `Strategy` proposals. `parameter_version` counts the sets adopted, so
+/// Learning agents can attribute outcomes to the set in force.
+#[account]
+pub struct StrategyConfig {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub parameter_version: u64,
+    pub allocations: Vec<TargetAllocation>,
+    /// Seconds of history signals are computed over
+    pub lookback_windows: Vec<i64>,
+    /// Signal levels, in the order the strategy defines
+    pub thresholds: Vec<i64>,
+    /// Proposal that adopted the current set
+    pub source_proposal: Pubkey,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl StrategyConfig {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        8 +   // parameter_version
+        (4 + MAX_REBALANCE_ALLOCATIONS * (32 + 2)) + // allocations
+        (4 + MAX_STRATEGY_LOOKBACKS * 8) + // lookback_windows
+        (4 + MAX_STRATEGY_THRESHOLDS * 8) + // thresholds
+        32 +  // source_proposal
+        8 +   // updated_at
+        1;    // bump
+
+    /// Adopt `parameters` as the next version
+    pub fn set(
+        &mut self,
+        parameters: StrategyParameters,
+        source_proposal: Pubkey,
+        current_time: i64,
+    ) -> Result<()> {
+        self.parameter_version = self
+            .parameter_version
+            .checked_add(1)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        self.allocations = parameters.allocations;
+        self.lookback_windows = parameters.lookback_windows;
+        self.thresholds = parameters.thresholds;
+        self.source_proposal = source_proposal;
+        self.updated_at = current_time;
+        Ok(())
+    }
+}
+
+/// A stop-loss or take-profit placed by an executed `Strategy` proposal.
+/// Anyone may fire it with `trigger_order` once its trigger is met, which
+/// runs the approved trade through the risk engine and closes the order.
+#[account]
+pub struct StandingOrder {
+    pub version: u8,
+    pub swarm: Pubkey,
+    /// `Strategy` proposal that placed the order, which seeds its address
+    pub proposal: Pubkey,
+    pub trigger: PriceCondition,
+    pub trade: TradePayload,
+    pub expires_at: i64,
+    /// Paid the order's rent and gets it back when the order closes
+    pub payer: Pubkey,
+    pub bump: u8,
+}
+
+impl StandingOrder {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        32 +  // proposal
+        (32 + 32 + 1 + 8 + 8) + // trigger
+        (32 + 32 + 8 + 8 + (1 + 4 + MAX_ORDER_ROUTE_DATA) + 8) + // trade
+        8 +   // expires_at
+        32 +  // payer
+        1;    // bump
+}
+
+/// A position built up in fixed legs, created by an executed `DcaSchedule`
+/// proposal. `execute_dca_leg` swaps one leg once `next_leg_at` passes and
+/// closes the schedule when the budget is spent.
+#[account]
+pub struct DcaSchedule {
+    pub version: u8,
+    pub swarm: Pubkey,
+    /// `DcaSchedule` proposal that created it, which seeds its address
+    pub proposal: Pubkey,
+    pub mint_in: Pubkey,
+    pub mint_out: Pubkey,
+    pub amount_per_leg: u64,
+    pub interval: i64,
+    pub total_budget: u64,
+    /// Of `mint_in`, across the legs executed so far
+    pub spent: u64,
+    /// Of `mint_out`, across the legs executed so far
+    pub received: u64,
+    pub max_slippage_bps: u16,
+    pub legs_executed: u32,
+    pub next_leg_at: i64,
+    /// Paid the schedule's rent and gets it back when the schedule closes
+    pub payer: Pubkey,
+    pub bump: u8,
+}
+
+impl DcaSchedule {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        32 +  // proposal
+        32 +  // mint_in
+        32 +  // mint_out
+        8 +   // amount_per_leg
+        8 +   // interval
+        8 +   // total_budget
+        8 +   // spent
+        8 +   // received
+        2 +   // max_slippage_bps
+        4 +   // legs_executed
+        8 +   // next_leg_at
+        32 +  // payer
+        1;    // bump
+
+    /// Size of the next leg: a full leg, or what is left of the budget
+    pub fn next_leg_amount(&self) -> u64 {
+        self.amount_per_leg.min(self.total_budget.saturating_sub(self.spent))
+    }
+
+    /// Count an executed leg and schedule the next one. Returns whether the
+    /// budget is spent.
+    pub fn record_leg(&mut self, spent: u64, received: u64, current_time: i64) -> Result<bool> {
+        self.spent = self.spent.checked_add(spent).ok_or(SwarmError::ArithmeticOverflow)?;
+        self.received = self.received.checked_add(received).ok_or(SwarmError::ArithmeticOverflow)?;
+        self.legs_executed = self.legs_executed.saturating_add(1);
+        self.next_leg_at = current_time
+            .checked_add(self.interval)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        Ok(self.next_leg_amount() == 0)
+    }
+}
+
+/// An opportunity spotted by an Arbitrage agent: buy on one venue, sell on
+/// another, along the route hashing to `route_hash`. An executed `Trade`
+/// following that route before `expires_at` credits the reporter with
+/// reputation and the swarm's `arb_report_bounty`, once.
+#[account]
+pub struct ArbReport {
+    pub version: u8,
+    pub swarm: Pubkey,
+    /// Owner of the reporting agent
+    pub reporter: Pubkey,
+    pub buy_venue: Pubkey,
+    pub sell_venue: Pubkey,
+    /// Expected profit of the route, relative to its input
+    pub expected_edge_bps: u16,
+    /// `TradePayload::route_hash` of the trade the report suggests
+    pub route_hash: [u8; 32],
+    pub reported_at: i64,
+    pub expires_at: i64,
+    /// Proposal whose execution followed the report, once one has
+    pub credited_proposal: Option<Pubkey>,
+    /// Paid the report's rent and gets it back when the report closes
+    pub payer: Pubkey,
+    pub bump: u8,
+}
+
+impl ArbReport {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        32 +  // reporter
+        32 +  // buy_venue
+        32 +  // sell_venue
+        2 +   // expected_edge_bps
+        32 +  // route_hash
+        8 +   // reported_at
+        8 +   // expires_at
+        (1 + 32) + // credited_proposal
+        32 +  // payer
+        1;    // bump
+
+    pub fn is_claimable(&self, route_hash: &[u8; 32], current_time: i64) -> bool {
+        self.credited_proposal.is_none()
+            && self.route_hash == *route_hash
+            && current_time <= self.expires_at
+    }
+}
+
+/// How the swarm harvests LP fees, set by `Harvest` proposals: the
+/// Whirlpool positions collected from, the base asset rewards are swapped
+/// into, and the limits under which `crank_harvest` may do so without a
+/// proposal
+#[account]
+pub struct HarvestConfig {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub base_mint: Pubkey,
+    pub positions: Vec<Pubkey>,
+    pub crank_threshold: u64,
+    pub crank_interval: i64,
+    pub max_slippage_bps: u16,
+    /// `Harvest` proposal that last set the terms
+    pub source_proposal: Pubkey,
+    pub harvests: u64,
+    pub last_harvest_at: i64,
+    pub bump: u8,
+}
+
+impl HarvestConfig {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        32 +  // base_mint
+        4 + 32 * MAX_HARVEST_POSITIONS + // positions
+        8 +   // crank_threshold
+        8 +   // crank_interval
+        2 +   // max_slippage_bps
+        32 +  // source_proposal
+        8 +   // harvests
+        8 +   // last_harvest_at
+        1;    // bump
+
+    pub fn set(&mut self, terms: HarvestTerms, proposal: Pubkey) {
+        self.base_mint = terms.base_mint;
+        self.positions = terms.positions;
+        self.crank_threshold = terms.crank_threshold;
+        self.crank_interval = terms.crank_interval;
+        self.max_slippage_bps = terms.max_slippage_bps;
+        self.source_proposal = proposal;
+    }
+
+    pub fn is_configured(&self) -> bool {
+        self.base_mint != Pubkey::default()
+    }
+
+    pub fn is_crank_due(&self, current_time: i64) -> bool {
+        current_time >= self.last_harvest_at.saturating_add(self.crank_interval)
+    }
+
+    /// The trade swapping `swap`'s rewards into the base asset
+    pub fn trade(&self, swap: HarvestSwap) -> Result<TradePayload> {
+        require!(swap.mint_in != self.base_mint, SwarmError::InvalidPayload);
+        Ok(TradePayload {
+            mint_in: swap.mint_in,
+            mint_out: self.base_mint,
+            amount: swap.amount,
+            min_out: swap.min_out,
+            route: swap.route,
+            tip_lamports: 0,
+        })
+    }
+
+    pub fn record_harvest(&mut self, current_time: i64) {
+        self.harvests = self.harvests.saturating_add(1);
+        self.last_harvest_at = current_time;
+    }
+}
+
+/// One mint of the treasury's portfolio
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Holding {
+    pub mint: Pubkey,
+    /// Vault token account balance when an execution last touched the mint
+    pub amount: u64,
+    /// Share of the portfolio the last rebalance targeted; 0 when untargeted
+    pub target_weight_bps: u16,
+    pub updated_at: i64,
+}
+
+impl Holding {
+    pub const LEN: usize = 32 + 8 + 2 + 8;
+}
+
+/// The treasury's holdings per mint and the weights it is rebalanced
+/// towards. Executions read balances back from the vault's token accounts
+/// after moving them, so each holding reflects the chain as of the last
+/// execution touching its mint. Mints neither held nor targeted drop out.
+#[account]
+pub struct Portfolio {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub holdings: Vec<Holding>,
+    /// Last proposal whose execution updated the portfolio
+    pub last_proposal: Pubkey,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl Portfolio {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        (4 + MAX_PORTFOLIO_HOLDINGS * Holding::LEN) + // holdings
+        32 +  // last_proposal
+        8 +   // updated_at
+        1;    // bump
+
+    pub fn holding(&self, mint: &Pubkey) -> Option<&Holding> {
+        self.holdings.iter().find(|holding| holding.mint == *mint)
+    }
+
+    fn holding_mut(&mut self, mint: Pubkey, current_time: i64) -> Result<&mut Holding> {
+        let index = match self.holdings.iter().position(|holding| holding.mint == mint) {
+            Some(index) => index,
+            None => {
+                require!(self.holdings.len() < MAX_PORTFOLIO_HOLDINGS, SwarmError::TooManyHoldings);
+                self.holdings.push(Holding {
+                    mint,
+                    amount: 0,
+                    target_weight_bps: 0,
+                    updated_at: current_time,
+                });
+                self.holdings.len() - 1
+            }
+        };
+        let holding = &mut self.holdings[index];
+        holding.updated_at = current_time;
+        Ok(holding)
+    }
+
+    /// Record the vault's balance of `mint` as read after an execution
+    pub fn record_balance(
+        &mut self,
+        mint: Pubkey,
+        amount: u64,
+        proposal: Pubkey,
+        current_time: i64,
+    ) -> Result<()> {
+        self.holding_mut(mint, current_time)?.amount = amount;
+        self.touch(proposal, current_time);
+        Ok(())
+    }
+
+    /// Replace every target weight with `allocations`
+    pub fn set_targets(
+        &mut self,
+        allocations: &[TargetAllocation],
+        proposal: Pubkey,
+        current_time: i64,
+    ) -> Result<()> {
+        for holding in &mut self.holdings {
+            holding.target_weight_bps = 0;
+        }
+        self.holdings.retain(|holding| holding.amount > 0);
+        for allocation in allocations {
+            self.holding_mut(allocation.mint, current_time)?.target_weight_bps =
+                allocation.weight_bps;
+        }
+        self.touch(proposal, current_time);
+        Ok(())
+    }
+
+    fn touch(&mut self, proposal: Pubkey, current_time: i64) {
+        self.holdings
+            .retain(|holding| holding.amount > 0 || holding.target_weight_bps > 0);
+        self.last_proposal = proposal;
+        self.updated_at = current_time;
+    }
+}
+
+/// A scheduled proposal waiting in the `ExecutionQueue`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct QueuedExecution {
+    pub proposal: Pubkey,
+    pub execute_not_before: i64,
+    /// Proposal expiry; the entry is dropped once it passes
+    pub expires_at: i64,
+}
+
+impl QueuedExecution {
+    pub const LEN: usize = 32 + 8 + 8;
+}
+
+/// Scheduled proposals of a swarm, polled by keepers that execute each one
+/// once it is due. Entries leave the queue when their proposal executes or
+/// expires.
+#[account]
+pub struct ExecutionQueue {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub entries: Vec<QueuedExecution>,
+    pub bump: u8,
+}
+
+impl ExecutionQueue {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        (4 + MAX_QUEUED_EXECUTIONS * QueuedExecution::LEN) + // entries
+        1;    // bump
+
+    /// Queue `entry`, replacing any earlier schedule for its proposal and
+    /// dropping entries whose proposals have expired
+    pub fn push(&mut self, entry: QueuedExecution, current_time: i64) -> Result<()> {
+        self.entries
+            .retain(|queued| queued.proposal != entry.proposal && queued.expires_at > current_time);
+        require!(self.entries.len() < MAX_QUEUED_EXECUTIONS, SwarmError::ExecutionQueueFull);
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, proposal: &Pubkey) {
+        self.entries.retain(|queued| queued.proposal != *proposal);
+    }
+
+    /// Entries that may execute at `current_time`
+    pub fn due(&self, current_time: i64) -> impl Iterator<Item = &QueuedExecution> {
+        self.entries.iter().filter(move |queued| {
+            queued.execute_not_before <= current_time && current_time < queued.expires_at
+        })
+    }
+}
+
+/// What a `Task` asks its claimant to do with `target`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TaskKind {
+    Analyze, // Produce a report on a market, protocol or account
+    Monitor, // Watch an account and flag changes
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TaskStatus {
+    Open,
+    Claimed,
+    Completed,
+}
+
+/// Unit of work a Consensus agent hands out to the swarm. Analytics and
+/// Execution agents claim it, submit a hash of their result, and earn
+/// `reputation_reward` once the creator accepts it.
+#[account]
+pub struct Task {
+    pub version: u8,
+    pub swarm: Pubkey,
+    /// Owner of the Consensus agent that created the task
+    pub creator: Pubkey,
+    pub task_id: u64,
+    pub kind: TaskKind,
+    pub target: Pubkey,
+    pub description: String,
+    pub reputation_reward: u16,
+    /// Seconds a claimant has to complete before others may claim
+    pub claim_timeout: i64,
+    pub status: TaskStatus,
+    /// Owner of the agent holding the claim
+    pub claimant: Option<Pubkey>,
+    pub claimed_at: i64,
+    /// Hash of the off-chain result, set on completion
+    pub result_hash: [u8; 32],
+    pub created_at: i64,
+    pub completed_at: i64,
+    pub bump: u8,
+}
+
+impl Task {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        32 +  // creator
+        8 +   // task_id
+        1 +   // kind
+        32 +  // target
+        (4 + MAX_DESCRIPTION_LENGTH) + // description
+        2 +   // reputation_reward
+        8 +   // claim_timeout
+        1 +   // status
+        (1 + 32) + // claimant
+        8 +   // claimed_at
+        32 +  // result_hash
+        8 +   // created_at
+        8 +   // completed_at
+        1;    // bump
+
+    /// Whether `claim_task` may (re)assign the task at `current_time`:
+    /// open, or claimed by an agent that let its claim lapse
+    pub fn is_claimable(&self, current_time: i64) -> bool {
+        match self.status {
+            TaskStatus::Open => true,
+            TaskStatus::Claimed => current_time >= self.claimed_at.saturating_add(self.claim_timeout),
+            TaskStatus::Completed => false,
+        }
+    }
+}
+
+/// Internal rules a coalition sets for itself
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CoalitionRules {
+    /// Agents may join without the founder's signature
+    pub open_membership: bool,
+    /// Share of members that must agree internally before the coalition
+    /// votes as a bloc; above half, so only one position can reach it
+    pub bloc_threshold_bps: u16,
+    /// Share of members that must sign a withdrawal from the coalition's
+    /// treasury
+    pub treasury_threshold_bps: u16,
+}
+
+impl CoalitionRules {
+    pub const LEN: usize = 1 + // open_membership
+        2 +   // bloc_threshold_bps
+        2;    // treasury_threshold_bps
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.bloc_threshold_bps > BPS_DENOMINATOR / 2
+                && self.bloc_threshold_bps <= BPS_DENOMINATOR
+                && self.treasury_threshold_bps > 0
+                && self.treasury_threshold_bps <= BPS_DENOMINATOR,
+            SwarmError::InvalidConfig
+        );
+        Ok(())
+    }
+}
+
+/// Persistent bloc of agents formed by a Governance agent. The account
+/// itself holds the coalition's shared SOL treasury.
+#[account]
+pub struct Coalition {
+    pub version: u8,
+    pub swarm: Pubkey,
+    /// Owner of the Governance agent that formed the coalition
+    pub founder: Pubkey,
+    pub coalition_id: u64,
+    pub name: String,
+    /// Owners of the member agents
+    pub members: Vec<Pubkey>,
+    pub rules: CoalitionRules,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Coalition {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        32 +  // founder
+        8 +   // coalition_id
+        (4 + MAX_AGENT_NAME_LENGTH) + // name
+        (4 + MAX_COALITION_MEMBERS * 32) + // members
+        CoalitionRules::LEN + // rules
+        8 +   // created_at
+        1;    // bump
+
+    pub fn is_member(&self, owner: &Pubkey) -> bool {
+        self.members.contains(owner)
+    }
+
+    /// Whether `count` members make up at least `threshold_bps` of the coalition
+    pub fn meets_threshold(&self, count: usize, threshold_bps: u16) -> bool {
+        count as u64 * BPS_DENOMINATOR as u64 >= threshold_bps as u64 * self.members.len() as u64
+    }
+}
+
+/// Members' internal votes on one swarm proposal. Once a position reaches
+/// the coalition's bloc threshold, `cast_coalition_vote` casts it for every
+/// member that took part.
+#[account]
+pub struct CoalitionBallot {
+    pub version: u8,
+    pub coalition: Pubkey,
+    pub proposal: Pubkey,
+    /// Members that voted internally
+    pub voters: Vec<Pubkey>,
+    pub approvals: u8,
+    pub rejections: u8,
+    pub abstentions: u8,
+    /// The bloc vote has been cast into the proposal
+    pub cast: bool,
+    pub bump: u8,
+}
+
+impl CoalitionBallot {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // coalition
+        32 +  // proposal
+        (4 + MAX_COALITION_MEMBERS * 32) + // voters
+        1 +   // approvals
+        1 +   // rejections
+        1 +   // abstentions
+        1 +   // cast
+        1;    // bump
+
+    pub fn record(&mut self, member: Pubkey, vote: &VoteType) {
+        self.voters.push(member);
+        match vote {
+            VoteType::Approve => self.approvals += 1,
+            VoteType::Reject => self.rejections += 1,
+            VoteType::Abstain => self.abstentions += 1,
+        }
+    }
+
+    /// Position that reached the bloc threshold, if any
+    pub fn position(&self, coalition: &Coalition) -> Option<VoteType> {
+        let threshold = coalition.rules.bloc_threshold_bps;
+        if coalition.meets_threshold(self.approvals as usize, threshold) {
+            Some(VoteType::Approve)
+        } else if coalition.meets_threshold(self.rejections as usize, threshold) {
+            Some(VoteType::Reject)
+        } else {
+            None
+        }
+    }
+}
+
+
+/// Sub-group of the swarm that alone votes on, and proposes, the proposal
+/// types it covers, with its own quorum. Set up by the authority; clearing
+/// its proposal types retires it without stranding proposals still open.
+#[account]
+pub struct Committee {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub committee_id: u8,
+    pub name: String,
+    /// Agent types that sit on the committee
+    pub agent_types: Vec<AgentType>,
+    /// Bit `ProposalType::index()` set for each type the committee handles
+    pub proposal_types: u16,
+    /// Votes a committee proposal needs, in place of the swarm's quorum
+    pub min_votes: u8,
+    pub bump: u8,
+}
+
+impl Committee {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        1 +   // committee_id
+        (4 + MAX_AGENT_TYPE_NAME_LENGTH) + // name
+        (4 + MAX_COMMITTEE_AGENT_TYPES * AgentType::LEN) + // agent_types
+        2 +   // proposal_types
+        1 +   // min_votes
+        1;    // bump
+
+    pub fn covers(&self, proposal_type: &ProposalType) -> bool {
+        self.proposal_types & (1 << proposal_type.index()) != 0
+    }
+
+    pub fn admits(&self, agent_type: AgentType) -> bool {
+        self.agent_types.contains(&agent_type)
+    }
+
+    /// The committee `proposal` was scoped to, which must be `committee`
+    pub fn for_proposal<'a>(proposal: &Proposal, committee: Option<&'a Committee>) -> Result<Option<&'a Committee>> {
+        let Some(committee_id) = proposal.committee_id() else {
+            return Ok(None);
+        };
+        let committee = committee
+            .filter(|committee| committee.swarm == proposal.swarm && committee.committee_id == committee_id)
+            .ok_or(SwarmError::MissingCommittee)?;
+        Ok(Some(committee))
+    }
+}
+
+/// Running price integral as of one oracle observation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TwapObservation {
+    /// Oracle publish time
+    pub timestamp: i64,
+    /// Sum of each earlier price times the seconds it stood
+    pub cumulative_price: u128,
+}
+
+impl TwapObservation {
+    pub const LEN: usize = 8 + 16;
+}
+
+/// Privileged action recorded in the `AuditLog`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AuditAction {
+    SwarmConfigUpdated,   // Authority changed swarm parameters
+    CommitteeConfigured,  // Authority created or reconfigured a committee
+    AuthorityTransferred, // Executed `AuthorityHandover` proposal
+    RiskLimitChanged,     // Executed `RiskLimit` proposal
+    AgentSlashed,         // Bond slashed by an executed `Emergency` proposal
+    TradingResumed,       // Circuit breaker pause lifted by an `Emergency` proposal
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AuditEntry {
+    /// Signer of the action, or the executed proposal for voted actions
+    pub actor: Pubkey,
+    pub action: AuditAction,
+    /// Account the action changed or the key it installed
+    pub target: Pubkey,
+    pub timestamp: i64,
+}
+
+impl AuditEntry {
+    pub const LEN: usize = 32 + 1 + 32 + 8;
+}
+
+/// Recent privileged actions of a swarm, so governance history can be read
+/// back without an indexer
+#[account]
+pub struct AuditLog {
+    pub version: u8,
+    pub swarm: Pubkey,
+    /// Ring of recent entries; the oldest is overwritten once full
+    pub entries: Vec<AuditEntry>,
+    /// Index the next entry is written to
+    pub next_slot: u8,
+    /// Entries ever recorded, including overwritten ones
+    pub total_entries: u64,
+    pub bump: u8,
+}
+
+impl AuditLog {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        (4 + MAX_AUDIT_ENTRIES * AuditEntry::LEN) + // entries
+        1 +   // next_slot
+        8 +   // total_entries
+        1;    // bump
+
+    pub fn record(&mut self, actor: Pubkey, action: AuditAction, target: Pubkey, timestamp: i64) {
+        let entry = AuditEntry { actor, action, target, timestamp };
+        match self.entries.get_mut(self.next_slot as usize) {
+            Some(slot) => *slot = entry,
+            None => self.entries.push(entry),
+        }
+        self.next_slot = ((self.next_slot as usize + 1) % MAX_AUDIT_ENTRIES) as u8;
+        self.total_entries = self.total_entries.saturating_add(1);
+    }
+
+    /// Entries from oldest to newest
+    pub fn chronological(&self) -> impl Iterator<Item = &AuditEntry> {
+        let (newer, older) = self.entries.split_at(self.next_slot as usize % self.entries.len().max(1));
+        older.iter().chain(newer)
+    }
+}
+
+/// Time-weighted average price of a mint, built from oracle prices agents
+/// crank in. A price only gains weight for the time it stands before the
+/// next observation, so one manipulated print barely moves the average.
+#[account]
+pub struct TwapTracker {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub mint: Pubkey,
+    /// Latest observed price, scaled by `TWAP_PRICE_EXPONENT`
+    pub last_price: i64,
+    /// Oracle publish time of `last_price`
+    pub last_observed_at: i64,
+    /// Ring of recent observations; the oldest is overwritten once full
+    pub observations: Vec<TwapObservation>,
+    /// Index the next observation is written to
+    pub next_slot: u8,
+    pub bump: u8,
+}
+
+impl TwapTracker {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        32 +  // mint
+        8 +   // last_price
+        8 +   // last_observed_at
+        (4 + MAX_TWAP_OBSERVATIONS * TwapObservation::LEN) + // observations
+        1 +   // next_slot
+        1;    // bump
+
+    fn latest(&self) -> Option<&TwapObservation> {
+        let slot = (self.next_slot as usize + MAX_TWAP_OBSERVATIONS - 1) % MAX_TWAP_OBSERVATIONS;
+        self.observations.get(slot)
+    }
+
+    /// Record `price` as published at `published_at`, which must be newer
+    /// than the last observation
+    pub fn observe(&mut self, price: i64, published_at: i64) -> Result<()> {
+        require!(price > 0, SwarmError::InvalidPriceFeed);
+        let cumulative_price = match self.latest() {
+            Some(latest) => {
+                require!(published_at > self.last_observed_at, SwarmError::StalePrice);
+                let elapsed = (published_at - self.last_observed_at) as u128;
+                latest
+                    .cumulative_price
+                    .checked_add(self.last_price as u128 * elapsed)
+                    .ok_or(SwarmError::ArithmeticOverflow)?
+            }
+            None => 0,
+        };
+
+        let observation = TwapObservation { timestamp: published_at, cumulative_price };
+        match self.observations.get_mut(self.next_slot as usize) {
+            Some(slot) => *slot = observation,
+            None => self.observations.push(observation),
+        }
+        self.next_slot = ((self.next_slot as usize + 1) % MAX_TWAP_OBSERVATIONS) as u8;
+        self.last_price = price;
+        self.last_observed_at = published_at;
+        Ok(())
+    }
+
+    /// Average price over at least the `window` seconds before the last
+    /// observation. The last price itself has no weight yet.
+    pub fn twap(&self, window: i64) -> Result<i64> {
+        let latest = self.latest().ok_or(SwarmError::InsufficientTwapHistory)?;
+        let window_start = latest.timestamp.saturating_sub(window.max(1));
+        let earliest = self
+            .observations
+            .iter()
+            .filter(|observation| observation.timestamp <= window_start)
+            .max_by_key(|observation| observation.timestamp)
+            .ok_or(SwarmError::InsufficientTwapHistory)?;
+
+        let elapsed = (latest.timestamp - earliest.timestamp) as u128;
+        let average = (latest.cumulative_price - earliest.cumulative_price) / elapsed;
+        i64::try_from(average).map_err(|_| error!(SwarmError::ArithmeticOverflow))
+    }
+}
+
+/// Trades are rejected when either side's oracle price strays further than
+/// `max_deviation_bps` from its `window`-second TWAP
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TwapGuard {
+    pub window: i64,
+    pub max_deviation_bps: u16,
+}
+
+impl TwapGuard {
+    pub const LEN: usize = 8 + 2;
+}
+
+/// Trade and rebalance execution pauses once the marked portfolio value
+/// falls more than `max_drawdown_bps` below its highest mark of the last
+/// `window` seconds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CircuitBreaker {
+    pub window: i64,
+    pub max_drawdown_bps: u16,
+}
+
+impl CircuitBreaker {
+    pub const LEN: usize = 8 + 2;
+}
+
+/// Oracle value of the vault's holdings, in quote units scaled by
+/// 10^-`TWAP_PRICE_EXPONENT`, at `marked_at`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ValueMark {
+    pub value: u64,
+    pub marked_at: i64,
+}
+
+impl ValueMark {
+    pub const LEN: usize = 8 + 8;
+}
+
+/// Cap on the summed size of approved-but-unexecuted trades selling `mint`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ExposureLimit {
+    pub mint: Pubkey,
+    pub max_amount: u64,
+}
+
+/// Cap on the absolute size of the vault's position in one Drift perp market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PerpLimit {
+    pub market_index: u16,
+    pub max_base_amount: u64,
+}
+
+/// An approved Trade proposal whose size is reserved until it executes or expires
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PendingTrade {
+    pub proposal: Pubkey,
+    pub mint_in: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+}
+
+/// Exposure of approved trades that have not executed yet, so trades that are
+/// individually within limits cannot jointly breach them
+#[account]
+pub struct PendingExposure {
+    pub version: u8,
+    pub limits: Vec<ExposureLimit>,
+    pub pending: Vec<PendingTrade>,
+    pub perp_limits: Vec<PerpLimit>,
+    pub bump: u8,
+}
+
+impl PendingExposure {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        (4 + MAX_EXPOSURE_LIMITS * (32 + 8)) + // limits
+        (4 + MAX_PENDING_TRADES * (32 + 32 + 8 + 8)) + // pending
+        (4 + MAX_PERP_LIMITS * (2 + 8)) + // perp_limits
+        1;    // bump
+
+    pub fn limit(&self, mint: &Pubkey) -> Option<u64> {
+        self.limits
+            .iter()
+            .find(|limit| limit.mint == *mint)
+            .map(|limit| limit.max_amount)
+    }
+
+    /// Summed size of pending trades selling `mint`
+    pub fn exposure(&self, mint: &Pubkey) -> u64 {
+        self.pending
+            .iter()
+            .filter(|trade| trade.mint_in == *mint)
+            .fold(0u64, |total, trade| total.saturating_add(trade.amount))
+    }
+
+    /// Set the limit for `mint`, or remove it when `max_amount` is `None`
+    pub fn set_limit(&mut self, mint: Pubkey, max_amount: Option<u64>) -> Result<()> {
+        self.limits.retain(|limit| limit.mint != mint);
+        if let Some(max_amount) = max_amount {
+            require!(self.limits.len() < MAX_EXPOSURE_LIMITS, SwarmError::InvalidConfig);
+            self.limits.push(ExposureLimit { mint, max_amount });
+        }
+        Ok(())
+    }
+
+    pub fn perp_limit(&self, market_index: u16) -> Option<u64> {
+        self.perp_limits
+            .iter()
+            .find(|limit| limit.market_index == market_index)
+            .map(|limit| limit.max_base_amount)
+    }
+
+    /// Set the position limit for a perp market, or remove it when
+    /// `max_base_amount` is `None`
+    pub fn set_perp_limit(&mut self, market_index: u16, max_base_amount: Option<u64>) -> Result<()> {
+        self.perp_limits.retain(|limit| limit.market_index != market_index);
+        if let Some(max_base_amount) = max_base_amount {
+            require!(self.perp_limits.len() < MAX_PERP_LIMITS, SwarmError::InvalidConfig);
+            self.perp_limits.push(PerpLimit { market_index, max_base_amount });
+        }
+        Ok(())
+    }
+
+    pub fn is_reserved(&self, proposal: &Pubkey) -> bool {
+        self.pending.iter().any(|trade| trade.proposal == *proposal)
+    }
+
+    /// Drop reservations whose proposals can no longer execute
+    pub fn prune_expired(&mut self, current_time: i64) {
+        self.pending.retain(|trade| current_time <= trade.expires_at);
+    }
+
+    /// Reserve `payload.amount` for `proposal` if the mint's aggregate
+    /// exposure stays within its limit
+    pub fn reserve(
+        &mut self,
+        proposal: Pubkey,
+        payload: &TradePayload,
+        expires_at: i64,
+        current_time: i64,
+    ) -> Result<()> {
+        self.prune_expired(current_time);
+
+        require!(!self.is_reserved(&proposal), SwarmError::ExposureAlreadyReserved);
+        require!(
+            self.pending.len() < MAX_PENDING_TRADES,
+            SwarmError::ExposureLimitExceeded
+        );
+
+        if let Some(max_amount) = self.limit(&payload.mint_in) {
+            let exposure = self
+                .exposure(&payload.mint_in)
+                .checked_add(payload.amount)
+                .ok_or(SwarmError::ArithmeticOverflow)?;
+            require!(exposure <= max_amount, SwarmError::ExposureLimitExceeded);
+        }
+
+        self.pending.push(PendingTrade {
+            proposal,
+            mint_in: payload.mint_in,
+            amount: payload.amount,
+            expires_at,
+        });
+        Ok(())
+    }
+
+    /// Release the reservation for an executing trade. Mints without a
+    /// limit never need one.
+    pub fn release(&mut self, proposal: &Pubkey, payload: &TradePayload) -> Result<()> {
+        if self.limit(&payload.mint_in).is_some() {
+            require!(self.is_reserved(proposal), SwarmError::ExposureNotReserved);
+        }
+        self.pending.retain(|trade| trade.proposal != *proposal);
+        Ok(())
+    }
+}
+
+/// Programs `Strategy` proposals may call, changed only by executed
+/// `RiskLimit` proposals
+#[account]
+pub struct ProgramWhitelist {
+    pub version: u8,
+    pub programs: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl ProgramWhitelist {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        (4 + 32 * MAX_ALLOWED_PROGRAMS) + // programs
+        1;    // bump
+
+    pub fn contains(&self, program: &Pubkey) -> bool {
+        self.programs.contains(program)
+    }
+
+    pub fn set(&mut self, program: Pubkey, allowed: bool) -> Result<()> {
+        self.programs.retain(|allowed| *allowed != program);
+        if allowed {
+            require!(program != crate::ID, SwarmError::InvalidConfig);
+            require!(self.programs.len() < MAX_ALLOWED_PROGRAMS, SwarmError::InvalidConfig);
+            self.programs.push(program);
+        }
+        Ok(())
+    }
+}
+
+/// Trading limits changed only by executed `RiskLimit` proposals and
+/// checked by the `risk` engine before every structured `Trade` and
+/// `Rebalance`. Sizes are in base units of the token sold.
+#[account]
+pub struct RiskConfig {
+    pub version: u8,
+    /// Largest single trade
+    pub max_position_size: Option<u64>,
+    /// Largest total traded within `RISK_VOLUME_WINDOW`
+    pub max_daily_volume: Option<u64>,
+    /// Largest discount to the oracle price a trade's `min_out` may accept;
+    /// `None` skips the oracle check
+    pub max_slippage_bps: Option<u16>,
+    /// Mints trades and rebalances may touch; empty allows any
+    pub allowed_mints: Vec<Pubkey>,
+    /// Largest Jito tip, in lamports, one execution may pay
+    pub max_tip_lamports: u64,
+    pub volume_window_start: i64,
+    pub daily_volume: u64,
+    /// Spot-against-TWAP check on both sides of a trade; `None` skips it
+    pub twap_guard: Option<TwapGuard>,
+    /// Largest target weight one mint may have in a rebalance
+    pub max_concentration_bps: Option<u16>,
+    /// Volume each capped swap venue may carry within `RISK_VOLUME_WINDOW`
+    pub venue_caps: Vec<VenueCap>,
+    /// Largest fall of `drawdown` from its peak a trade may cause, in
+    /// oracle value; trades then need both oracle prices
+    pub max_drawdown: Option<u64>,
+    pub drawdown: DrawdownTracker,
+    /// Drawdown of the marked portfolio value that pauses trading; `None`
+    /// disables the breaker
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// Value marks inside the breaker's window, oldest first
+    pub value_marks: Vec<ValueMark>,
+    /// Set when the breaker trips; only an `Emergency` proposal passed by
+    /// supermajority clears it
+    pub trading_paused: bool,
+    pub bump: u8,
+}
+
+impl RiskConfig {
+    /// v2 added `twap_guard`; v3 added the concentration, venue and
+    /// drawdown limits; v4 added the circuit breaker
+    pub const VERSION: u8 = 4;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        (1 + 8) + // max_position_size
+        (1 + 8) + // max_daily_volume
+        (1 + 2) + // max_slippage_bps
+        (4 + 32 * MAX_ALLOWED_MINTS) + // allowed_mints
+        8 +   // max_tip_lamports
+        8 +   // volume_window_start
+        8 +   // daily_volume
+        (1 + TwapGuard::LEN) + // twap_guard
+        (1 + 2) + // max_concentration_bps
+        (4 + MAX_VENUE_CAPS * VenueCap::LEN) + // venue_caps
+        (1 + 8) + // max_drawdown
+        DrawdownTracker::LEN + // drawdown
+        (1 + CircuitBreaker::LEN) + // circuit_breaker
+        (4 + MAX_VALUE_MARKS * ValueMark::LEN) + // value_marks
+        1 +   // trading_paused
+        1;    // bump
+}
+
+/// Volume cap on one swap program, counted in the same window as
+/// `RiskConfig::daily_volume`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct VenueCap {
+    pub program: Pubkey,
+    pub max_volume: u64,
+    pub volume: u64,
+}
+
+impl VenueCap {
+    pub const LEN: usize = 32 + 8 + 8;
+}
+
+/// Running oracle value gained or lost by the swarm's trades, in quote
+/// units scaled by 10^-`TWAP_PRICE_EXPONENT`, and the highest it has been
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct DrawdownTracker {
+    pub cumulative_pnl: i64,
+    pub peak_pnl: i64,
+}
+
+impl DrawdownTracker {
+    pub const LEN: usize = 8 + 8;
+}
+
+/// SOL bonded by an agent owner, held on this account and slashable by
+/// executed `Emergency` proposals
+#[account]
+pub struct AgentBond {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_slashed: u64,
+    pub unbonding_at: i64,
+    pub bump: u8,
+}
+
+impl AgentBond {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        32 +  // owner
+        8 +   // amount
+        8 +   // total_slashed
+        8 +   // unbonding_at
+        1;    // bump
+
+    pub fn is_withdrawable(&self, current_time: i64) -> bool {
+        self.unbonding_at != 0
+            && current_time >= self.unbonding_at.saturating_add(BOND_UNBONDING_PERIOD)
+    }
+
+    /// Remove `slash_bps` of the bond, returning the `(to_treasury, burned)`
+    /// split given the swarm's `treasury_bps` share
+    pub fn slash(&mut self, slash_bps: u16, treasury_bps: u16) -> Result<(u64, u64)> {
+        require!(
+            slash_bps > 0 && slash_bps <= BPS_DENOMINATOR,
+            SwarmError::InvalidPayload
+        );
+
+        let slashed = (self.amount as u128 * slash_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        let to_treasury = (slashed as u128 * treasury_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+
+        self.amount = self
+            .amount
+            .checked_sub(slashed)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        self.total_slashed = self
+            .total_slashed
+            .checked_add(slashed)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+
+        Ok((to_treasury, slashed - to_treasury))
+    }
+}
+
+/// History entry for a slash, one per executed proposal
+#[account]
+pub struct SlashRecord {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub agent: Pubkey,
+    pub to_treasury: u64,
+    pub burned: u64,
+    pub slashed_at: i64,
+    pub bump: u8,
+}
+
+impl SlashRecord {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // proposal
+        32 +  // agent
+        8 +   // to_treasury
+        8 +   // burned
+        8 +   // slashed_at
+        1;    // bump
+}
+
+/// The swarm's Wormhole emitter: tracks the sequence numbers the core bridge
+/// assigned to messages the swarm PDA posted
+#[account]
+pub struct CrossChainEmitter {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub messages_posted: u64,
+    pub last_sequence: u64,
+    pub last_proposal: Pubkey,
+    pub last_posted_at: i64,
+    pub bump: u8,
+}
+
+impl CrossChainEmitter {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        8 +   // messages_posted
+        8 +   // last_sequence
+        32 +  // last_proposal
+        8 +   // last_posted_at
+        1;    // bump
+}
+
+/// Governance tokens locked by an agent owner for a vote weight boost
+#[account]
+pub struct VoteEscrow {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub locked_at: i64,
+    pub lock_end: i64,
+    pub bump: u8,
+}
+
+impl VoteEscrow {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        32 +  // owner
+        32 +  // mint
+        8 +   // amount
+        8 +   // locked_at
+        8 +   // lock_end
+        1;    // bump
+
+    /// Locked amount scaled by the fraction of the maximum lock remaining
+    pub fn voting_power(&self, current_time: i64) -> u64 {
+        let remaining = self.lock_end.saturating_sub(current_time).clamp(0, MAX_VOTE_ESCROW_LOCK);
+        (self.amount as u128 * remaining as u128 / MAX_VOTE_ESCROW_LOCK as u128) as u64
+    }
+
+    /// Extra vote weight in basis points, reaching `VE_MAX_BOOST_BPS` at
+    /// `max_boost_power`
+    pub fn boost_bps(&self, max_boost_power: u64, current_time: i64) -> u16 {
+        if max_boost_power == 0 {
+            return 0;
+        }
+        let power = self.voting_power(current_time).min(max_boost_power);
+        (power as u128 * VE_MAX_BOOST_BPS as u128 / max_boost_power as u128) as u16
+    }
+}
+
+/// Swarm treasury. Holds SOL that can only leave through executed proposals.
+///
+/// v2 added `fees_collected`.
+#[account]
+pub struct Treasury {
+    pub version: u8,
+    pub swarm: Pubkey,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    /// Protocol fees received in SOL, of which the `InsuranceFund` takes
+    /// its share
+    pub fees_collected: u64,
+    pub bump: u8,
+}
+
+impl Treasury {
+    pub const VERSION: u8 = 2;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        8 +   // total_deposited
+        8 +   // total_withdrawn
+        8 +   // fees_collected
+        1;    // bump
+
+    /// Count `fee` lamports received as a protocol fee
+    pub fn record_fee(&mut self, fee: u64) -> Result<()> {
+        self.total_deposited = self
+            .total_deposited
+            .checked_add(fee)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        self.fees_collected = self
+            .fees_collected
+            .checked_add(fee)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+/// Lamports set aside from protocol fees to reimburse the treasury for
+/// strategies that lost funds to a protocol failure. Its terms and claims
+/// are set and paid by executed `Insurance` proposals; `fund_insurance`
+/// sweeps its share of new fees in.
+#[account]
+pub struct InsuranceFund {
+    pub version: u8,
+    pub swarm: Pubkey,
+    /// Share of the treasury's protocol fees swept into the fund
+    pub fee_share_bps: u16,
+    /// Smallest shortfall against its balance checks a claimed `Strategy`
+    /// outcome must show
+    pub loss_threshold_bps: u16,
+    /// `Treasury::fees_collected` already swept
+    pub fees_swept: u64,
+    pub total_premiums: u64,
+    pub total_claims_paid: u64,
+    /// Claims whose losses are still inside `INSURANCE_CLAIM_WINDOW`, so
+    /// none is paid twice
+    pub recent_claims: Vec<InsuranceClaimRecord>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InsuranceClaimRecord {
+    /// Executed `Strategy` proposal whose loss was reimbursed
+    pub loss_proposal: Pubkey,
+    pub loss_executed_at: i64,
+    pub lamports: u64,
+}
+
+impl InsuranceClaimRecord {
+    pub const LEN: usize = 32 + // loss_proposal
+        8 +   // loss_executed_at
+        8;    // lamports
+}
+
+impl InsuranceFund {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        32 +  // swarm
+        2 +   // fee_share_bps
+        2 +   // loss_threshold_bps
+        8 +   // fees_swept
+        8 +   // total_premiums
+        8 +   // total_claims_paid
+        (4 + MAX_RECENT_INSURANCE_CLAIMS * InsuranceClaimRecord::LEN) + // recent_claims
+        1;    // bump
+
+    /// Take the fund's share of fees collected since the last sweep
+    pub fn sweep_premium(&mut self, fees_collected: u64) -> Result<u64> {
+        let new_fees = fees_collected.saturating_sub(self.fees_swept);
+        let premium = (new_fees as u128 * self.fee_share_bps as u128
+            / BPS_DENOMINATOR as u128) as u64;
+        self.fees_swept = fees_collected;
+        self.total_premiums = self
+            .total_premiums
+            .checked_add(premium)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        Ok(premium)
+    }
+
+    /// Record a claim on the loss `record` describes, forgetting claims that
+    /// left the claim window. A loss outside the window, or already paid,
+    /// cannot be claimed.
+    pub fn record_claim(&mut self, record: InsuranceClaimRecord, current_time: i64) -> Result<()> {
+        let window_start = current_time.saturating_sub(INSURANCE_CLAIM_WINDOW);
+        require!(
+            record.loss_executed_at >= window_start,
+            SwarmError::InsuranceClaimNotEligible
+        );
+        self.recent_claims.retain(|claim| claim.loss_executed_at >= window_start);
+        require!(
+            !self
+                .recent_claims
+                .iter()
+                .any(|claim| claim.loss_proposal == record.loss_proposal),
+            SwarmError::InsuranceClaimNotEligible
+        );
+        require!(
+            self.recent_claims.len() < MAX_RECENT_INSURANCE_CLAIMS,
+            SwarmError::TooManyInsuranceClaims
+        );
+        self.recent_claims.push(record);
+        self.total_claims_paid = self
+            .total_claims_paid
+            .checked_add(record.lamports)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+/// Per-epoch treasury spend caps, indexed by `ProposalType::index()`.
+/// Amounts are in base units of the asset moved (lamports for SOL).
+#[account]
+pub struct SpendTracker {
+    pub version: u8,
+    pub caps: [u64; MAX_PROPOSAL_TYPES],
+    pub spent: [u64; MAX_PROPOSAL_TYPES],
+    pub epoch_start: i64,
+    pub epoch_length: i64,
+    pub bump: u8,
+}
+
+impl SpendTracker {
+    pub const VERSION: u8 = 1;
+    pub const LEN: usize = 8 + // discriminator
+        1 +   // version
+        8 * MAX_PROPOSAL_TYPES + // caps
+        8 * MAX_PROPOSAL_TYPES + // spent
+        8 +   // epoch_start
+        8 +   // epoch_length
+        1;    // bump
+
+    /// Reset spending if `current_time` has moved past the current epoch
+    pub fn roll_epoch(&mut self, current_time: i64) {
+        let elapsed = current_time.saturating_sub(self.epoch_start);
+        if elapsed >= self.epoch_length {
+            self.epoch_start = current_time - elapsed % self.epoch_length;
+            self.spent = [0; MAX_PROPOSAL_TYPES];
+        }
+    }
+
+    /// Count `amount` against the cap for `proposal_type`
+    pub fn record_spend(
+        &mut self,
+        proposal_type: &ProposalType,
+        amount: u64,
+        current_time: i64,
+    ) -> Result<()> {
+        self.roll_epoch(current_time);
+
+        let index = proposal_type.index();
+        let spent = self.spent[index]
+            .checked_add(amount)
+            .ok_or(SwarmError::ArithmeticOverflow)?;
+        require!(spent <= self.caps[index], SwarmError::SpendCapExceeded);
+
+        self.spent[index] = spent;
+        Ok(())
+    }
+}
@@ -24,21 +24,80 @@ mod tests {
         PROGRAM_ID_BYTES.parse().unwrap()
     }
 
-    fn find_swarm_pda() -> (Pubkey, u8) {
-        Pubkey::find_program_address(&[b"swarm"], &program_id())
+    fn find_swarm_pda(swarm_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"swarm", &swarm_id.to_le_bytes()], &program_id())
     }
 
-    fn find_agent_pda(owner: &Pubkey) -> (Pubkey, u8) {
-        Pubkey::find_program_address(&[b"agent", owner.as_ref()], &program_id())
+    fn find_agent_pda(swarm: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"agent", swarm.as_ref(), owner.as_ref()], &program_id())
     }
 
-    fn find_proposal_pda(proposal_id: u64) -> (Pubkey, u8) {
+    fn find_proposal_pda(swarm: &Pubkey, proposal_id: u64) -> (Pubkey, u8) {
         Pubkey::find_program_address(
-            &[b"proposal", &proposal_id.to_le_bytes()],
+            &[b"proposal", swarm.as_ref(), &proposal_id.to_le_bytes()],
             &program_id(),
         )
     }
 
+    /// Risk config with every limit unset
+    fn risk_config() -> agent_swarm::state::RiskConfig {
+        use agent_swarm::state::{DrawdownTracker, RiskConfig};
+
+        RiskConfig {
+            version: RiskConfig::VERSION,
+            max_position_size: None,
+            max_daily_volume: None,
+            max_slippage_bps: None,
+            allowed_mints: vec![],
+            max_tip_lamports: 0,
+            volume_window_start: 0,
+            daily_volume: 0,
+            twap_guard: None,
+            max_concentration_bps: None,
+            venue_caps: vec![],
+            max_drawdown: None,
+            drawdown: DrawdownTracker::default(),
+            circuit_breaker: None,
+            value_marks: vec![],
+            trading_paused: false,
+            bump: 0,
+        }
+    }
+
+    /// Unrated listing priced at 1 SOL
+    fn listing() -> agent_swarm::marketplace::AgentListing {
+        use agent_swarm::marketplace::{AgentListing, ListingCategory};
+
+        AgentListing {
+            layout_version: AgentListing::VERSION,
+            agent: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            agent_type: agent_swarm::AgentType::Analytics,
+            name: "listing".to_string(),
+            description: String::new(),
+            version: "1.0.0".to_string(),
+            price: 1_000_000_000,
+            price_mint: None,
+            downloads: 0,
+            rating: 0,
+            total_ratings: 0,
+            rating_sum: 0,
+            verified: false,
+            listed_at: 0,
+            updated_at: 0,
+            manifest: vec![],
+            category: ListingCategory::Analytics,
+            tags: vec![],
+            period_price: 0,
+            period_length: 0,
+            index: 0,
+            refunds_open_until: 0,
+            royalty_bps: 0,
+            bump: 0,
+        }
+    }
+
     /// LiteSVM: Initialize the swarm and verify state
     #[test]
     fn test_initialize_swarm_litesvm() {
@@ -58,11 +117,12 @@ mod tests {
         // let authority = Keypair::new();
         // svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
         //
-        // let (swarm_pda, _bump) = find_swarm_pda();
+        // let (swarm_pda, _bump) = find_swarm_pda(0);
         //
         // // Build initialize instruction
         // // (use Anchor discriminator: sha256("global:initialize")[..8])
         // let mut data = vec![175, 175, 109, 31, 13, 152, 155, 237]; // discriminator
+        // data.extend_from_slice(&0u64.to_le_bytes()); // swarm_id: u64
         // data.push(5);    // max_agents: u8
         // data.push(3);    // min_votes_required: u8
         // data.extend_from_slice(&3600i64.to_le_bytes()); // proposal_timeout: i64
@@ -91,7 +151,7 @@ mod tests {
         // assert!(account.data.len() > 0);
 
         // Scaffold passes — full test requires `anchor build` first
-        let (swarm_pda, bump) = find_swarm_pda();
+        let (swarm_pda, bump) = find_swarm_pda(0);
         assert_ne!(bump, 0, "PDA bump should be non-zero");
         println!("Swarm PDA: {} (bump: {})", swarm_pda, bump);
     }
@@ -100,9 +160,9 @@ mod tests {
     #[test]
     fn test_pda_derivation() {
         let owner = Keypair::new();
-        let (agent_pda, agent_bump) = find_agent_pda(&owner.pubkey());
-        let (proposal_pda, proposal_bump) = find_proposal_pda(0u64);
-        let (swarm_pda, swarm_bump) = find_swarm_pda();
+        let (swarm_pda, swarm_bump) = find_swarm_pda(0);
+        let (agent_pda, agent_bump) = find_agent_pda(&swarm_pda, &owner.pubkey());
+        let (proposal_pda, proposal_bump) = find_proposal_pda(&swarm_pda, 0u64);
 
         // PDA should be off-curve
         assert!(agent_bump <= 255);
@@ -110,15 +170,900 @@ mod tests {
         assert!(swarm_bump <= 255);
 
         // Deterministic
-        let (agent_pda_2, _) = find_agent_pda(&owner.pubkey());
+        let (agent_pda_2, _) = find_agent_pda(&swarm_pda, &owner.pubkey());
         assert_eq!(agent_pda, agent_pda_2, "PDA derivation must be deterministic");
 
+        // The same owner gets an independent agent in every swarm
+        let (other_swarm, _) = find_swarm_pda(1);
+        assert_ne!(swarm_pda, other_swarm);
+        assert_ne!(agent_pda, find_agent_pda(&other_swarm, &owner.pubkey()).0);
+
         println!("All PDAs derived successfully");
         println!("  Swarm:    {} (bump {})", swarm_pda, swarm_bump);
         println!("  Agent:    {} (bump {})", agent_pda, agent_bump);
         println!("  Proposal: {} (bump {})", proposal_pda, proposal_bump);
     }
 
+    /// The shared `pda` module must agree with the seeds used by clients
+    #[test]
+    fn test_pda_module_matches_seeds() {
+        use agent_swarm::pda;
+
+        let owner = Keypair::new().pubkey();
+        let (swarm, _) = find_swarm_pda(3);
+        assert_eq!(pda::find_swarm_address(3), find_swarm_pda(3));
+        assert_eq!(pda::find_agent_address(&swarm, &owner), find_agent_pda(&swarm, &owner));
+        assert_eq!(pda::find_proposal_address(&swarm, 7), find_proposal_pda(&swarm, 7));
+
+        let (proposal, _) = find_proposal_pda(&swarm, 7);
+        let (vote_record, _) = Pubkey::find_program_address(
+            &[b"vote", proposal.as_ref(), owner.as_ref()],
+            &program_id(),
+        );
+        assert_eq!(pda::find_vote_record_address(&proposal, &owner).0, vote_record);
+
+        let (payload_buffer, _) = Pubkey::find_program_address(
+            &[b"payload_buffer", proposal.as_ref(), owner.as_ref()],
+            &program_id(),
+        );
+        assert_eq!(pda::find_payload_buffer_address(&proposal, &owner).0, payload_buffer);
+
+        let (proposal_page, _) = Pubkey::find_program_address(
+            &[b"proposal_page", swarm.as_ref(), &0u64.to_le_bytes()],
+            &program_id(),
+        );
+        assert_eq!(pda::find_proposal_page_address(&swarm, 0).0, proposal_page);
+
+        let (listing, _) = Pubkey::find_program_address(
+            &[b"listing", owner.as_ref(), b"sentinel"],
+            &program_id(),
+        );
+        assert_eq!(pda::find_listing_address(&owner, "sentinel").0, listing);
+    }
+
+    /// Elo updates are zero-sum and favour upsets
+    #[test]
+    fn test_elo_rating_change() {
+        use agent_swarm::state::{elo_expected_score, elo_rating_change};
+
+        assert_eq!(elo_expected_score(1200, 1200), 500);
+        assert_eq!(elo_expected_score(1600, 1200) + elo_expected_score(1200, 1600), 1000);
+
+        // Evenly matched agents swap half the K-factor
+        assert_eq!(elo_rating_change(1200, 1200), 16);
+        // An underdog win moves more points than a favourite win
+        assert!(elo_rating_change(1000, 1400) > elo_rating_change(1400, 1000));
+    }
+
+    /// Manifests round-trip and malformed bytes are rejected
+    #[test]
+    fn test_agent_manifest_parsing() {
+        use agent_swarm::manifest::*;
+        use agent_swarm::ProposalType;
+
+        let manifest = AgentManifest {
+            version: [1, 2, 0],
+            capabilities: CAP_TRADING | CAP_RISK,
+            supported_proposal_types: 0b0_0011,
+            required_oracles: vec![Keypair::new().pubkey()],
+        };
+        let bytes = manifest.to_bytes();
+        assert_eq!(AgentManifest::parse(&bytes).unwrap(), manifest);
+        assert!(manifest.is_compatible(CAP_TRADING));
+        assert!(!manifest.is_compatible(CAP_TRADING | CAP_LEARNING));
+
+        // Truncated oracle list
+        assert!(AgentManifest::parse(&bytes[..bytes.len() - 1]).is_err());
+        // Unknown capability bit
+        let mut unknown = bytes.clone();
+        unknown[9] = 0x80;
+        assert!(AgentManifest::parse(&unknown).is_err());
+        // Unknown proposal type bit
+        let mut unknown = bytes.clone();
+        unknown[11] = 0x80;
+        assert!(AgentManifest::parse(&unknown).is_err());
+
+        // Proposal types past the first byte of the mask
+        let wide = AgentManifest {
+            supported_proposal_types: proposal_type_bit(&ProposalType::CrossChain),
+            ..manifest.clone()
+        };
+        let parsed = AgentManifest::parse(&wide.to_bytes()).unwrap();
+        assert!(parsed.supports(&ProposalType::CrossChain));
+        assert!(!parsed.supports(&ProposalType::Trade));
+
+        // Format 1 manifests carry a one-byte mask
+        let mut v1 = bytes[..10].to_vec();
+        v1[2] = 1;
+        v1.extend_from_slice(&[0b0_0011, 1]);
+        v1.extend_from_slice(&bytes[13..]);
+        assert_eq!(AgentManifest::parse(&v1).unwrap(), manifest);
+        // Empty manifests are only admitted when nothing is required
+        assert!(check_admission(&[], 0).is_ok());
+        assert!(check_admission(&[], CAP_TRADING).is_err());
+    }
+
+    /// Approved trades are limited jointly, not just one at a time
+    #[test]
+    fn test_pending_exposure_limits() {
+        use agent_swarm::payloads::{SwapRoute, TradePayload};
+        use agent_swarm::state::{ExposureLimit, PendingExposure};
+
+        let mint_in = Keypair::new().pubkey();
+        let trade = TradePayload {
+            mint_in,
+            mint_out: Keypair::new().pubkey(),
+            amount: 400,
+            min_out: 1,
+            route: SwapRoute::Jupiter { data: vec![0xe5] },
+            tip_lamports: 0,
+        };
+        let mut exposure = PendingExposure {
+            version: PendingExposure::VERSION,
+            limits: vec![ExposureLimit { mint: mint_in, max_amount: 1000 }],
+            pending: vec![],
+            perp_limits: vec![],
+            bump: 0,
+        };
+
+        let first = Keypair::new().pubkey();
+        assert!(exposure.reserve(first, &trade, 100, 0).is_ok());
+        assert!(exposure.reserve(first, &trade, 100, 0).is_err());
+        assert!(exposure.reserve(Keypair::new().pubkey(), &trade, 100, 0).is_ok());
+        // A third trade of the same size breaches the aggregate limit
+        assert!(exposure.reserve(Keypair::new().pubkey(), &trade, 100, 0).is_err());
+        assert_eq!(exposure.exposure(&mint_in), 800);
+
+        // Expired reservations free up room
+        assert!(exposure.reserve(Keypair::new().pubkey(), &trade, 200, 101).is_ok());
+        assert_eq!(exposure.exposure(&mint_in), 400);
+    }
+
+    /// Deregistered agents free their registry slot for the next agent
+    #[test]
+    fn test_agent_registry_slots() {
+        use agent_swarm::state::AgentRegistry;
+        use agent_swarm::AgentType;
+
+        let mut registry = AgentRegistry {
+            version: AgentRegistry::VERSION,
+            swarm: Pubkey::default(),
+            entries: vec![],
+            bump: 0,
+        };
+        let first = Keypair::new().pubkey();
+        let second = Keypair::new().pubkey();
+        let third = Keypair::new().pubkey();
+        registry.add(first, AgentType::Execution).unwrap();
+        registry.add(second, AgentType::RiskManagement).unwrap();
+        assert_eq!(registry.count_of_type(AgentType::Execution), 1);
+
+        registry.remove(&first).unwrap();
+        assert!(registry.remove(&first).is_err());
+        assert_eq!(registry.count_of_type(AgentType::Execution), 0);
+
+        registry.add(third, AgentType::Execution).unwrap();
+        assert_eq!(registry.entries.len(), 2);
+        assert_eq!(registry.entries[0].agent, third);
+        assert_eq!(registry.active().count(), 2);
+    }
+
+    /// A price weighs into the TWAP only for as long as it stood
+    #[test]
+    fn test_twap_tracker_weights_by_time() {
+        use agent_swarm::state::TwapTracker;
+
+        let mut tracker = TwapTracker {
+            version: TwapTracker::VERSION,
+            swarm: Pubkey::default(),
+            mint: Pubkey::default(),
+            last_price: 0,
+            last_observed_at: 0,
+            observations: vec![],
+            next_slot: 0,
+            bump: 0,
+        };
+        assert!(tracker.twap(60).is_err());
+
+        tracker.observe(100, 1_000).unwrap();
+        tracker.observe(110, 1_060).unwrap();
+        assert!(tracker.observe(120, 1_060).is_err());
+        // A spike that stood for one second barely moves the average
+        tracker.observe(1_000, 1_120).unwrap();
+        tracker.observe(110, 1_121).unwrap();
+        assert_eq!(tracker.twap(120).unwrap(), (100 * 60 + 110 * 60 + 1_000) / 121);
+        assert!(tracker.twap(600).is_err());
+    }
+
+    /// Venue caps count volume per swap program and reset with the daily
+    /// window; drawdown measures the fall from the best cumulative result
+    #[test]
+    fn test_risk_engine_venue_cap_and_drawdown() {
+        use agent_swarm::payloads::{SwapRoute, TradePayload};
+        use agent_swarm::state::DrawdownTracker;
+
+        let mut risk_config = risk_config();
+        let venue = Pubkey::new_unique();
+        risk_config.set_venue_cap(venue, Some(500)).unwrap();
+        let trade = TradePayload {
+            mint_in: Pubkey::new_unique(),
+            mint_out: Pubkey::new_unique(),
+            amount: 300,
+            min_out: 1,
+            route: SwapRoute::Raydium,
+            tip_lamports: 0,
+        };
+
+        risk_config.record_trade(&trade, &venue, 10).unwrap();
+        assert!(risk_config.record_trade(&trade, &venue, 20).is_err());
+        risk_config.record_trade(&trade, &Pubkey::new_unique(), 20).unwrap();
+        risk_config.record_trade(&trade, &venue, 86_400).unwrap();
+
+        let mut drawdown = DrawdownTracker::default();
+        for pnl in [100, -30, -50, 20] {
+            drawdown.record(pnl).unwrap();
+        }
+        assert_eq!(drawdown.peak_pnl, 100);
+        assert_eq!(drawdown.drawdown(), 60);
+    }
+
+    /// Rebalances replace target weights; mints neither held nor targeted
+    /// leave the portfolio
+    #[test]
+    fn test_portfolio_holdings_and_targets() {
+        use agent_swarm::payloads::TargetAllocation;
+        use agent_swarm::state::Portfolio;
+
+        let mut portfolio = Portfolio {
+            version: Portfolio::VERSION,
+            swarm: Pubkey::new_unique(),
+            holdings: vec![],
+            last_proposal: Pubkey::default(),
+            updated_at: 0,
+            bump: 0,
+        };
+        let (usdc, sol, jup) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let proposal = Pubkey::new_unique();
+        portfolio
+            .set_targets(
+                &[
+                    TargetAllocation { mint: usdc, weight_bps: 6000 },
+                    TargetAllocation { mint: sol, weight_bps: 4000 },
+                ],
+                proposal,
+                10,
+            )
+            .unwrap();
+        portfolio.record_balance(usdc, 1_000, proposal, 20).unwrap();
+        portfolio.record_balance(jup, 50, proposal, 20).unwrap();
+        assert_eq!(portfolio.holdings.len(), 3);
+
+        portfolio
+            .set_targets(&[TargetAllocation { mint: jup, weight_bps: 10_000 }], proposal, 30)
+            .unwrap();
+        assert!(portfolio.holding(&sol).is_none());
+        assert_eq!(portfolio.holding(&usdc).unwrap().target_weight_bps, 0);
+        assert_eq!(portfolio.holding(&jup).unwrap().target_weight_bps, 10_000);
+
+        portfolio.record_balance(usdc, 0, proposal, 40).unwrap();
+        assert!(portfolio.holding(&usdc).is_none());
+        assert_eq!(portfolio.updated_at, 40);
+    }
+
+    /// A rebalance's trades must move each holding to its target weight
+    #[test]
+    fn test_rebalance_trades_match_deltas() {
+        use agent_swarm::payloads::{RebalanceTrade, TargetAllocation};
+        use agent_swarm::risk::UnitPrice;
+        use agent_swarm::state::Portfolio;
+
+        let (usdc, sol) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut portfolio = Portfolio {
+            version: Portfolio::VERSION,
+            swarm: Pubkey::new_unique(),
+            holdings: vec![],
+            last_proposal: Pubkey::default(),
+            updated_at: 0,
+            bump: 0,
+        };
+        let proposal = Pubkey::new_unique();
+        // 1000 USDC and 10 SOL at $100: $2000, half in each
+        portfolio.record_balance(usdc, 1_000_000_000, proposal, 0).unwrap();
+        portfolio.record_balance(sol, 10_000_000_000, proposal, 0).unwrap();
+        portfolio
+            .set_targets(
+                &[
+                    TargetAllocation { mint: usdc, weight_bps: 8000 },
+                    TargetAllocation { mint: sol, weight_bps: 2000 },
+                ],
+                proposal,
+                0,
+            )
+            .unwrap();
+        let prices = [
+            UnitPrice { price: 100_000_000, decimals: 6 },
+            UnitPrice { price: 10_000_000_000, decimals: 9 },
+        ];
+        let sell_sol = |amount| RebalanceTrade { mint_in: sol, mint_out: usdc, amount };
+
+        // Moving to 80/20 means selling 6 SOL
+        portfolio.check_rebalance_trades(&prices, &[sell_sol(6_000_000_000)]).unwrap();
+        portfolio.check_rebalance_trades(&prices, &[sell_sol(5_900_000_000)]).unwrap();
+        assert!(portfolio.check_rebalance_trades(&prices, &[sell_sol(3_000_000_000)]).is_err());
+        assert!(portfolio.check_rebalance_trades(&prices[..1], &[sell_sol(6_000_000_000)]).is_err());
+    }
+
+    /// Standing orders fit the longest route their terms accept, and may
+    /// not pay execution tips
+    #[test]
+    fn test_standing_order_terms() {
+        use agent_swarm::constants::MAX_ORDER_ROUTE_DATA;
+        use agent_swarm::oracle::{PriceComparison, PriceCondition};
+        use agent_swarm::payloads::{StandingOrderTerms, SwapRoute, TradePayload};
+        use agent_swarm::state::StandingOrder;
+
+        let (sol, usdc) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut terms = StandingOrderTerms {
+            trigger: PriceCondition {
+                base_mint: sol,
+                quote_mint: usdc,
+                comparison: PriceComparison::Below,
+                threshold: 120_000_000,
+                twap_window: 0,
+            },
+            trade: TradePayload {
+                mint_in: sol,
+                mint_out: usdc,
+                amount: 5_000_000_000,
+                min_out: 500_000_000,
+                route: SwapRoute::Jupiter { data: vec![0xe5; MAX_ORDER_ROUTE_DATA] },
+                tip_lamports: 0,
+            },
+            expires_at: 1_700_000_000,
+        };
+        terms.validate().unwrap();
+
+        let order = StandingOrder {
+            version: StandingOrder::VERSION,
+            swarm: Pubkey::new_unique(),
+            proposal: Pubkey::new_unique(),
+            trigger: terms.trigger,
+            trade: terms.trade.clone(),
+            expires_at: terms.expires_at,
+            payer: Pubkey::new_unique(),
+            bump: 255,
+        };
+        assert_eq!(8 + order.try_to_vec().unwrap().len(), StandingOrder::LEN);
+
+        terms.trade.tip_lamports = 1;
+        assert!(terms.validate().is_err());
+        terms.trade.tip_lamports = 0;
+        terms.trade.route = SwapRoute::Jupiter { data: vec![0xe5; MAX_ORDER_ROUTE_DATA + 1] };
+        assert!(terms.validate().is_err());
+    }
+
+    /// The last DCA leg buys with what is left of the budget and completes
+    /// the schedule
+    #[test]
+    fn test_dca_schedule_legs() {
+        use agent_swarm::state::DcaSchedule;
+
+        let mut schedule = DcaSchedule {
+            version: DcaSchedule::VERSION,
+            swarm: Pubkey::new_unique(),
+            proposal: Pubkey::new_unique(),
+            mint_in: Pubkey::new_unique(),
+            mint_out: Pubkey::new_unique(),
+            amount_per_leg: 400,
+            interval: 3600,
+            total_budget: 1000,
+            spent: 0,
+            received: 0,
+            max_slippage_bps: 100,
+            legs_executed: 0,
+            next_leg_at: 0,
+            payer: Pubkey::new_unique(),
+            bump: 0,
+        };
+
+        assert!(!schedule.record_leg(400, 20, 100).unwrap());
+        assert!(!schedule.record_leg(400, 19, 3700).unwrap());
+        assert_eq!(schedule.next_leg_amount(), 200);
+        assert_eq!(schedule.next_leg_at, 7300);
+        assert!(schedule.record_leg(200, 10, 7300).unwrap());
+        assert_eq!((schedule.legs_executed, schedule.received), (3, 49));
+    }
+
+    /// An arbitrage report matches trades on its route, once, until it expires
+    #[test]
+    fn test_arb_report_claims() {
+        use agent_swarm::payloads::{SwapRoute, TradePayload};
+        use agent_swarm::state::ArbReport;
+
+        let trade = TradePayload {
+            mint_in: Pubkey::new_unique(),
+            mint_out: Pubkey::new_unique(),
+            amount: 1_000,
+            min_out: 990,
+            route: SwapRoute::Raydium,
+            tip_lamports: 0,
+        };
+        let route_hash = trade.route_hash().unwrap();
+        let mut report = ArbReport {
+            version: ArbReport::VERSION,
+            swarm: Pubkey::new_unique(),
+            reporter: Pubkey::new_unique(),
+            buy_venue: Pubkey::new_unique(),
+            sell_venue: Pubkey::new_unique(),
+            expected_edge_bps: 40,
+            route_hash,
+            reported_at: 100,
+            expires_at: 700,
+            credited_proposal: None,
+            payer: Pubkey::new_unique(),
+            bump: 0,
+        };
+
+        // A different amount is the same route; a reversed pair is not
+        let resized = TradePayload { amount: 5, ..trade.clone() };
+        let reversed = TradePayload { mint_in: trade.mint_out, mint_out: trade.mint_in, ..trade };
+        assert!(report.is_claimable(&resized.route_hash().unwrap(), 700));
+        assert!(!report.is_claimable(&reversed.route_hash().unwrap(), 700));
+        assert!(!report.is_claimable(&route_hash, 701));
+
+        report.credited_proposal = Some(Pubkey::new_unique());
+        assert!(!report.is_claimable(&route_hash, 200));
+    }
+
+    /// Harvest terms reject duplicate positions; the config swaps rewards
+    /// into its base asset and paces the crank
+    #[test]
+    fn test_harvest_config() {
+        use agent_swarm::payloads::{HarvestPayload, HarvestSwap, HarvestTerms, SwapRoute};
+        use agent_swarm::state::HarvestConfig;
+
+        let base_mint = Pubkey::new_unique();
+        let position = Pubkey::new_unique();
+        let terms = HarvestTerms {
+            base_mint,
+            positions: vec![position, position],
+            crank_threshold: 50_000_000,
+            crank_interval: 3600,
+            max_slippage_bps: 100,
+        };
+        let payload = HarvestPayload { terms: Some(terms.clone()), swap: None };
+        assert!(payload.validate().is_err());
+
+        let mut config = HarvestConfig {
+            version: HarvestConfig::VERSION,
+            swarm: Pubkey::new_unique(),
+            base_mint: Pubkey::default(),
+            positions: vec![],
+            crank_threshold: 0,
+            crank_interval: 0,
+            max_slippage_bps: 0,
+            source_proposal: Pubkey::default(),
+            harvests: 0,
+            last_harvest_at: 0,
+            bump: 0,
+        };
+        assert!(!config.is_configured());
+        config.set(HarvestTerms { positions: vec![position], ..terms }, Pubkey::new_unique());
+        assert!(config.is_configured());
+
+        let reward = HarvestSwap { mint_in: Pubkey::new_unique(), amount: 10, min_out: 1, route: SwapRoute::Raydium };
+        assert_eq!(config.trade(reward.clone()).unwrap().mint_out, base_mint);
+        assert!(config.trade(HarvestSwap { mint_in: base_mint, ..reward }).is_err());
+
+        config.record_harvest(1_000);
+        assert!(!config.is_crank_due(4_599));
+        assert!(config.is_crank_due(4_600));
+    }
+
+    /// The insurance fund takes its share of new fees only and pays each
+    /// loss inside the claim window once
+    #[test]
+    fn test_insurance_fund_claims() {
+        use agent_swarm::constants::INSURANCE_CLAIM_WINDOW;
+        use agent_swarm::state::{InsuranceClaimRecord, InsuranceFund};
+
+        let mut fund = InsuranceFund {
+            version: InsuranceFund::VERSION,
+            swarm: Pubkey::new_unique(),
+            fee_share_bps: 2_000,
+            loss_threshold_bps: 500,
+            fees_swept: 0,
+            total_premiums: 0,
+            total_claims_paid: 0,
+            recent_claims: vec![],
+            bump: 0,
+        };
+        assert_eq!(fund.sweep_premium(1_000).unwrap(), 200);
+        assert_eq!(fund.sweep_premium(1_000).unwrap(), 0);
+        assert_eq!(fund.sweep_premium(1_500).unwrap(), 100);
+        assert_eq!(fund.total_premiums, 300);
+
+        let now = INSURANCE_CLAIM_WINDOW + 1_000;
+        let claim = InsuranceClaimRecord {
+            loss_proposal: Pubkey::new_unique(),
+            loss_executed_at: now - 10,
+            lamports: 50,
+        };
+        fund.record_claim(claim, now).unwrap();
+        assert!(fund.record_claim(claim, now).is_err());
+        let stale = InsuranceClaimRecord {
+            loss_proposal: Pubkey::new_unique(),
+            loss_executed_at: 999,
+            ..claim
+        };
+        assert!(fund.record_claim(stale, now).is_err());
+        assert_eq!(fund.total_claims_paid, 50);
+    }
+
+    /// The circuit breaker pauses trading on a deep enough fall from the
+    /// window's peak mark, and stays paused until trading is resumed
+    #[test]
+    fn test_circuit_breaker_pauses_trading() {
+        use agent_swarm::payloads::{SwapRoute, TradePayload};
+        use agent_swarm::state::CircuitBreaker;
+
+        let mut risk_config = risk_config();
+        assert!(risk_config.mark_value(1_000, 0).is_err());
+        risk_config.set_circuit_breaker(Some(CircuitBreaker { window: 86_400, max_drawdown_bps: 1_000 }));
+
+        assert_eq!(risk_config.mark_value(1_000, 0).unwrap(), (1_000, 0));
+        assert!(risk_config.mark_value(800, 100).is_err());
+        assert_eq!(risk_config.mark_value(950, 3_600).unwrap(), (1_000, 500));
+        assert!(!risk_config.trading_paused);
+        assert_eq!(risk_config.mark_value(850, 7_200).unwrap(), (1_000, 1_500));
+        assert!(risk_config.trading_paused);
+
+        let trade = TradePayload {
+            mint_in: Pubkey::new_unique(),
+            mint_out: Pubkey::new_unique(),
+            amount: 10,
+            min_out: 1,
+            route: SwapRoute::Raydium,
+            tip_lamports: 0,
+        };
+        assert!(risk_config.record_trade(&trade, &Pubkey::new_unique(), 7_200).is_err());
+        risk_config.resume_trading();
+        risk_config.record_trade(&trade, &Pubkey::new_unique(), 7_200).unwrap();
+        assert_eq!(risk_config.mark_value(850, 10_800).unwrap(), (850, 0));
+    }
+
+    /// Approving voters share a trade's realized PnL by vote weight;
+    /// outcomes without a measured PnL attribute nothing
+    #[test]
+    fn test_outcome_voter_pnl_share() {
+        use agent_swarm::state::Outcome;
+
+        let mut outcome = Outcome {
+            version: Outcome::VERSION,
+            proposal: Pubkey::new_unique(),
+            executed_by: Pubkey::new_unique(),
+            success: false,
+            metrics: vec![],
+            executed_at: 0,
+            balance_deltas: vec![],
+            realized_pnl: None,
+            bump: 0,
+        };
+        assert_eq!(outcome.voter_pnl_share(1_000, 4_000), None);
+
+        outcome.realized_pnl = Some(-2_000);
+        assert_eq!(outcome.voter_pnl_share(1_000, 4_000), Some(-500));
+        assert_eq!(outcome.voter_pnl_share(4_000, 4_000), Some(-2_000));
+        assert_eq!(outcome.voter_pnl_share(1_000, 0), None);
+    }
+
+    /// A vote record keeps the hash of the full reasoning and an excerpt
+    /// cut at a character boundary
+    #[test]
+    fn test_vote_record_reasoning_excerpt() {
+        use agent_swarm::constants::MAX_REASONING_EXCERPT_LENGTH;
+        use agent_swarm::state::VoteRecord;
+        use agent_swarm::VoteType;
+        use solana_sdk::keccak;
+
+        let mut vote_record = VoteRecord {
+            version: VoteRecord::VERSION,
+            proposal: Pubkey::new_unique(),
+            voter: Pubkey::new_unique(),
+            vote: VoteType::Approve,
+            weight: 1_000,
+            voted_at: 0,
+            settled: false,
+            reward_claimed: false,
+            reasoning_hash: [0; 32],
+            reasoning_excerpt: String::new(),
+            bump: 0,
+        };
+        vote_record.set_reasoning("Spread is wide enough");
+        assert_eq!(vote_record.reasoning_excerpt, "Spread is wide enough");
+
+        let reasoning = format!("{}é and more", "a".repeat(MAX_REASONING_EXCERPT_LENGTH - 1));
+        vote_record.set_reasoning(&reasoning);
+        assert_eq!(vote_record.reasoning_excerpt.len(), MAX_REASONING_EXCERPT_LENGTH - 1);
+        assert_eq!(vote_record.reasoning_hash, keccak::hash(reasoning.as_bytes()).to_bytes());
+    }
+
+    /// Proposal tags are checked at creation and read back without their
+    /// zero padding
+    #[test]
+    fn test_proposal_tags() {
+        use agent_swarm::state::Proposal;
+
+        let tags = |tags: &[&str]| tags.iter().map(|tag| tag.to_string()).collect::<Vec<_>>();
+        assert!(Proposal::validate_tags(&tags(&["SOL", "hedging", "urgent"])).is_ok());
+        assert!(Proposal::validate_tags(&tags(&["a", "b", "c", "d", "e"])).is_err());
+        assert!(Proposal::validate_tags(&tags(&["sol", "SOL"])).is_err());
+        assert!(Proposal::validate_tags(&tags(&["has space"])).is_err());
+        assert!(Proposal::validate_tags(&tags(&[""])).is_err());
+        assert!(Proposal::validate_tags(&tags(&["seventeen-chars-x"])).is_err());
+
+        let mut proposal: Box<Proposal> = Box::new(bytemuck::Zeroable::zeroed());
+        assert!(proposal.tags().is_empty());
+        proposal.set_tags(&tags(&["SOL", "sixteen-chars-xx"]));
+        assert_eq!(proposal.tags(), tags(&["SOL", "sixteen-chars-xx"]));
+    }
+
+    /// A nominated executor has the proposal to itself for the window, and
+    /// a second nomination cannot restart it
+    #[test]
+    fn test_executor_exclusive_window() {
+        use agent_swarm::state::Proposal;
+        use agent_swarm::ExecutionMode;
+
+        let mut proposal: Box<Proposal> = Box::new(bytemuck::Zeroable::zeroed());
+        let executor = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let mode = ExecutionMode::Permissioned;
+
+        assert!(proposal.can_execute(&other, mode, 600, 0));
+        proposal.nominate_executor(executor, 100).unwrap();
+        assert!(proposal.can_execute(&executor, mode, 600, 100));
+        assert!(!proposal.can_execute(&other, mode, 600, 699));
+        assert!(proposal.can_execute(&other, mode, 600, 700));
+        assert!(proposal.can_execute(&other, ExecutionMode::Open, 600, 100));
+
+        assert!(proposal.nominate_executor(other, 650).is_err());
+        assert_eq!(proposal.assigned_at, 100);
+    }
+
+    /// Only the assigned executor or the proposer reports failures, and the
+    /// proposal fails on the last allowed attempt
+    #[test]
+    fn test_execution_failure_attempts() {
+        use agent_swarm::state::Proposal;
+
+        let mut proposal: Box<Proposal> = Box::new(bytemuck::Zeroable::zeroed());
+        let proposer = Pubkey::new_unique();
+        let executor = Pubkey::new_unique();
+        proposal.proposer = proposer;
+        proposal.assigned_executor = executor;
+
+        assert!(proposal.record_failure(&Pubkey::new_unique(), 7, 3).is_err());
+        assert_eq!(proposal.last_failure_code(), None);
+
+        assert!(!proposal.record_failure(&executor, 7, 3).unwrap());
+        assert!(!proposal.record_failure(&proposer, 9, 3).unwrap());
+        assert_eq!(proposal.execution_attempts, 2);
+        assert_eq!(proposal.last_failure_code(), Some(9));
+        assert!(proposal.record_failure(&executor, 11, 3).unwrap());
+        assert!(proposal.is_failed());
+
+        // Open bounties keep the failed proposal from being closed
+        proposal.open_bounties = 1;
+        assert!(!proposal.is_closable());
+        proposal.open_bounties = 0;
+        assert!(proposal.is_closable());
+    }
+
+    /// Bounties refund only once the proposal can no longer pay them out
+    #[test]
+    fn test_bounty_claim_and_refund() {
+        use agent_swarm::state::Proposal;
+
+        let mut proposal: Box<Proposal> = Box::new(bytemuck::Zeroable::zeroed());
+        proposal.expires_at = 100;
+        proposal.open_bounties = 2;
+
+        // Still open: neither a pending nor a successful execution refunds
+        assert!(!proposal.is_bounty_refundable(None, 50));
+        assert!(!proposal.is_bounty_refundable(Some(true), 200));
+        assert!(proposal.is_bounty_refundable(Some(false), 50));
+        assert!(proposal.is_bounty_refundable(None, 101));
+
+        // Executed proposals pay out through the outcome, not on expiry
+        proposal.executed = 1;
+        assert!(!proposal.is_bounty_refundable(None, 101));
+        proposal.executed = 0;
+        proposal.failed = 1;
+        assert!(proposal.is_bounty_refundable(None, 50));
+
+        // Each claim or refund releases one open bounty
+        assert!(!proposal.is_outcome_settled());
+        proposal.open_bounties -= 1;
+        assert!(!proposal.is_closable());
+        proposal.open_bounties -= 1;
+        assert!(proposal.is_closable());
+        assert!(proposal.is_outcome_settled());
+    }
+
+    /// The audit log keeps the newest entries once its ring wraps
+    #[test]
+    fn test_audit_log_ring_wraps() {
+        use agent_swarm::constants::MAX_AUDIT_ENTRIES;
+        use agent_swarm::state::{AuditAction, AuditLog};
+
+        let mut log = AuditLog {
+            version: AuditLog::VERSION,
+            swarm: Pubkey::default(),
+            entries: vec![],
+            next_slot: 0,
+            total_entries: 0,
+            bump: 0,
+        };
+        let actor = Pubkey::new_unique();
+        for timestamp in 0..MAX_AUDIT_ENTRIES as i64 + 3 {
+            log.record(actor, AuditAction::SwarmConfigUpdated, Pubkey::default(), timestamp);
+        }
+
+        assert_eq!(log.entries.len(), MAX_AUDIT_ENTRIES);
+        assert_eq!(log.total_entries, MAX_AUDIT_ENTRIES as u64 + 3);
+        let timestamps: Vec<i64> = log.chronological().map(|entry| entry.timestamp).collect();
+        assert_eq!(timestamps.first(), Some(&3));
+        assert_eq!(timestamps.last(), Some(&(MAX_AUDIT_ENTRIES as i64 + 2)));
+    }
+
+    /// Proposal deposits double per threshold of recent volume and fall
+    /// back as the volume decays
+    #[test]
+    fn test_proposal_congestion_pricing() {
+        use agent_swarm::state::ProposalCongestion;
+
+        let mut congestion = ProposalCongestion {
+            base_deposit: 1_000,
+            threshold: 2,
+            half_life: 60,
+            ..ProposalCongestion::default()
+        };
+        let deposits: Vec<u64> = (0..6).map(|_| congestion.record(0)).collect();
+        assert_eq!(deposits, vec![1_000, 1_000, 2_000, 2_000, 4_000, 4_000]);
+
+        // Two half-lives later six proposals count as one
+        assert_eq!(congestion.record(150), 1_000);
+        assert_eq!(congestion.volume, 2);
+        assert_eq!(congestion.decayed_at, 120);
+    }
+
+    /// A coalition votes as a bloc only once one position reaches its threshold
+    #[test]
+    fn test_coalition_ballot_position() {
+        use agent_swarm::state::{Coalition, CoalitionBallot, CoalitionRules};
+        use agent_swarm::VoteType;
+
+        let rules = CoalitionRules {
+            open_membership: true,
+            bloc_threshold_bps: 6000,
+            treasury_threshold_bps: 5000,
+        };
+        assert!(CoalitionRules { bloc_threshold_bps: 5000, ..rules }.validate().is_err());
+
+        let members: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let coalition = Coalition {
+            version: Coalition::VERSION,
+            swarm: Pubkey::default(),
+            founder: members[0],
+            coalition_id: 0,
+            name: "bloc".to_string(),
+            members: members.clone(),
+            rules,
+            created_at: 0,
+            bump: 0,
+        };
+        let mut ballot = CoalitionBallot {
+            version: CoalitionBallot::VERSION,
+            coalition: Pubkey::default(),
+            proposal: Pubkey::default(),
+            voters: vec![],
+            approvals: 0,
+            rejections: 0,
+            abstentions: 0,
+            cast: false,
+            bump: 0,
+        };
+        ballot.record(members[0], &VoteType::Approve);
+        ballot.record(members[1], &VoteType::Approve);
+        ballot.record(members[2], &VoteType::Reject);
+        assert_eq!(ballot.position(&coalition), None);
+        ballot.record(members[3], &VoteType::Approve);
+        assert_eq!(ballot.position(&coalition), Some(VoteType::Approve));
+        assert!(coalition.meets_threshold(3, rules.treasury_threshold_bps));
+        assert!(!coalition.meets_threshold(2, rules.treasury_threshold_bps));
+    }
+
+    /// Appending outcomes matches a merkle tree padded with zero leaves
+    #[test]
+    fn test_outcome_archive_root() {
+        use agent_swarm::constants::OUTCOME_TREE_DEPTH;
+        use agent_swarm::state::OutcomeArchive;
+        use solana_sdk::keccak::hashv;
+
+        let mut archive = OutcomeArchive {
+            version: OutcomeArchive::VERSION,
+            root: OutcomeArchive::empty_root(),
+            leaf_count: 0,
+            frontier: [[0; 32]; OUTCOME_TREE_DEPTH],
+            bump: 0,
+        };
+        let leaves = [[1; 32], [2; 32], [3; 32]];
+        for (index, leaf) in leaves.iter().enumerate() {
+            assert_eq!(archive.append(*leaf).unwrap(), index as u64);
+        }
+
+        // Hash the padded leaf level up to the root
+        let mut level = leaves.to_vec();
+        level.push([0; 32]);
+        let mut zero = [0; 32];
+        for _ in 0..OUTCOME_TREE_DEPTH {
+            if level.len() % 2 == 1 {
+                level.push(zero);
+            }
+            level = level.chunks(2).map(|pair| hashv(&[&pair[0], &pair[1]]).to_bytes()).collect();
+            zero = hashv(&[&zero, &zero]).to_bytes();
+        }
+        assert_eq!(archive.root, level[0]);
+    }
+
+    /// Refunds are open until the deadline while the payment is escrowed,
+    /// and take the buyer's rating out of the listing's average
+    #[test]
+    fn test_purchase_refund_window() {
+        use agent_swarm::marketplace::Purchase;
+
+        let mut purchase = Purchase {
+            version: Purchase::VERSION,
+            listing: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            seller: Pubkey::new_unique(),
+            mint: None,
+            price_paid: 100,
+            escrowed: 100,
+            purchased_at: 0,
+            refund_deadline: 3_600,
+            bump: 0,
+        };
+        assert!(purchase.is_refundable(3_599));
+        assert!(!purchase.is_refundable(3_600));
+        purchase.escrowed = 0;
+        assert!(!purchase.is_refundable(0));
+
+        let mut listing = listing();
+        listing.total_ratings = 2;
+        listing.rating_sum = 500 + 300;
+        listing.remove_rating(5);
+        assert_eq!((listing.total_ratings, listing.rating_sum, listing.rating), (1, 300, 300));
+        listing.remove_rating(3);
+        assert_eq!((listing.total_ratings, listing.rating_sum, listing.rating), (0, 0, 0));
+    }
+
+    /// Re-rating replaces the rater's previous stars instead of adding to
+    /// the count
+    #[test]
+    fn test_listing_rating_replacement() {
+        let mut listing = listing();
+        listing.record_rating(0, 5).unwrap();
+        listing.record_rating(0, 2).unwrap();
+        assert_eq!((listing.total_ratings, listing.rating_sum, listing.rating), (2, 700, 350));
+
+        listing.record_rating(2, 4).unwrap();
+        assert_eq!((listing.total_ratings, listing.rating_sum, listing.rating), (2, 900, 450));
+        listing.record_rating(5, 1).unwrap();
+        assert_eq!((listing.total_ratings, listing.rating_sum, listing.rating), (2, 500, 250));
+    }
+
     /// Mollusk: Instruction-level unit test (fast, no SVM boot)
     #[test]
     fn test_mollusk_instruction_validation() {
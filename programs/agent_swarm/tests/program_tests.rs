@@ -0,0 +1,1053 @@
+//! Instruction-level tests for the agent_swarm program
+//!
+//! The program runs natively in a `solana-program-test` bank, so these need
+//! no `anchor build`. Setup goes through the program's own instructions where
+//! that is cheap and patches account data where it is not. They live apart
+//! from the LiteSVM tests because the bank's syscall stubs are process-wide.
+//!
+//! Run: `cargo test --manifest-path programs/agent_swarm/Cargo.toml --test program_tests`
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{
+        AccountDeserialize, AccountSerialize, AnchorSerialize, InstructionData, ToAccountMetas,
+    };
+    use anchor_spl::token::spl_token;
+    use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+    use solana_sdk::{
+        account::{Account, AccountSharedData},
+        account_info::AccountInfo,
+        clock::Clock,
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program::invoke_signed,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_program,
+        transaction::{Transaction, TransactionError},
+    };
+
+    /// Anchor's entrypoint ties the accounts to the lifetime of their infos
+    fn process_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        data: &[u8],
+    ) -> ProgramResult {
+        let accounts = Box::leak(Box::new(accounts.to_vec()));
+        agent_swarm::entry(program_id, accounts, data)
+    }
+
+    fn program_test() -> ProgramTest {
+        ProgramTest::new(
+            "agent_swarm",
+            agent_swarm::ID,
+            processor!(process_instruction),
+        )
+    }
+
+    /// Stand-in Jupiter route paying the `u64` in its data out of a pool
+    /// owned by its PDA. Accounts: pool, destination, pool authority, token
+    /// program.
+    fn mock_jupiter_route(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        data: &[u8],
+    ) -> ProgramResult {
+        let amount = u64::from_le_bytes(data[..8].try_into().unwrap());
+        let (pool_authority, bump) = Pubkey::find_program_address(&[b"pool"], program_id);
+        let transfer = spl_token::instruction::transfer(
+            accounts[3].key,
+            accounts[0].key,
+            accounts[1].key,
+            &pool_authority,
+            &[],
+            amount,
+        )?;
+        invoke_signed(&transfer, accounts, &[&[b"pool", &[bump]]])
+    }
+
+    fn mint_account(decimals: u8) -> Account {
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            decimals,
+            is_initialized: true,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: spl_token::ID,
+            ..Account::default()
+        }
+    }
+
+    fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Account {
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: spl_token::ID,
+            ..Account::default()
+        }
+    }
+
+    /// A program-owned Anchor account holding `value`, padded to `space`
+    fn program_account(value: &impl AccountSerialize, space: usize) -> Account {
+        let mut data = Vec::with_capacity(space);
+        value.try_serialize(&mut data).unwrap();
+        data.resize(space, 0);
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: agent_swarm::ID,
+            ..Account::default()
+        }
+    }
+
+    /// A fully verified Pyth `PriceUpdateV2` of `price * 10^-8`
+    fn price_update_account(feed_id: [u8; 32], price: i64, publish_time: i64) -> Account {
+        // sha256("account:PriceUpdateV2")[..8]
+        let mut data = vec![34, 241, 35, 99, 157, 126, 244, 205];
+        data.extend_from_slice(Pubkey::new_unique().as_ref()); // write_authority
+        data.push(1); // VerificationLevel::Full
+        data.extend_from_slice(&feed_id);
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes()); // conf
+        data.extend_from_slice(&(-8i32).to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes()); // prev_publish_time
+        data.extend_from_slice(&price.to_le_bytes()); // ema_price
+        data.extend_from_slice(&0u64.to_le_bytes()); // ema_conf
+        data.extend_from_slice(&0u64.to_le_bytes()); // posted_slot
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: agent_swarm::oracle::PYTH_RECEIVER_PROGRAM_ID,
+            ..Account::default()
+        }
+    }
+
+    fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+        Instruction {
+            program_id: agent_swarm::ID,
+            accounts: accounts.to_account_metas(None),
+            data: data.data(),
+        }
+    }
+
+    /// The program error a failed transaction's first instruction returned
+    fn program_error(err: BanksClientError) -> u32 {
+        match err.unwrap() {
+            TransactionError::InstructionError(0, InstructionError::Custom(code)) => code,
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    /// Swarm 0 in a fresh bank; the bank's payer is its authority
+    struct TestSwarm {
+        context: ProgramTestContext,
+        swarm: Pubkey,
+    }
+
+    impl TestSwarm {
+        async fn start(program_test: ProgramTest) -> Self {
+            let context = program_test.start_with_context().await;
+            let (swarm, _) = agent_swarm::pda::find_swarm_address(0);
+            let mut test_swarm = TestSwarm { context, swarm };
+            let initialize = instruction(
+                agent_swarm::accounts::Initialize {
+                    swarm_state: swarm,
+                    authority: test_swarm.payer().pubkey(),
+                    system_program: system_program::ID,
+                },
+                agent_swarm::instruction::Initialize {
+                    swarm_id: 0,
+                    max_agents: 5,
+                    min_votes_required: 3,
+                    proposal_timeout: 3600,
+                },
+            );
+            test_swarm.send(&[initialize], &[]).await.unwrap();
+            test_swarm
+        }
+
+        fn payer(&self) -> Keypair {
+            self.context.payer.insecure_clone()
+        }
+
+        async fn now(&mut self) -> i64 {
+            let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+            clock.unix_timestamp
+        }
+
+        async fn send(
+            &mut self,
+            instructions: &[Instruction],
+            signers: &[&Keypair],
+        ) -> Result<(), BanksClientError> {
+            let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+            let mut all_signers = vec![&self.context.payer];
+            all_signers.extend_from_slice(signers);
+            let transaction = Transaction::new_signed_with_payer(
+                instructions,
+                Some(&self.context.payer.pubkey()),
+                &all_signers,
+                blockhash,
+            );
+            self.context
+                .banks_client
+                .process_transaction(transaction)
+                .await
+        }
+
+        async fn account_data(&mut self, address: Pubkey) -> Vec<u8> {
+            self.context
+                .banks_client
+                .get_account(address)
+                .await
+                .unwrap()
+                .unwrap()
+                .data
+        }
+
+        async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+            T::try_deserialize(&mut self.account_data(address).await.as_slice()).unwrap()
+        }
+
+        /// A zero-copy account's fields, which follow its discriminator
+        async fn zero_copy<T: bytemuck::Pod>(&mut self, address: Pubkey) -> T {
+            let data = self.account_data(address).await;
+            bytemuck::pod_read_unaligned(&data[8..8 + std::mem::size_of::<T>()])
+        }
+
+        /// Overwrite an account's data in place, for state no instruction
+        /// sets up cheaply
+        async fn patch(&mut self, address: Pubkey, patch: impl FnOnce(&mut Vec<u8>)) {
+            let mut account = self
+                .context
+                .banks_client
+                .get_account(address)
+                .await
+                .unwrap()
+                .unwrap();
+            patch(&mut account.data);
+            self.context
+                .set_account(&address, &AccountSharedData::from(account));
+        }
+
+        async fn update<T: AccountDeserialize + AccountSerialize>(
+            &mut self,
+            address: Pubkey,
+            update: impl FnOnce(&mut T),
+        ) {
+            self.patch(address, |data| {
+                let mut account = T::try_deserialize(&mut data.as_slice()).unwrap();
+                update(&mut account);
+                account.try_serialize(&mut data.as_mut_slice()).unwrap();
+            })
+            .await;
+        }
+
+        async fn update_zero_copy<T: bytemuck::Pod>(
+            &mut self,
+            address: Pubkey,
+            update: impl FnOnce(&mut T),
+        ) {
+            self.patch(address, |data| {
+                let fields = &mut data[8..8 + std::mem::size_of::<T>()];
+                let mut account: T = bytemuck::pod_read_unaligned(fields);
+                update(&mut account);
+                fields.copy_from_slice(bytemuck::bytes_of(&account));
+            })
+            .await;
+        }
+
+        async fn register_agent(
+            &mut self,
+            owner: &Keypair,
+            agent_type: agent_swarm::AgentType,
+            manifest: Vec<u8>,
+        ) -> Result<Pubkey, BanksClientError> {
+            let (agent, _) = agent_swarm::pda::find_agent_address(&self.swarm, &owner.pubkey());
+            let register = instruction(
+                agent_swarm::accounts::RegisterAgent {
+                    swarm_state: self.swarm,
+                    agent,
+                    agent_registry: agent_swarm::pda::find_agent_registry_address(&self.swarm).0,
+                    custom_agent_type: None,
+                    owner: owner.pubkey(),
+                    system_program: system_program::ID,
+                },
+                agent_swarm::instruction::RegisterAgent {
+                    agent_type,
+                    name: "agent".to_string(),
+                    manifest,
+                },
+            );
+            self.send(&[register], &[owner]).await?;
+            Ok(agent)
+        }
+
+        async fn create_proposal(
+            &mut self,
+            proposer: &Keypair,
+            proposal_type: agent_swarm::ProposalType,
+            data: Vec<u8>,
+        ) -> Pubkey {
+            self.propose(proposer, proposal_type, data, None).await
+        }
+
+        /// Create a proposal carrying `data`, or committing to `data_hash`
+        /// with its payload staged later
+        async fn propose(
+            &mut self,
+            proposer: &Keypair,
+            proposal_type: agent_swarm::ProposalType,
+            data: Vec<u8>,
+            data_hash: Option<[u8; 32]>,
+        ) -> Pubkey {
+            let id = self
+                .account::<agent_swarm::state::SwarmState>(self.swarm)
+                .await
+                .total_proposals;
+            let (proposal, _) = agent_swarm::pda::find_proposal_address(&self.swarm, id);
+            let create = instruction(
+                agent_swarm::accounts::CreateProposal {
+                    swarm_state: self.swarm,
+                    agent: agent_swarm::pda::find_agent_address(&self.swarm, &proposer.pubkey()).0,
+                    proposal,
+                    proposal_page: agent_swarm::pda::find_proposal_page_address(&self.swarm, 0).0,
+                    proposer: proposer.pubkey(),
+                    system_program: system_program::ID,
+                    committee: None,
+                    treasury: None,
+                },
+                agent_swarm::instruction::CreateProposal {
+                    proposal_type,
+                    total_data_len: data.len() as u16,
+                    data,
+                    description: "Instruction-level test proposal".to_string(),
+                    objective: None,
+                    data_hash,
+                    condition: None,
+                    tags: vec![],
+                },
+            );
+            self.send(&[create], &[proposer]).await.unwrap();
+            proposal
+        }
+
+        /// Record a unanimous quorum in place of casting each vote
+        async fn approve(&mut self, proposal: Pubkey) {
+            self.update_zero_copy(proposal, |proposal: &mut agent_swarm::state::Proposal| {
+                proposal.votes_for = 3;
+                proposal.weighted_votes_for = 3;
+                proposal.total_voters = 3;
+            })
+            .await;
+        }
+
+        /// `execute_proposal` accounts with every optional account omitted
+        fn execute_accounts(
+            &self,
+            agent: Pubkey,
+            proposal: Pubkey,
+            executor: Pubkey,
+        ) -> agent_swarm::accounts::ExecuteProposal {
+            agent_swarm::accounts::ExecuteProposal {
+                swarm_state: self.swarm,
+                agent,
+                proposal,
+                proposal_page: agent_swarm::pda::find_proposal_page_address(&self.swarm, 0).0,
+                executor,
+                instructions_sysvar: solana_sdk::sysvar::instructions::ID,
+                spend_tracker: None,
+                treasury: None,
+                recipient: None,
+                vault_authority: None,
+                treasury_token_account: None,
+                recipient_token_account: None,
+                mint: None,
+                token_program: None,
+                system_program: Some(system_program::ID),
+                pending_exposure: None,
+                program_whitelist: None,
+                audit_log: None,
+                output_token_account: None,
+                swap_program: None,
+                risk_config: None,
+                price_update_in: None,
+                price_update_out: None,
+                twap_tracker_in: None,
+                twap_tracker_out: None,
+                condition_price_base: None,
+                condition_price_quote: None,
+                tip_account: None,
+                outcome: None,
+                proposer_agent: None,
+                payload_buffer: None,
+                execution_queue: None,
+                rebalance_schedule: None,
+                strategy_config: None,
+                standing_order: None,
+                dca_schedule: None,
+                harvest_config: None,
+                insurance_fund: None,
+                claimed_proposal: None,
+                claimed_outcome: None,
+                cited_attestation: None,
+                portfolio: None,
+                arb_report: None,
+                arb_reporter: None,
+                committee: None,
+                linked_proposal: None,
+                linked_swarm_state: None,
+            }
+        }
+    }
+
+    /// An agent executing the trade its own arbitrage report suggested is
+    /// credited through the executing agent, bounty included
+    #[tokio::test]
+    async fn test_execute_trade_credits_executors_arb_report() {
+        use agent_swarm::constants::{ARB_REPORT_REPUTATION, INITIAL_REPUTATION};
+        use agent_swarm::payloads::{SwapRoute, TradePayload};
+        use agent_swarm::pda;
+        use agent_swarm::state::{Agent, ArbReport, Treasury};
+
+        let jupiter = agent_swarm::cpi::jupiter::PROGRAM_ID;
+        let (swarm, _) = pda::find_swarm_address(0);
+        let (vault_authority, _) = pda::find_vault_authority_address(&swarm);
+        let (pool_authority, _) = Pubkey::find_program_address(&[b"pool"], &jupiter);
+        let (mint_in, mint_out) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (treasury_token_account, output_token_account, pool) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        let mut program_test = program_test();
+        program_test.add_program("jupiter", jupiter, processor!(mock_jupiter_route));
+        program_test.add_account(mint_in, mint_account(6));
+        program_test.add_account(mint_out, mint_account(6));
+        program_test.add_account(
+            treasury_token_account,
+            token_account(mint_in, vault_authority, 1_000),
+        );
+        program_test.add_account(
+            output_token_account,
+            token_account(mint_out, vault_authority, 0),
+        );
+        program_test.add_account(pool, token_account(mint_out, pool_authority, 1_000));
+        let mut test_swarm = TestSwarm::start(program_test).await;
+
+        let owner = test_swarm.payer();
+        let agent = test_swarm
+            .register_agent(&owner, agent_swarm::AgentType::Arbitrage, vec![])
+            .await
+            .unwrap();
+        let payload = TradePayload {
+            mint_in,
+            mint_out,
+            amount: 100,
+            min_out: 90,
+            route: SwapRoute::Jupiter {
+                data: 95u64.to_le_bytes().to_vec(),
+            },
+            tip_lamports: 0,
+        };
+        let route_hash = payload.route_hash().unwrap();
+        let (arb_report, _) = pda::find_arb_report_address(&swarm, &route_hash);
+        let (treasury, _) = pda::find_treasury_address(&swarm);
+        let (spend_tracker, _) = pda::find_spend_tracker_address(&swarm);
+        let (risk_config, _) = pda::find_risk_config_address(&swarm);
+        test_swarm
+            .send(
+                &[
+                    instruction(
+                        agent_swarm::accounts::SubmitArbReport {
+                            swarm_state: swarm,
+                            agent,
+                            arb_report,
+                            reporter: owner.pubkey(),
+                            system_program: system_program::ID,
+                        },
+                        agent_swarm::instruction::SubmitArbReport {
+                            route_hash,
+                            buy_venue: jupiter,
+                            sell_venue: jupiter,
+                            expected_edge_bps: 50,
+                        },
+                    ),
+                    instruction(
+                        agent_swarm::accounts::InitializeTreasury {
+                            swarm_state: swarm,
+                            treasury,
+                            spend_tracker,
+                            authority: owner.pubkey(),
+                            system_program: system_program::ID,
+                        },
+                        agent_swarm::instruction::InitializeTreasury {},
+                    ),
+                    instruction(
+                        agent_swarm::accounts::DepositTreasury {
+                            treasury,
+                            depositor: owner.pubkey(),
+                            system_program: system_program::ID,
+                        },
+                        agent_swarm::instruction::DepositTreasury {
+                            lamports: 1_000_000_000,
+                        },
+                    ),
+                    instruction(
+                        agent_swarm::accounts::InitializeRiskConfig {
+                            swarm_state: swarm,
+                            risk_config,
+                            authority: owner.pubkey(),
+                            system_program: system_program::ID,
+                        },
+                        agent_swarm::instruction::InitializeRiskConfig {},
+                    ),
+                ],
+                &[],
+            )
+            .await
+            .unwrap();
+        test_swarm
+            .update(swarm, |swarm_state: &mut agent_swarm::state::SwarmState| {
+                swarm_state.arb_report_bounty = 5_000
+            })
+            .await;
+
+        let proposal = test_swarm
+            .create_proposal(
+                &owner,
+                agent_swarm::ProposalType::Trade,
+                payload.try_to_vec().unwrap(),
+            )
+            .await;
+        test_swarm.approve(proposal).await;
+        let mut execute = instruction(
+            agent_swarm::accounts::ExecuteProposal {
+                spend_tracker: Some(spend_tracker),
+                treasury: Some(treasury),
+                recipient: Some(owner.pubkey()),
+                vault_authority: Some(vault_authority),
+                treasury_token_account: Some(treasury_token_account),
+                output_token_account: Some(output_token_account),
+                swap_program: Some(jupiter),
+                risk_config: Some(risk_config),
+                portfolio: Some(pda::find_portfolio_address(&swarm).0),
+                arb_report: Some(arb_report),
+                arb_reporter: Some(agent),
+                ..test_swarm.execute_accounts(agent, proposal, owner.pubkey())
+            },
+            agent_swarm::instruction::ExecuteProposal {},
+        );
+        execute.accounts.extend([
+            AccountMeta::new(pool, false),
+            AccountMeta::new(output_token_account, false),
+            AccountMeta::new_readonly(pool_authority, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ]);
+        test_swarm.send(&[execute], &[]).await.unwrap();
+
+        let agent: Agent = test_swarm.zero_copy(agent).await;
+        assert_eq!(agent.reputation, INITIAL_REPUTATION + ARB_REPORT_REPUTATION);
+        let arb_report: ArbReport = test_swarm.account(arb_report).await;
+        assert_eq!(arb_report.credited_proposal, Some(proposal));
+        let treasury: Treasury = test_swarm.account(treasury).await;
+        assert_eq!(treasury.total_withdrawn, 5_000);
+        let output = test_swarm.account_data(output_token_account).await;
+        assert_eq!(
+            spl_token::state::Account::unpack(&output).unwrap().amount,
+            95
+        );
+    }
+
+    /// A swarm holding 1000 USDC and 10 SOL at $100, half in each, with an
+    /// approved `Rebalance` to 80/20 that sells 6 SOL
+    struct RebalanceFixture {
+        test_swarm: TestSwarm,
+        owner: Keypair,
+        agent: Pubkey,
+        proposal: Pubkey,
+        usdc: Pubkey,
+        sol: Pubkey,
+        usdc_price: Pubkey,
+        sol_price: Pubkey,
+    }
+
+    impl RebalanceFixture {
+        async fn start() -> Self {
+            use agent_swarm::oracle::OracleSource;
+            use agent_swarm::payloads::{RebalancePayload, RebalanceTrade, TargetAllocation};
+            use agent_swarm::pda;
+            use agent_swarm::state::{Holding, Portfolio, PriceFeed, SwarmState};
+
+            let (swarm, _) = pda::find_swarm_address(0);
+            let (portfolio, portfolio_bump) = pda::find_portfolio_address(&swarm);
+            let (usdc, sol) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let (usdc_price, sol_price) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let holding = |mint, amount| Holding {
+                mint,
+                amount,
+                target_weight_bps: 5000,
+                updated_at: 0,
+            };
+
+            let mut program_test = program_test();
+            program_test.add_account(
+                portfolio,
+                program_account(
+                    &Portfolio {
+                        version: Portfolio::VERSION,
+                        swarm,
+                        holdings: vec![holding(usdc, 1_000_000_000), holding(sol, 10_000_000_000)],
+                        last_proposal: Pubkey::default(),
+                        updated_at: 0,
+                        bump: portfolio_bump,
+                    },
+                    Portfolio::LEN,
+                ),
+            );
+            let mut test_swarm = TestSwarm::start(program_test).await;
+            let now = test_swarm.now().await;
+            for (address, feed_id, price) in [
+                (usdc_price, [1; 32], 100_000_000),
+                (sol_price, [2; 32], 10_000_000_000),
+            ] {
+                let account = price_update_account(feed_id, price, now);
+                test_swarm
+                    .context
+                    .set_account(&address, &AccountSharedData::from(account));
+            }
+            let feed = |mint, feed_id, decimals| PriceFeed {
+                mint,
+                source: OracleSource::Pyth { feed_id },
+                decimals,
+                max_age: 60,
+                max_deviation_bps: 0,
+            };
+            test_swarm
+                .update(swarm, |swarm_state: &mut SwarmState| {
+                    swarm_state.price_feeds = vec![feed(usdc, [1; 32], 6), feed(sol, [2; 32], 9)];
+                })
+                .await;
+
+            let owner = test_swarm.payer();
+            let agent = test_swarm
+                .register_agent(&owner, agent_swarm::AgentType::RiskManagement, vec![])
+                .await
+                .unwrap();
+            test_swarm
+                .send(
+                    &[instruction(
+                        agent_swarm::accounts::InitializeRiskConfig {
+                            swarm_state: swarm,
+                            risk_config: pda::find_risk_config_address(&swarm).0,
+                            authority: owner.pubkey(),
+                            system_program: system_program::ID,
+                        },
+                        agent_swarm::instruction::InitializeRiskConfig {},
+                    )],
+                    &[],
+                )
+                .await
+                .unwrap();
+            let payload = RebalancePayload {
+                allocations: vec![
+                    TargetAllocation {
+                        mint: usdc,
+                        weight_bps: 8000,
+                    },
+                    TargetAllocation {
+                        mint: sol,
+                        weight_bps: 2000,
+                    },
+                ],
+                schedule: None,
+                trades: vec![RebalanceTrade {
+                    mint_in: sol,
+                    mint_out: usdc,
+                    amount: 6_000_000_000,
+                }],
+            };
+            let proposal = test_swarm
+                .create_proposal(
+                    &owner,
+                    agent_swarm::ProposalType::Rebalance,
+                    payload.try_to_vec().unwrap(),
+                )
+                .await;
+            test_swarm.approve(proposal).await;
+
+            RebalanceFixture {
+                test_swarm,
+                owner,
+                agent,
+                proposal,
+                usdc,
+                sol,
+                usdc_price,
+                sol_price,
+            }
+        }
+
+        /// Execute the rebalance, pricing holdings with `price_updates`
+        async fn execute(&mut self, price_updates: &[Pubkey]) -> Result<(), BanksClientError> {
+            let swarm = self.test_swarm.swarm;
+            let mut execute = instruction(
+                agent_swarm::accounts::ExecuteProposal {
+                    risk_config: Some(agent_swarm::pda::find_risk_config_address(&swarm).0),
+                    portfolio: Some(agent_swarm::pda::find_portfolio_address(&swarm).0),
+                    ..self.test_swarm.execute_accounts(
+                        self.agent,
+                        self.proposal,
+                        self.owner.pubkey(),
+                    )
+                },
+                agent_swarm::instruction::ExecuteProposal {},
+            );
+            execute.accounts.extend(
+                price_updates
+                    .iter()
+                    .map(|price_update| AccountMeta::new_readonly(*price_update, false)),
+            );
+            self.test_swarm.send(&[execute], &[]).await
+        }
+    }
+
+    /// Rebalance trades are priced with one update per holding, each from
+    /// the feed registered for that holding's mint
+    #[tokio::test]
+    async fn test_rebalance_prices_each_holding() {
+        use agent_swarm::errors::SwarmError;
+        use agent_swarm::state::Portfolio;
+
+        let mut fixture = RebalanceFixture::start().await;
+        let (usdc_price, sol_price) = (fixture.usdc_price, fixture.sol_price);
+
+        let err = fixture.execute(&[sol_price, usdc_price]).await.unwrap_err();
+        assert_eq!(program_error(err), u32::from(SwarmError::InvalidPriceFeed));
+
+        fixture.execute(&[usdc_price, sol_price]).await.unwrap();
+        let (swarm, _) = agent_swarm::pda::find_swarm_address(0);
+        let (portfolio, _) = agent_swarm::pda::find_portfolio_address(&swarm);
+        let portfolio: Portfolio = fixture.test_swarm.account(portfolio).await;
+        assert_eq!(
+            portfolio.holding(&fixture.usdc).unwrap().target_weight_bps,
+            8000
+        );
+        assert_eq!(
+            portfolio.holding(&fixture.sol).unwrap().target_weight_bps,
+            2000
+        );
+    }
+
+    /// A rebalance missing a holding's price update is rejected outright
+    #[tokio::test]
+    async fn test_rebalance_requires_every_holding_price() {
+        use agent_swarm::errors::SwarmError;
+
+        let mut fixture = RebalanceFixture::start().await;
+        let usdc_price = fixture.usdc_price;
+
+        let err = fixture.execute(&[usdc_price]).await.unwrap_err();
+        assert_eq!(
+            program_error(err),
+            u32::from(SwarmError::MissingExecutionAccount)
+        );
+        let err = fixture.execute(&[]).await.unwrap_err();
+        assert_eq!(
+            program_error(err),
+            u32::from(SwarmError::MissingExecutionAccount)
+        );
+    }
+
+    /// A hash-committed payload staged in chunks at the executor's buffer
+    /// address executes, and the buffer is closed back to them
+    #[tokio::test]
+    async fn test_execute_committed_payload_from_buffer() {
+        use agent_swarm::pda;
+        use agent_swarm::state::{PayloadBuffer, Proposal};
+        use anchor_lang::solana_program::keccak;
+
+        let mut test_swarm = TestSwarm::start(program_test()).await;
+        let owner = test_swarm.payer();
+        let agent = test_swarm
+            .register_agent(&owner, agent_swarm::AgentType::Analytics, vec![])
+            .await
+            .unwrap();
+
+        // Free-form strategy notes, which only signal once executed
+        let payload = b"rotate the treasury into stables ahead of the unlock".to_vec();
+        let proposal = test_swarm
+            .propose(
+                &owner,
+                agent_swarm::ProposalType::Strategy,
+                vec![],
+                Some(keccak::hash(&payload).to_bytes()),
+            )
+            .await;
+        test_swarm.approve(proposal).await;
+
+        let (payload_buffer, _) = pda::find_payload_buffer_address(&proposal, &owner.pubkey());
+        let (head, tail) = payload.split_at(16);
+        let create = instruction(
+            agent_swarm::accounts::CreatePayloadBuffer {
+                proposal,
+                payload_buffer,
+                owner: owner.pubkey(),
+                system_program: system_program::ID,
+            },
+            agent_swarm::instruction::CreatePayloadBuffer {
+                chunk: head.to_vec(),
+            },
+        );
+        let extend = instruction(
+            agent_swarm::accounts::ExtendPayloadBuffer {
+                payload_buffer,
+                owner: owner.pubkey(),
+                system_program: system_program::ID,
+            },
+            agent_swarm::instruction::ExtendPayloadBuffer {
+                chunk: tail.to_vec(),
+            },
+        );
+        test_swarm.send(&[create, extend], &[]).await.unwrap();
+
+        let staged = test_swarm.account::<PayloadBuffer>(payload_buffer).await;
+        assert_eq!(staged.proposal, proposal);
+        assert_eq!(staged.data, payload);
+
+        let execute = instruction(
+            agent_swarm::accounts::ExecuteProposal {
+                payload_buffer: Some(payload_buffer),
+                ..test_swarm.execute_accounts(agent, proposal, owner.pubkey())
+            },
+            agent_swarm::instruction::ExecuteProposal {},
+        );
+        test_swarm.send(&[execute], &[]).await.unwrap();
+
+        assert!(test_swarm
+            .zero_copy::<Proposal>(proposal)
+            .await
+            .is_executed());
+        assert!(test_swarm
+            .context
+            .banks_client
+            .get_account(payload_buffer)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    /// A full-length format 2 manifest announcing CrossChain support, a bit
+    /// past the first byte of the mask, is admitted and stored intact
+    #[tokio::test]
+    async fn test_register_agent_stores_cross_chain_manifest() {
+        use agent_swarm::manifest::*;
+        use agent_swarm::state::{Agent, SwarmState};
+        use agent_swarm::ProposalType;
+
+        let mut test_swarm = TestSwarm::start(program_test()).await;
+        let swarm = test_swarm.swarm;
+        test_swarm
+            .update(swarm, |swarm_state: &mut SwarmState| {
+                swarm_state.required_capabilities = CAP_EXECUTION;
+            })
+            .await;
+
+        let manifest = AgentManifest {
+            version: [2, 0, 0],
+            capabilities: CAP_EXECUTION,
+            supported_proposal_types: proposal_type_bit(&ProposalType::Trade)
+                | proposal_type_bit(&ProposalType::CrossChain),
+            required_oracles: (0..MAX_MANIFEST_ORACLES)
+                .map(|_| Pubkey::new_unique())
+                .collect(),
+        };
+        assert_eq!(manifest.to_bytes().len(), MAX_MANIFEST_LENGTH);
+
+        let owner = test_swarm.payer();
+        let agent = test_swarm
+            .register_agent(
+                &owner,
+                agent_swarm::AgentType::Execution,
+                manifest.to_bytes(),
+            )
+            .await
+            .unwrap();
+
+        let stored = test_swarm.zero_copy::<Agent>(agent).await;
+        let parsed = AgentManifest::parse(stored.manifest()).unwrap();
+        assert_eq!(parsed, manifest);
+        assert!(parsed.supports(&ProposalType::CrossChain));
+        assert!(!parsed.supports(&ProposalType::Strategy));
+    }
+
+    /// A failed proposal stays open while a bounty or endorsement still
+    /// needs it, and is closable once both have settled
+    #[tokio::test]
+    async fn test_close_failed_proposal_waits_for_open_claims() {
+        use agent_swarm::errors::SwarmError;
+        use agent_swarm::pda;
+        use agent_swarm::state::{Proposal, SwarmState};
+
+        let mut test_swarm = TestSwarm::start(program_test()).await;
+        let swarm = test_swarm.swarm;
+        test_swarm
+            .update(swarm, |swarm_state: &mut SwarmState| {
+                swarm_state.max_execution_attempts = 1;
+            })
+            .await;
+
+        let proposer = test_swarm.payer();
+        let endorser = Keypair::new();
+        let fund = solana_sdk::system_instruction::transfer(
+            &proposer.pubkey(),
+            &endorser.pubkey(),
+            1_000_000_000,
+        );
+        test_swarm.send(&[fund], &[]).await.unwrap();
+        let proposer_agent = test_swarm
+            .register_agent(&proposer, agent_swarm::AgentType::Analytics, vec![])
+            .await
+            .unwrap();
+        let endorser_agent = test_swarm
+            .register_agent(&endorser, agent_swarm::AgentType::Analytics, vec![])
+            .await
+            .unwrap();
+        let proposal = test_swarm
+            .create_proposal(
+                &proposer,
+                agent_swarm::ProposalType::Strategy,
+                b"rotate the treasury into stables".to_vec(),
+            )
+            .await;
+        test_swarm.approve(proposal).await;
+
+        let (bounty, _) = pda::find_bounty_address(&proposal, &proposer.pubkey());
+        let attach = instruction(
+            agent_swarm::accounts::AttachBounty {
+                proposal,
+                bounty,
+                funder: proposer.pubkey(),
+                funder_token_account: None,
+                escrow_token_account: None,
+                token_program: None,
+                system_program: system_program::ID,
+            },
+            agent_swarm::instruction::AttachBounty { amount: 1_000_000 },
+        );
+        let (endorsement, _) = pda::find_endorsement_address(&proposal, &endorser.pubkey());
+        let endorse = instruction(
+            agent_swarm::accounts::EndorseProposal {
+                agent: endorser_agent,
+                proposal,
+                endorsement,
+                endorser: endorser.pubkey(),
+                system_program: system_program::ID,
+            },
+            agent_swarm::instruction::EndorseProposal { stake: 10 },
+        );
+        test_swarm
+            .send(&[attach, endorse], &[&endorser])
+            .await
+            .unwrap();
+
+        let open = test_swarm.zero_copy::<Proposal>(proposal).await;
+        assert_eq!((open.open_bounties, open.open_endorsements), (1, 1));
+
+        // The proposer's report uses up the only execution attempt
+        let proposal_page = pda::find_proposal_page_address(&swarm, 0).0;
+        let report = instruction(
+            agent_swarm::accounts::ReportExecutionFailure {
+                swarm_state: swarm,
+                agent: proposer_agent,
+                proposal,
+                proposal_page,
+                executor: proposer.pubkey(),
+                committee: None,
+            },
+            agent_swarm::instruction::ReportExecutionFailure { error_code: 1 },
+        );
+        test_swarm.send(&[report], &[]).await.unwrap();
+        assert!(test_swarm.zero_copy::<Proposal>(proposal).await.is_failed());
+
+        let close = || {
+            instruction(
+                agent_swarm::accounts::CloseFailedProposal {
+                    proposal,
+                    proposer_agent,
+                    proposal_page,
+                    proposer: proposer.pubkey(),
+                },
+                agent_swarm::instruction::CloseFailedProposal {},
+            )
+        };
+        let err = test_swarm.send(&[close()], &[]).await.unwrap_err();
+        assert_eq!(
+            program_error(err),
+            u32::from(SwarmError::ProposalHasOpenClaims)
+        );
+
+        let refund = instruction(
+            agent_swarm::accounts::RefundBounty {
+                proposal,
+                outcome: None,
+                bounty,
+                funder: proposer.pubkey(),
+                escrow_token_account: None,
+                funder_token_account: None,
+                token_program: None,
+                cranker: proposer.pubkey(),
+            },
+            agent_swarm::instruction::RefundBounty {},
+        );
+        test_swarm.send(&[refund], &[]).await.unwrap();
+        let open = test_swarm.zero_copy::<Proposal>(proposal).await;
+        assert_eq!((open.open_bounties, open.open_endorsements), (0, 1));
+
+        // The endorsement still holds the proposal open
+        let err = test_swarm.send(&[close()], &[]).await.unwrap_err();
+        assert_eq!(
+            program_error(err),
+            u32::from(SwarmError::ProposalHasOpenClaims)
+        );
+
+        let settle = instruction(
+            agent_swarm::accounts::SettleEndorsement {
+                proposal,
+                outcome: None,
+                endorsement,
+                agent: endorser_agent,
+                cranker: proposer.pubkey(),
+            },
+            agent_swarm::instruction::SettleEndorsement {},
+        );
+        test_swarm.send(&[settle], &[]).await.unwrap();
+        let open = test_swarm.zero_copy::<Proposal>(proposal).await;
+        assert_eq!((open.open_bounties, open.open_endorsements), (0, 0));
+
+        test_swarm.send(&[close()], &[]).await.unwrap();
+        assert!(test_swarm
+            .context
+            .banks_client
+            .get_account(proposal)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
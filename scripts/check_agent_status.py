@@ -26,6 +26,7 @@ async def check_agent_status():
     # Load configuration
     rpc_url = os.getenv("RPC_URL", "https://api.devnet.solana.com")
     program_id = Pubkey.from_string(os.getenv("PROGRAM_ID"))
+    swarm_id = int(os.getenv("SWARM_ID", 0))
     idl_path = os.getenv("IDL_PATH")
     authority_keypair_path = os.getenv("AUTHORITY_KEYPAIR")
     
@@ -47,12 +48,15 @@ async def check_agent_status():
     
     try:
         # Get swarm state
-        swarm_pda, _ = Pubkey.find_program_address([b"swarm"], program_id)
+        swarm_pda, _ = Pubkey.find_program_address(
+            [b"swarm", swarm_id.to_bytes(8, 'little')], program_id
+        )
         swarm_state = await program.account["SwarmState"].fetch(swarm_pda)
         
         logger.info("=" * 60)
         logger.info("SWARM STATUS")
         logger.info("=" * 60)
+        logger.info(f"Swarm ID: {swarm_state.swarm_id}")
         logger.info(f"Authority: {swarm_state.authority}")
         logger.info(f"Active Agents: {swarm_state.active_agents}/{swarm_state.max_agents}")
         logger.info(f"Total Proposals: {swarm_state.total_proposals}")
@@ -92,7 +96,7 @@ async def check_agent_status():
             
             # Get agent PDA
             agent_pda, _ = Pubkey.find_program_address(
-                [b"agent", bytes(agent_keypair.pubkey())],
+                [b"agent", bytes(swarm_pda), bytes(agent_keypair.pubkey())],
                 program_id
             )
             
@@ -33,6 +33,8 @@ if not PROGRAM_ID_STR:
     raise ValueError("PROGRAM_ID environment variable not set")
 
 PROGRAM_ID = Pubkey.from_string(PROGRAM_ID_STR)
+# Swarm to act in; one deployment hosts many swarms, each seeded by its id
+SWARM_ID = int(os.getenv("SWARM_ID", 0))
 
 # Agent configurations
 AGENTS = {
@@ -57,9 +59,11 @@ async def create_proposal(client: AsyncClient, proposer_kp: Keypair, description
         print(f"   Description: {description}")
         
         # Derive PDAs
-        swarm_state_pda, _ = Pubkey.find_program_address([b"swarm"], PROGRAM_ID)
+        swarm_state_pda, _ = Pubkey.find_program_address(
+            [b"swarm", struct.pack('<Q', SWARM_ID)], PROGRAM_ID
+        )
         agent_pda, _ = Pubkey.find_program_address(
-            [b"agent", bytes(proposer_kp.pubkey())],
+            [b"agent", bytes(swarm_state_pda), bytes(proposer_kp.pubkey())],
             PROGRAM_ID
         )
         
@@ -71,14 +75,14 @@ async def create_proposal(client: AsyncClient, proposer_kp: Keypair, description
         
         # Parse total_proposals from account data (offset 8 for discriminator + layout)
         account_data = swarm_account.value.data
-        # SwarmState layout: discriminator(8) + authority(32) + max_agents(1) + active_agents(1) + 
-        # min_votes_required(1) + proposal_timeout(8) + total_proposals(8)
-        total_proposals = struct.unpack_from('<Q', account_data, 8 + 32 + 1 + 1 + 1 + 8)[0]
+        # SwarmState layout: discriminator(8) + version(1) + swarm_id(8) + authority(32) +
+        # max_agents(1) + active_agents(1) + min_votes_required(1) + proposal_timeout(8) + total_proposals(8)
+        total_proposals = struct.unpack_from('<Q', account_data, 8 + 1 + 8 + 32 + 1 + 1 + 1 + 8)[0]
         print(f"   Current proposal count: {total_proposals}")
         
         # Derive proposal PDA using total_proposals
         proposal_pda, _ = Pubkey.find_program_address(
-            [b"proposal", struct.pack('<Q', total_proposals)],
+            [b"proposal", bytes(swarm_state_pda), struct.pack('<Q', total_proposals)],
             PROGRAM_ID
         )
         print(f"   Proposal PDA: {proposal_pda}")
@@ -87,7 +91,7 @@ async def create_proposal(client: AsyncClient, proposer_kp: Keypair, description
         # Discriminator for create_proposal
         discriminator = bytes([132, 116, 68, 174, 216, 160, 198, 22])
         
-        # Instruction data: discriminator + proposal_type (u8) + data (Vec<u8>) + description (String)
+        # Instruction data: discriminator + proposal_type (u8) + data (Vec<u8>) + description (String) + objective (Option<[u8; 32]>)
         proposal_type = 0  # Rebalance
         data_vec = b""  # Empty data for demo
         data_length = len(data_vec)
@@ -100,7 +104,8 @@ async def create_proposal(client: AsyncClient, proposer_kp: Keypair, description
             discriminator + 
             struct.pack('<B', proposal_type) + 
             struct.pack('<I', data_length) + data_vec +
-            struct.pack('<I', desc_length) + desc_bytes
+            struct.pack('<I', desc_length) + desc_bytes +
+            struct.pack('<B', 0)  # objective: None
         )
         
         keys = [
@@ -144,13 +149,19 @@ async def vote_on_proposal(client: AsyncClient, voter_kp: Keypair, proposal_coun
         print(f"   {agent_name}: {vote_str}")
         
         # Derive PDAs
-        swarm_state_pda, _ = Pubkey.find_program_address([b"swarm"], PROGRAM_ID)
+        swarm_state_pda, _ = Pubkey.find_program_address(
+            [b"swarm", struct.pack('<Q', SWARM_ID)], PROGRAM_ID
+        )
         proposal_pda, _ = Pubkey.find_program_address(
-            [b"proposal", struct.pack('<Q', proposal_counter)],
+            [b"proposal", bytes(swarm_state_pda), struct.pack('<Q', proposal_counter)],
             PROGRAM_ID
         )
         agent_pda, _ = Pubkey.find_program_address(
-            [b"agent", bytes(voter_kp.pubkey())],
+            [b"agent", bytes(swarm_state_pda), bytes(voter_kp.pubkey())],
+            PROGRAM_ID
+        )
+        vote_record_pda, _ = Pubkey.find_program_address(
+            [b"vote", bytes(proposal_pda), bytes(voter_kp.pubkey())],
             PROGRAM_ID
         )
         
@@ -170,7 +181,9 @@ async def vote_on_proposal(client: AsyncClient, voter_kp: Keypair, proposal_coun
             AccountMeta(pubkey=swarm_state_pda, is_signer=False, is_writable=False),
             AccountMeta(pubkey=agent_pda, is_signer=False, is_writable=True),
             AccountMeta(pubkey=proposal_pda, is_signer=False, is_writable=True),
-            AccountMeta(pubkey=voter_kp.pubkey(), is_signer=True, is_writable=False),
+            AccountMeta(pubkey=vote_record_pda, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=voter_kp.pubkey(), is_signer=True, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
         ]
         
         ix = Instruction(PROGRAM_ID, instruction_data, keys)
@@ -29,6 +29,7 @@ async def initialize_swarm():
     # Load configuration
     rpc_url = os.getenv("RPC_URL", "https://api.devnet.solana.com")
     program_id = Pubkey.from_string(os.getenv("PROGRAM_ID"))
+    swarm_id = int(os.getenv("SWARM_ID", 0))
     idl_path = os.getenv("IDL_PATH")
     authority_keypair_path = os.getenv("AUTHORITY_KEYPAIR")
     
@@ -52,8 +53,10 @@ async def initialize_swarm():
     provider = Provider(client, wallet)
     program = Program(idl, program_id, provider)
     
-    # Get swarm PDA
-    swarm_pda, bump = Pubkey.find_program_address([b"swarm"], program_id)
+    # Get swarm PDA; one deployment hosts many swarms, each seeded by its id
+    swarm_pda, bump = Pubkey.find_program_address(
+        [b"swarm", swarm_id.to_bytes(8, 'little')], program_id
+    )
     logger.info(f"Swarm PDA: {swarm_pda}")
     
     # Configuration
@@ -62,6 +65,7 @@ async def initialize_swarm():
     proposal_timeout = int(os.getenv("PROPOSAL_TIMEOUT", 3600))
     
     logger.info(f"Configuration:")
+    logger.info(f"  Swarm ID: {swarm_id}")
     logger.info(f"  Max Agents: {max_agents}")
     logger.info(f"  Min Votes Required: {min_votes_required}")
     logger.info(f"  Proposal Timeout: {proposal_timeout}s")
@@ -81,6 +85,7 @@ async def initialize_swarm():
         logger.info("Initializing swarm...")
         
         tx = await program.rpc["initialize"](
+            swarm_id,
             max_agents,
             min_votes_required,
             proposal_timeout,
@@ -26,6 +26,8 @@ if not PROGRAM_ID_STR:
     raise ValueError("PROGRAM_ID environment variable not set")
 
 PROGRAM_ID = Pubkey.from_string(PROGRAM_ID_STR)
+# Swarm to act in; one deployment hosts many swarms, each seeded by its id
+SWARM_ID = int(os.getenv("SWARM_ID", 0))
 
 # Agent configurations
 AGENTS = [
@@ -72,9 +74,11 @@ async def register_agent(client: AsyncClient, agent_config: dict) -> bool:
         print(f"   Address: {agent_pubkey}")
         
         # Derive PDAs
-        swarm_state_pda, _ = Pubkey.find_program_address([b"swarm"], PROGRAM_ID)
+        swarm_state_pda, _ = Pubkey.find_program_address(
+            [b"swarm", SWARM_ID.to_bytes(8, 'little')], PROGRAM_ID
+        )
         agent_pda, _ = Pubkey.find_program_address(
-            [b"agent", bytes(agent_pubkey)], 
+            [b"agent", bytes(swarm_state_pda), bytes(agent_pubkey)],
             PROGRAM_ID
         )
         
@@ -90,13 +94,14 @@ async def register_agent(client: AsyncClient, agent_config: dict) -> bool:
         # Discriminator for register_agent (sha256("global:register_agent")[:8])
         discriminator = bytes([135, 157, 66, 195, 2, 113, 175, 30])
         
-        # Instruction data: discriminator + agent_type (u8) + name (String with length prefix)
+        # Instruction data: discriminator + agent_type (u8) + name (String with length prefix) + manifest (Vec<u8>)
         agent_name = agent_config["name"]
         name_bytes = agent_name.encode('utf-8')
         name_length = len(name_bytes)
         
         # Anchor String format: 4-byte length prefix + string bytes
         instruction_data = discriminator + struct.pack('<B', agent_config["agent_type"]) + struct.pack('<I', name_length) + name_bytes
+        instruction_data += struct.pack('<I', 0)  # empty manifest
         
         # Create instruction
         keys = [
@@ -145,8 +150,10 @@ async def register_all_agents():
     print(f"RPC URL: {RPC_URL}")
     
     # Derive swarm state PDA
-    swarm_state_pda, _ = Pubkey.find_program_address([b"swarm"], PROGRAM_ID)
-    print(f"Swarm State: {swarm_state_pda}")
+    swarm_state_pda, _ = Pubkey.find_program_address(
+        [b"swarm", SWARM_ID.to_bytes(8, 'little')], PROGRAM_ID
+    )
+    print(f"Swarm State ({SWARM_ID}): {swarm_state_pda}")
     
     client = AsyncClient(RPC_URL, commitment=Confirmed, timeout=30)
     
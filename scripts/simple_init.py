@@ -23,6 +23,8 @@ load_dotenv()
 RPC_URL = os.getenv("RPC_URL", "https://api.devnet.solana.com")
 PROGRAM_ID = Pubkey.from_string(os.getenv("PROGRAM_ID"))
 AUTHORITY_KEYPAIR_PATH = os.getenv("AUTHORITY_KEYPAIR")
+# Swarm to create; one deployment hosts many swarms, each seeded by its id
+SWARM_ID = int(os.getenv("SWARM_ID", 0))
 
 def load_keypair(path: str) -> Keypair:
     """Load keypair from file"""
@@ -42,7 +44,7 @@ async def initialize_swarm():
     print(f"Program ID: {PROGRAM_ID}")
     
     # Derive swarm state PDA
-    swarm_state_seeds = [b"swarm"]
+    swarm_state_seeds = [b"swarm", struct.pack('<Q', SWARM_ID)]
     swarm_state_pda, bump = Pubkey.find_program_address(swarm_state_seeds, PROGRAM_ID)
     print(f"Swarm State PDA: {swarm_state_pda}")
     
@@ -60,12 +62,12 @@ async def initialize_swarm():
         # Discriminator for initialize (first 8 bytes of sha256("global:initialize"))
         discriminator = bytes([175, 175, 109, 31, 13, 152, 155, 237])
         
-        # Instruction data: discriminator + max_agents (u8) + min_votes_required (u8) + proposal_timeout (i64)
+        # Instruction data: discriminator + swarm_id (u64) + max_agents (u8) + min_votes_required (u8) + proposal_timeout (i64)
         max_agents = 5  # 5 agents
         min_votes_required = 3  # 3 out of 5 agents for consensus
         proposal_timeout = 3600  # 1 hour in seconds
         
-        instruction_data = discriminator + struct.pack('<BBq', max_agents, min_votes_required, proposal_timeout)
+        instruction_data = discriminator + struct.pack('<QBBq', SWARM_ID, max_agents, min_votes_required, proposal_timeout)
         
         # Create instruction
         keys = [
@@ -12,6 +12,8 @@ load_dotenv()
 
 RPC_URL = os.getenv("RPC_URL", "https://api.devnet.solana.com")
 PROGRAM_ID = Pubkey.from_string(os.getenv("PROGRAM_ID"))
+# Swarm to verify; one deployment hosts many swarms, each seeded by its id
+SWARM_ID = int(os.getenv("SWARM_ID", 0))
 
 async def verify_deployment():
     """Verify all on-chain accounts"""
@@ -27,8 +29,10 @@ async def verify_deployment():
         print(f"🔗 RPC: {RPC_URL}")
         
         # Verify SwarmState
-        swarm_pda, _ = Pubkey.find_program_address([b"swarm"], PROGRAM_ID)
-        print(f"\n📦 SwarmState PDA: {swarm_pda}")
+        swarm_pda, _ = Pubkey.find_program_address(
+            [b"swarm", SWARM_ID.to_bytes(8, 'little')], PROGRAM_ID
+        )
+        print(f"\n📦 SwarmState PDA (swarm {SWARM_ID}): {swarm_pda}")
         
         swarm_account = await client.get_account_info(swarm_pda)
         if swarm_account.value:
@@ -51,7 +55,7 @@ async def verify_deployment():
         for name, address in agents.items():
             agent_pubkey = Pubkey.from_string(address)
             agent_pda, _ = Pubkey.find_program_address(
-                [b"agent", bytes(agent_pubkey)],
+                [b"agent", bytes(swarm_pda), bytes(agent_pubkey)],
                 PROGRAM_ID
             )
             
@@ -66,7 +70,7 @@ async def verify_deployment():
         print(f"\n📝 Proposals:")
         for i in range(6):  # Check first 6 proposals
             proposal_pda, _ = Pubkey.find_program_address(
-                [b"proposal", i.to_bytes(8, 'little')],
+                [b"proposal", bytes(swarm_pda), i.to_bytes(8, 'little')],
                 PROGRAM_ID
             )
             
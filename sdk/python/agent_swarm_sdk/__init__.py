@@ -6,6 +6,7 @@ from .agent import Agent, AgentType, AgentConfig
 from .proposal import Proposal, ProposalType, VoteType
 from .governance import GovernanceCoalition
 from .utils import generate_keypair, load_keypair, get_token_balance
+from . import pda
 
 __version__ = "0.1.0"
 
@@ -21,4 +22,5 @@ __all__ = [
     "generate_keypair",
     "load_keypair",
     "get_token_balance",
+    "pda",
 ]
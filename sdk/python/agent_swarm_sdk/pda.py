@@ -0,0 +1,640 @@
+"""
+PDA derivation for every Agent Swarm program account
+
+Mirrors programs/agent_swarm/src/pda.rs so Python clients derive the same
+addresses as the program constraints. Swarm-scoped accounts hang off the
+swarm address, which is itself seeded by the swarm id.
+"""
+from typing import Tuple
+from solders.pubkey import Pubkey
+
+PROGRAM_ID = Pubkey.from_string("56Vy8e8V4E6UZnsa6uDRg8HFiPwroz6nRKh7rm9xAfeK")
+
+SWARM_SEED = b"swarm"
+AGENT_SEED = b"agent"
+AGENT_REGISTRY_SEED = b"agent_registry"
+CUSTOM_AGENT_TYPE_SEED = b"custom_agent_type"
+EXECUTION_QUEUE_SEED = b"execution_queue"
+REBALANCE_SCHEDULE_SEED = b"rebalance_schedule"
+STRATEGY_CONFIG_SEED = b"strategy_config"
+PORTFOLIO_SEED = b"portfolio"
+STANDING_ORDER_SEED = b"standing_order"
+DCA_SCHEDULE_SEED = b"dca_schedule"
+ARB_REPORT_SEED = b"arb_report"
+HARVEST_CONFIG_SEED = b"harvest_config"
+INSURANCE_FUND_SEED = b"insurance_fund"
+TASK_SEED = b"task"
+COALITION_SEED = b"coalition"
+COALITION_BALLOT_SEED = b"coalition_ballot"
+COMMITTEE_SEED = b"committee"
+AGENT_IDENTITY_SEED = b"agent_identity"
+MEMBERSHIP_SEED = b"membership"
+BADGE_CONFIG_SEED = b"badge_config"
+BADGE_SEED = b"badge"
+ATTESTATION_SEED = b"attestation"
+CROSS_CHAIN_EMITTER_SEED = b"cross_chain_emitter"
+CROSS_CHAIN_MESSAGE_SEED = b"cross_chain_message"
+RANDOMNESS_SEED = b"randomness"
+PROPOSAL_SEED = b"proposal"
+PROPOSAL_PAGE_SEED = b"proposal_page"
+OUTCOME_SEED = b"outcome"
+VOTE_RECORD_SEED = b"vote"
+ELO_MATCH_SEED = b"elo_match"
+ENDORSEMENT_SEED = b"endorsement"
+PAYLOAD_BUFFER_SEED = b"payload_buffer"
+TREASURY_SEED = b"treasury"
+VAULT_AUTHORITY_SEED = b"vault_authority"
+PROGRAM_WHITELIST_SEED = b"program_whitelist"
+AUDIT_LOG_SEED = b"audit_log"
+RISK_CONFIG_SEED = b"risk_config"
+TWAP_TRACKER_SEED = b"twap_tracker"
+OUTCOME_ARCHIVE_SEED = b"outcome_archive"
+SPEND_TRACKER_SEED = b"spend_tracker"
+BOUNTY_SEED = b"bounty"
+PENDING_EXPOSURE_SEED = b"pending_exposure"
+BOND_SEED = b"bond"
+SLASH_SEED = b"slash"
+EPOCH_REWARDS_SEED = b"epoch_rewards"
+EPOCH_STATS_SEED = b"epoch_stats"
+VOTE_ESCROW_SEED = b"vote_escrow"
+GOVERNANCE_MINT_SEED = b"governance_mint"
+MARKETPLACE_SEED = b"marketplace"
+LISTING_SEED = b"listing"
+LISTING_INDEX_SEED = b"listing_index"
+LISTING_VERSION_SEED = b"listing_version"
+RATING_SEED = b"rating"
+REVIEW_SEED = b"review"
+SUBSCRIPTION_SEED = b"subscription"
+AUCTION_SEED = b"auction"
+BID_SEED = b"bid"
+PURCHASE_SEED = b"purchase"
+
+
+def _u64(value: int) -> bytes:
+    return value.to_bytes(8, "little")
+
+
+def find_swarm_address(
+    swarm_id: int,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Configuration account of swarm `swarm_id`"""
+    return Pubkey.find_program_address([SWARM_SEED, _u64(swarm_id)], program_id)
+
+
+def find_agent_address(
+    swarm: Pubkey,
+    owner: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Agent account owned by `owner` in `swarm`"""
+    return Pubkey.find_program_address(
+        [AGENT_SEED, bytes(swarm), bytes(owner)], program_id
+    )
+
+
+def find_agent_registry_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Registry enumerating every agent of `swarm`"""
+    return Pubkey.find_program_address([AGENT_REGISTRY_SEED, bytes(swarm)], program_id)
+
+
+def find_custom_agent_type_address(
+    swarm: Pubkey,
+    id: int,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Registration of `AgentType::Custom(id)` in `swarm`"""
+    return Pubkey.find_program_address(
+        [CUSTOM_AGENT_TYPE_SEED, bytes(swarm), bytes([id])], program_id
+    )
+
+
+def find_execution_queue_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Scheduled proposals of `swarm` awaiting keepers"""
+    return Pubkey.find_program_address([EXECUTION_QUEUE_SEED, bytes(swarm)], program_id)
+
+
+def find_rebalance_schedule_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Recurring rebalance approved in `swarm`"""
+    return Pubkey.find_program_address(
+        [REBALANCE_SCHEDULE_SEED, bytes(swarm)], program_id
+    )
+
+
+def find_strategy_config_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Strategy parameters adopted by `swarm`'s `Strategy` proposals"""
+    return Pubkey.find_program_address([STRATEGY_CONFIG_SEED, bytes(swarm)], program_id)
+
+
+def find_portfolio_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Treasury holdings and target weights of `swarm`"""
+    return Pubkey.find_program_address([PORTFOLIO_SEED, bytes(swarm)], program_id)
+
+
+def find_standing_order_address(
+    proposal: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Standing order placed by the `Strategy` proposal `proposal`"""
+    return Pubkey.find_program_address(
+        [STANDING_ORDER_SEED, bytes(proposal)], program_id
+    )
+
+
+def find_dca_schedule_address(
+    proposal: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """DCA schedule created by the `DcaSchedule` proposal `proposal`"""
+    return Pubkey.find_program_address([DCA_SCHEDULE_SEED, bytes(proposal)], program_id)
+
+
+def find_arb_report_address(
+    swarm: Pubkey,
+    route_hash: bytes,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Arbitrage report of the route hashing to `route_hash` in `swarm`"""
+    return Pubkey.find_program_address(
+        [ARB_REPORT_SEED, bytes(swarm), route_hash], program_id
+    )
+
+
+def find_harvest_config_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Harvest settings and registered positions of `swarm`"""
+    return Pubkey.find_program_address([HARVEST_CONFIG_SEED, bytes(swarm)], program_id)
+
+
+def find_insurance_fund_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Insurance fund reimbursing `swarm`'s treasury"""
+    return Pubkey.find_program_address([INSURANCE_FUND_SEED, bytes(swarm)], program_id)
+
+
+def find_task_address(
+    swarm: Pubkey,
+    creator: Pubkey,
+    task_id: int,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Task `task_id` created by `creator` in `swarm`"""
+    return Pubkey.find_program_address(
+        [TASK_SEED, bytes(swarm), bytes(creator), _u64(task_id)], program_id
+    )
+
+
+def find_coalition_address(
+    swarm: Pubkey,
+    founder: Pubkey,
+    coalition_id: int,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Coalition `coalition_id` formed by `founder` in `swarm`"""
+    return Pubkey.find_program_address(
+        [COALITION_SEED, bytes(swarm), bytes(founder), _u64(coalition_id)], program_id
+    )
+
+
+def find_coalition_ballot_address(
+    coalition: Pubkey,
+    proposal: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Internal ballot of `coalition` on `proposal`"""
+    return Pubkey.find_program_address(
+        [COALITION_BALLOT_SEED, bytes(coalition), bytes(proposal)], program_id
+    )
+
+
+def find_committee_address(
+    swarm: Pubkey,
+    committee_id: int,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Committee `committee_id` of `swarm`"""
+    return Pubkey.find_program_address(
+        [COMMITTEE_SEED, bytes(swarm), bytes([committee_id])], program_id
+    )
+
+
+def find_agent_identity_address(
+    owner: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Cross-swarm identity of agents owned by `owner`"""
+    return Pubkey.find_program_address([AGENT_IDENTITY_SEED, bytes(owner)], program_id)
+
+
+def find_membership_address(
+    identity: Pubkey,
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Link between `identity` and its agent in `swarm`"""
+    return Pubkey.find_program_address(
+        [MEMBERSHIP_SEED, bytes(identity), bytes(swarm)], program_id
+    )
+
+
+def find_badge_config_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Badge rules of `swarm`, also the delegate of its badge tree"""
+    return Pubkey.find_program_address([BADGE_CONFIG_SEED, bytes(swarm)], program_id)
+
+
+def find_agent_badge_address(
+    agent: Pubkey,
+    kind_index: int,
+    epoch: int,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Receipt of `agent`'s badge of `kind_index`; `epoch` is 0 for milestones"""
+    return Pubkey.find_program_address(
+        [BADGE_SEED, bytes(agent), bytes([kind_index]), _u64(epoch)], program_id
+    )
+
+
+def find_attestation_address(
+    agent: Pubkey,
+    payload_hash: bytes,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """`agent`'s attestation of the output hashing to `payload_hash`"""
+    return Pubkey.find_program_address(
+        [ATTESTATION_SEED, bytes(agent), payload_hash], program_id
+    )
+
+
+def find_cross_chain_emitter_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Wormhole sequence tracker of `swarm`"""
+    return Pubkey.find_program_address(
+        [CROSS_CHAIN_EMITTER_SEED, bytes(swarm)], program_id
+    )
+
+
+def find_cross_chain_message_address(
+    proposal: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Wormhole message account posted for an executed CrossChain `proposal`"""
+    return Pubkey.find_program_address(
+        [CROSS_CHAIN_MESSAGE_SEED, bytes(proposal)], program_id
+    )
+
+
+def find_randomness_request_address(
+    proposal: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Pending randomness commit for `proposal`"""
+    return Pubkey.find_program_address([RANDOMNESS_SEED, bytes(proposal)], program_id)
+
+
+def find_proposal_address(
+    swarm: Pubkey,
+    proposal_id: int,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Proposal number `proposal_id` in `swarm` (its `total_proposals` at creation)"""
+    return Pubkey.find_program_address(
+        [PROPOSAL_SEED, bytes(swarm), _u64(proposal_id)], program_id
+    )
+
+
+def find_proposal_page_address(
+    swarm: Pubkey,
+    page: int,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """
+    Page `page` of `swarm`'s proposal registry, holding proposals
+    `page * PROPOSAL_PAGE_SIZE` onwards
+    """
+    return Pubkey.find_program_address(
+        [PROPOSAL_PAGE_SEED, bytes(swarm), _u64(page)], program_id
+    )
+
+
+def find_outcome_address(
+    proposal: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Execution outcome recorded for `proposal`"""
+    return Pubkey.find_program_address([OUTCOME_SEED, bytes(proposal)], program_id)
+
+
+def find_vote_record_address(
+    proposal: Pubkey,
+    voter: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Vote cast by `voter` on `proposal`"""
+    return Pubkey.find_program_address(
+        [VOTE_RECORD_SEED, bytes(proposal), bytes(voter)], program_id
+    )
+
+
+def find_elo_match_address(
+    winner: Pubkey,
+    loser: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Settled Elo match between two competing proposals"""
+    return Pubkey.find_program_address(
+        [ELO_MATCH_SEED, bytes(winner), bytes(loser)], program_id
+    )
+
+
+def find_endorsement_address(
+    proposal: Pubkey,
+    endorser: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Reputation stake by `endorser` behind `proposal`"""
+    return Pubkey.find_program_address(
+        [ENDORSEMENT_SEED, bytes(proposal), bytes(endorser)], program_id
+    )
+
+
+def find_payload_buffer_address(
+    proposal: Pubkey,
+    owner: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Payload of hash-committed `proposal` staged by `owner`"""
+    return Pubkey.find_program_address(
+        [PAYLOAD_BUFFER_SEED, bytes(proposal), bytes(owner)], program_id
+    )
+
+
+def find_treasury_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """SOL treasury of `swarm`"""
+    return Pubkey.find_program_address([TREASURY_SEED, bytes(swarm)], program_id)
+
+
+def find_vault_authority_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Data-less signer owning `swarm`'s vault token accounts"""
+    return Pubkey.find_program_address([VAULT_AUTHORITY_SEED, bytes(swarm)], program_id)
+
+
+def find_program_whitelist_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Programs `swarm`'s `Strategy` calls may target"""
+    return Pubkey.find_program_address(
+        [PROGRAM_WHITELIST_SEED, bytes(swarm)], program_id
+    )
+
+
+def find_audit_log_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Ring of `swarm`'s recent privileged actions"""
+    return Pubkey.find_program_address([AUDIT_LOG_SEED, bytes(swarm)], program_id)
+
+
+def find_risk_config_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Trading limits set by `swarm`'s `RiskLimit` proposals"""
+    return Pubkey.find_program_address([RISK_CONFIG_SEED, bytes(swarm)], program_id)
+
+
+def find_twap_tracker_address(
+    swarm: Pubkey,
+    mint: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Time-weighted average price of `mint` in `swarm`"""
+    return Pubkey.find_program_address(
+        [TWAP_TRACKER_SEED, bytes(swarm), bytes(mint)], program_id
+    )
+
+
+def find_outcome_archive_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Merkle root over `swarm`'s archived outcomes"""
+    return Pubkey.find_program_address([OUTCOME_ARCHIVE_SEED, bytes(swarm)], program_id)
+
+
+def find_spend_tracker_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Per-epoch spend tracker of `swarm`'s treasury"""
+    return Pubkey.find_program_address([SPEND_TRACKER_SEED, bytes(swarm)], program_id)
+
+
+def find_bounty_address(
+    proposal: Pubkey,
+    funder: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Bounty escrowed by `funder` on `proposal`"""
+    return Pubkey.find_program_address(
+        [BOUNTY_SEED, bytes(proposal), bytes(funder)], program_id
+    )
+
+
+def find_pending_exposure_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """`swarm`'s approved-but-unexecuted trade exposure"""
+    return Pubkey.find_program_address(
+        [PENDING_EXPOSURE_SEED, bytes(swarm)], program_id
+    )
+
+
+def find_bond_address(
+    swarm: Pubkey,
+    owner: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """SOL bond posted by agent `owner` in `swarm`"""
+    return Pubkey.find_program_address(
+        [BOND_SEED, bytes(swarm), bytes(owner)], program_id
+    )
+
+
+def find_slash_record_address(
+    proposal: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Slash applied by `proposal`"""
+    return Pubkey.find_program_address([SLASH_SEED, bytes(proposal)], program_id)
+
+
+def find_epoch_rewards_address(
+    swarm: Pubkey,
+    epoch: int,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """`swarm`'s settled performance reward epoch `epoch`"""
+    return Pubkey.find_program_address(
+        [EPOCH_REWARDS_SEED, bytes(swarm), _u64(epoch)], program_id
+    )
+
+
+def find_epoch_stats_address(
+    swarm: Pubkey,
+    epoch: int,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Activity recorded for `epoch` of `swarm`"""
+    return Pubkey.find_program_address(
+        [EPOCH_STATS_SEED, bytes(swarm), _u64(epoch)], program_id
+    )
+
+
+def find_vote_escrow_address(
+    swarm: Pubkey,
+    owner: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """`swarm` governance tokens locked by `owner`"""
+    return Pubkey.find_program_address(
+        [VOTE_ESCROW_SEED, bytes(swarm), bytes(owner)], program_id
+    )
+
+
+def find_governance_mint_address(
+    swarm: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Governance token mint of `swarm`"""
+    return Pubkey.find_program_address([GOVERNANCE_MINT_SEED, bytes(swarm)], program_id)
+
+
+def find_marketplace_address(program_id: Pubkey = PROGRAM_ID) -> Tuple[Pubkey, int]:
+    """Agent marketplace singleton"""
+    return Pubkey.find_program_address([MARKETPLACE_SEED], program_id)
+
+
+def find_listing_address(
+    creator: Pubkey,
+    name: str,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Marketplace listing `name` originally published by `creator`"""
+    return Pubkey.find_program_address(
+        [LISTING_SEED, bytes(creator), name.encode()], program_id
+    )
+
+
+def find_listing_index_address(
+    page: int,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Page `page` of the marketplace's listing index"""
+    return Pubkey.find_program_address([LISTING_INDEX_SEED, _u64(page)], program_id)
+
+
+def find_listing_version_address(
+    listing: Pubkey,
+    version: str,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Release `version` of `listing`"""
+    return Pubkey.find_program_address(
+        [LISTING_VERSION_SEED, bytes(listing), version.encode()], program_id
+    )
+
+
+def find_rating_address(
+    listing: Pubkey,
+    rater: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Rating left by `rater` on `listing`"""
+    return Pubkey.find_program_address(
+        [RATING_SEED, bytes(listing), bytes(rater)], program_id
+    )
+
+
+def find_review_address(
+    listing: Pubkey,
+    reviewer: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Written review left by `reviewer` on `listing`"""
+    return Pubkey.find_program_address(
+        [REVIEW_SEED, bytes(listing), bytes(reviewer)], program_id
+    )
+
+
+def find_subscription_address(
+    listing: Pubkey,
+    subscriber: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Subscription held by `subscriber` on `listing`"""
+    return Pubkey.find_program_address(
+        [SUBSCRIPTION_SEED, bytes(listing), bytes(subscriber)], program_id
+    )
+
+
+def find_auction_address(
+    listing: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Running ownership auction for `listing`"""
+    return Pubkey.find_program_address([AUCTION_SEED, bytes(listing)], program_id)
+
+
+def find_bid_address(
+    auction: Pubkey,
+    bidder: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Escrowed bid by `bidder` in `auction`"""
+    return Pubkey.find_program_address(
+        [BID_SEED, bytes(auction), bytes(bidder)], program_id
+    )
+
+
+def find_purchase_address(
+    listing: Pubkey,
+    buyer: Pubkey,
+    program_id: Pubkey = PROGRAM_ID,
+) -> Tuple[Pubkey, int]:
+    """Purchase receipt for `buyer` on `listing`"""
+    return Pubkey.find_program_address(
+        [PURCHASE_SEED, bytes(listing), bytes(buyer)], program_id
+    )
@@ -8,6 +8,7 @@ from solders.pubkey import Pubkey
 from solana.rpc.async_api import AsyncClient
 from .agent import Agent
 from .proposal import Proposal
+from .pda import find_swarm_address
 
 
 class AgentSwarm:
@@ -18,7 +19,8 @@ class AgentSwarm:
         ```python
         swarm = AgentSwarm(
             program_id="56Vy8e8V4E6UZnsa6uDRg8HFiPwroz6nRKh7rm9xAfeK",
-            rpc_url="https://api.devnet.solana.com"
+            rpc_url="https://api.devnet.solana.com",
+            swarm_id=0
         )
         
         await swarm.initialize(max_agents=10, min_votes_required=6)
@@ -29,9 +31,12 @@ class AgentSwarm:
         self,
         program_id: str,
         rpc_url: str = "https://api.devnet.solana.com",
-        authority: Optional[Keypair] = None
+        authority: Optional[Keypair] = None,
+        swarm_id: int = 0
     ):
         self.program_id = Pubkey.from_string(program_id)
+        self.swarm_id = swarm_id
+        self.swarm_address, _ = find_swarm_address(swarm_id, self.program_id)
         self.rpc_url = rpc_url
         self.client = AsyncClient(rpc_url, timeout=30)
         self.authority = authority
@@ -145,6 +150,8 @@ class AgentSwarm:
         """Get current swarm state from on-chain"""
         return {
             "program_id": str(self.program_id),
+            "swarm_id": self.swarm_id,
+            "swarm_address": str(self.swarm_address),
             "total_agents": len(self._agents),
             "total_proposals": len(self._proposals),
             "rpc_url": self.rpc_url
@@ -0,0 +1,35 @@
+"""Seed parity between the Python SDK and programs/agent_swarm/src/pda.rs"""
+import sys
+from pathlib import Path
+
+# Add parent directory to path
+sys.path.insert(0, str(Path(__file__).parent.parent))
+
+from solders.pubkey import Pubkey
+
+from sdk.python.agent_swarm_sdk import pda
+
+# Derived by the Rust `pda` module for swarm 3, proposal 7 and this owner
+OWNER = Pubkey.from_string("8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR")
+SWARM = Pubkey.from_string("2MvZDg2qWsfzn8WWwALoWmoPrLrgU2TsysKDSJUj1Akx")
+PROPOSAL = Pubkey.from_string("6vKH9iZmARUVxMjyRvFTHVrPxbJWYqA9a4MpitYk6Qrg")
+VOTE_RECORD = Pubkey.from_string("AVbnJD6T86jcPb5cLAqQseQSjqBJV32TDeTiMbihsMsC")
+PAYLOAD_BUFFER = Pubkey.from_string("8wWUEbFMuvpaS23qhmuDtuZCeegNQKD6Tr2g6EGVrsga")
+
+
+def test_swarm_scoped_addresses():
+    """Swarm and proposal addresses match the program"""
+    assert pda.find_swarm_address(3) == (SWARM, 252)
+    assert pda.find_proposal_address(SWARM, 7) == (PROPOSAL, 255)
+
+
+def test_proposal_scoped_addresses():
+    """Per-signer proposal accounts match the program"""
+    assert pda.find_vote_record_address(PROPOSAL, OWNER) == (VOTE_RECORD, 253)
+    assert pda.find_payload_buffer_address(PROPOSAL, OWNER) == (PAYLOAD_BUFFER, 254)
+
+
+if __name__ == "__main__":
+    test_swarm_scoped_addresses()
+    test_proposal_scoped_addresses()
+    print("[OK] PDA seeds match the program")